@@ -57,14 +57,13 @@ use std::rc::Rc;
 use std::ptr::{null, null_mut};
 use glib_sys::GMainContext;
 use glib::{MainContext, translate::ToGlibPtr};
-use std::mem;
 use pulse::mainloop::api::{MainloopInternalType, MainloopInner, MainloopApi};
 use pulse::mainloop::signal::MainloopSignals;
 use pulse::mainloop::api::Mainloop as MainloopTrait;
 
 /* Note, we cannot simply use the object defined in the ‘sys’ crate, since either the type or the
  * trait need to be defined locally in order to link them. Thus, we create the below type (an empty
- * one, just used as an opaque pointer), and transmute to the ‘sys’ crate one.
+ * one, just used as an opaque pointer), and cast pointers to and from the ‘sys’ crate one.
  */
 
 /// An opaque GLIB main loop object.
@@ -79,6 +78,9 @@ impl MainloopInternalType for MainloopInternal {}
 /// the inner ref counted mainloop object to them. Giving this to events serves two purposes,
 /// firstly because they need the API pointer, secondly, it ensures that event objects do not
 /// outlive the mainloop object.
+///
+/// Note, this is neither `Send` nor `Sync`, since it holds an `Rc`; it must only ever be used from
+/// the thread that created it.
 pub struct Mainloop {
     /// The ref-counted inner data.
     pub _inner: Rc<MainloopInner<MainloopInternal>>,
@@ -96,7 +98,7 @@ impl MainloopSignals for Mainloop {}
 
 /// Drop function for MainloopInner<MainloopInternal>.
 fn drop_actual(self_: &mut MainloopInner<MainloopInternal>) {
-    unsafe { capi::pa_glib_mainloop_free(mem::transmute(&self_.ptr)) };
+    unsafe { capi::pa_glib_mainloop_free(self_.ptr as *mut capi::pa_glib_mainloop) };
     self_.ptr = null_mut::<MainloopInternal>();
     self_.api = null::<MainloopApi>();
 }
@@ -109,21 +111,19 @@ impl Mainloop {
     ///
     /// This returns the object in an Rc wrapper, allowing multiple references to be held, which
     /// allows event objects to hold one, thus ensuring they do not outlive it.
-    pub fn new(context: Option<&mut MainContext>) -> Option<Self> {
+    pub fn new(context: Option<&MainContext>) -> Option<Self> {
         let p_ctx = context.map_or(null_mut::<GMainContext>(), |c| c.to_glib_none().0);
 
         let ptr = unsafe { capi::pa_glib_mainloop_new(p_ctx) };
         if ptr.is_null() {
             return None;
         }
-        let api_ptr = unsafe {
-            mem::transmute(capi::pa_glib_mainloop_get_api(ptr))
-        };
+        let api_ptr = MainloopApi::from_capi_ptr(unsafe { capi::pa_glib_mainloop_get_api(ptr) });
         Some(
             Self {
                 _inner: Rc::new(
                     MainloopInner::<MainloopInternal> {
-                        ptr: unsafe { mem::transmute(ptr) },
+                        ptr: ptr as *mut MainloopInternal,
                         api: api_ptr,
                         dropfn: drop_actual,
                         supports_rtclock: false,
@@ -78,12 +78,29 @@
 //! # }
 //! ```
 //!
+//! Alternatively, a [`Builder`] can be used, which takes defaults for everything except the client
+//! and stream names (with a default of playback of 16-bit, 44.1 kHz stereo audio):
+//!
+//! ```rust,no_run
+//! # extern crate libpulse_binding as pulse;
+//! # extern crate libpulse_simple_binding as psimple;
+//! #
+//! # use psimple::Simple;
+//! # fn main() {
+//! let s = Simple::builder("FooApp", "Music").connect().unwrap();
+//! # }
+//! ```
+//!
 //! # Transferring data
 //!
 //! Once the connection is established to the server, data can start flowing. Using the connection
 //! is very similar to the normal read() and write() system calls using [`read`] and [`write`]
 //! methods of the [`Simple`] object. Note that these operations always block.
 //!
+//! [`Simple`] also implements the standard [`std::io::Write`] and [`std::io::Read`] traits, for
+//! playback and record streams respectively, allowing use with anything expecting a writer or
+//! reader, such as [`std::io::copy`].
+//!
 //! # Buffer control
 //!
 //! * [`Simple::get_latency`]: Will return the total latency of the playback or record pipeline,
@@ -100,6 +117,10 @@
 //! is done automatically once the object is dropped.
 //!
 //! [`Simple`]: struct.Simple.html
+//! [`Builder`]: struct.Builder.html
+//! [`std::io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+//! [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+//! [`std::io::copy`]: https://doc.rust-lang.org/std/io/fn.copy.html
 //! [`read`]: struct.Simple.html#method.read
 //! [`write`]: struct.Simple.html#method.write
 //! [`Simple::get_latency`]: struct.Simple.html#method.get_latency
@@ -115,8 +136,8 @@ extern crate libpulse_simple_sys as capi;
 
 use std::os::raw::{c_char, c_void};
 use std::{ffi::CString, ptr::null};
-use std::mem;
-use pulse::{error::PAErr, time::MicroSeconds};
+use std::{io, mem};
+use pulse::{error::{PAErr, Code}, time::MicroSeconds};
 use pulse::{stream, sample, channelmap, def};
 
 use capi::pa_simple as SimpleInternal;
@@ -125,6 +146,10 @@ use capi::pa_simple as SimpleInternal;
 pub struct Simple {
     /// The actual C object.
     ptr: *mut SimpleInternal,
+    /// The sample spec given at construction.
+    ss: sample::Spec,
+    /// The stream direction given at construction.
+    dir: stream::Direction,
 }
 
 unsafe impl Send for Simple {}
@@ -143,6 +168,10 @@ impl Simple {
     /// * `ss`: The sample type to use.
     /// * `map`: The channel map to use, or `None` for default.
     /// * `attr`: Buffering attributes, or `None` for default.
+    ///
+    /// Returns an `Invalid` error if any of the strings contain a nul byte.
+    ///
+    /// See also [`builder`](#method.builder), a more convenient means of construction.
     pub fn new(server: Option<&str>, name: &str, dir: stream::Direction, dev: Option<&str>,
         stream_name: &str, ss: &sample::Spec, map: Option<&channelmap::Map>,
         attr: Option<&def::BufferAttr>) -> Result<Self, PAErr>
@@ -150,20 +179,20 @@ impl Simple {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
         let c_server = match server {
-            Some(server) => CString::new(server.clone()).unwrap(),
-            None => CString::new("").unwrap(),
+            Some(server) => Some(to_cstring(server)?),
+            None => None,
         };
         let c_dev = match dev {
-            Some(dev) => CString::new(dev.clone()).unwrap(),
-            None => CString::new("").unwrap(),
+            Some(dev) => Some(to_cstring(dev)?),
+            None => None,
         };
+        let c_name = to_cstring(name)?;
+        let c_stream_name = to_cstring(stream_name)?;
 
         let p_map = map.map_or(null::<pcapi::pa_channel_map>(), |m| m.as_ref());
         let p_attr = attr.map_or(null::<pcapi::pa_buffer_attr>(), |a| a.as_ref());
-        let p_server = server.map_or(null::<c_char>(), |_| c_server.as_ptr() as *const c_char);
-        let p_dev = dev.map_or(null::<c_char>(), |_| c_dev.as_ptr() as *const c_char);
-        let c_name = CString::new(name.clone()).unwrap();
-        let c_stream_name = CString::new(stream_name.clone()).unwrap();
+        let p_server = c_server.as_ref().map_or(null::<c_char>(), |s| s.as_ptr());
+        let p_dev = c_dev.as_ref().map_or(null::<c_char>(), |d| d.as_ptr());
 
         let mut error: i32 = 0;
         let ptr = unsafe {
@@ -180,34 +209,82 @@ impl Simple {
             )
         };
         match ptr.is_null() {
-            false => Ok(Self::from_raw(ptr)),
-            true => Err(PAErr(error)),
+            false => Ok(Self::from_raw(ptr, *ss, dir)),
+            true => Err(error_from_out_param(error)),
+        }
+    }
+
+    /// Creates a builder for a new connection to the server.
+    ///
+    /// `name` is a descriptive name for this client (application name, ...), and `stream_name` a
+    /// descriptive name for the stream (application name, song title, ...).
+    ///
+    /// The builder defaults to a playback stream on the default server and device, using signed
+    /// 16-bit native endian, 44.1 kHz stereo samples, with default channel map and buffering
+    /// attributes. The common case is thus simply:
+    ///
+    /// ```rust,no_run
+    /// # extern crate libpulse_simple_binding as psimple;
+    /// # use psimple::Simple;
+    /// let s = Simple::builder("FooApp", "Music").connect().unwrap();
+    /// ```
+    pub fn builder<'a>(name: &'a str, stream_name: &'a str) -> Builder<'a> {
+        Builder {
+            server: None,
+            name,
+            dir: stream::Direction::Playback,
+            dev: None,
+            stream_name,
+            ss: sample::Spec { format: sample::SAMPLE_S16NE, rate: 44100, channels: 2 },
+            map: None,
+            attr: None,
         }
     }
 
     /// Creates a new `Simple` from an existing [`SimpleInternal`](capi/enum.pa_simple.html) pointer.
-    fn from_raw(ptr: *mut SimpleInternal) -> Self {
+    fn from_raw(ptr: *mut SimpleInternal, ss: sample::Spec, dir: stream::Direction) -> Self {
         assert_eq!(false, ptr.is_null());
-        Self { ptr }
+        Self { ptr, ss, dir }
+    }
+
+    /// Gets the sample spec the connection was created with.
+    #[inline]
+    pub fn get_sample_spec(&self) -> &sample::Spec {
+        &self.ss
     }
 
     /// Writes some data to the server.
+    ///
+    /// This function blocks until all of the data has been accepted by the server, or until an
+    /// error occurs.
     pub fn write(&self, data: &[u8]) -> Result<(), PAErr> {
         let mut error: i32 = 0;
         match unsafe { capi::pa_simple_write(self.ptr, data.as_ptr() as *mut c_void, data.len(),
             &mut error) }
         {
             0 => Ok(()),
-            _ => Err(PAErr(error)),
+            _ => Err(error_from_out_param(error)),
         }
     }
 
+    /// Writes samples to the server.
+    ///
+    /// This is a typed alternative to [`write`](#method.write). Returns an `Invalid` error if the
+    /// sample type does not match the sample format of the connection’s spec.
+    pub fn write_samples<S: Sampleable>(&self, data: &[S]) -> Result<(), PAErr> {
+        self.check_sample_type::<S>()?;
+        let bytes = unsafe {
+            std::slice::from_raw_parts(data.as_ptr() as *const u8, mem::size_of_val(data))
+        };
+        self.write(bytes)
+    }
+
     /// Waits until all data already written is played by the daemon.
     pub fn drain(&self) -> Result<(), PAErr> {
         let mut error: i32 = 0;
         match unsafe { capi::pa_simple_drain(self.ptr, &mut error) } {
             0 => Ok(()),
-            _ => Err(PAErr(error)),
+            _ => Err(error_from_out_param(error)),
         }
     }
 
@@ -221,18 +298,47 @@ impl Simple {
             &mut error) }
         {
             0 => Ok(()),
-            _ => Err(PAErr(error)),
+            _ => Err(error_from_out_param(error)),
+        }
+    }
+
+    /// Reads samples from the server.
+    ///
+    /// This is a typed alternative to [`read`](#method.read), and similarly blocks until the
+    /// buffer has been filled. Returns an `Invalid` error if the sample type does not match the
+    /// sample format of the connection’s spec.
+    pub fn read_samples<S: Sampleable>(&self, data: &mut [S]) -> Result<(), PAErr> {
+        self.check_sample_type::<S>()?;
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, mem::size_of_val(data))
+        };
+        self.read(bytes)
+    }
+
+    /// Checks that the connection is of the given direction, for use with the std I/O traits.
+    fn check_direction(&self, dir: stream::Direction) -> io::Result<()> {
+        match self.dir == dir {
+            true => Ok(()),
+            false => Err(to_io_error(PAErr::from(Code::BadState))),
+        }
+    }
+
+    /// Checks that the given sample type is usable with the connection’s sample format.
+    fn check_sample_type<S: Sampleable>(&self) -> Result<(), PAErr> {
+        match S::is_compatible(self.ss.format) {
+            true => Ok(()),
+            false => Err(PAErr::from(Code::Invalid)),
         }
     }
 
     /// Gets the playback or record latency.
-    pub fn get_latency(&self) -> Option<MicroSeconds> {
+    pub fn get_latency(&self) -> Result<MicroSeconds, PAErr> {
         let mut error: i32 = 0;
         let ret = unsafe { capi::pa_simple_get_latency(self.ptr, &mut error) };
-        if error != 0 {
-            return None;
+        match ret {
+            std::u64::MAX => Err(error_from_out_param(error)),
+            _ => Ok(MicroSeconds(ret)),
         }
-        Some(MicroSeconds(ret))
     }
 
     /// Flushes the playback or record buffer.
@@ -242,11 +348,209 @@ impl Simple {
         let mut error: i32 = 0;
         match unsafe { capi::pa_simple_flush(self.ptr, &mut error) } {
             0 => Ok(()),
-            _ => Err(PAErr(error)),
+            _ => Err(error_from_out_param(error)),
         }
     }
 }
 
+/// Playback streams can be written to with [`std::io::Write`] (e.g. via [`std::io::copy`]).
+///
+/// The simple API has no concept of partial writes, so each write is all-or-nothing, blocking until
+/// the entire buffer has been accepted, as with [`Simple::write`].
+///
+/// Note that [`flush`](#method.flush-1) waits for all data written to be played, as with
+/// [`Simple::drain`]. This is **not** the same as the inherent [`Simple::flush`] method, which
+/// discards buffered data. Errors are returned as [`std::io::Error`]s of kind `Other`, wrapping
+/// the [`PAErr`]. Using these with a record stream gives a `BadState` error (regardless of the
+/// buffer given).
+///
+/// [`std::io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+/// [`std::io::copy`]: https://doc.rust-lang.org/std/io/fn.copy.html
+/// [`std::io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+/// [`Simple::write`]: struct.Simple.html#method.write
+/// [`Simple::drain`]: struct.Simple.html#method.drain
+/// [`Simple::flush`]: struct.Simple.html#method.flush
+/// [`PAErr`]: ../libpulse_binding/error/struct.PAErr.html
+impl io::Write for Simple {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.check_direction(stream::Direction::Playback)?;
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        Simple::write(self, buf).map_err(to_io_error)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.check_direction(stream::Direction::Playback)?;
+        self.drain().map_err(to_io_error)
+    }
+}
+
+/// Record streams can be read from with [`std::io::Read`].
+///
+/// The simple API has no concept of partial reads, so each read is all-or-nothing, blocking until
+/// the entire buffer has been filled, as with [`Simple::read`]. Errors are returned as
+/// [`std::io::Error`]s of kind `Other`, wrapping the [`PAErr`]. Using this with a playback stream
+/// gives a `BadState` error (regardless of the buffer given).
+///
+/// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+/// [`std::io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+/// [`Simple::read`]: struct.Simple.html#method.read
+/// [`PAErr`]: ../libpulse_binding/error/struct.PAErr.html
+impl io::Read for Simple {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.check_direction(stream::Direction::Record)?;
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        Simple::read(self, buf).map_err(to_io_error)?;
+        Ok(buf.len())
+    }
+}
+
+/// Converts an error for use with the std I/O traits, preserving the error code.
+fn to_io_error(e: PAErr) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e)
+}
+
+/// Sample types usable with [`Simple::read_samples`] and [`Simple::write_samples`].
+///
+/// This is implemented for the primitive types directly corresponding to the native endian sample
+/// formats: `u8` for `U8` (and for the 8-bit `ALaw` and `ULaw` encodings), `i16` for `S16NE`,
+/// `i32` for `S32NE` and `S24_32NE`, and `f32` for `FLOAT32NE`.
+///
+/// # Safety
+///
+/// Implementors must be plain data types, valid for any bit pattern, whose size is that of a
+/// sample of every format for which `is_compatible` returns `true`.
+///
+/// [`Simple::read_samples`]: struct.Simple.html#method.read_samples
+/// [`Simple::write_samples`]: struct.Simple.html#method.write_samples
+pub unsafe trait Sampleable: Copy {
+    /// Whether or not this type can hold samples of the given format.
+    fn is_compatible(format: sample::Format) -> bool;
+}
+
+unsafe impl Sampleable for u8 {
+    fn is_compatible(format: sample::Format) -> bool {
+        match format {
+            sample::Format::U8 | sample::Format::ALaw | sample::Format::ULaw => true,
+            _ => false,
+        }
+    }
+}
+
+unsafe impl Sampleable for i16 {
+    fn is_compatible(format: sample::Format) -> bool {
+        format == sample::SAMPLE_S16NE
+    }
+}
+
+unsafe impl Sampleable for i32 {
+    fn is_compatible(format: sample::Format) -> bool {
+        format == sample::SAMPLE_S32NE || format == sample::SAMPLE_S24_32NE
+    }
+}
+
+unsafe impl Sampleable for f32 {
+    fn is_compatible(format: sample::Format) -> bool {
+        format == sample::SAMPLE_FLOAT32NE
+    }
+}
+
+/// Builder for a [`Simple`](struct.Simple.html) connection.
+///
+/// Created by [`Simple::builder`](struct.Simple.html#method.builder).
+#[derive(Debug, Clone)]
+pub struct Builder<'a> {
+    server: Option<&'a str>,
+    name: &'a str,
+    dir: stream::Direction,
+    dev: Option<&'a str>,
+    stream_name: &'a str,
+    ss: sample::Spec,
+    map: Option<&'a channelmap::Map>,
+    attr: Option<&'a def::BufferAttr>,
+}
+
+impl<'a> Builder<'a> {
+    /// Sets the server to connect to (the default is used otherwise).
+    #[inline]
+    pub fn server(mut self, server: &'a str) -> Self {
+        self.server = Some(server);
+        self
+    }
+
+    /// Sets the stream direction (playback is used otherwise).
+    #[inline]
+    pub fn direction(mut self, dir: stream::Direction) -> Self {
+        self.dir = dir;
+        self
+    }
+
+    /// Sets the stream direction to playback.
+    #[inline]
+    pub fn playback(self) -> Self {
+        self.direction(stream::Direction::Playback)
+    }
+
+    /// Sets the stream direction to record.
+    #[inline]
+    pub fn record(self) -> Self {
+        self.direction(stream::Direction::Record)
+    }
+
+    /// Sets the sink (resp. source) name (the default is used otherwise).
+    #[inline]
+    pub fn device(mut self, dev: &'a str) -> Self {
+        self.dev = Some(dev);
+        self
+    }
+
+    /// Sets the sample spec.
+    #[inline]
+    pub fn spec(mut self, ss: sample::Spec) -> Self {
+        self.ss = ss;
+        self
+    }
+
+    /// Sets the channel map (the default is used otherwise).
+    #[inline]
+    pub fn channel_map(mut self, map: &'a channelmap::Map) -> Self {
+        self.map = Some(map);
+        self
+    }
+
+    /// Sets the buffering attributes (the defaults are used otherwise).
+    #[inline]
+    pub fn buffer_attr(mut self, attr: &'a def::BufferAttr) -> Self {
+        self.attr = Some(attr);
+        self
+    }
+
+    /// Creates the connection, as per [`Simple::new`](struct.Simple.html#method.new).
+    pub fn connect(&self) -> Result<Simple, PAErr> {
+        Simple::new(self.server, self.name, self.dir, self.dev, self.stream_name, &self.ss,
+            self.map, self.attr)
+    }
+}
+
+/// Converts a string for the C API, giving an `Invalid` error if it contains a nul byte.
+fn to_cstring(s: &str) -> Result<CString, PAErr> {
+    CString::new(s).or(Err(PAErr::from(Code::Invalid)))
+}
+
+/// Converts the error given back through the error out-parameter of the simple API functions to an
+/// error value.
+///
+/// Note, the out-parameter holds the error code as a positive value, whereas error values are
+/// negative, such that they compare equal to the corresponding `Code` converted to a `PAErr`.
+#[inline]
+fn error_from_out_param(error: i32) -> PAErr {
+    PAErr(-error)
+}
+
 impl Drop for Simple {
     fn drop(&mut self) {
         // Close and free the connection to the server.
@@ -254,3 +558,22 @@ impl Drop for Simple {
         self.ptr = null::<SimpleInternal>() as *mut SimpleInternal;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn error_codes() {
+        assert_eq!(error_from_out_param(Code::NoEntity as i32), PAErr::from(Code::NoEntity));
+        assert_eq!(Code::try_from(error_from_out_param(Code::Timeout as i32)), Ok(Code::Timeout));
+
+        // A real failure, connecting to a server that does not exist, gives a known code
+        let spec = sample::Spec { format: sample::Format::S16le, channels: 2, rate: 44100 };
+        let err = Simple::new(Some("unix:/nonexistent/pulse-binding-simple"), "FooApp",
+            stream::Direction::Playback, None, "Music", &spec, None, None).err().unwrap();
+        assert!(err.0 < 0);
+        assert!(Code::try_from(err).is_ok());
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! A minimal `parec` like example.
+//!
+//! Records raw PCM audio (signed 16-bit native endian, 44100 Hz, stereo) from the default source,
+//! writing it to stdout, using the standard mainloop, the read callback, and `peek`/`discard`.
+//! Recording continues until interrupted, or until stdout is closed.
+//!
+//! For instance:
+//!
+//! ```text
+//! cargo run --example record > audio.raw
+//! ```
+
+extern crate libpulse_binding as pulse;
+
+use std::cell::{Cell, RefCell};
+use std::io::{self, Write};
+use std::rc::{Rc, Weak};
+use pulse::context::{self, Context};
+use pulse::mainloop::standard::{IterateResult, Mainloop};
+use pulse::proplist::{properties, Proplist};
+use pulse::sample;
+use pulse::stream::{self, PeekResult, Stream};
+
+/// Writes all data currently available from the stream to stdout, returning `false` should
+/// anything fail.
+fn read_data(stream: &mut Stream) -> bool {
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+    loop {
+        match stream.peek() {
+            Ok(PeekResult::Empty) => break,
+            Ok(PeekResult::Hole(_)) => {},
+            Ok(PeekResult::Data(data)) => {
+                if let Err(e) = output.write_all(data) {
+                    eprintln!("Failed to write output: {}", e);
+                    return false;
+                }
+            },
+            Err(e) => {
+                eprintln!("Failed to read from stream: {}", e);
+                return false;
+            },
+        }
+        if stream.discard().is_err() {
+            eprintln!("Failed to discard data from stream");
+            return false;
+        }
+    }
+    output.flush().is_ok()
+}
+
+/// Iterates the mainloop until `check` gives a result.
+fn iterate_until<F>(mainloop: &Rc<RefCell<Mainloop>>, mut check: F) -> Result<(), ()>
+    where F: FnMut() -> Option<Result<(), ()>>
+{
+    loop {
+        if let Some(result) = check() {
+            return result;
+        }
+        match mainloop.borrow_mut().iterate(true) {
+            IterateResult::Success(_) => {},
+            IterateResult::Quit(_) | IterateResult::Err(_) => return Err(()),
+        }
+    }
+}
+
+fn main() {
+    let spec = sample::Spec {
+        format: sample::SAMPLE_S16NE,
+        channels: 2,
+        rate: 44100,
+    };
+    assert!(spec.is_valid());
+
+    let mut proplist = Proplist::new().unwrap();
+    proplist.set_str(properties::APPLICATION_NAME, "record").unwrap();
+
+    let mainloop = Rc::new(RefCell::new(Mainloop::new().expect("Failed to create mainloop")));
+
+    let context = Rc::new(RefCell::new(
+        Context::new_with_proplist(&*mainloop.borrow(), "RecordContext", &proplist)
+            .expect("Failed to create new context")));
+
+    context.borrow_mut().connect(None, context::Flags::NOFLAGS, None)
+        .expect("Failed to connect context");
+
+    // Wait for context to be ready
+    let ready = iterate_until(&mainloop, || match context.borrow().get_state() {
+        context::State::Ready => Some(Ok(())),
+        context::State::Failed | context::State::Terminated => Some(Err(())),
+        _ => None,
+    });
+    if ready.is_err() {
+        eprintln!("Context failed to become ready, quitting...");
+        return;
+    }
+
+    let stream = Rc::new(RefCell::new(
+        Stream::new(&mut context.borrow_mut(), "Record", &spec, None)
+            .expect("Failed to create new stream")));
+
+    // Read data whenever some is available, noting any failure. Note, the callback only holds a
+    // weak reference, to avoid a reference cycle.
+    let failed = Rc::new(Cell::new(false));
+    {
+        let stream_ref: Weak<RefCell<Stream>> = Rc::downgrade(&stream);
+        let failed_ref = Rc::clone(&failed);
+        stream.borrow_mut().set_read_callback(Some(Box::new(move |_| {
+            if failed_ref.get() {
+                return;
+            }
+            if let Some(stream) = stream_ref.upgrade() {
+                if !read_data(&mut stream.borrow_mut()) {
+                    failed_ref.set(true);
+                }
+            }
+        })));
+    }
+
+    stream.borrow_mut().connect_record(None, None, stream::Flags::NOFLAGS)
+        .expect("Failed to connect record");
+
+    // Record until something fails
+    let _ = iterate_until(&mainloop, || match stream.borrow().get_state() {
+        stream::State::Failed | stream::State::Terminated => Some(Err(())),
+        _ if failed.get() => Some(Ok(())),
+        _ => None,
+    });
+
+    stream.borrow_mut().set_read_callback(None);
+    let _ = stream.borrow_mut().disconnect();
+    context.borrow_mut().disconnect();
+}
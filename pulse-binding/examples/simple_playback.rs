@@ -0,0 +1,177 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! A minimal `paplay` like example.
+//!
+//! Reads raw PCM audio (signed 16-bit native endian, 44100 Hz, stereo) from stdin, and plays it,
+//! using the standard mainloop, the write callback, and zero-copy writes with `begin_write`.
+//!
+//! For instance:
+//!
+//! ```text
+//! cargo run --example simple_playback < audio.raw
+//! ```
+
+extern crate libpulse_binding as pulse;
+
+use std::cell::{Cell, RefCell};
+use std::io::{self, Read};
+use std::rc::{Rc, Weak};
+use pulse::context::{self, Context};
+use pulse::mainloop::standard::{IterateResult, Mainloop};
+use pulse::proplist::{properties, Proplist};
+use pulse::sample;
+use pulse::stream::{self, SeekMode, Stream};
+
+/// Fills as much of `buf` as possible from `input`, returning the number of bytes read, which is
+/// less than the size of `buf` only upon reaching the end of the input.
+fn fill(input: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match input.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Writes data from stdin to the stream, as requested by the server, returning `false` once the
+/// end of the input is reached.
+fn write_data(stream: &mut Stream, frame_size: usize, mut requested: usize) -> bool {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    while requested > 0 {
+        let buf = match stream.begin_write(Some(requested)) {
+            Ok(Some(buf)) => buf,
+            Ok(None) | Err(_) => {
+                eprintln!("Failed to obtain a write buffer");
+                return false;
+            },
+        };
+        let len = match fill(&mut input, buf) {
+            Ok(len) => len - (len % frame_size),
+            Err(e) => {
+                eprintln!("Failed to read input: {}", e);
+                0
+            },
+        };
+        if len == 0 {
+            let _ = stream.cancel_write();
+            return false;
+        }
+        if stream.write(&buf[..len], None, 0, SeekMode::Relative).is_err() {
+            eprintln!("Failed to write to stream");
+            return false;
+        }
+        requested = requested.saturating_sub(len);
+    }
+    true
+}
+
+/// Iterates the mainloop until `check` gives a result.
+fn iterate_until<F>(mainloop: &Rc<RefCell<Mainloop>>, mut check: F) -> Result<(), ()>
+    where F: FnMut() -> Option<Result<(), ()>>
+{
+    loop {
+        if let Some(result) = check() {
+            return result;
+        }
+        match mainloop.borrow_mut().iterate(true) {
+            IterateResult::Success(_) => {},
+            IterateResult::Quit(_) | IterateResult::Err(_) => return Err(()),
+        }
+    }
+}
+
+fn main() {
+    let spec = sample::Spec {
+        format: sample::SAMPLE_S16NE,
+        channels: 2,
+        rate: 44100,
+    };
+    assert!(spec.is_valid());
+    let frame_size = spec.frame_size();
+
+    let mut proplist = Proplist::new().unwrap();
+    proplist.set_str(properties::APPLICATION_NAME, "simple_playback").unwrap();
+
+    let mainloop = Rc::new(RefCell::new(Mainloop::new().expect("Failed to create mainloop")));
+
+    let context = Rc::new(RefCell::new(
+        Context::new_with_proplist(&*mainloop.borrow(), "PlaybackContext", &proplist)
+            .expect("Failed to create new context")));
+
+    context.borrow_mut().connect(None, context::Flags::NOFLAGS, None)
+        .expect("Failed to connect context");
+
+    // Wait for context to be ready
+    let ready = iterate_until(&mainloop, || match context.borrow().get_state() {
+        context::State::Ready => Some(Ok(())),
+        context::State::Failed | context::State::Terminated => Some(Err(())),
+        _ => None,
+    });
+    if ready.is_err() {
+        eprintln!("Context failed to become ready, quitting...");
+        return;
+    }
+
+    let stream = Rc::new(RefCell::new(
+        Stream::new(&mut context.borrow_mut(), "Playback", &spec, None)
+            .expect("Failed to create new stream")));
+
+    // Write data whenever the server requests more, noting when the input is exhausted. Note, the
+    // callback only holds a weak reference, to avoid a reference cycle.
+    let finished = Rc::new(Cell::new(false));
+    {
+        let stream_ref: Weak<RefCell<Stream>> = Rc::downgrade(&stream);
+        let finished_ref = Rc::clone(&finished);
+        stream.borrow_mut().set_write_callback(Some(Box::new(move |requested| {
+            if finished_ref.get() {
+                return;
+            }
+            if let Some(stream) = stream_ref.upgrade() {
+                if !write_data(&mut stream.borrow_mut(), frame_size, requested) {
+                    finished_ref.set(true);
+                }
+            }
+        })));
+    }
+
+    stream.borrow_mut().connect_playback(None, None, stream::Flags::NOFLAGS, None, None)
+        .expect("Failed to connect playback");
+
+    // Play until the end of the input is reached
+    let played = iterate_until(&mainloop, || match stream.borrow().get_state() {
+        stream::State::Failed | stream::State::Terminated => Some(Err(())),
+        _ if finished.get() => Some(Ok(())),
+        _ => None,
+    });
+    if played.is_err() {
+        eprintln!("Stream failed, quitting...");
+        return;
+    }
+
+    // Wait for the remaining data to be played
+    let drained = Rc::new(Cell::new(None));
+    {
+        let drained_ref = Rc::clone(&drained);
+        let _op = stream.borrow_mut().drain(Some(Box::new(move |result| {
+            drained_ref.set(Some(result));
+        })));
+    }
+    let _ = iterate_until(&mainloop, || drained.get().map(|_| Ok(())));
+
+    stream.borrow_mut().set_write_callback(None);
+    let _ = stream.borrow_mut().disconnect();
+    context.borrow_mut().disconnect();
+}
@@ -54,6 +54,101 @@ pub type PositionMask = capi::channelmap::pa_channel_position_mask_t;
 /// Position mask covering all positions.
 pub const POSITION_MASK_ALL: PositionMask = 0xffffffffffffffffu64;
 
+/// Position mask covering all left side positions (at any height, front to rear).
+///
+/// This, along with the other masks of “regions”, mirrors those used by the C library, where for
+/// instance a map is considered to support balancing if it has positions within both
+/// [`POSITION_MASK_LEFT`] and [`POSITION_MASK_RIGHT`].
+pub const POSITION_MASK_LEFT: PositionMask = 1 << Position::FrontLeft as PositionMask
+    | 1 << Position::RearLeft as PositionMask | 1 << Position::FrontLeftOfCenter as PositionMask
+    | 1 << Position::SideLeft as PositionMask | 1 << Position::TopFrontLeft as PositionMask
+    | 1 << Position::TopRearLeft as PositionMask;
+
+/// Position mask covering all right side positions (at any height, front to rear).
+pub const POSITION_MASK_RIGHT: PositionMask = 1 << Position::FrontRight as PositionMask
+    | 1 << Position::RearRight as PositionMask | 1 << Position::FrontRightOfCenter as PositionMask
+    | 1 << Position::SideRight as PositionMask | 1 << Position::TopFrontRight as PositionMask
+    | 1 << Position::TopRearRight as PositionMask;
+
+/// Position mask covering all center positions (at any height, front to rear).
+pub const POSITION_MASK_CENTER: PositionMask = 1 << Position::FrontCenter as PositionMask
+    | 1 << Position::RearCenter as PositionMask | 1 << Position::TopCenter as PositionMask
+    | 1 << Position::TopFrontCenter as PositionMask | 1 << Position::TopRearCenter as PositionMask;
+
+/// Position mask covering all front positions (at any height).
+pub const POSITION_MASK_FRONT: PositionMask = 1 << Position::FrontLeft as PositionMask
+    | 1 << Position::FrontRight as PositionMask | 1 << Position::FrontCenter as PositionMask
+    | 1 << Position::FrontLeftOfCenter as PositionMask
+    | 1 << Position::FrontRightOfCenter as PositionMask
+    | 1 << Position::TopFrontLeft as PositionMask | 1 << Position::TopFrontRight as PositionMask
+    | 1 << Position::TopFrontCenter as PositionMask;
+
+/// Position mask covering all rear positions (at any height).
+pub const POSITION_MASK_REAR: PositionMask = 1 << Position::RearLeft as PositionMask
+    | 1 << Position::RearRight as PositionMask | 1 << Position::RearCenter as PositionMask
+    | 1 << Position::TopRearLeft as PositionMask | 1 << Position::TopRearRight as PositionMask
+    | 1 << Position::TopRearCenter as PositionMask;
+
+/// Position mask of the LFE (subwoofer) position.
+pub const POSITION_MASK_LFE: PositionMask = 1 << Position::Lfe as PositionMask;
+
+/// Position mask of the mono layout.
+///
+/// This, along with the other masks of standard layouts, gives exactly the positions of the
+/// layout, matching the C library’s naming of maps (see [`Map::to_name`]). Note that the surround
+/// layouts use the rear positions for their surround channels, with the side positions only used
+/// by 7.1, in addition to the rear ones. Thus for instance a 5.1 layout made up with side rather
+/// than rear positions does not match [`POSITION_MASK_SURROUND_51`].
+///
+/// [`Map::to_name`]: struct.Map.html#method.to_name
+pub const POSITION_MASK_MONO: PositionMask = 1 << Position::Mono as PositionMask;
+
+/// Position mask of the stereo layout (front left and right).
+pub const POSITION_MASK_STEREO: PositionMask = 1 << Position::FrontLeft as PositionMask
+    | 1 << Position::FrontRight as PositionMask;
+
+/// Position mask of the 4.0 surround layout (stereo plus rear left and right).
+pub const POSITION_MASK_SURROUND_40: PositionMask = POSITION_MASK_STEREO
+    | 1 << Position::RearLeft as PositionMask | 1 << Position::RearRight as PositionMask;
+
+/// Position mask of the 4.1 surround layout (4.0 plus LFE).
+pub const POSITION_MASK_SURROUND_41: PositionMask = POSITION_MASK_SURROUND_40 | POSITION_MASK_LFE;
+
+/// Position mask of the 5.0 surround layout (4.0 plus front center).
+pub const POSITION_MASK_SURROUND_50: PositionMask = POSITION_MASK_SURROUND_40
+    | 1 << Position::FrontCenter as PositionMask;
+
+/// Position mask of the 5.1 surround layout (5.0 plus LFE).
+pub const POSITION_MASK_SURROUND_51: PositionMask = POSITION_MASK_SURROUND_50 | POSITION_MASK_LFE;
+
+/// Position mask of the 7.1 surround layout (5.1 plus side left and right).
+pub const POSITION_MASK_SURROUND_71: PositionMask = POSITION_MASK_SURROUND_51
+    | 1 << Position::SideLeft as PositionMask | 1 << Position::SideRight as PositionMask;
+
+/// The positions of the speaker bits of WAVE (`WAVEFORMATEXTENSIBLE`) channel masks, in bit order.
+///
+/// WAVE files and devices using such masks give their channels in this order.
+const WAVE_POSITIONS: [Position; 18] = [
+    Position::FrontLeft,
+    Position::FrontRight,
+    Position::FrontCenter,
+    Position::Lfe,
+    Position::RearLeft,
+    Position::RearRight,
+    Position::FrontLeftOfCenter,
+    Position::FrontRightOfCenter,
+    Position::RearCenter,
+    Position::SideLeft,
+    Position::SideRight,
+    Position::TopCenter,
+    Position::TopFrontLeft,
+    Position::TopFrontCenter,
+    Position::TopFrontRight,
+    Position::TopRearLeft,
+    Position::TopRearCenter,
+    Position::TopRearRight,
+];
+
 /// A list of channel labels.
 ///
 /// Note, certain aliases, specifically `Left`, `Right`, `Center` and `Subwoofer`, available in the
@@ -182,7 +277,7 @@ impl From<capi::pa_channel_position_t> for Position {
 ///
 /// These values are relevant for conversion and mixing of streams.
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Copy, Clone)]
 pub struct Map {
     /* NOTE: This struct must be directly usable by the C API, thus same attributes/layout/etc */
     /// Number of channels mapped.
@@ -245,6 +340,17 @@ impl Default for Map {
     }
 }
 
+impl std::fmt::Debug for Map {
+    /// Only the positions of the mapped channels are shown.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let positions: &[Position] = self.borrow();
+        f.debug_struct("Map")
+            .field("channels", &self.channels)
+            .field("map", &positions)
+            .finish()
+    }
+}
+
 impl PartialEq for Map {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
@@ -392,6 +498,25 @@ impl Map {
         self.borrow_mut()
     }
 
+    /// Gets the position of the channel at the given index.
+    ///
+    /// Returns `None` if the index is beyond the number of active channels (see
+    /// [`len`](#method.len)).
+    #[inline]
+    pub fn get_position(&self, index: usize) -> Option<Position> {
+        self.get().get(index).copied()
+    }
+
+    /// Sets the position of the channel at the given index.
+    ///
+    /// Returns `None` if the index is beyond the number of active channels (see
+    /// [`len`](#method.len)), in which case the map is left unchanged.
+    #[inline]
+    pub fn set_position(&mut self, index: usize, position: Position) -> Option<&mut Self> {
+        *self.get_mut().get_mut(index)? = position;
+        Some(self)
+    }
+
     /// Makes a human readable string from the map.
     pub fn print(&self) -> String {
         const PRINT_MAX: usize = capi::PA_CHANNEL_MAP_SNPRINT_MAX;
@@ -471,4 +596,264 @@ impl Map {
     pub fn get_mask(&self) -> PositionMask {
         unsafe { capi::pa_channel_map_mask(self.as_ref()) }
     }
+
+    /// Checks whether the map has exactly the positions of the given mask, regardless of order.
+    ///
+    /// This is intended for comparison with the masks of standard layouts, such as
+    /// [`POSITION_MASK_SURROUND_51`](constant.POSITION_MASK_SURROUND_51.html).
+    pub fn has_layout(&self, mask: PositionMask) -> bool {
+        self.get().iter().fold(0, |m, p| m | p.to_mask()) == mask
+    }
+
+    /// Checks whether the map has any (LFE) subwoofer channels.
+    #[inline]
+    pub fn has_lfe(&self) -> bool {
+        self.get().contains(&Position::Lfe)
+    }
+
+    /// Checks whether the map is of the stereo layout (front left and right), in any order.
+    #[inline]
+    pub fn is_stereo(&self) -> bool {
+        self.has_layout(POSITION_MASK_STEREO)
+    }
+
+    /// Checks whether the map is of the 4.0 surround layout, in any order.
+    ///
+    /// See [`POSITION_MASK_MONO`](constant.POSITION_MASK_MONO.html) regarding the convention for
+    /// surround layouts.
+    #[inline]
+    pub fn is_surround_40(&self) -> bool {
+        self.has_layout(POSITION_MASK_SURROUND_40)
+    }
+
+    /// Checks whether the map is of the 4.1 surround layout, in any order.
+    ///
+    /// See [`POSITION_MASK_MONO`](constant.POSITION_MASK_MONO.html) regarding the convention for
+    /// surround layouts.
+    #[inline]
+    pub fn is_surround_41(&self) -> bool {
+        self.has_layout(POSITION_MASK_SURROUND_41)
+    }
+
+    /// Checks whether the map is of the 5.0 surround layout, in any order.
+    ///
+    /// See [`POSITION_MASK_MONO`](constant.POSITION_MASK_MONO.html) regarding the convention for
+    /// surround layouts.
+    #[inline]
+    pub fn is_surround_50(&self) -> bool {
+        self.has_layout(POSITION_MASK_SURROUND_50)
+    }
+
+    /// Checks whether the map is of the 5.1 surround layout, in any order.
+    ///
+    /// See [`POSITION_MASK_MONO`](constant.POSITION_MASK_MONO.html) regarding the convention for
+    /// surround layouts.
+    #[inline]
+    pub fn is_surround_51(&self) -> bool {
+        self.has_layout(POSITION_MASK_SURROUND_51)
+    }
+
+    /// Checks whether the map is of the 7.1 surround layout, in any order.
+    ///
+    /// See [`POSITION_MASK_MONO`](constant.POSITION_MASK_MONO.html) regarding the convention for
+    /// surround layouts.
+    #[inline]
+    pub fn is_surround_71(&self) -> bool {
+        self.has_layout(POSITION_MASK_SURROUND_71)
+    }
+
+    /// Creates a map from a WAVE (`WAVEFORMATEXTENSIBLE`) channel mask, as also used by ALSA and
+    /// others.
+    ///
+    /// The map has a channel for each speaker bit set, in bit order (the order in which such
+    /// channels are given). Reserved bits (those above `SPEAKER_TOP_BACK_RIGHT`) are ignored. Note
+    /// that a mask without any speaker bits set gives an empty, thus invalid, map.
+    ///
+    /// Note, this is unrelated to the C library’s own position masks (see [`get_mask`]).
+    ///
+    /// [`get_mask`]: #method.get_mask
+    pub fn from_wave_mask(mask: u32) -> Self {
+        let mut map = Self::default();
+        let mut len = 0;
+        for (bit, position) in WAVE_POSITIONS.iter().enumerate() {
+            if mask & (1 << bit) != 0 {
+                map.map[len] = *position;
+                len += 1;
+            }
+        }
+        map.channels = len as u8;
+        map
+    }
+
+    /// Gets the WAVE (`WAVEFORMATEXTENSIBLE`) channel mask equivalent to the map, as understood by
+    /// [`from_wave_mask`].
+    ///
+    /// Returns `None` if the map cannot be described by a mask, i.e. if it is empty, has a position
+    /// without a WAVE equivalent (such as the auxiliary positions), or has positions repeated or
+    /// out of bit order. A mono map is given as front center, per the WAVE convention.
+    ///
+    /// [`from_wave_mask`]: #method.from_wave_mask
+    pub fn to_wave_mask(&self) -> Option<u32> {
+        if self.get() == [Position::Mono] {
+            return Some(1 << 2);
+        }
+        let mut mask = 0u32;
+        for position in self.get() {
+            let bit = WAVE_POSITIONS.iter().position(|p| p == position)?;
+            // Each bit must be above those already set
+            if mask >> bit != 0 {
+                return None;
+            }
+            mask |= 1 << bit;
+        }
+        match mask {
+            0 => None,
+            m => Some(m),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plain value types can be freely shared between threads.
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Map>();
+    }
+
+    #[test]
+    fn default_and_debug() {
+        let mut map = Map::default();
+        assert_eq!(map.len(), 0);
+        assert!(!map.is_valid());
+        assert_eq!(format!("{:?}", map), "Map { channels: 0, map: [] }");
+
+        map.init_stereo();
+        assert_eq!(format!("{:?}", map), "Map { channels: 2, map: [FrontLeft, FrontRight] }");
+    }
+
+    #[test]
+    fn layouts() {
+        use Position::*;
+
+        fn map(positions: &[Position]) -> Map {
+            let mut map = Map::default();
+            map.set_len(positions.len() as u8);
+            map.get_mut().copy_from_slice(positions);
+            map
+        }
+
+        // Positions, expected layout (by C library name), and whether there is an LFE channel
+        let table: &[(&[Position], Option<&str>, bool)] = &[
+            (&[Mono], Some("mono"), false),
+            (&[FrontLeft, FrontRight], Some("stereo"), false),
+            (&[FrontRight, FrontLeft], Some("stereo"), false),
+            (&[FrontLeft, FrontRight, RearLeft, RearRight], Some("surround-40"), false),
+            (&[FrontLeft, FrontRight, RearLeft, RearRight, Lfe], Some("surround-41"), true),
+            (&[FrontLeft, FrontRight, RearLeft, RearRight, FrontCenter], Some("surround-50"),
+                false),
+            (&[FrontLeft, FrontRight, FrontCenter, Lfe, RearLeft, RearRight], Some("surround-51"),
+                true),
+            (&[FrontLeft, FrontRight, RearLeft, RearRight, FrontCenter, Lfe, SideLeft, SideRight],
+                Some("surround-71"), true),
+            // 5.1 using side rather than rear positions
+            (&[FrontLeft, FrontRight, FrontCenter, Lfe, SideLeft, SideRight], None, true),
+            (&[FrontLeft, FrontCenter], None, false),
+            (&[FrontLeft, FrontRight, Lfe], None, true),
+        ];
+        for &(positions, name, lfe) in table {
+            let m = map(positions);
+            assert!(m.is_valid());
+            let name = name.unwrap_or("");
+            assert_eq!(m.has_layout(POSITION_MASK_MONO), name == "mono", "{:?}", positions);
+            assert_eq!(m.is_stereo(), name == "stereo", "{:?}", positions);
+            assert_eq!(m.is_surround_40(), name == "surround-40", "{:?}", positions);
+            assert_eq!(m.is_surround_41(), name == "surround-41", "{:?}", positions);
+            assert_eq!(m.is_surround_50(), name == "surround-50", "{:?}", positions);
+            assert_eq!(m.is_surround_51(), name == "surround-51", "{:?}", positions);
+            assert_eq!(m.is_surround_71(), name == "surround-71", "{:?}", positions);
+            assert_eq!(m.has_lfe(), lfe, "{:?}", positions);
+            assert_eq!(m.has_lfe(), m.has_position(Lfe));
+
+            // Agreement with the C library
+            assert_eq!(m.get().iter().fold(0, |a, p| a | p.to_mask()), m.get_mask());
+            if !name.is_empty() {
+                assert_eq!(m.to_name().as_ref().map(|n| &**n), Some(name));
+            }
+            let mask = m.get_mask();
+            assert_eq!(m.can_balance(),
+                mask & POSITION_MASK_LEFT != 0 && mask & POSITION_MASK_RIGHT != 0);
+            assert_eq!(m.can_fade(),
+                mask & POSITION_MASK_FRONT != 0 && mask & POSITION_MASK_REAR != 0);
+        }
+
+        // Opposing regions do not overlap
+        assert_eq!(POSITION_MASK_LEFT & POSITION_MASK_RIGHT, 0);
+        assert_eq!(POSITION_MASK_LEFT & POSITION_MASK_CENTER, 0);
+        assert_eq!(POSITION_MASK_FRONT & POSITION_MASK_REAR, 0);
+        assert_eq!(POSITION_MASK_SURROUND_71.count_ones(), 8);
+        assert_eq!(Invalid.to_mask(), 0);
+        assert_eq!(POSITION_MASK_LFE, Lfe.to_mask());
+        assert_eq!(POSITION_MASK_STEREO, FrontLeft.to_mask() | FrontRight.to_mask());
+    }
+
+    #[test]
+    fn indexed_positions() {
+        let mut map = Map::default();
+        map.init_stereo();
+        assert_eq!(map.get_position(0), Some(Position::FrontLeft));
+        assert_eq!(map.get_position(1), Some(Position::FrontRight));
+        // Out of bounds, including inactive entries of the internal array
+        assert_eq!(map.get_position(2), None);
+        assert_eq!(map.get_position(sample::CHANNELS_MAX), None);
+
+        assert!(map.set_position(1, Position::RearRight).is_some());
+        assert_eq!(map.get(), &[Position::FrontLeft, Position::RearRight]);
+        assert!(map.set_position(2, Position::Lfe).is_none());
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(), &[Position::FrontLeft, Position::RearRight]);
+    }
+
+    #[test]
+    fn wave_masks() {
+        use Position::*;
+
+        // 5.1, as given by `KSAUDIO_SPEAKER_5POINT1` (FL, FR, FC, LFE, BL, BR)
+        let map = Map::from_wave_mask(0x3f);
+        assert_eq!(map.get(), [FrontLeft, FrontRight, FrontCenter, Lfe, RearLeft, RearRight]);
+        assert!(map.is_surround_51());
+        assert_eq!(map.to_wave_mask(), Some(0x3f));
+        // 7.1, with side positions, and reserved bits ignored
+        let map = Map::from_wave_mask(0x8000_063f);
+        assert_eq!(map.len(), 8);
+        assert_eq!(&map.get()[6..], [SideLeft, SideRight]);
+        assert_eq!(map.to_wave_mask(), Some(0x63f));
+        // All
+        let map = Map::from_wave_mask(0x3ffff);
+        assert_eq!(map.get(), WAVE_POSITIONS);
+        assert_eq!(map.to_wave_mask(), Some(0x3ffff));
+
+        assert!(!Map::from_wave_mask(0).is_valid());
+        assert_eq!(Map::default().to_wave_mask(), None);
+        assert_eq!(Map::from_wave_mask(0x4).to_wave_mask(), Some(0x4));
+        assert_eq!(Map::new_from_string("mono").unwrap().to_wave_mask(), Some(0x4));
+
+        let mut map = Map::from_wave_mask(0x3f);
+        // Non-standard positions
+        map.get_mut()[5] = Aux0;
+        assert_eq!(map.to_wave_mask(), None);
+        map.get_mut()[5] = Mono;
+        assert_eq!(map.to_wave_mask(), None);
+        // Out of order, or repeated
+        map.get_mut().swap(0, 1);
+        map.get_mut()[5] = RearRight;
+        assert_eq!(map.to_wave_mask(), None);
+        map.get_mut()[0] = FrontLeft;
+        assert_eq!(map.to_wave_mask(), None);
+        map.get_mut()[1] = FrontRight;
+        assert_eq!(map.to_wave_mask(), Some(0x3f));
+    }
 }
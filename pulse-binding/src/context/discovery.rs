@@ -0,0 +1,217 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Reporting of the servers a context would try to connect to.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The location of the system wide instance’s runtime directory, as typically configured.
+const SYSTEM_RUNTIME_PATH: &str = "/var/run/pulse";
+
+/// Name of the native protocol socket within a runtime directory.
+const NATIVE_SOCKET: &str = "native";
+
+/// Where a server candidate comes from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CandidateSource {
+    /// Given by the `PULSE_SERVER` environment variable. When set, only the servers it lists are
+    /// tried.
+    EnvServer,
+    /// The per-user instance’s native socket, within the user’s runtime directory.
+    User,
+    /// The system wide instance’s native socket.
+    System,
+}
+
+/// A server that a context would try to connect to, as reported by
+/// [`default_server_candidates`](fn.default_server_candidates.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerCandidate {
+    /// The server string, in the form accepted by [`Context::connect`].
+    ///
+    /// [`Context::connect`]: struct.Context.html#method.connect
+    pub server: String,
+    /// Where the candidate comes from.
+    pub source: CandidateSource,
+    /// The path of the socket, for local (unix socket) servers.
+    pub socket_path: Option<PathBuf>,
+}
+
+impl ServerCandidate {
+    fn local(path: PathBuf, source: CandidateSource) -> Self {
+        Self { server: format!("unix:{}", path.display()), source: source, socket_path: Some(path) }
+    }
+
+    /// Checks whether the socket of a local server candidate currently exists.
+    ///
+    /// Always `false` for non-local servers.
+    pub fn socket_exists(&self) -> bool {
+        self.socket_path.as_ref().map_or(false, |p| p.exists())
+    }
+}
+
+/// Gets the servers that connecting a context without specifying a server would try, in order.
+///
+/// This is intended for diagnostics (e.g. reporting why connecting failed), and follows the search
+/// order documented by PulseAudio, implemented here since the C library does not export it:
+///
+///  1. If the `PULSE_SERVER` environment variable is set, the (whitespace separated) servers it
+///     lists, and nothing else.
+///  2. Otherwise, the per-user instance’s socket, within the runtime directory. This is given by
+///     `PULSE_RUNTIME_PATH` if set, otherwise is `pulse` within `XDG_RUNTIME_DIR` if that is set,
+///     otherwise is the legacy `<machine-id>-runtime` within the `pulse` directory of the user’s
+///     configuration directory (`XDG_CONFIG_HOME`, or `.config` within `HOME`).
+///  3. Followed by the system wide instance’s socket.
+///
+/// Not covered are servers set within `client.conf` or in X11 root window properties, which take
+/// the place of `PULSE_SERVER` when it is not set, nor any TCP localhost fallback enabled in
+/// `client.conf`. Note also that [`Context::get_server`] only gives a server once connecting has
+/// begun.
+///
+/// [`Context::connect`]: struct.Context.html#method.connect
+/// [`Context::get_server`]: struct.Context.html#method.get_server
+pub fn default_server_candidates() -> Vec<ServerCandidate> {
+    server_candidates(|name| env::var(name).ok(), machine_id)
+}
+
+/// Implementation of [`default_server_candidates`](fn.default_server_candidates.html), taking the
+/// sources of environment variables and the machine ID.
+fn server_candidates<E, M>(var: E, machine_id: M) -> Vec<ServerCandidate>
+    where E: Fn(&str) -> Option<String>, M: FnOnce() -> Option<String>
+{
+    let var = |name: &str| var(name).filter(|v| !v.is_empty());
+
+    if let Some(servers) = var("PULSE_SERVER") {
+        return servers.split_whitespace().map(|server| ServerCandidate {
+            server: server.to_string(),
+            source: CandidateSource::EnvServer,
+            socket_path: local_socket_path(server),
+        }).collect();
+    }
+
+    let mut candidates = Vec::new();
+    let runtime_dir = match (var("PULSE_RUNTIME_PATH"), var("XDG_RUNTIME_DIR")) {
+        (Some(dir), _) => Some(PathBuf::from(dir)),
+        (None, Some(dir)) => Some(Path::new(&dir).join("pulse")),
+        (None, None) => {
+            let config_dir = var("XDG_CONFIG_HOME").map(PathBuf::from)
+                .or_else(|| var("HOME").map(|home| Path::new(&home).join(".config")));
+            match (config_dir, machine_id()) {
+                (Some(dir), Some(id)) => Some(dir.join("pulse").join(format!("{}-runtime", id))),
+                _ => None,
+            }
+        },
+    };
+    if let Some(dir) = runtime_dir {
+        candidates.push(ServerCandidate::local(dir.join(NATIVE_SOCKET), CandidateSource::User));
+    }
+    candidates.push(ServerCandidate::local(Path::new(SYSTEM_RUNTIME_PATH).join(NATIVE_SOCKET),
+        CandidateSource::System));
+    candidates
+}
+
+/// Gets the socket path of a server string, if it refers to a local server.
+///
+/// Any `{<machine-id>}` prefix, restricting the server to a particular machine, is ignored.
+fn local_socket_path(server: &str) -> Option<PathBuf> {
+    let server = match server.starts_with('{') {
+        true => &server[server.find('}')? + 1..],
+        false => server,
+    };
+    let path = match server.starts_with("unix:") {
+        true => &server["unix:".len()..],
+        false => server,
+    };
+    match path.starts_with('/') {
+        true => Some(PathBuf::from(path)),
+        false => None,
+    }
+}
+
+/// Reads the machine ID, as used by the C library.
+fn machine_id() -> Option<String> {
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"].iter()
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .map(|id| id.trim().to_string())
+        .find(|id| !id.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn candidates(vars: &[(&str, &str)], machine_id: Option<&str>) -> Vec<ServerCandidate> {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect();
+        server_candidates(|name| vars.get(name).cloned(), || machine_id.map(String::from))
+    }
+
+    fn system() -> ServerCandidate {
+        ServerCandidate::local(PathBuf::from("/var/run/pulse/native"), CandidateSource::System)
+    }
+
+    #[test]
+    fn env_server() {
+        let found = candidates(&[("PULSE_SERVER", " tcp:example.com  unix:/tmp/a/native\t/b "),
+            ("XDG_RUNTIME_DIR", "/run/user/1000")], None);
+        let servers: Vec<_> = found.iter().map(|c| c.server.as_str()).collect();
+        assert_eq!(servers, ["tcp:example.com", "unix:/tmp/a/native", "/b"]);
+        assert!(found.iter().all(|c| c.source == CandidateSource::EnvServer));
+        assert_eq!(found[0].socket_path, None);
+        assert_eq!(found[1].socket_path, Some(PathBuf::from("/tmp/a/native")));
+        assert_eq!(found[2].socket_path, Some(PathBuf::from("/b")));
+        assert!(!found[0].socket_exists());
+
+        assert_eq!(local_socket_path("{abc}unix:/x"), Some(PathBuf::from("/x")));
+        assert_eq!(local_socket_path("{abc"), None);
+        assert_eq!(local_socket_path("tcp6:[::1]:4713"), None);
+
+        // Empty is as though unset
+        assert_eq!(candidates(&[("PULSE_SERVER", "")], None), [system()]);
+    }
+
+    #[test]
+    fn xdg_runtime_dir() {
+        let xdg = env::temp_dir().join(format!("pulse-binding-test-{}", std::process::id()));
+        let socket = xdg.join("pulse").join("native");
+        let found = candidates(&[("XDG_RUNTIME_DIR", xdg.to_str().unwrap()),
+            ("HOME", "/home/foo")], Some("1234"));
+        let user = ServerCandidate::local(socket.clone(), CandidateSource::User);
+        assert_eq!(found, [user, system()]);
+        assert_eq!(found[0].server, format!("unix:{}", socket.display()));
+
+        assert!(!found[0].socket_exists());
+        fs::create_dir_all(socket.parent().unwrap()).unwrap();
+        fs::write(&socket, b"").unwrap();
+        assert!(found[0].socket_exists());
+        fs::remove_dir_all(&xdg).unwrap();
+    }
+
+    #[test]
+    fn runtime_path_precedence() {
+        let user = |path: &str| ServerCandidate::local(PathBuf::from(path), CandidateSource::User);
+
+        let found = candidates(&[("PULSE_RUNTIME_PATH", "/custom"),
+            ("XDG_RUNTIME_DIR", "/run/user/1000")], Some("1234"));
+        assert_eq!(found, [user("/custom/native"), system()]);
+
+        // Legacy fallbacks, without `XDG_RUNTIME_DIR`
+        let found = candidates(&[("XDG_CONFIG_HOME", "/conf"), ("HOME", "/home/foo")],
+            Some("1234"));
+        assert_eq!(found, [user("/conf/pulse/1234-runtime/native"), system()]);
+        let found = candidates(&[("HOME", "/home/foo")], Some("1234"));
+        assert_eq!(found, [user("/home/foo/.config/pulse/1234-runtime/native"), system()]);
+        assert_eq!(candidates(&[("HOME", "/home/foo")], None), [system()]);
+        assert_eq!(candidates(&[], Some("1234")), [system()]);
+    }
+}
@@ -21,6 +21,7 @@ use capi::pa_ext_device_manager_info as InfoInternal;
 use capi::pa_ext_device_manager_role_priority_info as RolePriorityInfoInternal;
 use super::{ContextInternal, Context};
 use crate::def;
+use crate::error::PAErr;
 use crate::callbacks::{ListResult, box_closure_get_capi_ptr, callback_for_list_instance, ListInstanceCallback};
 use crate::operation::Operation;
 
@@ -110,9 +111,6 @@ pub struct DeviceManager {
     cb_ptrs: CallbackPointers,
 }
 
-unsafe impl Send for DeviceManager {}
-unsafe impl Sync for DeviceManager {}
-
 /// Holds copies of callback closure pointers, for those that are “multi-use” (may be fired multiple
 /// times), for freeing at the appropriate time.
 #[derive(Default)]
@@ -166,34 +164,35 @@ impl DeviceManager {
 
     /// Sets the description for a device.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     pub fn set_device_description<F>(&mut self, device: &str, description: &str, callback: F)
-        -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a
         // variable, leading to as_ptr() giving dangling pointers!
         let c_dev = CString::new(device.clone()).unwrap();
         let c_desc = CString::new(description.clone()).unwrap();
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe {
             capi::pa_ext_device_manager_set_device_description(self.context, c_dev.as_ptr(),
                 c_desc.as_ptr(), Some(super::success_cb_proxy), cb_data)
         };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Deletes entries from the device database.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn delete<F>(&mut self, devices: &[&str], callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn delete<F>(&mut self, devices: &[&str], callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -210,39 +209,39 @@ impl DeviceManager {
         }
         c_dev_ptrs.push(null());
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_ext_device_manager_delete(self.context, c_dev_ptrs.as_ptr(),
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Enables the role-based device-priority routing mode.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     pub fn enable_role_device_priority_routing<F>(&mut self, enable: bool, callback: F)
-        -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe {
             capi::pa_ext_device_manager_enable_role_device_priority_routing(self.context,
                 enable as i32, Some(super::success_cb_proxy), cb_data)
         };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Reorders the position of a given device in the priority list to give preference to it.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     pub fn reorder_devices_for_role<F>(&mut self, role: &str, devices: &[&str], callback: F)
-        -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -260,28 +259,29 @@ impl DeviceManager {
         }
         c_dev_ptrs.push(null());
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe {
             capi::pa_ext_device_manager_reorder_devices_for_role(self.context, c_role.as_ptr(),
                 c_dev_ptrs.as_ptr(), Some(super::success_cb_proxy), cb_data)
         };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Subscribes to changes in the device database.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn subscribe<F>(&mut self, enable: bool, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn subscribe<F>(&mut self, enable: bool, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_ext_device_manager_subscribe(self.context, enable as i32,
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the subscription callback that is called when [`subscribe`](#method.subscribe) was
@@ -19,22 +19,23 @@ use std::mem;
 use capi::pa_ext_device_restore_info as InfoInternal;
 use super::{ContextInternal, Context};
 use crate::{def, format};
+use crate::error::PAErr;
 use crate::callbacks::{ListResult, box_closure_get_capi_ptr, callback_for_list_instance, ListInstanceCallback, MultiUseCallback};
 use crate::operation::Operation;
 
 /// Stores information about one device in the device database that is maintained by
 /// module-device-manager.
 #[derive(Debug)]
-pub struct Info {
+pub struct Info<'a> {
     /// Device type sink or source?
     pub dtype: def::Device,
     /// The device index.
     pub index: u32,
     /// A set of formats.
-    pub formats: Vec<format::Info>,
+    pub formats: Vec<format::InfoRef<'a>>,
 }
 
-impl Info {
+impl<'a> Info<'a> {
     fn new_from_raw(p: *const InfoInternal) -> Self {
         assert!(!p.is_null());
         let src = unsafe { p.as_ref().unwrap() };
@@ -44,7 +45,7 @@ impl Info {
         for i in 0..src.n_formats as isize {
             let indexed_ptr = unsafe { (*src.formats.offset(i)) as *mut format::InfoInternal };
             if !indexed_ptr.is_null() {
-                formats_vec.push(format::Info::from_raw_weak(indexed_ptr));
+                formats_vec.push(format::InfoRef::from_raw(indexed_ptr));
             }
         }
 
@@ -65,9 +66,6 @@ pub struct DeviceRestore {
     cb_ptrs: CallbackPointers,
 }
 
-unsafe impl Send for DeviceRestore {}
-unsafe impl Sync for DeviceRestore {}
-
 /// Holds copies of callback closure pointers, for those that are “multi-use” (may be fired multiple
 /// times), for freeing at the appropriate time.
 #[derive(Default)]
@@ -113,17 +111,18 @@ impl DeviceRestore {
 
     /// Subscribes to changes in the device database.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn subscribe<F>(&mut self, enable: bool, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn subscribe<F>(&mut self, enable: bool, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_ext_device_restore_subscribe(self.context, enable as i32,
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the subscription callback that is called when [`subscribe`](#method.subscribe) was
@@ -171,12 +170,12 @@ impl DeviceRestore {
 
     /// Reads an entry from the device database.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     pub fn save_formats<F>(&mut self, type_: def::Device, index: u32,
-        formats: &mut [&mut format::Info], callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+        formats: &mut [&mut format::Info], callback: F) -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Capture array of pointers to the above `format::InfoInternal` objects
         let mut format_ptrs: Vec<*mut capi::pa_format_info> = Vec::with_capacity(formats.len());
@@ -184,14 +183,14 @@ impl DeviceRestore {
             format_ptrs.push(unsafe { mem::transmute(&format.ptr) });
         }
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe {
             capi::pa_ext_device_restore_save_formats(self.context, type_, index,
                 format_ptrs.len() as u8, format_ptrs.as_ptr(), Some(super::success_cb_proxy),
                 cb_data)
         };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 }
 
@@ -21,6 +21,7 @@ use std::mem;
 use capi::pa_ext_stream_restore_info as InfoInternal;
 use super::{ContextInternal, Context};
 use crate::{channelmap, proplist};
+use crate::error::PAErr;
 use crate::callbacks::{ListResult, box_closure_get_capi_ptr, callback_for_list_instance, ListInstanceCallback};
 use crate::{operation::Operation, volume::ChannelVolumes};
 
@@ -72,9 +73,6 @@ pub struct StreamRestore {
     cb_ptrs: CallbackPointers,
 }
 
-unsafe impl Send for StreamRestore {}
-unsafe impl Sync for StreamRestore {}
-
 /// Holds copies of callback closure pointers, for those that are “multi-use” (may be fired multiple
 /// times), for freeing at the appropriate time.
 #[derive(Default)]
@@ -128,30 +126,31 @@ impl StreamRestore {
 
     /// Stores entries in the stream database.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     pub fn write<F>(&mut self, mode: proplist::UpdateMode, data: &[&Info],
-        apply_immediately: bool, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+        apply_immediately: bool, callback: F) -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe {
             capi::pa_ext_stream_restore_write(self.context, mode, mem::transmute(data.as_ptr()),
                 data.len() as u32, apply_immediately as i32, Some(super::success_cb_proxy),
                 cb_data)
         };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Deletes entries from the stream database.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn delete<F>(&mut self, streams: &[&str], callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn delete<F>(&mut self, streams: &[&str], callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -168,26 +167,27 @@ impl StreamRestore {
         }
         c_stream_ptrs.push(null());
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_ext_stream_restore_delete(self.context, c_stream_ptrs.as_ptr(),
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Subscribes to changes in the stream database.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn subscribe<F>(&mut self, enable: bool, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn subscribe<F>(&mut self, enable: bool, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_ext_stream_restore_subscribe(self.context, enable as i32,
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the subscription callback that is called when [`subscribe`](#method.subscribe) was
@@ -0,0 +1,1006 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Higher level utilities built upon the core API.
+//!
+//! # Default device monitoring
+//!
+//! Mixer like applications commonly need to always know the current default sink, along with its
+//! volume and mute state, and to be told whenever any of these changes. [`DefaultDeviceMonitor`]
+//! does exactly this:
+//!
+//! ```rust,ignore
+//! use pulse::context::helpers::DefaultDeviceMonitor;
+//!
+//! let monitor = DefaultDeviceMonitor::new(&mut my_context, |sink| {
+//!     match sink {
+//!         Some(sink) => println!("default sink: {:?}, muted: {}", sink.name, sink.mute),
+//!         None => println!("no default sink"),
+//!     }
+//! }).unwrap();
+//! ```
+//!
+//! # Peak metering
+//!
+//! Level (VU) meters require a very particular record stream configuration, which
+//! [`PeakMeter`] takes care of, delivering per-channel peak values at the requested interval:
+//!
+//! ```rust,ignore
+//! use pulse::context::helpers::{MeterTarget, PeakMeter};
+//! use pulse::time::MicroSeconds;
+//!
+//! let target = MeterTarget::Device("alsa_output.pci-0000_00_1f.3.analog-stereo.monitor".into());
+//! let meter = PeakMeter::new(&mut my_context, target, 2, MicroSeconds(50_000), |peaks| {
+//!     println!("left: {:.2}, right: {:.2}", peaks[0], peaks[1]);
+//! }).unwrap();
+//! ```
+//!
+//! # Surviving server restarts
+//!
+//! Long running clients should survive the server restarting, which leaves the context failed,
+//! requiring a fresh one to be created and connected, and the application’s streams and
+//! subscriptions to be re-established upon it. [`ReconnectingContext`] takes care of this, retrying
+//! with exponential backoff:
+//!
+//! ```rust,ignore
+//! use pulse::context::{self, helpers::ReconnectingContext};
+//!
+//! let mut reconnecting = ReconnectingContext::new(&mainloop, "MyApp", None, None,
+//!     context::Flags::NOAUTOSPAWN).unwrap();
+//! reconnecting.on_connected(|context| {
+//!     // Create streams, subscribe to events, etc.
+//! });
+//! reconnecting.on_disconnected(|| println!("lost connection, reconnecting..."));
+//! ```
+//!
+//! [`DefaultDeviceMonitor`]: struct.DefaultDeviceMonitor.html
+//! [`PeakMeter`]: struct.PeakMeter.html
+//! [`ReconnectingContext`]: struct.ReconnectingContext.html
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::mem;
+use std::ptr::null_mut;
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+use super::{Context, ContextError, Flags, State};
+use super::introspect::{Introspector, ServerInfo, SinkInfo};
+use super::subscribe::{subscription_masks, Facility, Operation, SubscriptionEvent};
+use crate::callbacks::ListResult;
+use crate::def::BufferAttr;
+use crate::error::{Code, PAErr};
+use crate::mainloop::api::{AnyMainloop, ErasedInner, ErasedMainloop, Mainloop};
+use crate::mainloop::events::timer::TimeEvent;
+use crate::operation;
+use crate::proplist::Proplist;
+use crate::sample;
+use crate::stream::{self, PeekResult, Stream};
+use crate::time::{MicroSeconds, MonotonicTs, MICROS_PER_SEC};
+use crate::volume::ChannelVolumes;
+
+/// Tracks the server’s default sink, reporting changes of its identity, volume or mute state.
+///
+/// Upon creation, the current default sink is looked up and reported, and thereafter it is
+/// reported again whenever the default sink changes to a different sink, or whenever its volume
+/// or mute state changes. `None` is reported should there be no default sink (e.g. if it
+/// disappears entirely).
+///
+/// Events arriving while a lookup is in progress (such as the burst of events generated while
+/// switching devices) are coalesced into a single follow up lookup, upon completion of the one in
+/// progress, thus at most one change is reported per burst.
+///
+/// # Notes
+///
+/// The monitor takes over the context’s subscription, setting the interest mask to server and
+/// sink events, and installing its own subscribe callback with [`Context::set_subscribe_callback`].
+/// Changing either of these with [`Context::subscribe`] or [`Context::set_subscribe_callback`] will
+/// break the monitor, as will dropping the `Context` object (which clears its callbacks). Once the
+/// monitor is dropped, its subscribe callback does nothing further, but remains installed until
+/// replaced (and the interest mask is left as is).
+///
+/// Should the context fail, the monitor simply stops reporting.
+///
+/// [`Context::subscribe`]: ../struct.Context.html#method.subscribe
+/// [`Context::set_subscribe_callback`]: ../struct.Context.html#method.set_subscribe_callback
+pub struct DefaultDeviceMonitor {
+    /// Shared with the callbacks (which only hold weak references).
+    _shared: Rc<Shared>,
+    /// Keeps the mainloop alive for as long as the monitor exists.
+    _mainloop: Rc<dyn Any>,
+}
+
+struct Shared {
+    /// Holds a reference to the context.
+    introspector: Introspector,
+    tracker: RefCell<Tracker>,
+    callback: RefCell<Box<dyn FnMut(Option<SinkInfo<'static>>)>>,
+    /// The operations of the latest lookup, for detecting those cancelled (e.g. upon the context
+    /// failing), whose callbacks will never be called.
+    server_op: RefCell<Option<operation::Operation<dyn FnMut(&ServerInfo)>>>,
+    sink_op: RefCell<Option<operation::Operation<dyn FnMut(ListResult<&SinkInfo>)>>>,
+}
+
+impl Shared {
+    /// Whether or not either operation of the latest lookup is still running.
+    fn lookup_running(&self) -> bool {
+        let running = |state| state == operation::State::Running;
+        self.server_op.borrow().as_ref().map_or(false, |op| running(op.get_state())) ||
+        self.sink_op.borrow().as_ref().map_or(false, |op| running(op.get_state()))
+    }
+}
+
+/// The properties of the default sink which are compared to determine whether or not to report.
+#[derive(Debug, Clone, PartialEq)]
+struct Snapshot {
+    name: Option<String>,
+    index: u32,
+    volume: ChannelVolumes,
+    mute: bool,
+}
+
+impl<'a> From<&SinkInfo<'a>> for Snapshot {
+    fn from(info: &SinkInfo<'a>) -> Self {
+        Self {
+            name: info.name.as_ref().map(|n| n.clone().into_owned()),
+            index: info.index,
+            volume: info.volume,
+            mute: info.mute,
+        }
+    }
+}
+
+/// Lookup coalescing and change detection state.
+#[derive(Debug, Default)]
+struct Tracker {
+    /// The last reported state, `None` if nothing has yet been reported.
+    reported: Option<Option<Snapshot>>,
+    /// Whether or not a lookup is in progress.
+    in_flight: bool,
+    /// Whether or not another lookup is needed once the one in progress completes.
+    dirty: bool,
+}
+
+impl Tracker {
+    /// Called for each lookup request, returning whether or not to actually start a lookup.
+    fn begin(&mut self) -> bool {
+        match self.in_flight {
+            true => { self.dirty = true; false },
+            false => { self.in_flight = true; true },
+        }
+    }
+
+    /// Called should a lookup in progress have been cancelled or failed without completing, such
+    /// that a new one may be started.
+    fn abandon(&mut self) {
+        self.in_flight = false;
+    }
+
+    /// Called upon completion of a lookup, returning whether or not to report the result, and
+    /// whether or not to start another lookup.
+    fn finish(&mut self, result: Option<Snapshot>) -> (bool, bool) {
+        self.in_flight = false;
+        let changed = self.reported.as_ref() != Some(&result);
+        if changed {
+            self.reported = Some(result);
+        }
+        let again = self.dirty;
+        self.dirty = false;
+        (changed, again)
+    }
+
+    /// The index of the last reported default sink, if any.
+    fn current_index(&self) -> Option<u32> {
+        match self.reported {
+            Some(Some(ref info)) => Some(info.index),
+            _ => None,
+        }
+    }
+}
+
+/// Whether or not an event could affect the default sink, or its volume or mute state.
+fn is_relevant(event: &SubscriptionEvent, current_index: Option<u32>) -> bool {
+    match event.facility {
+        Some(Facility::Server) => true,
+        Some(Facility::Sink) => {
+            event.operation == Some(Operation::New) || Some(event.index) == current_index
+        },
+        _ => false,
+    }
+}
+
+impl DefaultDeviceMonitor {
+    /// Starts monitoring the default sink of the given context.
+    ///
+    /// The callback is given an owned copy of the default sink’s information upon each change, as
+    /// described in the [type’s documentation](struct.DefaultDeviceMonitor.html).
+    ///
+    /// Returns an error if the context is not in the [`Ready`](../enum.State.html#variant.Ready)
+    /// state, or if the subscription request fails.
+    pub fn new<F>(context: &mut Context, callback: F) -> Result<Self, PAErr>
+        where F: FnMut(Option<SinkInfo<'static>>) + 'static
+    {
+        if context.get_state() != State::Ready {
+            return Err(PAErr::from(Code::BadState));
+        }
+
+        let shared = Rc::new(Shared {
+            introspector: context.introspect(),
+            tracker: RefCell::new(Tracker::default()),
+            callback: RefCell::new(Box::new(callback)),
+            server_op: RefCell::new(None),
+            sink_op: RefCell::new(None),
+        });
+
+        let op = unsafe { capi::pa_context_subscribe(context.ptr,
+            subscription_masks::SERVER | subscription_masks::SINK, None, null_mut()) };
+        match op.is_null() {
+            false => unsafe { capi::pa_operation_unref(op) },
+            true => return Err(PAErr::from_context(context.ptr)),
+        }
+
+        // Any callbacks still running after the monitor is dropped only hold weak references, and
+        // so will do nothing
+        let weak = Rc::downgrade(&shared);
+        context.set_subscribe_callback(Some(Box::new(move |event| {
+            if let Some(shared) = weak.upgrade() {
+                let current = shared.tracker.borrow().current_index();
+                if is_relevant(&event, current) {
+                    refresh(&shared);
+                }
+            }
+        })));
+
+        refresh(&shared);
+
+        Ok(Self {
+            _shared: shared,
+            _mainloop: Rc::clone(&context.mainloop),
+        })
+    }
+}
+
+/// Starts a lookup of the default sink, unless one is already in progress.
+fn refresh(shared: &Rc<Shared>) {
+    let ptr = shared.introspector.context;
+    if unsafe { capi::pa_context_get_state(ptr) } != capi::PA_CONTEXT_READY {
+        return;
+    }
+    // A lookup never completing (its operation having been cancelled) must not block all others
+    let stalled = shared.tracker.borrow().in_flight && !shared.lookup_running();
+    if stalled {
+        shared.tracker.borrow_mut().abandon();
+    }
+    if !shared.tracker.borrow_mut().begin() {
+        return;
+    }
+
+    let weak = Rc::downgrade(shared);
+    let op = shared.introspector.get_server_info(move |info| {
+        let shared = match weak.upgrade() {
+            Some(shared) => shared,
+            None => return,
+        };
+        match info.default_sink_name {
+            Some(ref name) => {
+                let weak = Rc::downgrade(&shared);
+                let op = shared.introspector.get_sink_info_by_name_single(name, move |result| {
+                    if let Some(shared) = weak.upgrade() {
+                        complete(&shared, result.ok());
+                    }
+                });
+                *shared.sink_op.borrow_mut() = Some(op);
+            },
+            None => complete(&shared, None),
+        }
+    });
+    *shared.sink_op.borrow_mut() = None;
+    *shared.server_op.borrow_mut() = Some(op);
+}
+
+/// Handles the result of a lookup.
+fn complete(shared: &Rc<Shared>, info: Option<SinkInfo<'static>>) {
+    let (changed, again) = shared.tracker.borrow_mut().finish(info.as_ref().map(Snapshot::from));
+    if changed {
+        (shared.callback.borrow_mut())(info);
+    }
+    if again {
+        refresh(shared);
+    }
+}
+
+/// The sample rate of peak meter streams, i.e. the peak detection resolution (in Hz).
+const PEAK_RATE: u32 = 100;
+
+/// What a [`PeakMeter`](struct.PeakMeter.html) measures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MeterTarget {
+    /// A source, specified by name, such as the monitor source of a sink.
+    Device(String),
+    /// A single sink input (playback stream), specified by index.
+    SinkInput(u32),
+}
+
+/// A record stream, preconfigured for level metering, delivering per-channel peak values.
+///
+/// The stream records in 32-bit float format at a low rate, with peak detection enabled, such that
+/// each frame holds the peak values over its period, and with the fragment size tuned for the
+/// requested update interval; the largest values of each channel over each fragment are given to
+/// the callback. Peak values are linear, nominally within `0.0..=1.0`.
+///
+/// The stream does not inhibit auto-suspension of the device. It is disconnected when the meter
+/// is dropped. The meter may be dropped from within its own callback, in which case this happens
+/// on the next mainloop iteration.
+pub struct PeakMeter {
+    stream: Rc<RefCell<Stream>>,
+    /// For deferring teardown, should the meter be dropped from within its own callback.
+    mainloop: ErasedMainloop,
+}
+
+impl PeakMeter {
+    /// Creates a peak meter, connecting its stream.
+    ///
+    /// # Params
+    ///
+    /// * `context`: The context to create the stream in
+    /// * `target`: What to meter
+    /// * `channels`: The number of channels to meter (the server remaps to this as necessary)
+    /// * `interval`: The desired update interval, rounded to a multiple of the peak detection
+    ///   period of 10 milliseconds (with a minimum of one period)
+    /// * `callback`: Called with the peak value of each channel, upon each update
+    pub fn new<F>(context: &mut Context, target: MeterTarget, channels: u8, interval: MicroSeconds,
+        callback: F) -> Result<Self, PAErr>
+        where F: FnMut(&[f32]) + 'static
+    {
+        let spec = sample::Spec {
+            format: sample::SAMPLE_FLOAT32NE,
+            channels: channels,
+            rate: PEAK_RATE,
+        };
+        if !spec.is_valid() {
+            return Err(PAErr::from(Code::Invalid));
+        }
+        let attr = buffer_attr(&spec, interval);
+
+        let mut stream = Stream::new(context, "Peak detect", &spec, None)
+            .ok_or_else(|| context.errno())?;
+        let mut flags = stream::Flags::PEAK_DETECT | stream::Flags::ADJUST_LATENCY |
+            stream::Flags::DONT_INHIBIT_AUTO_SUSPEND;
+        let dev = match target {
+            MeterTarget::Device(ref name) => Some(name.as_str()),
+            MeterTarget::SinkInput(index) => {
+                stream.set_monitor_stream(index)?;
+                flags |= stream::Flags::DONT_MOVE;
+                None
+            },
+        };
+
+        let stream = Rc::new(RefCell::new(stream));
+        let weak = Rc::downgrade(&stream);
+        let mut callback = callback;
+        let mut peaks = vec![0f32; channels as usize];
+        stream.borrow_mut().set_read_callback(Some(Box::new(move |_| {
+            let stream = match weak.upgrade() {
+                Some(stream) => stream,
+                None => return,
+            };
+            let mut stream = match stream.try_borrow_mut() {
+                Ok(stream) => stream,
+                Err(_) => return,
+            };
+            for peak in peaks.iter_mut() {
+                *peak = 0.0;
+            }
+            let mut have_data = false;
+            loop {
+                match stream.peek() {
+                    Ok(PeekResult::Empty) | Err(_) => break,
+                    Ok(PeekResult::Hole(_)) => {},
+                    Ok(PeekResult::Data(data)) => have_data |= fold_peaks(data, &mut peaks),
+                }
+                if stream.discard().is_err() {
+                    break;
+                }
+            }
+            if have_data {
+                callback(&peaks);
+            }
+        })));
+
+        stream.borrow_mut().connect_record(dev, Some(&attr), flags)?;
+        Ok(Self { stream: stream, mainloop: context.erased_mainloop() })
+    }
+}
+
+impl Drop for PeakMeter {
+    fn drop(&mut self) {
+        match self.stream.try_borrow_mut() {
+            Ok(mut stream) => close_meter_stream(&mut stream),
+            // Dropped from within the callback, which the stream (owning the read callback closure)
+            // must outlive, thus the teardown (and dropping of the stream) is deferred until the
+            // next mainloop iteration.
+            Err(_) => {
+                let stream = Rc::clone(&self.stream);
+                self.mainloop.defer_once(Box::new(move || {
+                    close_meter_stream(&mut stream.borrow_mut());
+                }));
+            },
+        }
+    }
+}
+
+/// Clears the read callback of a peak meter stream, and disconnects it.
+fn close_meter_stream(stream: &mut Stream) {
+    stream.set_read_callback(None);
+    let _ = stream.disconnect();
+}
+
+/// Gets the buffer attributes of a peak meter stream of the given spec and update interval.
+fn buffer_attr(spec: &sample::Spec, interval: MicroSeconds) -> BufferAttr {
+    let period = MICROS_PER_SEC / spec.rate as u64;
+    let frames = ((interval.0 + period / 2) / period).max(1).min(spec.rate as u64);
+    BufferAttr {
+        maxlength: std::u32::MAX,
+        tlength: std::u32::MAX,
+        prebuf: std::u32::MAX,
+        minreq: std::u32::MAX,
+        fragsize: frames as u32 * spec.frame_size() as u32,
+    }
+}
+
+/// Raises each channel’s value in `peaks` to the largest (absolute) value of that channel in the
+/// given interleaved 32-bit float data, returning whether or not any complete frames were found.
+fn fold_peaks(data: &[u8], peaks: &mut [f32]) -> bool {
+    let sample_size = mem::size_of::<f32>();
+    let frame_size = sample_size * peaks.len();
+    if frame_size == 0 {
+        return false;
+    }
+    let mut found = false;
+    for frame in data.chunks_exact(frame_size) {
+        for (peak, bytes) in peaks.iter_mut().zip(frame.chunks_exact(sample_size)) {
+            let mut raw = [0u8; 4];
+            raw.copy_from_slice(bytes);
+            let value = f32::from_bits(u32::from_ne_bytes(raw)).abs();
+            if value > *peak {
+                *peak = value;
+            }
+        }
+        found = true;
+    }
+    found
+}
+
+/// The default delay before the first reconnection attempt.
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(250);
+/// The default maximum delay between reconnection attempts.
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// A context that automatically reconnects, should the connection fail (e.g. upon the server
+/// being restarted), by creating and connecting a new context.
+///
+/// Each context created is a “generation”. Upon a generation becoming ready, the
+/// [`on_connected`] hook is called with it, giving the application opportunity to (re-)establish
+/// its streams and subscriptions. Upon it failing, the [`on_disconnected`] hook is called (if it
+/// had become ready), and a new generation is connected after a delay, which starts at 250
+/// milliseconds, doubling upon each consecutive failure, up to a maximum of 30 seconds (see
+/// [`set_backoff`]). The delay is reset once a generation becomes ready.
+///
+/// A failed generation is released before the next is connected. The callbacks set upon it
+/// through the [`Context`] object given to the hook (state, event and subscribe callbacks) are
+/// released along with it, and so nothing from it is delivered thereafter. Streams and other
+/// objects created upon it however belong to the application, and should be dropped by the
+/// [`on_disconnected`] hook (they are unusable anyway).
+///
+/// Dropping the wrapper cancels any pending reconnection attempt, and disconnects the current
+/// generation, without calling any hooks.
+///
+/// # Notes
+///
+/// The wrapper owns the contexts, and installs its own state callback upon each, which must not be
+/// changed. The current generation is only accessible through [`with_context`] and the hooks, and
+/// the former must not be used from within the latter.
+///
+/// The hooks should be set before the mainloop is next run, since connecting begins immediately.
+///
+/// [`on_connected`]: #method.on_connected
+/// [`on_disconnected`]: #method.on_disconnected
+/// [`set_backoff`]: #method.set_backoff
+/// [`with_context`]: #method.with_context
+/// [`Context`]: ../struct.Context.html
+pub struct ReconnectingContext {
+    shared: Rc<Reconnector>,
+}
+
+struct Reconnector {
+    mainloop: ErasedMainloop,
+    name: String,
+    proplist: Option<Proplist>,
+    server: Option<String>,
+    flags: Flags,
+    /// The current generation, if any.
+    context: RefCell<Option<Context>>,
+    supervisor: RefCell<Supervisor>,
+    /// The timer used for reconnection attempts, created upon first use, and reused thereafter
+    /// (it cannot be released from within its own callback).
+    retry_timer: RefCell<Option<TimeEvent<ErasedInner>>>,
+    on_connected: RefCell<Option<Box<dyn FnMut(&mut Context)>>>,
+    on_disconnected: RefCell<Option<Box<dyn FnMut()>>>,
+}
+
+/// Exponentially increasing delays.
+#[derive(Debug, Clone)]
+struct Backoff {
+    initial: MicroSeconds,
+    max: MicroSeconds,
+    /// The delay to give next, `None` after a reset.
+    next: Option<MicroSeconds>,
+}
+
+impl Backoff {
+    fn new(initial: Duration, max: Duration) -> Self {
+        let initial = MicroSeconds::from(initial);
+        Self { initial: initial, max: MicroSeconds::from(max).max(initial), next: None }
+    }
+
+    /// Gets the next delay, doubling the one after it.
+    fn next(&mut self) -> MicroSeconds {
+        let delay = self.next.unwrap_or(self.initial);
+        self.next = Some(MicroSeconds(delay.0.saturating_mul(2)).min(self.max));
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.next = None;
+    }
+}
+
+/// What to do in response to a generation’s state changing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Action {
+    /// Nothing to do.
+    None,
+    /// Call the connected hook.
+    Connected,
+    /// Connect a new generation after the given delay, first calling the disconnected hook if
+    /// `disconnected` is `true`.
+    Retry { disconnected: bool, delay: MicroSeconds },
+}
+
+/// Generation tracking and retry decisions.
+#[derive(Debug)]
+struct Supervisor {
+    generation: u64,
+    /// Whether or not the current generation has not (yet) failed.
+    live: bool,
+    /// Whether or not the current generation became ready.
+    connected: bool,
+    backoff: Backoff,
+}
+
+impl Supervisor {
+    fn new(backoff: Backoff) -> Self {
+        Self { generation: 0, live: false, connected: false, backoff: backoff }
+    }
+
+    /// Starts a new generation, returning its number.
+    fn begin(&mut self) -> u64 {
+        self.generation += 1;
+        self.live = true;
+        self.connected = false;
+        self.generation
+    }
+
+    /// Called for each state change of a generation.
+    fn state_changed(&mut self, generation: u64, state: State) -> Action {
+        if generation != self.generation || !self.live {
+            return Action::None;
+        }
+        match state {
+            State::Ready => {
+                self.connected = true;
+                self.backoff.reset();
+                Action::Connected
+            },
+            State::Failed | State::Terminated => self.failed(generation),
+            _ => Action::None,
+        }
+    }
+
+    /// Called should a generation fail, including upon failing to create or connect it. Only the
+    /// first failure of the current generation results in a retry.
+    fn failed(&mut self, generation: u64) -> Action {
+        if generation != self.generation || !self.live {
+            return Action::None;
+        }
+        self.live = false;
+        let disconnected = mem::replace(&mut self.connected, false);
+        Action::Retry { disconnected: disconnected, delay: self.backoff.next() }
+    }
+}
+
+impl ReconnectingContext {
+    /// Creates a reconnecting context, and starts connecting it.
+    ///
+    /// The parameters are those given to [`Context::new_with_proplist`] and [`Context::connect`]
+    /// for each generation (with [`Context::new`] used where `proplist` is `None`). The property
+    /// list is copied.
+    ///
+    /// Returns an error if the first generation could not be created. Failing to connect it is not
+    /// an error, it is retried as usual.
+    ///
+    /// [`Context::new_with_proplist`]: ../struct.Context.html#method.new_with_proplist
+    /// [`Context::connect`]: ../struct.Context.html#method.connect
+    /// [`Context::new`]: ../struct.Context.html#method.new
+    pub fn new<M>(mainloop: &M, name: &str, proplist: Option<&Proplist>, server: Option<&str>,
+        flags: Flags) -> Result<Self, ContextError>
+        where M: AnyMainloop + ?Sized
+    {
+        let shared = Rc::new(Reconnector {
+            mainloop: mainloop.erased(),
+            name: name.to_string(),
+            proplist: proplist.cloned(),
+            server: server.map(String::from),
+            flags: flags,
+            context: RefCell::new(None),
+            supervisor: RefCell::new(Supervisor::new(
+                Backoff::new(RECONNECT_INITIAL_DELAY, RECONNECT_MAX_DELAY))),
+            retry_timer: RefCell::new(None),
+            on_connected: RefCell::new(None),
+            on_disconnected: RefCell::new(None),
+        });
+        // Report creation errors for the first generation, since they are most likely permanent
+        // (e.g. an invalid name)
+        let context = shared.create_context()?;
+        connect(&shared, context);
+        Ok(Self { shared: shared })
+    }
+
+    /// Sets the hook called with each generation upon it becoming ready.
+    pub fn on_connected<F>(&mut self, callback: F)
+        where F: FnMut(&mut Context) + 'static
+    {
+        *self.shared.on_connected.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Sets the hook called upon a generation that had become ready failing.
+    pub fn on_disconnected<F>(&mut self, callback: F)
+        where F: FnMut() + 'static
+    {
+        *self.shared.on_disconnected.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Sets the initial and maximum delays between reconnection attempts, taking effect from the
+    /// next failure.
+    ///
+    /// The maximum is raised to the initial delay, should it be smaller.
+    pub fn set_backoff(&mut self, initial: Duration, max: Duration) {
+        self.shared.supervisor.borrow_mut().backoff = Backoff::new(initial, max);
+    }
+
+    /// Checks whether or not the current generation is ready.
+    pub fn is_connected(&self) -> bool {
+        let supervisor = self.shared.supervisor.borrow();
+        supervisor.live && supervisor.connected
+    }
+
+    /// Calls the given closure with the current generation, if it is ready, giving its result.
+    ///
+    /// Must not be used from within the hooks.
+    pub fn with_context<F, R>(&self, f: F) -> Option<R>
+        where F: FnOnce(&mut Context) -> R
+    {
+        if !self.is_connected() {
+            return None;
+        }
+        self.shared.context.borrow_mut().as_mut().map(f)
+    }
+}
+
+impl Drop for ReconnectingContext {
+    fn drop(&mut self) {
+        // The callbacks only hold weak references, however these can still be upgraded until this
+        // drop completes, so everything is explicitly torn down
+        let _ = self.shared.retry_timer.borrow_mut().take();
+        if let Some(mut context) = self.shared.context.borrow_mut().take() {
+            context.set_state_callback(None);
+            context.disconnect();
+        }
+    }
+}
+
+impl Reconnector {
+    fn create_context(&self) -> Result<Context, ContextError> {
+        match self.proplist {
+            Some(ref proplist) => Context::new_with_proplist(&self.mainloop, &self.name, proplist),
+            None => Context::new(&self.mainloop, &self.name),
+        }
+    }
+
+    /// Starts the retry timer.
+    fn schedule_retry(shared: &Rc<Self>, delay: MicroSeconds) {
+        let deadline = MonotonicTs::now() + delay;
+        let mut timer = shared.retry_timer.borrow_mut();
+        match timer.as_mut() {
+            Some(timer) => timer.restart_rt(deadline),
+            None => {
+                let weak = Rc::downgrade(shared);
+                *timer = shared.mainloop.clone().new_timer_event_rt(deadline, Box::new(move |_| {
+                    retry(&weak);
+                }));
+            },
+        }
+    }
+}
+
+/// Connects the given context as a new generation.
+fn connect(shared: &Rc<Reconnector>, mut context: Context) {
+    let generation = shared.supervisor.borrow_mut().begin();
+
+    let weak = Rc::downgrade(shared);
+    let ptr = context.ptr;
+    context.set_state_callback(Some(Box::new(move || {
+        if let Some(shared) = weak.upgrade() {
+            let state = unsafe { capi::pa_context_get_state(ptr) }.into();
+            let action = shared.supervisor.borrow_mut().state_changed(generation, state);
+            act(&shared, action);
+        }
+    })));
+
+    let result = context.connect(shared.server.as_ref().map(String::as_str), shared.flags, None);
+    // Note, the state callback may have been called synchronously, so the context is only stored
+    // now, not being needed by it
+    *shared.context.borrow_mut() = Some(context);
+    if result.is_err() {
+        let action = shared.supervisor.borrow_mut().failed(generation);
+        act(shared, action);
+    }
+}
+
+/// Carries out an action, as decided by the supervisor.
+fn act(shared: &Rc<Reconnector>, action: Action) {
+    match action {
+        Action::None => {},
+        Action::Connected => {
+            let mut context = shared.context.borrow_mut();
+            let mut hook = shared.on_connected.borrow_mut();
+            if let (Some(context), Some(hook)) = (context.as_mut(), hook.as_mut()) {
+                hook(context);
+            }
+        },
+        Action::Retry { disconnected, delay } => {
+            if disconnected {
+                if let Some(hook) = shared.on_disconnected.borrow_mut().as_mut() {
+                    hook();
+                }
+            }
+            // Note, the failed generation cannot be released here, since we may be within its own
+            // state callback; it is released upon the retry
+            Reconnector::schedule_retry(shared, delay);
+        },
+    }
+}
+
+/// Releases the failed generation, and connects a new one, as fired by the retry timer.
+fn retry(weak: &Weak<Reconnector>) {
+    let shared = match weak.upgrade() {
+        Some(shared) => shared,
+        None => return,
+    };
+    let _ = shared.context.borrow_mut().take();
+    match shared.create_context() {
+        Ok(context) => connect(&shared, context),
+        Err(_) => {
+            let generation = shared.supervisor.borrow_mut().begin();
+            let action = shared.supervisor.borrow_mut().failed(generation);
+            act(&shared, action);
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(index: u32, mute: bool) -> Snapshot {
+        let mut volume = ChannelVolumes::default();
+        volume.set(2, crate::volume::VOLUME_NORM);
+        Snapshot { name: Some(format!("sink{}", index)), index: index, volume: volume, mute: mute }
+    }
+
+    #[test]
+    fn tracker_coalescing() {
+        let mut tracker = Tracker::default();
+        assert!(tracker.begin());
+        // A burst of events while the lookup is in progress
+        assert!(!tracker.begin());
+        assert!(!tracker.begin());
+        assert!(!tracker.begin());
+        // The first result is always reported, with a single follow up lookup
+        assert_eq!(tracker.finish(Some(snapshot(1, false))), (true, true));
+        assert!(tracker.begin());
+        // Unchanged, nothing further to do
+        assert_eq!(tracker.finish(Some(snapshot(1, false))), (false, false));
+        assert_eq!(tracker.current_index(), Some(1));
+
+        // A lookup abandoned (never completing) does not block further lookups
+        assert!(tracker.begin());
+        assert!(!tracker.begin());
+        tracker.abandon();
+        assert!(tracker.begin());
+        assert_eq!(tracker.finish(Some(snapshot(1, false))), (false, true));
+    }
+
+    #[test]
+    fn tracker_changes() {
+        let mut tracker = Tracker::default();
+
+        // Nothing to begin with, is still reported
+        assert!(tracker.begin());
+        assert_eq!(tracker.finish(None), (true, false));
+        assert!(tracker.begin());
+        assert_eq!(tracker.finish(None), (false, false));
+
+        assert!(tracker.begin());
+        assert_eq!(tracker.finish(Some(snapshot(1, false))), (true, false));
+        // Mute change
+        assert!(tracker.begin());
+        assert_eq!(tracker.finish(Some(snapshot(1, true))), (true, false));
+        // Volume change
+        let mut changed = snapshot(1, true);
+        changed.volume.get_mut()[0] = crate::volume::VOLUME_MUTED;
+        assert!(tracker.begin());
+        assert_eq!(tracker.finish(Some(changed)), (true, false));
+        // Identity change
+        assert!(tracker.begin());
+        assert_eq!(tracker.finish(Some(snapshot(2, true))), (true, false));
+        // Disappearance
+        assert!(tracker.begin());
+        assert_eq!(tracker.finish(None), (true, false));
+        assert_eq!(tracker.current_index(), None);
+    }
+
+    #[test]
+    fn event_relevance() {
+        let event = |facility, operation, index| SubscriptionEvent {
+            facility: Some(facility),
+            operation: Some(operation),
+            index: index,
+        };
+        assert!(is_relevant(&event(Facility::Server, Operation::Changed, 0), Some(1)));
+        assert!(is_relevant(&event(Facility::Sink, Operation::Changed, 1), Some(1)));
+        assert!(is_relevant(&event(Facility::Sink, Operation::Removed, 1), Some(1)));
+        assert!(is_relevant(&event(Facility::Sink, Operation::New, 5), Some(1)));
+        assert!(is_relevant(&event(Facility::Sink, Operation::New, 5), None));
+        assert!(!is_relevant(&event(Facility::Sink, Operation::Changed, 2), Some(1)));
+        assert!(!is_relevant(&event(Facility::Source, Operation::Changed, 1), Some(1)));
+    }
+
+    #[test]
+    fn requires_ready_context() {
+        let mainloop = crate::mainloop::standard::Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "test").unwrap();
+        let monitor = DefaultDeviceMonitor::new(&mut context, |_| {});
+        assert_eq!(monitor.err(), Some(PAErr::from(Code::BadState)));
+    }
+
+    #[test]
+    fn peak_meter_buffer_attr() {
+        let spec = sample::Spec { format: sample::SAMPLE_FLOAT32NE, channels: 2, rate: PEAK_RATE };
+        // 10 ms periods, 8 byte frames
+        assert_eq!(buffer_attr(&spec, MicroSeconds(50_000)).fragsize, 5 * 8);
+        assert_eq!(buffer_attr(&spec, MicroSeconds(44_000)).fragsize, 4 * 8);
+        assert_eq!(buffer_attr(&spec, MicroSeconds(46_000)).fragsize, 5 * 8);
+        // At least one period, at most one second
+        assert_eq!(buffer_attr(&spec, MicroSeconds(0)).fragsize, 8);
+        assert_eq!(buffer_attr(&spec, MicroSeconds(2_000)).fragsize, 8);
+        assert_eq!(buffer_attr(&spec, MicroSeconds(5 * MICROS_PER_SEC)).fragsize, 100 * 8);
+        let attr = buffer_attr(&spec, MicroSeconds(50_000));
+        assert_eq!(attr.maxlength, std::u32::MAX);
+        assert_eq!(attr.tlength, std::u32::MAX);
+    }
+
+    #[test]
+    fn peak_folding() {
+        let samples: [f32; 7] = [0.25, -0.5, -0.75, 0.125, 0.5, 0.0, 1.0];
+        let mut data = Vec::new();
+        for s in samples.iter() {
+            data.extend_from_slice(&s.to_bits().to_ne_bytes());
+        }
+
+        let mut peaks = [0f32; 2];
+        // The trailing incomplete frame is ignored
+        assert!(fold_peaks(&data, &mut peaks));
+        assert_eq!(peaks, [0.75, 0.5]);
+        // Folds in further data
+        let more: Vec<u8> = [1.0f32, 0.25].iter()
+            .flat_map(|s| s.to_bits().to_ne_bytes().to_vec())
+            .collect();
+        assert!(fold_peaks(&more, &mut peaks));
+        assert_eq!(peaks, [1.0, 0.5]);
+        // Nothing complete
+        let mut peaks = [0f32; 2];
+        assert!(!fold_peaks(&data[..4], &mut peaks));
+        assert!(!fold_peaks(&[], &mut peaks));
+        assert_eq!(peaks, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn backoff() {
+        let mut backoff = Backoff::new(Duration::from_millis(250), Duration::from_secs(2));
+        let delays: Vec<u64> = (0..6).map(|_| backoff.next().0).collect();
+        assert_eq!(delays, [250_000, 500_000, 1_000_000, 2_000_000, 2_000_000, 2_000_000]);
+        backoff.reset();
+        assert_eq!(backoff.next(), MicroSeconds(250_000));
+
+        // The maximum is raised to the initial delay
+        let mut backoff = Backoff::new(Duration::from_secs(5), Duration::from_secs(1));
+        assert_eq!(backoff.next(), MicroSeconds(5_000_000));
+        assert_eq!(backoff.next(), MicroSeconds(5_000_000));
+    }
+
+    #[test]
+    fn supervisor_generations() {
+        let initial = MicroSeconds(100_000);
+        let mut supervisor = Supervisor::new(Backoff::new(Duration::from_millis(100),
+            Duration::from_secs(10)));
+
+        // Failing before becoming ready, backing off, without reporting a disconnection
+        let first = supervisor.begin();
+        assert_eq!(supervisor.state_changed(first, State::Connecting), Action::None);
+        assert_eq!(supervisor.state_changed(first, State::Failed),
+            Action::Retry { disconnected: false, delay: initial });
+        // Only the first failure counts, e.g. should connecting also return an error
+        assert_eq!(supervisor.failed(first), Action::None);
+        assert_eq!(supervisor.state_changed(first, State::Terminated), Action::None);
+
+        let second = supervisor.begin();
+        assert_eq!(supervisor.failed(second),
+            Action::Retry { disconnected: false, delay: MicroSeconds(200_000) });
+
+        // Becoming ready resets the backoff, and failing thereafter reports a disconnection
+        let third = supervisor.begin();
+        assert_eq!(supervisor.state_changed(third, State::Authorizing), Action::None);
+        assert_eq!(supervisor.state_changed(third, State::Ready), Action::Connected);
+        assert!(supervisor.connected);
+        // Stale events from earlier generations are ignored
+        assert_eq!(supervisor.state_changed(first, State::Ready), Action::None);
+        assert_eq!(supervisor.state_changed(second, State::Failed), Action::None);
+        assert_eq!(supervisor.failed(second), Action::None);
+        assert_eq!(supervisor.state_changed(third, State::Failed),
+            Action::Retry { disconnected: true, delay: initial });
+        assert!(!supervisor.connected);
+
+        let fourth = supervisor.begin();
+        assert_eq!(supervisor.state_changed(third, State::Ready), Action::None);
+        assert_eq!(supervisor.state_changed(fourth, State::Ready), Action::Connected);
+    }
+
+    #[test]
+    fn reconnecting_context_drop() {
+        let mainloop = crate::mainloop::standard::Mainloop::new().unwrap();
+        let connected = Rc::new(RefCell::new(false));
+        {
+            let flags = Flags::NOAUTOSPAWN;
+            let mut reconnecting = ReconnectingContext::new(&mainloop, "test", None,
+                Some("unix:/nonexistent/pulse-binding-test"), flags).unwrap();
+            let connected_ref = Rc::clone(&connected);
+            reconnecting.on_connected(move |_| *connected_ref.borrow_mut() = true);
+            reconnecting.set_backoff(Duration::from_millis(1), Duration::from_millis(1));
+            assert!(!reconnecting.is_connected());
+            assert_eq!(reconnecting.with_context(|_| ()), None);
+        }
+        // Nothing is left to fire, with everything released
+        assert!(!*connected.borrow());
+        assert_eq!(Rc::strong_count(&connected), 1);
+
+        assert_eq!(ReconnectingContext::new(&mainloop, "te\0st", None, None, Flags::NOFLAGS).err(),
+            Some(ContextError::InvalidName));
+    }
+}
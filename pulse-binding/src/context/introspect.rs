@@ -219,6 +219,8 @@
 //! [`Introspector::stat`]: struct.Introspector.html#method.stat
 //! [`Introspector::unload_module`]: struct.Introspector.html#method.unload_module
 
+use std::any::Any;
+use std::rc::Rc;
 use std::mem;
 use std::os::raw::c_void;
 use std::ffi::{CStr, CString};
@@ -243,29 +245,46 @@ use capi::pa_sample_info as SampleInfoInternal;
 use super::{Context, ContextInternal};
 use crate::{def, sample, channelmap, format, direction};
 use crate::time::MicroSeconds;
+use crate::error::{PAErr, Code};
 use crate::callbacks::{ListResult, box_closure_get_capi_ptr, callback_for_list_instance, get_su_capi_params, get_su_callback, ListInstanceCallback};
 use crate::volume::{ChannelVolumes, Volume};
-use crate::{operation::Operation, proplist::Proplist};
+use crate::operation::Operation;
+use crate::proplist::ProplistRef;
 
 pub use capi::pa_stat_info as StatInfo;
 
 /// A wrapper object providing introspection routines to a context.
+///
+/// Rather than borrowing the context, an introspector holds a reference on it. Any number of
+/// introspectors may thus coexist with each other and with normal use of the context, and an
+/// introspector remains valid even should the `Context` object be dropped, keeping alive both the
+/// context and the mainloop internals it uses. Callbacks given to its methods similarly do not
+/// borrow it; they may outlive it, since pending operations hold their own reference on the
+/// context.
+///
+/// Note, as with [`Context`], this is neither `Send` nor `Sync`, since it holds an `Rc` keeping the
+/// mainloop alive.
+///
+/// [`Context`]: ../struct.Context.html
 pub struct Introspector {
-    context: *mut super::ContextInternal,
+    pub(crate) context: *mut super::ContextInternal,
+    /// Keeps the mainloop alive for as long as the introspector exists.
+    _mainloop: Rc<dyn Any>,
 }
 
-unsafe impl Send for Introspector {}
-unsafe impl Sync for Introspector {}
-
 impl Context {
     /// Gets an introspection object linked to the current context, giving access to introspection
     /// routines.
     ///
+    /// This takes only a shared borrow, and the introspector does not borrow the context at all,
+    /// instead holding a reference on it, see
+    /// [`Introspector`](introspect/struct.Introspector.html).
+    ///
     /// See [`context::introspect`](introspect/index.html).
     #[inline]
     pub fn introspect(&self) -> Introspector {
         unsafe { capi::pa_context_ref(self.ptr) };
-        Introspector::from_raw(self.ptr)
+        Introspector::from_raw(self.ptr, Rc::clone(&self.mainloop))
     }
 }
 
@@ -273,8 +292,8 @@ impl Introspector {
     /// Creates a new `Introspector` from an existing
     /// [`ContextInternal`](../struct.ContextInternal.html) pointer.
     #[inline(always)]
-    fn from_raw(context: *mut ContextInternal) -> Self {
-        Self { context: context }
+    fn from_raw(context: *mut ContextInternal, mainloop: Rc<dyn Any>) -> Self {
+        Self { context: context, _mainloop: mainloop }
     }
 }
 
@@ -285,6 +304,75 @@ impl Drop for Introspector {
     }
 }
 
+/// Accumulates the items of a list callback, for the `_collected` list variants.
+///
+/// The items are converted to owned copies, and the callback is called exactly once, upon reaching
+/// the end of the list, or should an error occur. The collector is kept within the list callback
+/// closure, and thus is freed along with it.
+struct ListCollector<T, F> {
+    context: *mut ContextInternal,
+    items: Vec<T>,
+    callback: Option<F>,
+}
+
+impl<T, F> ListCollector<T, F>
+    where F: FnOnce(Result<Vec<T>, PAErr>)
+{
+    fn new(context: *mut ContextInternal, callback: F) -> Self {
+        Self { context: context, items: Vec::new(), callback: Some(callback) }
+    }
+
+    fn handle<I, C>(&mut self, result: ListResult<I>, convert: C)
+        where C: FnOnce(I) -> T
+    {
+        match result {
+            ListResult::Item(i) => self.items.push(convert(i)),
+            ListResult::End => {
+                if let Some(callback) = self.callback.take() {
+                    callback(Ok(mem::replace(&mut self.items, Vec::new())));
+                }
+            },
+            ListResult::Error => {
+                self.items.clear();
+                if let Some(callback) = self.callback.take() {
+                    callback(Err(PAErr::from_context(self.context)));
+                }
+            },
+        }
+    }
+}
+
+/// Takes the single item of a by-index or by-name lookup, for the `_single` variants.
+///
+/// The server normally reports a non-existent entity as an error, but should it instead simply
+/// give an empty list, that is mapped to the same `NoEntity` error.
+fn single_item<T>(items: Vec<T>) -> Result<T, PAErr> {
+    items.into_iter().next().ok_or(PAErr::from(Code::NoEntity))
+}
+
+/// Defines a `_single` variant of a by-index or by-name lookup method, giving an owned copy.
+macro_rules! single_lookup {
+    (
+        $(#[$outer:meta])*
+        pub fn $name:ident($key:ident: $key_t:ty) => $lookup:ident, $info:ident;
+    ) => {
+        $(#[$outer])*
+        ///
+        /// The callback is called exactly once, with either the item, or an error: `NoEntity`
+        /// should no such item exist, otherwise the context’s error should the request fail.
+        ///
+        /// Panics on error, i.e. invalid arguments or state.
+        pub fn $name<F>(&self, $key: $key_t, callback: F)
+            -> Operation<dyn FnMut(ListResult<&$info>)>
+            where F: FnOnce(Result<$info<'static>, PAErr>) + 'static
+        {
+            let mut collector = ListCollector::new(self.context,
+                move |r: Result<Vec<$info<'static>>, PAErr>| callback(r.and_then(single_item)));
+            self.$lookup($key, move |result| collector.handle(result, |i| i.to_owned()))
+        }
+    };
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 // Sink info
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -324,6 +412,16 @@ impl<'a> SinkPortInfo<'a> {
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> SinkPortInfo<'static> {
+        SinkPortInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            description: self.description.clone().map(|s| Cow::Owned(s.into_owned())),
+            ..*self
+        }
+    }
 }
 
 /// Stores information about sinks.
@@ -356,10 +454,16 @@ pub struct SinkInfo<'a> {
     pub latency: MicroSeconds,
     /// Driver name.
     pub driver: Option<Cow<'a, str>>,
-    /// Flags.
+    /// Flags, see [`def::SinkFlagSet`]. These may be more easily checked with methods such as
+    /// [`supports_hardware_volume`] and [`has_decibel_volume`]. Any flags unknown to this binding
+    /// are retained.
+    ///
+    /// [`def::SinkFlagSet`]: ../../def/struct.SinkFlagSet.html
+    /// [`supports_hardware_volume`]: #method.supports_hardware_volume
+    /// [`has_decibel_volume`]: #method.has_decibel_volume
     pub flags: def::SinkFlagSet,
     /// Property list.
-    pub proplist: Proplist,
+    pub proplist: ProplistRef<'a>,
     /// The latency this device has been configured to.
     pub configured_latency: MicroSeconds,
     /// Some kind of “base” volume that refers to unamplified/unattenuated volume in the context of
@@ -368,6 +472,11 @@ pub struct SinkInfo<'a> {
     /// State.
     pub state: def::SinkState,
     /// Number of volume steps for sinks which do not support arbitrary volumes.
+    ///
+    /// Volume UIs may use this as the granularity of volume controls, where it is non-zero and the
+    /// sink lacks [`DECIBEL_VOLUME`] support.
+    ///
+    /// [`DECIBEL_VOLUME`]: ../../def/struct.SinkFlagSet.html#associatedconstant.DECIBEL_VOLUME
     pub n_volume_steps: u32,
     /// Card index, or `None` if invalid.
     pub card: Option<u32>,
@@ -376,7 +485,7 @@ pub struct SinkInfo<'a> {
     /// Pointer to active port in the set, or `None`.
     pub active_port: Option<Box<SinkPortInfo<'a>>>,
     /// Set of formats supported by the sink.
-    pub formats: Vec<format::Info>,
+    pub formats: Vec<format::InfoRef<'a>>,
 }
 
 impl<'a> SinkInfo<'a> {
@@ -397,7 +506,7 @@ impl<'a> SinkInfo<'a> {
         for i in 0..src.n_formats as isize {
             let indexed_ptr = unsafe { (*src.formats.offset(i)) as *mut format::InfoInternal };
             if !indexed_ptr.is_null() {
-                formats_vec.push(format::Info::from_raw_weak(indexed_ptr));
+                formats_vec.push(format::InfoRef::from_raw(indexed_ptr));
             }
         }
 
@@ -430,11 +539,12 @@ impl<'a> SinkInfo<'a> {
                     false => Some(CStr::from_ptr(src.driver).to_string_lossy()),
                     true => None,
                 },
-                flags: src.flags,
-                proplist: Proplist::from_raw_weak(src.proplist),
+                flags: def::SinkFlagSet::from_bits_retain(src.flags),
+                proplist: ProplistRef::from_raw(src.proplist),
                 configured_latency: MicroSeconds(src.configured_latency),
                 base_volume: Volume(src.base_volume),
-                state: src.state.into(),
+                // Read as a raw integer, since a newer server may give a value unknown to us
+                state: std::ptr::read(&src.state as *const _ as *const i32).into(),
                 n_volume_steps: src.n_volume_steps,
                 card: match src.card {
                     def::INVALID_INDEX => None,
@@ -449,6 +559,106 @@ impl<'a> SinkInfo<'a> {
             }
         }
     }
+
+    /// Checks whether the sink supports hardware volume control.
+    #[inline]
+    pub fn supports_hardware_volume(&self) -> bool {
+        self.flags.supports_hardware_volume()
+    }
+
+    /// Checks whether the sink supports hardware mute control.
+    #[inline]
+    pub fn supports_hardware_mute(&self) -> bool {
+        self.flags.supports_hardware_mute()
+    }
+
+    /// Checks whether the sink volume can be translated to dB.
+    #[inline]
+    pub fn has_decibel_volume(&self) -> bool {
+        self.flags.has_decibel_volume()
+    }
+
+    /// Checks whether the sink supports latency querying.
+    #[inline]
+    pub fn supports_latency(&self) -> bool {
+        self.flags.supports_latency()
+    }
+
+    /// Checks whether the sink latency can be adjusted dynamically.
+    #[inline]
+    pub fn has_dynamic_latency(&self) -> bool {
+        self.flags.has_dynamic_latency()
+    }
+
+    /// Gets the [`latency`](#structfield.latency), or `None` if the sink does not support latency
+    /// querying (lacking the `LATENCY` flag), or should it be the [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../../time/constant.USEC_INVALID.html
+    pub fn reported_latency(&self) -> Option<MicroSeconds> {
+        match self.supports_latency() {
+            true => self.latency.valid(),
+            false => None,
+        }
+    }
+
+    /// Gets the [`configured_latency`](#structfield.configured_latency), or `None` if the sink
+    /// does not support latency querying (lacking the `LATENCY` flag), or should it be the
+    /// [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../../time/constant.USEC_INVALID.html
+    pub fn reported_configured_latency(&self) -> Option<MicroSeconds> {
+        match self.supports_latency() {
+            true => self.configured_latency.valid(),
+            false => None,
+        }
+    }
+
+    /// Checks whether the sink is in flat volume mode.
+    #[inline]
+    pub fn has_flat_volume(&self) -> bool {
+        self.flags.has_flat_volume()
+    }
+
+    /// Checks whether the sink is a hardware device of some kind.
+    #[inline]
+    pub fn is_hardware(&self) -> bool {
+        self.flags.is_hardware()
+    }
+
+    /// Checks whether the sink is a networked device of some kind.
+    #[inline]
+    pub fn is_network(&self) -> bool {
+        self.flags.is_network()
+    }
+
+    /// Checks whether the sink allows setting what formats are supported by the connected hardware.
+    #[inline]
+    pub fn can_set_formats(&self) -> bool {
+        self.flags.can_set_formats()
+    }
+
+    /// Checks whether any of the formats supported by the sink has the given encoding, e.g. to
+    /// check for AC3 passthrough support before attempting it.
+    pub fn supports_encoding(&self, encoding: format::Encoding) -> bool {
+        self.formats.iter().any(|f| f.get_encoding() == encoding)
+    }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> SinkInfo<'static> {
+        SinkInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            description: self.description.clone().map(|s| Cow::Owned(s.into_owned())),
+            monitor_source_name: self.monitor_source_name.clone()
+                .map(|s| Cow::Owned(s.into_owned())),
+            driver: self.driver.clone().map(|s| Cow::Owned(s.into_owned())),
+            proplist: self.proplist.to_owned().into(),
+            ports: self.ports.iter().map(|p| p.to_owned()).collect(),
+            active_port: self.active_port.as_ref().map(|p| Box::new((**p).to_owned())),
+            formats: self.formats.iter().map(|f| f.to_owned().into()).collect(),
+            ..*self
+        }
+    }
 }
 
 impl Introspector {
@@ -470,6 +680,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a sink by its name, as an owned copy.
+        ///
+        /// This is an alternative to [`get_sink_info_by_name`], for looking up a single item.
+        ///
+        /// [`get_sink_info_by_name`]: #method.get_sink_info_by_name
+        pub fn get_sink_info_by_name_single(name: &str) => get_sink_info_by_name, SinkInfo;
+    }
+
     /// Gets information about a sink by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -484,6 +703,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a sink by its index, as an owned copy.
+        ///
+        /// This is an alternative to [`get_sink_info_by_index`], for looking up a single item.
+        ///
+        /// [`get_sink_info_by_index`]: #method.get_sink_info_by_index
+        pub fn get_sink_info_by_index_single(index: u32) => get_sink_info_by_index, SinkInfo;
+    }
+
     /// Gets the complete sink list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -497,29 +725,48 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInfo>)>)
     }
 
+    /// Gets the complete sink list, collected into a `Vec`.
+    ///
+    /// This is a convenience alternative to [`get_sink_info_list`], where owned copies of the items
+    /// are collected, in the order delivered, with the callback called exactly once, at the end of
+    /// the list, or with the context’s error should the request fail.
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    ///
+    /// [`get_sink_info_list`]: #method.get_sink_info_list
+    pub fn get_sink_info_list_collected<F>(&self, callback: F)
+        -> Operation<dyn FnMut(ListResult<&SinkInfo>)>
+        where F: FnOnce(Result<Vec<SinkInfo<'static>>, PAErr>) + 'static
+    {
+        let mut collector = ListCollector::new(self.context, callback);
+        self.get_sink_info_list(move |result| collector.handle(result, |i| i.to_owned()))
+    }
+
     /// Sets the volume of a sink device specified by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_sink_volume_by_index(&mut self, index: u32, volume: &ChannelVolumes,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_sink_volume_by_index(self.context, index,
             volume.as_ref(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the volume of a sink device specified by its name.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_sink_volume_by_name(&mut self, name: &str, volume: &ChannelVolumes,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -530,32 +777,38 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_set_sink_volume_by_name(self.context, c_name.as_ptr(),
             volume.as_ref(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the mute switch of a sink device specified by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_sink_mute_by_index(&mut self, index: u32, mute: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_sink_mute_by_index(self.context, index, mute as i32,
             cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the mute switch of a sink device specified by its name.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure. The
+    /// current state can be read back from the `mute` attribute of [`SinkInfo`], e.g. via
+    /// [`get_sink_info_by_name`](#method.get_sink_info_by_name).
+    ///
+    /// [`SinkInfo`]: struct.SinkInfo.html
     pub fn set_sink_mute_by_name(&mut self, name: &str, mute: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -566,16 +819,17 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_set_sink_mute_by_name(self.context, c_name.as_ptr(),
             mute as i32, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Suspends/Resumes a sink.
     /// 
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn suspend_sink_by_name(&mut self, sink_name: &str, suspend: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -586,7 +840,7 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_suspend_sink_by_name(self.context, c_name.as_ptr(),
             suspend as i32, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Suspends/Resumes a sink.
@@ -594,25 +848,27 @@ impl Introspector {
     /// If `index` is [`def::INVALID_INDEX`](../../def/constant.INVALID_INDEX.html) all sinks will
     /// be suspended. Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn suspend_sink_by_index(&mut self, index: u32, suspend: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_suspend_sink_by_index(self.context, index,
             suspend as i32, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Changes the profile of a sink.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_sink_port_by_index(&mut self, index: u32, port: &str,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -623,16 +879,17 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_set_sink_port_by_index(self.context, index,
             c_port.as_ptr(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Changes the profile of a sink.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_sink_port_by_name(&mut self, name: &str, port: &str,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -644,7 +901,7 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_set_sink_port_by_name(self.context, c_name.as_ptr(),
             c_port.as_ptr(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 }
 
@@ -706,6 +963,16 @@ impl<'a> SourcePortInfo<'a> {
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> SourcePortInfo<'static> {
+        SourcePortInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            description: self.description.clone().map(|s| Cow::Owned(s.into_owned())),
+            ..*self
+        }
+    }
 }
 
 /// Stores information about sources.
@@ -738,10 +1005,12 @@ pub struct SourceInfo<'a> {
     pub latency: MicroSeconds,
     /// Driver name.
     pub driver: Option<Cow<'a, str>>,
-    /// Flags.
+    /// Flags, see [`def::SourceFlagSet`]. Any flags unknown to this binding are retained.
+    ///
+    /// [`def::SourceFlagSet`]: ../../def/struct.SourceFlagSet.html
     pub flags: def::SourceFlagSet,
     /// Property list.
-    pub proplist: Proplist,
+    pub proplist: ProplistRef<'a>,
     /// The latency this device has been configured to.
     pub configured_latency: MicroSeconds,
     /// Some kind of “base” volume that refers to unamplified/unattenuated volume in the context of
@@ -758,7 +1027,7 @@ pub struct SourceInfo<'a> {
     /// Pointer to active port in the set, or `None`.
     pub active_port: Option<Box<SourcePortInfo<'a>>>,
     /// Set of formats supported by the sink.
-    pub formats: Vec<format::Info>,
+    pub formats: Vec<format::InfoRef<'a>>,
 }
 
 impl<'a> SourceInfo<'a> {
@@ -779,7 +1048,7 @@ impl<'a> SourceInfo<'a> {
         for i in 0..src.n_formats as isize {
             let indexed_ptr = unsafe { (*src.formats.offset(i)) as *mut format::InfoInternal };
             if !indexed_ptr.is_null() {
-                formats_vec.push(format::Info::from_raw_weak(indexed_ptr));
+                formats_vec.push(format::InfoRef::from_raw(indexed_ptr));
             }
         }
 
@@ -815,11 +1084,12 @@ impl<'a> SourceInfo<'a> {
                     false => Some(CStr::from_ptr(src.driver).to_string_lossy()),
                     true => None,
                 },
-                flags: src.flags,
-                proplist: Proplist::from_raw_weak(src.proplist),
+                flags: def::SourceFlagSet::from_bits_retain(src.flags),
+                proplist: ProplistRef::from_raw(src.proplist),
                 configured_latency: MicroSeconds(src.configured_latency),
                 base_volume: Volume(src.base_volume),
-                state: src.state.into(),
+                // Read as a raw integer, since a newer server may give a value unknown to us
+                state: std::ptr::read(&src.state as *const _ as *const i32).into(),
                 n_volume_steps: src.n_volume_steps,
                 card: match src.card {
                     def::INVALID_INDEX => None,
@@ -834,6 +1104,102 @@ impl<'a> SourceInfo<'a> {
             }
         }
     }
+
+    /// Checks whether the source supports hardware volume control.
+    #[inline]
+    pub fn supports_hardware_volume(&self) -> bool {
+        self.flags.supports_hardware_volume()
+    }
+
+    /// Checks whether the source supports hardware mute control.
+    #[inline]
+    pub fn supports_hardware_mute(&self) -> bool {
+        self.flags.supports_hardware_mute()
+    }
+
+    /// Checks whether the source volume can be translated to dB.
+    #[inline]
+    pub fn has_decibel_volume(&self) -> bool {
+        self.flags.has_decibel_volume()
+    }
+
+    /// Checks whether the source supports latency querying.
+    #[inline]
+    pub fn supports_latency(&self) -> bool {
+        self.flags.supports_latency()
+    }
+
+    /// Checks whether the source latency can be adjusted dynamically.
+    #[inline]
+    pub fn has_dynamic_latency(&self) -> bool {
+        self.flags.has_dynamic_latency()
+    }
+
+    /// Gets the [`latency`](#structfield.latency), or `None` if the source does not support latency
+    /// querying (lacking the `LATENCY` flag), or should it be the [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../../time/constant.USEC_INVALID.html
+    pub fn reported_latency(&self) -> Option<MicroSeconds> {
+        match self.supports_latency() {
+            true => self.latency.valid(),
+            false => None,
+        }
+    }
+
+    /// Gets the [`configured_latency`](#structfield.configured_latency), or `None` if the source
+    /// does not support latency querying (lacking the `LATENCY` flag), or should it be the
+    /// [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../../time/constant.USEC_INVALID.html
+    pub fn reported_configured_latency(&self) -> Option<MicroSeconds> {
+        match self.supports_latency() {
+            true => self.configured_latency.valid(),
+            false => None,
+        }
+    }
+
+    /// Checks whether the source is in flat volume mode.
+    #[inline]
+    pub fn has_flat_volume(&self) -> bool {
+        self.flags.has_flat_volume()
+    }
+
+    /// Checks whether the source is a hardware device of some kind.
+    #[inline]
+    pub fn is_hardware(&self) -> bool {
+        self.flags.is_hardware()
+    }
+
+    /// Checks whether the source is a networked device of some kind.
+    #[inline]
+    pub fn is_network(&self) -> bool {
+        self.flags.is_network()
+    }
+
+    /// Checks whether the source is the monitor of a sink (see [`monitor_of_sink`]).
+    ///
+    /// [`monitor_of_sink`]: #structfield.monitor_of_sink
+    #[inline]
+    pub fn is_monitor(&self) -> bool {
+        self.monitor_of_sink.is_some()
+    }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> SourceInfo<'static> {
+        SourceInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            description: self.description.clone().map(|s| Cow::Owned(s.into_owned())),
+            monitor_of_sink_name: self.monitor_of_sink_name.clone()
+                .map(|s| Cow::Owned(s.into_owned())),
+            driver: self.driver.clone().map(|s| Cow::Owned(s.into_owned())),
+            proplist: self.proplist.to_owned().into(),
+            ports: self.ports.iter().map(|p| p.to_owned()).collect(),
+            active_port: self.active_port.as_ref().map(|p| Box::new((**p).to_owned())),
+            formats: self.formats.iter().map(|f| f.to_owned().into()).collect(),
+            ..*self
+        }
+    }
 }
 
 impl Introspector {
@@ -855,6 +1221,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a source by its name, as an owned copy.
+        ///
+        /// This is an alternative to [`get_source_info_by_name`], for looking up a single item.
+        ///
+        /// [`get_source_info_by_name`]: #method.get_source_info_by_name
+        pub fn get_source_info_by_name_single(name: &str) => get_source_info_by_name, SourceInfo;
+    }
+
     /// Gets information about a source by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -869,6 +1244,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a source by its index, as an owned copy.
+        ///
+        /// This is an alternative to [`get_source_info_by_index`], for looking up a single item.
+        ///
+        /// [`get_source_info_by_index`]: #method.get_source_info_by_index
+        pub fn get_source_info_by_index_single(index: u32) => get_source_info_by_index, SourceInfo;
+    }
+
     /// Gets the complete source list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -883,85 +1267,161 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceInfo>)>)
     }
 
+    /// Gets the complete source list, collected into a `Vec`.
+    ///
+    /// This is a convenience alternative to [`get_source_info_list`], where owned copies of the
+    /// items are collected, in the order delivered, with the callback called exactly once, at the
+    /// end of the list, or with the context’s error should the request fail.
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    ///
+    /// [`get_source_info_list`]: #method.get_source_info_list
+    pub fn get_source_info_list_collected<F>(&self, callback: F)
+        -> Operation<dyn FnMut(ListResult<&SourceInfo>)>
+        where F: FnOnce(Result<Vec<SourceInfo<'static>>, PAErr>) + 'static
+    {
+        let mut collector = ListCollector::new(self.context, callback);
+        self.get_source_info_list(move |result| collector.handle(result, |i| i.to_owned()))
+    }
+
     /// Sets the volume of a source device specified by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_source_volume_by_index(&mut self, index: u32, volume: &ChannelVolumes,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_source_volume_by_index(self.context, index,
             volume.as_ref(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the volume of a source device specified by its name.
     ///
-    /// Panics on error, i.e. invalid arguments or state.
+    /// Panics on error, i.e. invalid arguments or state. See
+    /// [`try_set_source_volume_by_name`](#method.try_set_source_volume_by_name) for a
+    /// non-panicking equivalent.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_source_volume_by_name(&mut self, name: &str, volume: &ChannelVolumes,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
+        self.try_set_source_volume_by_name(name, volume, callback).unwrap()
+    }
+
+    /// Sets the volume of a source device specified by its name, e.g. to control microphone gain.
+    ///
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
+    ///
+    /// This is the same as [`set_source_volume_by_name`](#method.set_source_volume_by_name),
+    /// except that errors are returned rather than causing a panic: an `Invalid` error if the name
+    /// contains a nul byte or if `volume` is not valid (including having no channels, or too
+    /// many), otherwise the context’s error should the request fail to be issued (e.g. `BadState`
+    /// if the context is not connected). The callback is not called upon error.
+    pub fn try_set_source_volume_by_name(&mut self, name: &str, volume: &ChannelVolumes,
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
+    {
+        if !volume.is_valid() {
+            return Err(PAErr::from(Code::Invalid));
+        }
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
-        let c_name = CString::new(name.clone()).unwrap();
+        let c_name = CString::new(name).or(Err(PAErr::from(Code::Invalid)))?;
 
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_source_volume_by_name(self.context,
             c_name.as_ptr(), volume.as_ref(), cb_fn, cb_data) };
-        assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        self.issued_success_op(ptr, cb_data)
     }
 
     /// Sets the mute switch of a source device specified by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_source_mute_by_index(&mut self, index: u32, mute: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_source_mute_by_index(self.context, index,
             mute as i32, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the mute switch of a source device specified by its name.
     ///
-    /// Panics on error, i.e. invalid arguments or state.
+    /// Panics on error, i.e. invalid arguments or state. See
+    /// [`try_set_source_mute_by_name`](#method.try_set_source_mute_by_name) for a non-panicking
+    /// equivalent.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_source_mute_by_name(&mut self, name: &str, mute: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+    {
+        self.try_set_source_mute_by_name(name, mute, callback).unwrap()
+    }
+
+    /// Sets the mute switch of a source device specified by its name.
+    ///
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
+    ///
+    /// This is the same as [`set_source_mute_by_name`](#method.set_source_mute_by_name), except
+    /// that errors are returned rather than causing a panic: an `Invalid` error if the name
+    /// contains a nul byte, otherwise the context’s error should the request fail to be issued
+    /// (e.g. `BadState` if the context is not connected). The callback is not called upon error.
+    pub fn try_set_source_mute_by_name(&mut self, name: &str, mute: bool,
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
-        let c_name = CString::new(name.clone()).unwrap();
+        let c_name = CString::new(name).or(Err(PAErr::from(Code::Invalid)))?;
 
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_source_mute_by_name(self.context, c_name.as_ptr(),
             mute as i32, cb_fn, cb_data) };
-        assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        self.issued_success_op(ptr, cb_data)
+    }
+
+    /// Wraps the operation pointer returned for a request taking a success callback, or, should it
+    /// be null (the request having failed to be issued), releases the callback, which will never
+    /// be called, and returns the context’s error.
+    fn issued_success_op(&self, ptr: *mut capi::pa_operation, cb_data: *mut c_void)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
+    {
+        match ptr.is_null() {
+            false => Ok(Operation::from_raw(ptr,
+                cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)),
+            true => {
+                if !cb_data.is_null() {
+                    drop(get_su_callback::<dyn FnOnce(Result<(), PAErr>)>(cb_data));
+                }
+                Err(PAErr::from_context(self.context))
+            },
+        }
     }
 
     /// Suspends/Resumes a source.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn suspend_source_by_name(&mut self, name: &str, suspend: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -972,7 +1432,7 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_suspend_source_by_name(self.context, c_name.as_ptr(),
             suspend as i32, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Suspends/Resumes a source.
@@ -980,25 +1440,27 @@ impl Introspector {
     /// If `index` is [`def::INVALID_INDEX`](../../def/constant.INVALID_INDEX.html), all sources
     /// will be suspended. Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn suspend_source_by_index(&mut self, index: u32, suspend: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_suspend_source_by_index(self.context, index,
             suspend as i32, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Changes the profile of a source.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_source_port_by_index(&mut self, index: u32, port: &str,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -1009,16 +1471,17 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_set_source_port_by_index(self.context, index,
             c_port.as_ptr(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Changes the profile of a source.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_source_port_by_name(&mut self, name: &str, port: &str,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -1030,7 +1493,7 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_set_source_port_by_name(self.context, c_name.as_ptr(),
             c_port.as_ptr(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 }
 
@@ -1120,6 +1583,21 @@ impl<'a> ServerInfo<'a> {
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> ServerInfo<'static> {
+        ServerInfo {
+            user_name: self.user_name.clone().map(|s| Cow::Owned(s.into_owned())),
+            host_name: self.host_name.clone().map(|s| Cow::Owned(s.into_owned())),
+            server_version: self.server_version.clone().map(|s| Cow::Owned(s.into_owned())),
+            server_name: self.server_name.clone().map(|s| Cow::Owned(s.into_owned())),
+            default_sink_name: self.default_sink_name.clone().map(|s| Cow::Owned(s.into_owned())),
+            default_source_name: self.default_source_name.clone()
+                .map(|s| Cow::Owned(s.into_owned())),
+            ..*self
+        }
+    }
 }
 
 impl Introspector {
@@ -1172,7 +1650,7 @@ pub struct ModuleInfo<'a> {
     /// Usage counter or `None` if invalid.
     pub n_used: Option<u32>,
     /// Property list.
-    pub proplist: Proplist,
+    pub proplist: ProplistRef<'a>,
 }
 
 impl<'a> ModuleInfo<'a> {
@@ -1194,10 +1672,21 @@ impl<'a> ModuleInfo<'a> {
                     def::INVALID_INDEX => None,
                     i => Some(i),
                 },
-                proplist: Proplist::from_raw_weak(src.proplist),
+                proplist: ProplistRef::from_raw(src.proplist),
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> ModuleInfo<'static> {
+        ModuleInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            argument: self.argument.clone().map(|s| Cow::Owned(s.into_owned())),
+            proplist: self.proplist.to_owned().into(),
+            ..*self
+        }
+    }
 }
 
 impl Introspector {
@@ -1215,6 +1704,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&ModuleInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets some information about a module by its index, as an owned copy.
+        ///
+        /// This is an alternative to [`get_module_info`], for looking up a single item.
+        ///
+        /// [`get_module_info`]: #method.get_module_info
+        pub fn get_module_info_single(index: u32) => get_module_info, ModuleInfo;
+    }
+
     /// Gets the complete list of currently loaded modules.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1229,6 +1727,23 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&ModuleInfo>)>)
     }
 
+    /// Gets the complete list of currently loaded modules, collected into a `Vec`.
+    ///
+    /// This is a convenience alternative to [`get_module_info_list`], where owned copies of the
+    /// items are collected, in the order delivered, with the callback called exactly once, at the
+    /// end of the list, or with the context’s error should the request fail.
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    ///
+    /// [`get_module_info_list`]: #method.get_module_info_list
+    pub fn get_module_info_list_collected<F>(&self, callback: F)
+        -> Operation<dyn FnMut(ListResult<&ModuleInfo>)>
+        where F: FnOnce(Result<Vec<ModuleInfo<'static>>, PAErr>) + 'static
+    {
+        let mut collector = ListCollector::new(self.context, callback);
+        self.get_module_info_list(move |result| collector.handle(result, |i| i.to_owned()))
+    }
+
     /// Loads a module.
     ///
     /// Panics on error, i.e. invalid arguments or state. The callback is provided with the
@@ -1253,15 +1768,16 @@ impl Introspector {
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The callback must accept a `bool`, which indicates success.
-    pub fn unload_module<F>(&mut self, index: u32, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    /// The callback must accept a `Result`, which gives the error upon failure.
+    pub fn unload_module<F>(&mut self, index: u32, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_unload_module(self.context, index,
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 }
 
@@ -1316,7 +1832,7 @@ pub struct ClientInfo<'a> {
     /// Driver name.
     pub driver: Option<Cow<'a, str>>,
     /// Property list.
-    pub proplist: Proplist,
+    pub proplist: ProplistRef<'a>,
 }
 
 impl<'a> ClientInfo<'a> {
@@ -1338,10 +1854,21 @@ impl<'a> ClientInfo<'a> {
                     false => Some(CStr::from_ptr(src.driver).to_string_lossy()),
                     true => None,
                 },
-                proplist: Proplist::from_raw_weak(src.proplist),
+                proplist: ProplistRef::from_raw(src.proplist),
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> ClientInfo<'static> {
+        ClientInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            driver: self.driver.clone().map(|s| Cow::Owned(s.into_owned())),
+            proplist: self.proplist.to_owned().into(),
+            ..*self
+        }
+    }
 }
 
 impl Introspector {
@@ -1359,6 +1886,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&ClientInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a client by its index, as an owned copy.
+        ///
+        /// This is an alternative to [`get_client_info`], for looking up a single item.
+        ///
+        /// [`get_client_info`]: #method.get_client_info
+        pub fn get_client_info_single(index: u32) => get_client_info, ClientInfo;
+    }
+
     /// Gets the complete client list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1373,19 +1909,37 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&ClientInfo>)>)
     }
 
+    /// Gets the complete client list, collected into a `Vec`.
+    ///
+    /// This is a convenience alternative to [`get_client_info_list`], where owned copies of the
+    /// items are collected, in the order delivered, with the callback called exactly once, at the
+    /// end of the list, or with the context’s error should the request fail.
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    ///
+    /// [`get_client_info_list`]: #method.get_client_info_list
+    pub fn get_client_info_list_collected<F>(&self, callback: F)
+        -> Operation<dyn FnMut(ListResult<&ClientInfo>)>
+        where F: FnOnce(Result<Vec<ClientInfo<'static>>, PAErr>) + 'static
+    {
+        let mut collector = ListCollector::new(self.context, callback);
+        self.get_client_info_list(move |result| collector.handle(result, |i| i.to_owned()))
+    }
+
     /// Kills a client.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The callback must accept a `bool`, which indicates success.
-    pub fn kill_client<F>(&mut self, index: u32, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    /// The callback must accept a `Result`, which gives the error upon failure.
+    pub fn kill_client<F>(&mut self, index: u32, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_kill_client(self.context, index,
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 }
 
@@ -1481,6 +2035,16 @@ impl<'a> CardProfileInfo<'a> {
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> CardProfileInfo<'static> {
+        CardProfileInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            description: self.description.clone().map(|s| Cow::Owned(s.into_owned())),
+            ..*self
+        }
+    }
 }
 
 #[cfg(any(feature = "pa_v5", feature = "dox"))]
@@ -1505,6 +2069,16 @@ impl<'a> CardProfileInfo2<'a> {
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> CardProfileInfo2<'static> {
+        CardProfileInfo2 {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            description: self.description.clone().map(|s| Cow::Owned(s.into_owned())),
+            ..*self
+        }
+    }
 }
 
 /// Stores information about a specific port of a card.
@@ -1522,9 +2096,9 @@ pub struct CardPortInfo<'a> {
     /// Availability status of this port.
     pub available: def::PortAvailable,
     /// The direction of this port.
-    pub direction: direction::FlagSet,
+    pub direction: direction::Direction,
     /// Property list.
-    pub proplist: Proplist,
+    pub proplist: ProplistRef<'a>,
     /// Latency offset of the port that gets added to the sink/source latency when the port is
     /// active.
     pub latency_offset: i64,
@@ -1575,13 +2149,25 @@ impl<'a> CardPortInfo<'a> {
                 },
                 priority: src.priority,
                 available: mem::transmute(src.available),
-                direction: src.direction,
-                proplist: Proplist::from_raw_weak(src.proplist),
+                direction: direction::Direction::from_bits_retain(src.direction),
+                proplist: ProplistRef::from_raw(src.proplist),
                 latency_offset: src.latency_offset,
                 profiles: profiles_vec,
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> CardPortInfo<'static> {
+        CardPortInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            description: self.description.clone().map(|s| Cow::Owned(s.into_owned())),
+            proplist: self.proplist.to_owned().into(),
+            profiles: self.profiles.iter().map(|p| p.to_owned()).collect(),
+            ..*self
+        }
+    }
 }
 
 /// Stores information about cards.
@@ -1599,7 +2185,7 @@ pub struct CardInfo<'a> {
     /// Driver name.
     pub driver: Option<Cow<'a, str>>,
     /// Property list.
-    pub proplist: Proplist,
+    pub proplist: ProplistRef<'a>,
     /// Set of ports.
     pub ports: Vec<CardPortInfo<'a>>,
     /// Set of available profiles.
@@ -1666,7 +2252,7 @@ impl<'a> CardInfo<'a> {
                     false => Some(CStr::from_ptr(src.driver).to_string_lossy()),
                     true => None,
                 },
-                proplist: Proplist::from_raw_weak(src.proplist),
+                proplist: ProplistRef::from_raw(src.proplist),
                 ports: ports_vec,
                 profiles: profiles_vec,
                 #[cfg(all(not(feature = "pa_v5"), not(feature = "dox")))]
@@ -1682,6 +2268,20 @@ impl<'a> CardInfo<'a> {
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> CardInfo<'static> {
+        CardInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            driver: self.driver.clone().map(|s| Cow::Owned(s.into_owned())),
+            proplist: self.proplist.to_owned().into(),
+            ports: self.ports.iter().map(|p| p.to_owned()).collect(),
+            profiles: self.profiles.iter().map(|p| p.to_owned()).collect(),
+            active_profile: self.active_profile.as_ref().map(|p| Box::new((**p).to_owned())),
+            ..*self
+        }
+    }
 }
 
 impl Introspector {
@@ -1699,6 +2299,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&CardInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a card by its index, as an owned copy.
+        ///
+        /// This is an alternative to [`get_card_info_by_index`], for looking up a single item.
+        ///
+        /// [`get_card_info_by_index`]: #method.get_card_info_by_index
+        pub fn get_card_info_by_index_single(index: u32) => get_card_info_by_index, CardInfo;
+    }
+
     /// Gets information about a card by its name.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1717,6 +2326,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&CardInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a card by its name, as an owned copy.
+        ///
+        /// This is an alternative to [`get_card_info_by_name`], for looking up a single item.
+        ///
+        /// [`get_card_info_by_name`]: #method.get_card_info_by_name
+        pub fn get_card_info_by_name_single(name: &str) => get_card_info_by_name, CardInfo;
+    }
+
     /// Gets the complete card list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1730,13 +2348,31 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&CardInfo>)>)
     }
 
+    /// Gets the complete card list, collected into a `Vec`.
+    ///
+    /// This is a convenience alternative to [`get_card_info_list`], where owned copies of the items
+    /// are collected, in the order delivered, with the callback called exactly once, at the end of
+    /// the list, or with the context’s error should the request fail.
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    ///
+    /// [`get_card_info_list`]: #method.get_card_info_list
+    pub fn get_card_info_list_collected<F>(&self, callback: F)
+        -> Operation<dyn FnMut(ListResult<&CardInfo>)>
+        where F: FnOnce(Result<Vec<CardInfo<'static>>, PAErr>) + 'static
+    {
+        let mut collector = ListCollector::new(self.context, callback);
+        self.get_card_info_list(move |result| collector.handle(result, |i| i.to_owned()))
+    }
+
     /// Changes the profile of a card.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_card_profile_by_index(&mut self, index: u32, profile: &str,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -1747,16 +2383,17 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_set_card_profile_by_index(self.context, index,
             c_profile.as_ptr(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Changes the profile of a card.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_card_profile_by_name(&mut self, name: &str, profile: &str,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -1768,16 +2405,17 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_set_card_profile_by_name(self.context, c_name.as_ptr(),
             c_profile.as_ptr(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the latency offset of a port.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_port_latency_offset(&mut self, card_name: &str, port_name: &str, offset: i64,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -1789,7 +2427,7 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_set_port_latency_offset(self.context, c_name.as_ptr(),
             c_port.as_ptr(), offset, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 }
 
@@ -1854,7 +2492,7 @@ pub struct SinkInputInfo<'a> {
     /// Stream muted.
     pub mute: bool,
     /// Property list.
-    pub proplist: Proplist,
+    pub proplist: ProplistRef<'a>,
     /// Stream corked.
     pub corked: bool,
     /// Stream has volume. If not set, then the meaning of this struct’s volume member is unspecified.
@@ -1863,7 +2501,7 @@ pub struct SinkInputInfo<'a> {
     /// control the volume.
     pub volume_writable: bool,
     /// Stream format information.
-    pub format: format::Info,
+    pub format: format::InfoRef<'a>,
 }
 
 impl<'a> SinkInputInfo<'a> {
@@ -1900,14 +2538,45 @@ impl<'a> SinkInputInfo<'a> {
                     true => None,
                 },
                 mute: match src.mute { 0 => false, _ => true },
-                proplist: Proplist::from_raw_weak(src.proplist),
+                proplist: ProplistRef::from_raw(src.proplist),
                 corked: match src.corked { 0 => false, _ => true },
                 has_volume: match src.has_volume { 0 => false, _ => true },
                 volume_writable: match src.volume_writable { 0 => false, _ => true },
-                format: format::Info::from_raw_weak(src.format as *mut format::InfoInternal),
+                format: format::InfoRef::from_raw(src.format as *mut format::InfoInternal),
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> SinkInputInfo<'static> {
+        SinkInputInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            resample_method: self.resample_method.clone().map(|s| Cow::Owned(s.into_owned())),
+            driver: self.driver.clone().map(|s| Cow::Owned(s.into_owned())),
+            proplist: self.proplist.to_owned().into(),
+            format: self.format.to_owned().into(),
+            ..*self
+        }
+    }
+
+    /// Gets the [`buffer_usec`](#structfield.buffer_usec), or `None` should it be the
+    /// [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../../time/constant.USEC_INVALID.html
+    #[inline]
+    pub fn reported_buffer_usec(&self) -> Option<MicroSeconds> {
+        self.buffer_usec.valid()
+    }
+
+    /// Gets the [`sink_usec`](#structfield.sink_usec), or `None` should it be the
+    /// [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../../time/constant.USEC_INVALID.html
+    #[inline]
+    pub fn reported_sink_usec(&self) -> Option<MicroSeconds> {
+        self.sink_usec.valid()
+    }
 }
 
 impl Introspector {
@@ -1926,6 +2595,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInputInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets some information about a sink input by its index, as an owned copy.
+        ///
+        /// This is an alternative to [`get_sink_input_info`], for looking up a single item.
+        ///
+        /// [`get_sink_input_info`]: #method.get_sink_input_info
+        pub fn get_sink_input_info_single(index: u32) => get_sink_input_info, SinkInputInfo;
+    }
+
     /// Gets the complete sink input list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1941,13 +2619,31 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInputInfo>)>)
     }
 
+    /// Gets the complete sink input list, collected into a `Vec`.
+    ///
+    /// This is a convenience alternative to [`get_sink_input_info_list`], where owned copies of the
+    /// items are collected, in the order delivered, with the callback called exactly once, at the
+    /// end of the list, or with the context’s error should the request fail.
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    ///
+    /// [`get_sink_input_info_list`]: #method.get_sink_input_info_list
+    pub fn get_sink_input_info_list_collected<F>(&self, callback: F)
+        -> Operation<dyn FnMut(ListResult<&SinkInputInfo>)>
+        where F: FnOnce(Result<Vec<SinkInputInfo<'static>>, PAErr>) + 'static
+    {
+        let mut collector = ListCollector::new(self.context, callback);
+        self.get_sink_input_info_list(move |result| collector.handle(result, |i| i.to_owned()))
+    }
+
     /// Moves the specified sink input to a different sink.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn move_sink_input_by_name(&mut self, index: u32, sink_name: &str,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -1958,70 +2654,74 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_move_sink_input_by_name(self.context, index,
             c_name.as_ptr(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Moves the specified sink input to a different sink.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn move_sink_input_by_index(&mut self, index: u32, sink_index: u32,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_move_sink_input_by_index(self.context, index,
             sink_index, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the volume of a sink input stream.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_sink_input_volume(&mut self, index: u32, volume: &ChannelVolumes,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_sink_input_volume(self.context, index,
             volume.as_ref(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the mute switch of a sink input stream.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_sink_input_mute(&mut self, index: u32, mute: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_sink_input_mute(self.context, index, mute as i32,
             cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Kills a sink input.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The callback must accept a `bool`, which indicates success.
-    pub fn kill_sink_input<F>(&mut self, index: u32, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    /// The callback must accept a `Result`, which gives the error upon failure.
+    pub fn kill_sink_input<F>(&mut self, index: u32, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_kill_sink_input(self.context, index,
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 }
 
@@ -2082,7 +2782,7 @@ pub struct SourceOutputInfo<'a> {
     /// Driver name.
     pub driver: Option<Cow<'a, str>>,
     /// Property list.
-    pub proplist: Proplist,
+    pub proplist: ProplistRef<'a>,
     /// Stream corked.
     pub corked: bool,
     /// The volume of this source output.
@@ -2095,7 +2795,7 @@ pub struct SourceOutputInfo<'a> {
     /// control the volume.
     pub volume_writable: bool,
     /// Stream format information.
-    pub format: format::Info,
+    pub format: format::InfoRef<'a>,
 }
 
 impl<'a> SourceOutputInfo<'a> {
@@ -2130,16 +2830,47 @@ impl<'a> SourceOutputInfo<'a> {
                     false => Some(CStr::from_ptr(src.driver).to_string_lossy()),
                     true => None,
                 },
-                proplist: Proplist::from_raw_weak(src.proplist),
+                proplist: ProplistRef::from_raw(src.proplist),
                 corked: match src.corked { 0 => false, _ => true },
                 volume: src.volume.into(),
                 mute: match src.mute { 0 => false, _ => true },
                 has_volume: match src.has_volume { 0 => false, _ => true },
                 volume_writable: match src.volume_writable { 0 => false, _ => true },
-                format: format::Info::from_raw_weak(src.format as *mut format::InfoInternal),
+                format: format::InfoRef::from_raw(src.format as *mut format::InfoInternal),
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> SourceOutputInfo<'static> {
+        SourceOutputInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            resample_method: self.resample_method.clone().map(|s| Cow::Owned(s.into_owned())),
+            driver: self.driver.clone().map(|s| Cow::Owned(s.into_owned())),
+            proplist: self.proplist.to_owned().into(),
+            format: self.format.to_owned().into(),
+            ..*self
+        }
+    }
+
+    /// Gets the [`buffer_usec`](#structfield.buffer_usec), or `None` should it be the
+    /// [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../../time/constant.USEC_INVALID.html
+    #[inline]
+    pub fn reported_buffer_usec(&self) -> Option<MicroSeconds> {
+        self.buffer_usec.valid()
+    }
+
+    /// Gets the [`source_usec`](#structfield.source_usec), or `None` should it be the
+    /// [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../../time/constant.USEC_INVALID.html
+    #[inline]
+    pub fn reported_source_usec(&self) -> Option<MicroSeconds> {
+        self.source_usec.valid()
+    }
 }
 
 impl Introspector {
@@ -2158,6 +2889,16 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceOutputInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a source output by its index, as an owned copy.
+        ///
+        /// This is an alternative to [`get_source_output_info`], for looking up a single item.
+        ///
+        /// [`get_source_output_info`]: #method.get_source_output_info
+        pub fn get_source_output_info_single(index: u32)
+            => get_source_output_info, SourceOutputInfo;
+    }
+
     /// Gets the complete list of source outputs.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -2173,13 +2914,31 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceOutputInfo>)>)
     }
 
+    /// Gets the complete list of source outputs, collected into a `Vec`.
+    ///
+    /// This is a convenience alternative to [`get_source_output_info_list`], where owned copies of
+    /// the items are collected, in the order delivered, with the callback called exactly once, at
+    /// the end of the list, or with the context’s error should the request fail.
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    ///
+    /// [`get_source_output_info_list`]: #method.get_source_output_info_list
+    pub fn get_source_output_info_list_collected<F>(&self, callback: F)
+        -> Operation<dyn FnMut(ListResult<&SourceOutputInfo>)>
+        where F: FnOnce(Result<Vec<SourceOutputInfo<'static>>, PAErr>) + 'static
+    {
+        let mut collector = ListCollector::new(self.context, callback);
+        self.get_source_output_info_list(move |result| collector.handle(result, |i| i.to_owned()))
+    }
+
     /// Moves the specified source output to a different source.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn move_source_output_by_name(&mut self, index: u32, source_name: &str,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -2190,70 +2949,74 @@ impl Introspector {
         let ptr = unsafe { capi::pa_context_move_source_output_by_name(self.context, index,
             c_name.as_ptr(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Moves the specified source output to a different source.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn move_source_output_by_index(&mut self, index: u32, source_index: u32,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_move_source_output_by_index(self.context, index,
             source_index, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the volume of a source output stream.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_source_output_volume(&mut self, index: u32, volume: &ChannelVolumes,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_source_output_volume(self.context, index,
             volume.as_ref(), cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the mute switch of a source output stream.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     pub fn set_source_output_mute(&mut self, index: u32, mute: bool,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, super::success_cb_proxy);
         let ptr = unsafe { capi::pa_context_set_source_output_mute(self.context, index, mute as i32,
             cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Kills a source output.
     ///
     /// Panics on error, i.e. invalid arguments or state.
     ///
-    /// The callback must accept a `bool`, which indicates success.
-    pub fn kill_source_output<F>(&mut self, index: u32, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    /// The callback must accept a `Result`, which gives the error upon failure.
+    pub fn kill_source_output<F>(&mut self, index: u32, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_kill_source_output(self.context, index,
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 }
 
@@ -2338,7 +3101,7 @@ pub struct SampleInfo<'a> {
     /// In case this is a lazy cache entry, the filename for the sound file to be loaded on demand.
     pub filename: Option<Cow<'a, str>>,
     /// Property list for this sample.
-    pub proplist: Proplist,
+    pub proplist: ProplistRef<'a>,
 }
 
 impl<'a> SampleInfo<'a> {
@@ -2362,10 +3125,21 @@ impl<'a> SampleInfo<'a> {
                     false => Some(CStr::from_ptr(src.filename).to_string_lossy()),
                     true => None,
                 },
-                proplist: Proplist::from_raw_weak(src.proplist),
+                proplist: ProplistRef::from_raw(src.proplist),
             }
         }
     }
+
+    /// Creates a deep copy of the information, which can be kept beyond the duration of the
+    /// callback it was provided to.
+    pub fn to_owned(&self) -> SampleInfo<'static> {
+        SampleInfo {
+            name: self.name.clone().map(|s| Cow::Owned(s.into_owned())),
+            filename: self.filename.clone().map(|s| Cow::Owned(s.into_owned())),
+            proplist: self.proplist.to_owned().into(),
+            ..*self
+        }
+    }
 }
 
 impl Introspector {
@@ -2387,6 +3161,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SampleInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a sample by its name, as an owned copy.
+        ///
+        /// This is an alternative to [`get_sample_info_by_name`], for looking up a single item.
+        ///
+        /// [`get_sample_info_by_name`]: #method.get_sample_info_by_name
+        pub fn get_sample_info_by_name_single(name: &str) => get_sample_info_by_name, SampleInfo;
+    }
+
     /// Gets information about a sample by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -2401,6 +3184,15 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SampleInfo>)>)
     }
 
+    single_lookup! {
+        /// Gets information about a sample by its index, as an owned copy.
+        ///
+        /// This is an alternative to [`get_sample_info_by_index`], for looking up a single item.
+        ///
+        /// [`get_sample_info_by_index`]: #method.get_sample_info_by_index
+        pub fn get_sample_info_by_index_single(index: u32) => get_sample_info_by_index, SampleInfo;
+    }
+
     /// Gets the complete list of samples stored in the daemon.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -2414,6 +3206,23 @@ impl Introspector {
         assert!(!ptr.is_null());
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SampleInfo>)>)
     }
+
+    /// Gets the complete list of samples stored in the daemon, collected into a `Vec`.
+    ///
+    /// This is a convenience alternative to [`get_sample_info_list`], where owned copies of the
+    /// items are collected, in the order delivered, with the callback called exactly once, at the
+    /// end of the list, or with the context’s error should the request fail.
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    ///
+    /// [`get_sample_info_list`]: #method.get_sample_info_list
+    pub fn get_sample_info_list_collected<F>(&self, callback: F)
+        -> Operation<dyn FnMut(ListResult<&SampleInfo>)>
+        where F: FnOnce(Result<Vec<SampleInfo<'static>>, PAErr>) + 'static
+    {
+        let mut collector = ListCollector::new(self.context, callback);
+        self.get_sample_info_list(move |result| collector.handle(result, |i| i.to_owned()))
+    }
 }
 
 /// Proxy for get sample info list callbacks.
@@ -2435,3 +3244,468 @@ fn get_sample_info_list_cb_proxy(_: *mut ContextInternal, i: *const SampleInfoIn
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use crate::mainloop::standard::Mainloop;
+    use crate::proplist::Proplist;
+
+    #[test]
+    fn set_source_volume_and_mute_by_name() {
+        let mainloop = Mainloop::new().unwrap();
+        let context = Context::new(&mainloop, "FooApp").unwrap();
+        let mut introspect = context.introspect();
+        let invalid = PAErr::from(Code::Invalid);
+
+        // The callback is released, uncalled, upon error
+        let calls = Rc::new(RefCell::new(0));
+        let callback = || {
+            let calls_ref = Rc::clone(&calls);
+            Some(Box::new(move |_| *calls_ref.borrow_mut() += 1)
+                as Box<dyn FnOnce(Result<(), PAErr>)>)
+        };
+
+        let mut volume = ChannelVolumes::default();
+        assert_eq!(introspect.try_set_source_volume_by_name("mic", &volume, callback()).err(),
+            Some(invalid));
+        volume.set(2, crate::volume::VOLUME_NORM);
+        assert_eq!(introspect.try_set_source_volume_by_name("m\0ic", &volume, callback()).err(),
+            Some(invalid));
+        // Valid requests get as far as the C library, which reports not being connected
+        assert_eq!(introspect.try_set_source_volume_by_name("mic", &volume, callback()).err(),
+            Some(PAErr::from(Code::BadState)));
+
+        assert_eq!(introspect.try_set_source_mute_by_name("m\0ic", true, callback()).err(),
+            Some(invalid));
+        for &mute in &[true, false] {
+            assert_eq!(introspect.try_set_source_mute_by_name("mic", mute, callback()).err(),
+                Some(PAErr::from(Code::BadState)));
+        }
+        assert_eq!(*calls.borrow(), 0);
+        assert_eq!(Rc::strong_count(&calls), 1);
+    }
+
+    #[test]
+    fn introspector_lifetime() {
+        let mainloop = Mainloop::new().unwrap();
+        let inner = Rc::downgrade(&mainloop._inner);
+        let context = Context::new(&mainloop, "FooApp").unwrap();
+
+        // Any number may coexist, alongside normal use of the context
+        let first = context.introspect();
+        let second = context.introspect();
+        assert_eq!(context.get_state(), crate::context::State::Unconnected);
+        drop(first);
+
+        // Outliving the context and mainloop objects keeps their internals alive
+        drop(context);
+        drop(mainloop);
+        assert!(inner.upgrade().is_some());
+        drop(second);
+        assert!(inner.upgrade().is_none());
+    }
+
+    #[test]
+    fn list_collector() {
+        let mainloop = Mainloop::new().unwrap();
+        let context = Context::new(&mainloop, "FooApp").unwrap();
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let calls_ref = Rc::clone(&calls);
+        let mut collector = ListCollector::new(context.ptr,
+            move |r: Result<Vec<u32>, PAErr>| calls_ref.borrow_mut().push(r));
+        for i in &[3u32, 1, 2] {
+            collector.handle(ListResult::Item(i), |i| *i);
+        }
+        assert!(calls.borrow().is_empty());
+        collector.handle(ListResult::End, |i: &u32| *i);
+        collector.handle(ListResult::End, |i: &u32| *i);
+        // Called exactly once, with items in delivery order
+        assert_eq!(*calls.borrow(), vec![Ok(vec![3, 1, 2])]);
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let calls_ref = Rc::clone(&calls);
+        let mut collector = ListCollector::new(context.ptr,
+            move |r: Result<Vec<u32>, PAErr>| calls_ref.borrow_mut().push(r.is_err()));
+        collector.handle(ListResult::Item(&7u32), |i| *i);
+        collector.handle(ListResult::Error, |i: &u32| *i);
+        assert!(collector.items.is_empty());
+        assert_eq!(*calls.borrow(), vec![true]);
+    }
+
+    #[test]
+    fn single_lookup() {
+        let mainloop = Mainloop::new().unwrap();
+        let context = Context::new(&mainloop, "FooApp").unwrap();
+
+        let run = |results: &[ListResult<&u32>]| {
+            let calls = Rc::new(RefCell::new(Vec::new()));
+            let calls_ref = Rc::clone(&calls);
+            let mut collector = ListCollector::new(context.ptr,
+                move |r: Result<Vec<u32>, PAErr>| {
+                    calls_ref.borrow_mut().push(r.and_then(single_item))
+                });
+            for r in results {
+                let r = match *r {
+                    ListResult::Item(i) => ListResult::Item(i),
+                    ListResult::End => ListResult::End,
+                    ListResult::Error => ListResult::Error,
+                };
+                collector.handle(r, |i: &u32| *i);
+            }
+            drop(collector);
+            Rc::try_unwrap(calls).ok().unwrap().into_inner()
+        };
+
+        // Item followed by the end of list marker
+        assert_eq!(run(&[ListResult::Item(&5), ListResult::End]), vec![Ok(5)]);
+        // Only the end of list marker
+        assert_eq!(run(&[ListResult::End]), vec![Err(PAErr::from(Code::NoEntity))]);
+        // Server error
+        assert_eq!(run(&[ListResult::Error]).len(), 1);
+    }
+
+    #[test]
+    fn sink_info_mute() {
+        let props = Proplist::new().unwrap();
+        let name = CString::new("alsa_output.pci-0000_00_1f.3.analog-stereo").unwrap();
+        let mut raw: SinkInfoInternal = unsafe { mem::zeroed() };
+        raw.name = name.as_ptr();
+        raw.owner_module = def::INVALID_INDEX;
+        raw.card = def::INVALID_INDEX;
+        raw.proplist = props.0.ptr;
+
+        assert_eq!(SinkInfo::new_from_raw(&raw).mute, false);
+        raw.mute = 1;
+        assert_eq!(SinkInfo::new_from_raw(&raw).mute, true);
+        // Any non-zero value is true
+        raw.mute = 2;
+        let info = SinkInfo::new_from_raw(&raw);
+        assert_eq!(info.mute, true);
+        // Survives being made owned
+        assert_eq!(info.to_owned().mute, true);
+    }
+
+    #[test]
+    fn sink_info_volume_details() {
+        let props = Proplist::new().unwrap();
+        let mut raw: SinkInfoInternal = unsafe { mem::zeroed() };
+        raw.owner_module = def::INVALID_INDEX;
+        raw.card = def::INVALID_INDEX;
+        raw.proplist = props.0.ptr;
+        raw.base_volume = crate::volume::VOLUME_NORM.0 / 2;
+        raw.n_volume_steps = 65537;
+        raw.flags = (def::SinkFlagSet::HW_VOLUME_CTRL | def::SinkFlagSet::DECIBEL_VOLUME).bits();
+
+        let info = SinkInfo::new_from_raw(&raw);
+        assert_eq!(info.base_volume, Volume(crate::volume::VOLUME_NORM.0 / 2));
+        assert_eq!(info.n_volume_steps, 65537);
+        assert!(info.supports_hardware_volume());
+        assert!(info.has_decibel_volume());
+        assert!(!info.supports_hardware_mute());
+        assert!(!info.is_network());
+
+        let owned = info.to_owned();
+        assert_eq!((owned.base_volume, owned.n_volume_steps, owned.flags),
+            (info.base_volume, info.n_volume_steps, info.flags));
+
+        raw.flags = def::SinkFlagSet::NETWORK.bits();
+        let info = SinkInfo::new_from_raw(&raw);
+        assert!(info.is_network());
+        assert!(!info.supports_hardware_volume());
+        assert!(!info.has_decibel_volume());
+    }
+
+    #[test]
+    fn reported_latencies() {
+        use crate::time::USEC_INVALID;
+
+        let props = Proplist::new().unwrap();
+        let mut sink: SinkInfoInternal = unsafe { mem::zeroed() };
+        sink.owner_module = def::INVALID_INDEX;
+        sink.card = def::INVALID_INDEX;
+        sink.proplist = props.0.ptr;
+        sink.latency = 12_000;
+        sink.configured_latency = 25_000;
+        let mut source: SourceInfoInternal = unsafe { mem::zeroed() };
+        source.owner_module = def::INVALID_INDEX;
+        source.monitor_of_sink = def::INVALID_INDEX;
+        source.card = def::INVALID_INDEX;
+        source.proplist = props.0.ptr;
+        source.latency = 12_000;
+        source.configured_latency = 25_000;
+
+        // Without the `LATENCY` flag, the values are not meaningful
+        let info = SinkInfo::new_from_raw(&sink);
+        assert_eq!((info.reported_latency(), info.reported_configured_latency()), (None, None));
+        let info = SourceInfo::new_from_raw(&source);
+        assert_eq!((info.reported_latency(), info.reported_configured_latency()), (None, None));
+
+        sink.flags = (def::SinkFlagSet::LATENCY | def::SinkFlagSet::DYNAMIC_LATENCY).bits();
+        source.flags = def::SourceFlagSet::LATENCY.bits();
+        let info = SinkInfo::new_from_raw(&sink).to_owned();
+        assert_eq!(info.reported_latency(), Some(MicroSeconds(12_000)));
+        assert_eq!(info.reported_configured_latency(), Some(MicroSeconds(25_000)));
+        let info = SourceInfo::new_from_raw(&source).to_owned();
+        assert_eq!(info.reported_latency(), Some(MicroSeconds(12_000)));
+        assert_eq!(info.reported_configured_latency(), Some(MicroSeconds(25_000)));
+
+        sink.configured_latency = USEC_INVALID.0;
+        let info = SinkInfo::new_from_raw(&sink);
+        assert_eq!(info.reported_latency(), Some(MicroSeconds(12_000)));
+        assert_eq!(info.reported_configured_latency(), None);
+    }
+
+    #[test]
+    fn reported_stream_latencies() {
+        use crate::time::USEC_INVALID;
+
+        let props = Proplist::new().unwrap();
+        let pcm = format::Info::new().unwrap();
+        let mut input: SinkInputInfoInternal = unsafe { mem::zeroed() };
+        input.owner_module = def::INVALID_INDEX;
+        input.client = def::INVALID_INDEX;
+        input.proplist = props.0.ptr;
+        input.format = pcm.ptr as *mut capi::pa_format_info;
+        input.buffer_usec = 4_000;
+        input.sink_usec = USEC_INVALID.0;
+        let mut output: SourceOutputInfoInternal = unsafe { mem::zeroed() };
+        output.owner_module = def::INVALID_INDEX;
+        output.client = def::INVALID_INDEX;
+        output.proplist = props.0.ptr;
+        output.format = pcm.ptr as *mut capi::pa_format_info;
+        output.buffer_usec = USEC_INVALID.0;
+        output.source_usec = 9_000;
+
+        let info = SinkInputInfo::new_from_raw(&input);
+        assert_eq!(info.reported_buffer_usec(), Some(MicroSeconds(4_000)));
+        assert_eq!(info.reported_sink_usec(), None);
+        assert_eq!(info.sink_usec, USEC_INVALID);
+        let info = SourceOutputInfo::new_from_raw(&output);
+        assert_eq!(info.reported_buffer_usec(), None);
+        assert_eq!(info.reported_source_usec(), Some(MicroSeconds(9_000)));
+    }
+
+    #[test]
+    fn sink_info_formats() {
+        let props = Proplist::new().unwrap();
+        let mut pcm = format::Info::new().unwrap();
+        pcm.set_encoding(format::Encoding::PCM);
+        let mut ac3 = format::Info::new().unwrap();
+        ac3.set_encoding(format::Encoding::AC3_IEC61937);
+        let mut formats = [pcm.ptr, ac3.ptr];
+
+        let mut raw: SinkInfoInternal = unsafe { mem::zeroed() };
+        raw.owner_module = def::INVALID_INDEX;
+        raw.card = def::INVALID_INDEX;
+        raw.proplist = props.0.ptr;
+        raw.n_formats = formats.len() as u8;
+        raw.formats = formats.as_mut_ptr() as *mut *mut capi::pa_format_info;
+
+        let info = SinkInfo::new_from_raw(&raw);
+        let encodings: Vec<_> = info.formats.iter().map(|f| f.get_encoding()).collect();
+        assert_eq!(encodings, vec![format::Encoding::PCM, format::Encoding::AC3_IEC61937]);
+        assert!(info.formats[0].is_pcm());
+        assert!(!info.formats[1].is_pcm());
+        assert!(info.supports_encoding(format::Encoding::AC3_IEC61937));
+        assert!(!info.supports_encoding(format::Encoding::DTS_IEC61937));
+
+        // Owned copies outlive the originals
+        let owned = info.to_owned();
+        drop(info);
+        drop(pcm);
+        drop(ac3);
+        assert!(owned.supports_encoding(format::Encoding::AC3_IEC61937));
+
+        // No formats
+        raw.n_formats = 0;
+        raw.formats = std::ptr::null_mut();
+        assert!(SinkInfo::new_from_raw(&raw).formats.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_sink_mute_by_name_unconnected() {
+        let mainloop = Mainloop::new().unwrap();
+        let context = Context::new(&mainloop, "FooApp").unwrap();
+        let mut introspector = context.introspect();
+        // Invalid state, an operation cannot be created
+        let _ = introspector.set_sink_mute_by_name("foo", true, None);
+    }
+
+    #[test]
+    fn source_info_monitor_detection() {
+        let props = Proplist::new().unwrap();
+
+        let hw_name = CString::new("alsa_input.pci-0000_00_1f.3.analog-stereo").unwrap();
+        let mut raw: SourceInfoInternal = unsafe { mem::zeroed() };
+        raw.name = hw_name.as_ptr();
+        raw.monitor_of_sink = def::INVALID_INDEX;
+        raw.owner_module = def::INVALID_INDEX;
+        raw.card = def::INVALID_INDEX;
+        raw.flags = def::SourceFlagSet::HARDWARE.bits();
+        raw.proplist = props.0.ptr;
+        let info = SourceInfo::new_from_raw(&raw);
+        assert!(!info.is_monitor());
+        assert!(info.is_hardware());
+        assert_eq!(info.monitor_of_sink, None);
+        assert_eq!(info.monitor_of_sink_name, None);
+
+        let mon_name = CString::new("alsa_output.pci-0000_00_1f.3.analog-stereo.monitor").unwrap();
+        let sink_name = CString::new("alsa_output.pci-0000_00_1f.3.analog-stereo").unwrap();
+        raw.name = mon_name.as_ptr();
+        raw.monitor_of_sink = 2;
+        raw.monitor_of_sink_name = sink_name.as_ptr();
+        raw.flags = 0;
+        let info = SourceInfo::new_from_raw(&raw);
+        assert!(info.is_monitor());
+        assert!(!info.is_hardware());
+        assert_eq!(info.monitor_of_sink, Some(2));
+        assert_eq!(info.monitor_of_sink_name,
+            Some("alsa_output.pci-0000_00_1f.3.analog-stereo".into()));
+    }
+
+    #[test]
+    fn source_output_info_list() {
+        let mainloop = Mainloop::new().unwrap();
+        let context = Context::new(&mainloop, "FooApp").unwrap();
+        let props = Proplist::new().unwrap();
+        let mut pcm = format::Info::new().unwrap();
+        pcm.set_encoding(format::Encoding::PCM);
+        let name = CString::new("Recording Stream").unwrap();
+
+        let mut volume = ChannelVolumes::default();
+        volume.set(2, Volume(crate::volume::VOLUME_NORM.0 / 2));
+        let mut raw: SourceOutputInfoInternal = unsafe { mem::zeroed() };
+        raw.index = 7;
+        raw.name = name.as_ptr();
+        raw.owner_module = def::INVALID_INDEX;
+        raw.client = 3;
+        raw.source = 1;
+        raw.proplist = props.0.ptr;
+        raw.format = pcm.ptr as *mut capi::pa_format_info;
+        raw.volume = *volume.as_ref();
+        raw.mute = 1;
+        raw.has_volume = 1;
+        raw.volume_writable = 1;
+
+        // Deliver the stub items as the C library would, collecting owned copies
+        let results = Rc::new(RefCell::new(Vec::new()));
+        let results_ref = Rc::clone(&results);
+        let mut collector = ListCollector::new(context.ptr,
+            move |r: Result<Vec<SourceOutputInfo<'static>>, PAErr>| {
+                results_ref.borrow_mut().push(r.unwrap())
+            });
+        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(ListResult<&SourceOutputInfo>)>(
+            Box::new(move |r| collector.handle(r, |i| i.to_owned())));
+        get_source_output_info_list_cb_proxy(context.ptr, &raw, 0, cb_data);
+        raw.index = 8;
+        raw.name = std::ptr::null();
+        raw.client = def::INVALID_INDEX;
+        raw.mute = 0;
+        raw.volume_writable = 0;
+        get_source_output_info_list_cb_proxy(context.ptr, &raw, 0, cb_data);
+        get_source_output_info_list_cb_proxy(context.ptr, std::ptr::null(), 1, cb_data);
+        drop(name);
+        drop(pcm);
+        drop(props);
+
+        let results = results.borrow();
+        assert_eq!(results.len(), 1);
+        let items = &results[0];
+        assert_eq!(items.iter().map(|i| i.index).collect::<Vec<_>>(), vec![7, 8]);
+        assert_eq!(items[0].name, Some("Recording Stream".into()));
+        assert_eq!(items[0].owner_module, None);
+        assert_eq!(items[0].client, Some(3));
+        assert_eq!(items[0].source, 1);
+        assert_eq!(items[0].volume.len(), 2);
+        assert_eq!(items[0].volume.avg(), Volume(crate::volume::VOLUME_NORM.0 / 2));
+        assert!(items[0].mute);
+        assert!(items[0].has_volume && items[0].volume_writable);
+        assert!(items[0].format.is_pcm());
+        assert_eq!(items[1].name, None);
+        assert_eq!(items[1].client, None);
+        assert!(!items[1].mute);
+        assert!(!items[1].volume_writable);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_source_output_volume_unconnected() {
+        let mainloop = Mainloop::new().unwrap();
+        let context = Context::new(&mainloop, "FooApp").unwrap();
+        let mut introspector = context.introspect();
+        let mut volume = ChannelVolumes::default();
+        volume.set(2, crate::volume::VOLUME_NORM);
+        // Invalid state, an operation cannot be created
+        let _ = introspector.set_source_output_volume(7, &volume, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_source_output_mute_unconnected() {
+        let mainloop = Mainloop::new().unwrap();
+        let context = Context::new(&mainloop, "FooApp").unwrap();
+        let mut introspector = context.introspect();
+        // Invalid state, an operation cannot be created
+        let _ = introspector.set_source_output_mute(7, true, None);
+    }
+
+    #[test]
+    fn sample_info_from_raw() {
+        let name = CString::new("bell-window-system").unwrap();
+        let filename = CString::new("/usr/share/sounds/bell.ogg").unwrap();
+        let spec = sample::Spec { format: sample::Format::S16le, channels: 2, rate: 44100 };
+        let mut props = Proplist::new().unwrap();
+        props.set_str("media.role", "event").unwrap();
+
+        let mut raw: SampleInfoInternal = unsafe { mem::zeroed() };
+        raw.index = 4;
+        raw.name = name.as_ptr();
+        raw.sample_spec = *spec.as_ref();
+        raw.duration = 250_000;
+        raw.bytes = 44100;
+        raw.lazy = 1;
+        raw.filename = filename.as_ptr();
+        raw.proplist = props.0.ptr;
+
+        let owned = {
+            let info = SampleInfo::new_from_raw(&raw);
+            assert_eq!(info.index, 4);
+            assert_eq!(info.name, Some("bell-window-system".into()));
+            assert_eq!(info.sample_spec, spec);
+            assert_eq!(info.duration, MicroSeconds(250_000));
+            assert_eq!(info.bytes, 44100);
+            assert!(info.lazy);
+            assert_eq!(info.filename, Some("/usr/share/sounds/bell.ogg".into()));
+            assert_eq!(info.proplist.get_str("media.role"), Some("event".to_string()));
+            info.to_owned()
+        };
+        drop(name);
+        drop(filename);
+        drop(props);
+        // The deep copy must not depend upon the memory it was translated from
+        assert_eq!(owned.name, Some("bell-window-system".into()));
+        assert!(owned.lazy);
+        assert_eq!(owned.proplist.get_str("media.role"), Some("event".to_string()));
+
+        // A (valid) property list is always given by the C library
+        let empty_props = Proplist::new().unwrap();
+        raw = unsafe { mem::zeroed() };
+        raw.lazy = 0;
+        raw.proplist = empty_props.0.ptr;
+        let info = SampleInfo::new_from_raw(&raw);
+        assert_eq!(info.name, None);
+        assert_eq!(info.filename, None);
+        assert!(!info.lazy);
+    }
+
+    #[test]
+    fn not_send_sync() {
+        assert_not_impl!(Introspector: Send);
+        assert_not_impl!(Introspector: Sync);
+    }
+}
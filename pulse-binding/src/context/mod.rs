@@ -76,28 +76,46 @@
 pub mod ext_device_manager;
 pub mod ext_device_restore;
 pub mod ext_stream_restore;
+pub mod helpers;
 pub mod introspect;
 pub mod scache;
 pub mod subscribe;
 
+mod discovery;
+
+pub use self::discovery::{default_server_candidates, CandidateSource, ServerCandidate};
+
 use std::os::raw::{c_char, c_void};
 use std::ffi::{CStr, CString};
 use std::ptr::{null, null_mut};
+use std::any::Any;
 use std::rc::Rc;
 use crate::{def, sample};
-use crate::mainloop::api::{Mainloop, MainloopInnerType};
-use crate::mainloop::events;
+use crate::mainloop::api::{AnyMainloop, ErasedInner, ErasedMainloop, Mainloop};
+use crate::mainloop::{events, standard};
 use crate::mainloop::events::timer::{TimeEvent, TimeEventRef};
 use crate::operation::Operation;
 use crate::error::PAErr;
 use crate::time::MonotonicTs;
-use crate::proplist::{self, Proplist, ProplistInternal};
+use crate::proplist::{self, Proplist, ProplistInternal, ProplistRef};
 use crate::callbacks::{box_closure_get_capi_ptr, get_su_callback, MultiUseCallback};
 use crate::capi::pa_context as ContextInternal;
 
 /// An opaque connection context to a daemon.
 ///
-/// Note: Saves a copy of active multi-use closure callbacks, which it frees on drop.
+/// Note: Saves a copy of active multi-use closure callbacks, which it frees on drop. Such callbacks
+/// belong to the `Context` object they were set through, with dropping it clearing them, even
+/// where the underlying context lives on through other references to it, such as another `Context`
+/// object obtained with [`Stream::get_context`].
+///
+/// Equality compares identity, i.e. whether two objects refer to the same underlying context.
+///
+/// Note, this is neither `Send` nor `Sync`, since it holds an `Rc` keeping the mainloop alive, and
+/// since its callbacks need not be `Send`. With the threaded mainloop, it may only be used by the
+/// thread that created it (with the mainloop lock held) and from within callbacks run by the
+/// mainloop thread (where the lock is already held).
+///
+/// [`Stream::get_context`]: ../stream/struct.Stream.html#method.get_context
 pub struct Context {
     /// The actual C object.
     pub(crate) ptr: *mut ContextInternal,
@@ -105,11 +123,10 @@ pub struct Context {
     weak: bool,
     /// Multi-use callback closure pointers.
     cb_ptrs: CallbackPointers,
+    /// Keeps the mainloop alive for as long as the context (which uses its API vtable) exists.
+    pub(crate) mainloop: Rc<dyn Any>,
 }
 
-unsafe impl Send for Context {}
-unsafe impl Sync for Context {}
-
 /// Holds copies of callback closure pointers, for those that are “multi-use” (may be fired multiple
 /// times), for freeing at the appropriate time.
 #[derive(Default)]
@@ -121,7 +138,7 @@ struct CallbackPointers {
 
 type NotifyCb = MultiUseCallback<dyn FnMut(), extern "C" fn(*mut ContextInternal, *mut c_void)>;
 
-type EventCb = MultiUseCallback<dyn FnMut(String, Proplist),
+type EventCb = MultiUseCallback<dyn FnMut(String, ProplistRef),
     extern "C" fn(*mut ContextInternal, name: *const c_char, pl: *mut ProplistInternal, *mut c_void)>;
 
 type ExtSubscribeCb = MultiUseCallback<dyn FnMut(), extern "C" fn(*mut ContextInternal, *mut c_void)>;
@@ -161,10 +178,28 @@ impl From<State> for capi::pa_context_state_t {
         unsafe { std::mem::transmute(s) }
     }
 }
+/// Checked, as with the conversion from a raw value, in case of a state value unknown to us.
 impl From<capi::pa_context_state_t> for State {
     #[inline]
     fn from(s: capi::pa_context_state_t) -> Self {
-        unsafe { std::mem::transmute(s) }
+        State::from(s as i32)
+    }
+}
+
+/// Converts a raw state value, as given by the C API, conservatively treating unknown values (e.g.
+/// a state added by a newer version of PulseAudio) as `Failed`.
+impl From<i32> for State {
+    fn from(s: i32) -> Self {
+        match s {
+            0 => State::Unconnected,
+            1 => State::Connecting,
+            2 => State::Authorizing,
+            3 => State::SettingName,
+            4 => State::Ready,
+            5 => State::Failed,
+            6 => State::Terminated,
+            _ => State::Failed,
+        }
     }
 }
 
@@ -176,58 +211,151 @@ impl State {
         self == State::SettingName ||
         self == State::Ready
     }
+
+    /// Checks if the passed state is a terminal state, i.e. `Failed` or `Terminated`, from which
+    /// the context cannot recover (returns `true` if so).
+    #[inline]
+    pub fn is_terminal(self) -> bool {
+        self == State::Failed || self == State::Terminated
+    }
+
+    /// Checks if the passed state is the `Ready` state (returns `true` if so).
+    #[inline]
+    pub fn is_ready(self) -> bool {
+        self == State::Ready
+    }
+}
+
+impl std::fmt::Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match *self {
+            State::Unconnected => "unconnected",
+            State::Connecting => "connecting",
+            State::Authorizing => "authorizing",
+            State::SettingName => "setting name",
+            State::Ready => "ready",
+            State::Failed => "failed",
+            State::Terminated => "terminated",
+        };
+        f.write_str(s)
+    }
+}
+
+flags_type! {
+    /// Flags for contexts.
+    pub struct Flags: capi::pa_context_flags_t {
+        /// Flag to pass when no specific options are needed.
+        const NOFLAGS = capi::PA_CONTEXT_NOFLAGS;
+
+        /// Disable autospawning of the PulseAudio daemon if required.
+        const NOAUTOSPAWN = capi::PA_CONTEXT_NOAUTOSPAWN;
+
+        /// Don’t fail if the daemon is not available when
+        /// [`Context::connect`](struct.Context.html#method.connect) is called, instead enter
+        /// [`State::Connecting`](enum.State.html#Connecting.v) state and wait for the daemon to
+        /// appear.
+        const NOFAIL = capi::PA_CONTEXT_NOFAIL;
+    }
 }
 
-pub type FlagSet = capi::pa_context_flags_t;
+/// Deprecated alias of [`Flags`](struct.Flags.html).
+#[deprecated(since = "2.17.0", note = "use `Flags` instead")]
+pub type FlagSet = Flags;
 
 /// Some special flags for contexts.
+///
+/// These are deprecated aliases of the associated constants of [`Flags`](../struct.Flags.html).
 pub mod flags {
-    use capi;
-    use super::FlagSet;
-
-    pub const NOFLAGS: FlagSet = capi::PA_CONTEXT_NOFLAGS;
-    /// Disable autospawning of the PulseAudio daemon if required.
-    pub const NOAUTOSPAWN: FlagSet = capi::PA_CONTEXT_NOAUTOSPAWN;
-    /// Don’t fail if the daemon is not available when
-    /// [`Context::connect`](../struct.Context.html#method.connect) is called, instead enter
-    /// [`State::Connecting`](../enum.State.html#Connecting.v) state and wait for the daemon to
-    /// appear.
-    pub const NOFAIL: FlagSet = capi::PA_CONTEXT_NOFAIL;
+    use super::Flags;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::NOFLAGS` instead")]
+    pub const NOFLAGS: Flags = Flags::NOFLAGS;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::NOAUTOSPAWN` instead")]
+    pub const NOAUTOSPAWN: Flags = Flags::NOAUTOSPAWN;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::NOFAIL` instead")]
+    pub const NOFAIL: Flags = Flags::NOFAIL;
+}
+
+/// Reasons for failure of context creation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ContextError {
+    /// The name contained a nul byte, and thus could not be passed to the C API.
+    InvalidName,
+    /// The underlying library failed to create the context.
+    CreationFailed,
+}
+
+impl std::fmt::Display for ContextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ContextError::InvalidName => write!(f, "invalid context name (contains a nul byte)"),
+            ContextError::CreationFailed => write!(f, "failed to create context"),
+        }
+    }
 }
 
+impl std::error::Error for ContextError {}
+
 impl Context {
     /// Instantiates a new connection context with an abstract mainloop API and an application name.
     ///
     /// It is recommended to use [`new_with_proplist`](#method.new_with_proplist) instead and
     /// specify some initial properties.
-    pub fn new(mainloop: &impl Mainloop, name: &str) -> Option<Self> {
+    ///
+    /// The context keeps the mainloop’s internals alive, thus the mainloop object may safely be
+    /// dropped before the context (though of course the context can then no longer make progress).
+    pub fn new<M>(mainloop: &M, name: &str) -> Result<Self, ContextError>
+        where M: AnyMainloop + ?Sized
+    {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
-        let c_name = CString::new(name.clone()).unwrap();
-        let ptr = unsafe { capi::pa_context_new(mainloop.inner().get_api().as_ref(),
-            c_name.as_ptr()) };
-        match ptr.is_null() { false => Some(Self::from_raw(ptr)), true => None }
+        let c_name = CString::new(name).or(Err(ContextError::InvalidName))?;
+        let erased = mainloop.erased();
+        let ptr = unsafe { capi::pa_context_new(erased.get_api().as_ref(), c_name.as_ptr()) };
+        match ptr.is_null() {
+            false => Ok(Self::from_raw(ptr, erased.inner())),
+            true => Err(ContextError::CreationFailed),
+        }
     }
 
     /// Instantiates a new connection context with an abstract mainloop API and an application name,
     /// and specify the initial client property list.
-    pub fn new_with_proplist(mainloop: &impl Mainloop, name: &str, proplist: &Proplist)
-        -> Option<Self>
+    ///
+    /// As with [`new`](#method.new), the context keeps the mainloop’s internals alive.
+    ///
+    /// The property list is copied, it is not modified, nor kept borrowed.
+    pub fn new_with_proplist<M>(mainloop: &M, name: &str, proplist: &Proplist)
+        -> Result<Self, ContextError>
+        where M: AnyMainloop + ?Sized
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
-        let c_name = CString::new(name.clone()).unwrap();
-        let ptr = unsafe { capi::pa_context_new_with_proplist(mainloop.inner().get_api().as_ref(),
+        let c_name = CString::new(name).or(Err(ContextError::InvalidName))?;
+        let erased = mainloop.erased();
+        let ptr = unsafe { capi::pa_context_new_with_proplist(erased.get_api().as_ref(),
             c_name.as_ptr(), proplist.0.ptr) };
-        match ptr.is_null() { false => Some(Self::from_raw(ptr)), true => None }
+        match ptr.is_null() {
+            false => Ok(Self::from_raw(ptr, erased.inner())),
+            true => Err(ContextError::CreationFailed),
+        }
     }
 
     /// Creates a new `Context` from an existing [`ContextInternal`](enum.ContextInternal.html)
     /// pointer.
     #[inline]
-    pub(crate) fn from_raw(ptr: *mut ContextInternal) -> Self {
+    pub(crate) fn from_raw(ptr: *mut ContextInternal, mainloop: Rc<dyn Any>) -> Self {
         assert_eq!(false, ptr.is_null());
-        Self { ptr: ptr, weak: false, cb_ptrs: Default::default() }
+        Self { ptr: ptr, weak: false, cb_ptrs: Default::default(), mainloop: mainloop }
+    }
+
+    /// Gets a handle to the mainloop the context was created with.
+    pub(crate) fn erased_mainloop(&self) -> ErasedMainloop {
+        // Contexts are only ever created with the inner wrapper of an `ErasedMainloop`
+        let inner = Rc::downcast::<ErasedInner>(Rc::clone(&self.mainloop)).ok()
+            .expect("context mainloop is not type erased");
+        ErasedMainloop::from_inner(inner)
     }
 
     /// Sets a callback function that is called whenever the context status changes.
@@ -242,10 +370,13 @@ impl Context {
     ///
     /// The callback is given a name which represents what event occurred. The set of defined events
     /// can be extended at any time. Also, server modules may introduce additional message types so
-    /// make sure that your callback function ignores messages it doesn’t know. It is also given an
-    /// (owned) property list.
+    /// make sure that your callback function ignores messages it doesn’t know. It is also given a
+    /// view of a property list, which is only valid for the duration of the callback (use
+    /// [`ProplistRef::to_owned`] to keep a copy).
+    ///
+    /// [`ProplistRef::to_owned`]: ../proplist/struct.ProplistRef.html#method.to_owned
     pub fn set_event_callback(&mut self,
-        callback: Option<Box<dyn FnMut(String, Proplist) + 'static>>)
+        callback: Option<Box<dyn FnMut(String, ProplistRef) + 'static>>)
     {
         let saved = &mut self.cb_ptrs.event;
         *saved = EventCb::new(callback);
@@ -254,45 +385,70 @@ impl Context {
     }
 
     /// Gets the error number of the last failed operation.
+    ///
+    /// As with errors returned elsewhere, this is negative, thus compares equal to the
+    /// corresponding [`Code`] converted to a `PAErr`.
+    ///
+    /// [`Code`]: ../error/enum.Code.html
     #[inline]
     pub fn errno(&self) -> PAErr {
-        PAErr(unsafe { capi::pa_context_errno(self.ptr) })
+        PAErr::from_context(self.ptr)
     }
 
     /// Checks if some data is pending to be written to the connection (returns `true` if so).
+    ///
+    /// This is useful for checking for outstanding operations before shutting down, e.g. before
+    /// disconnecting. Note that this always returns `false` if the context is not in a ‘good’ state
+    /// (see [`State::is_good`]), since there can then be no pending data.
+    ///
+    /// [`State::is_good`]: enum.State.html#method.is_good
     #[inline]
     pub fn is_pending(&self) -> bool {
-        unsafe { capi::pa_context_is_pending(self.ptr) != 0 }
+        // Note, this returns a negative value on error (i.e. bad state)
+        unsafe { capi::pa_context_is_pending(self.ptr) > 0 }
     }
 
     /// Gets the current context status.
     #[inline]
     pub fn get_state(&self) -> State {
-        unsafe { capi::pa_context_get_state(self.ptr).into() }
+        unsafe { capi::pa_context_get_state(self.ptr) }.into()
     }
 
     /// Connects the context to the specified server.
     ///
     /// If server is `None`, connect to the default server. This routine may but will not always
     /// return synchronously on error. Use [`set_state_callback`](#method.set_state_callback) to be
-    /// notified when the connection is established. If `flags` doesn’t have
-    /// [`flags::NOAUTOSPAWN`](flags/constant.NOAUTOSPAWN.html) set and no specific server is
-    /// specified or accessible, a new daemon is spawned. If `api` is not `None`, the functions
-    /// specified in the structure are used when forking a new child process.
-    pub fn connect(&mut self, server: Option<&str>, flags: FlagSet, api: Option<&def::SpawnApi>)
+    /// notified when the connection is established. If `flags` doesn’t have [`Flags::NOAUTOSPAWN`]
+    /// set and no specific server is specified or accessible, a new daemon is spawned. If `api` is
+    /// not `None`, the functions specified in the structure are used when forking a new child
+    /// process; it is thus irrelevant when [`Flags::NOAUTOSPAWN`] is set. See [`def::SpawnApi`].
+    ///
+    /// A given server string is validated before anything is attempted, with an
+    /// [`error::Code::Invalid`] error returned if it is empty or contains a nul byte.
+    ///
+    /// [`Flags::NOAUTOSPAWN`]: struct.Flags.html#associatedconstant.NOAUTOSPAWN
+    /// [`def::SpawnApi`]: ../def/struct.SpawnApi.html
+    /// [`error::Code::Invalid`]: ../error/enum.Code.html#variant.Invalid
+    pub fn connect(&mut self, server: Option<&str>, flags: Flags, api: Option<&def::SpawnApi>)
         -> Result<(), PAErr>
     {
+        if let Some(server) = server {
+            if server.is_empty() {
+                return Err(PAErr::from(crate::error::Code::Invalid));
+            }
+        }
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
         let c_server = match server {
-            Some(server) => CString::new(server.clone()).unwrap(),
+            Some(server) => CString::new(server)
+                .or(Err(PAErr::from(crate::error::Code::Invalid)))?,
             None => CString::new("").unwrap(),
         };
 
         let p_api = api.map_or(null::<capi::pa_spawn_api>(), |a| a.as_ref());
         let p_server = server.map_or(null::<c_char>(), |_| c_server.as_ptr() as *const c_char);
 
-        match unsafe { capi::pa_context_connect(self.ptr, p_server, flags, p_api) } {
+        match unsafe { capi::pa_context_connect(self.ptr, p_server, flags.bits(), p_api) } {
             0 => Ok(()),
             e => Err(PAErr(e)),
         }
@@ -304,6 +460,27 @@ impl Context {
         unsafe { capi::pa_context_disconnect(self.ptr); }
     }
 
+    /// Shuts down the context in an orderly fashion, for use with the standard mainloop.
+    ///
+    /// This waits for all pending operations to complete (i.e. drains the context), iterating the
+    /// mainloop (blocking) until [`is_pending`] reports no more, then disconnects. Note, this thus
+    /// dispatches any other events of the mainloop in the meantime, including running callbacks.
+    ///
+    /// At most `max_iterations` iterations are run, to avoid hanging forever should a reply never
+    /// come, after which an [`error::Code::Timeout`] error is returned. An error is also returned
+    /// if iterating the mainloop fails, with [`error::Code::Killed`] should it be quit. The context
+    /// is disconnected regardless.
+    ///
+    /// [`is_pending`]: #method.is_pending
+    /// [`error::Code::Timeout`]: ../error/enum.Code.html#variant.Timeout
+    /// [`error::Code::Killed`]: ../error/enum.Code.html#variant.Killed
+    pub fn shutdown(&mut self, mainloop: &mut standard::Mainloop, max_iterations: u32)
+        -> Result<(), PAErr>
+    {
+        let mut target = ShutdownTarget { context: self, mainloop: mainloop };
+        shutdown_sequence(&mut target, max_iterations)
+    }
+
     /// Drains the context.
     ///
     /// If there is nothing to drain, the function returns `None`.
@@ -325,7 +502,11 @@ impl Context {
         // a null pointer may be returned if there is nothing to drain! Do not remove it!
         match ptr.is_null() {
             false => Some(Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut()>)),
-            true => None,
+            true => {
+                // Release the callback, which will never be called
+                drop(get_su_callback::<dyn FnMut()>(cb_data));
+                None
+            },
         }
     }
 
@@ -334,54 +515,56 @@ impl Context {
     /// The returned operation is unlikely to complete successfully, since the daemon probably died
     /// before returning a success notification.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn exit_daemon<F>(&mut self, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn exit_daemon<F>(&mut self, callback: F) -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_exit_daemon(self.ptr, Some(success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the name of the default sink.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn set_default_sink<F>(&mut self, name: &str, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn set_default_sink<F>(&mut self, name: &str, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
         let c_name = CString::new(name.clone()).unwrap();
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_set_default_sink(self.ptr, c_name.as_ptr(),
             Some(success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the name of the default source.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn set_default_source<F>(&mut self, name: &str, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn set_default_source<F>(&mut self, name: &str, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
         let c_name = CString::new(name.clone()).unwrap();
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_set_default_source(self.ptr, c_name.as_ptr(),
             Some(success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Checks if this is a connection to a local daemon.
@@ -399,18 +582,19 @@ impl Context {
     /// Sets a different application name for context on the server.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn set_name<F>(&mut self, name: &str, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn set_name<F>(&mut self, name: &str, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
         let c_name = CString::new(name.clone()).unwrap();
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_set_name(self.ptr, c_name.as_ptr(),
             Some(success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Gets the server name this context is connected to.
@@ -447,21 +631,22 @@ impl Context {
     ///
     /// Panics if the underlying C function returns a null pointer.
     pub fn proplist_update<F>(&mut self, mode: proplist::UpdateMode, pl: &Proplist, callback: F)
-        -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_proplist_update(self.ptr, mode, pl.0.ptr,
             Some(success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Updates the property list of the client, remove entries.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn proplist_remove<F>(&mut self, keys: &[&str], callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn proplist_remove<F>(&mut self, keys: &[&str], callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -478,11 +663,11 @@ impl Context {
         }
         c_key_ptrs.push(null());
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_proplist_remove(self.ptr, c_key_ptrs.as_ptr(),
             Some(success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Gets the client index this context is identified in the server with.
@@ -593,26 +778,111 @@ impl Context {
     }
 }
 
+/// Steps of an orderly context shutdown, abstracted for testing.
+trait Shutdown {
+    fn is_pending(&self) -> bool;
+    fn iterate(&mut self) -> Result<(), PAErr>;
+    fn disconnect(&mut self);
+}
+
+/// Adapts a context and standard mainloop to `Shutdown`.
+struct ShutdownTarget<'a> {
+    context: &'a mut Context,
+    mainloop: &'a mut standard::Mainloop,
+}
+
+impl Shutdown for ShutdownTarget<'_> {
+    fn is_pending(&self) -> bool {
+        self.context.is_pending()
+    }
+
+    fn iterate(&mut self) -> Result<(), PAErr> {
+        match self.mainloop.iterate(true) {
+            standard::IterateResult::Success(_) => Ok(()),
+            standard::IterateResult::Quit(_) => Err(PAErr::from(crate::error::Code::Killed)),
+            standard::IterateResult::Err(e) => Err(e),
+        }
+    }
+
+    fn disconnect(&mut self) {
+        self.context.disconnect()
+    }
+}
+
+/// Implementation of [`Context::shutdown`](struct.Context.html#method.shutdown).
+fn shutdown_sequence<T: Shutdown>(target: &mut T, max_iterations: u32) -> Result<(), PAErr> {
+    let mut result = Ok(());
+    let mut iterations = 0;
+    while target.is_pending() {
+        if iterations == max_iterations {
+            result = Err(PAErr::from(crate::error::Code::Timeout));
+            break;
+        }
+        if let Err(e) = target.iterate() {
+            result = Err(e);
+            break;
+        }
+        iterations += 1;
+    }
+    target.disconnect();
+    result
+}
+
+impl PartialEq for Context {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl Eq for Context {}
+
 impl Drop for Context {
     fn drop(&mut self) {
         if !self.weak {
-            unsafe { capi::pa_context_unref(self.ptr) };
+            // The context may live on through other references to it, so stop it from using the
+            // callback closures about to be freed.
+            unsafe {
+                if !self.cb_ptrs.set_state.get_capi_params(notify_cb_proxy_multi).1.is_null() {
+                    capi::pa_context_set_state_callback(self.ptr, None, null_mut());
+                }
+                if !self.cb_ptrs.event.get_capi_params(event_cb_proxy).1.is_null() {
+                    capi::pa_context_set_event_callback(self.ptr, None, null_mut());
+                }
+                if !self.cb_ptrs.subscribe.get_capi_params(subscribe::cb_proxy).1.is_null() {
+                    capi::pa_context_set_subscribe_callback(self.ptr, None, null_mut());
+                }
+                capi::pa_context_unref(self.ptr);
+            }
         }
         self.ptr = null_mut::<ContextInternal>();
     }
 }
 
+/// Converts the success flag given to an operation’s callback into a `Result`.
+///
+/// Upon failure, the context’s error is looked up there and then, before any later operation can
+/// overwrite it. Should the context not have recorded an error, `Code::Unknown` is given.
+pub(crate) fn success_result(context: *mut ContextInternal, success: i32) -> Result<(), PAErr> {
+    match success {
+        0 => match PAErr::from_context(context) {
+            PAErr(0) => Err(PAErr::from(crate::error::Code::Unknown)),
+            e => Err(e),
+        },
+        _ => Ok(()),
+    }
+}
+
 /// Proxy for completion success callbacks.
 ///
 /// Warning: This is for single-use cases only! It destroys the actual closure callback.
 extern "C"
-fn success_cb_proxy(_: *mut ContextInternal, success: i32, userdata: *mut c_void) {
-    let success_actual = match success { 0 => false, _ => true };
+fn success_cb_proxy(c: *mut ContextInternal, success: i32, userdata: *mut c_void) {
     let _ = std::panic::catch_unwind(|| {
-        assert!(!userdata.is_null());
+        let result = success_result(c, success);
         // Note, destroys closure callback after use - restoring outer box means it gets dropped
-        let mut callback = unsafe { Box::from_raw(userdata as *mut Box<dyn FnMut(bool)>) };
-        (callback)(success_actual);
+        let callback = get_su_callback::<dyn FnOnce(Result<(), PAErr>)>(userdata);
+        (callback)(result);
     });
 }
 
@@ -655,7 +925,9 @@ fn event_cb_proxy(_: *mut ContextInternal, name: *const c_char, proplist: *mut P
             let tmp = unsafe { CStr::from_ptr(name) };
             tmp.to_string_lossy().into_owned()
         };
-        let pl = Proplist::from_raw_weak(proplist);
+        // Note, the property list is owned by PA and only valid for the duration of the callback,
+        // hence only a view is given.
+        let pl = ProplistRef::from_raw(proplist);
 
         let callback = EventCb::get_callback(userdata);
         (callback)(n, pl);
@@ -685,3 +957,263 @@ fn ext_subscribe_cb_proxy(_: *mut ContextInternal, userdata: *mut c_void) {
         (callback)();
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mainloop::standard::Mainloop;
+
+    /// Reports pending for a given number of iterations.
+    struct StubShutdown {
+        pending_for: u32,
+        iterations: u32,
+        fail_at: Option<u32>,
+        disconnected: bool,
+    }
+
+    impl Shutdown for StubShutdown {
+        fn is_pending(&self) -> bool {
+            assert!(!self.disconnected);
+            self.iterations < self.pending_for
+        }
+
+        fn iterate(&mut self) -> Result<(), PAErr> {
+            self.iterations += 1;
+            match self.fail_at {
+                Some(n) if n == self.iterations => Err(PAErr::from(crate::error::Code::Killed)),
+                _ => Ok(()),
+            }
+        }
+
+        fn disconnect(&mut self) {
+            self.disconnected = true;
+        }
+    }
+
+    #[test]
+    fn shutdown_sequence_waits() {
+        let stub = |pending_for, fail_at| {
+            StubShutdown { pending_for: pending_for, iterations: 0, fail_at: fail_at,
+                disconnected: false }
+        };
+
+        // Pending for two iterations, then clears
+        let mut target = stub(2, None);
+        assert_eq!(shutdown_sequence(&mut target, 10), Ok(()));
+        assert_eq!(target.iterations, 2);
+        assert!(target.disconnected);
+
+        // Nothing pending
+        let mut target = stub(0, None);
+        assert_eq!(shutdown_sequence(&mut target, 0), Ok(()));
+        assert_eq!(target.iterations, 0);
+        assert!(target.disconnected);
+
+        // Times out, still disconnecting
+        let mut target = stub(5, None);
+        assert_eq!(shutdown_sequence(&mut target, 2),
+            Err(PAErr::from(crate::error::Code::Timeout)));
+        assert_eq!(target.iterations, 2);
+        assert!(target.disconnected);
+
+        // Iteration failure
+        let mut target = stub(5, Some(1));
+        assert_eq!(shutdown_sequence(&mut target, 10),
+            Err(PAErr::from(crate::error::Code::Killed)));
+        assert!(target.disconnected);
+    }
+
+    #[test]
+    fn shutdown_unconnected() {
+        let mut mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "test").unwrap();
+        assert_eq!(context.shutdown(&mut mainloop, 5), Ok(()));
+    }
+
+    #[test]
+    fn mainloop_dropped_first() {
+        let mainloop = Mainloop::new().unwrap();
+        let inner = Rc::downgrade(&mainloop._inner);
+        let context = Context::new(&mainloop, "test").unwrap();
+        drop(mainloop);
+        // The context keeps the mainloop internals alive, so is still usable
+        assert!(inner.upgrade().is_some());
+        assert_eq!(context.get_state(), State::Unconnected);
+        drop(context);
+        assert!(inner.upgrade().is_none());
+    }
+
+    #[test]
+    fn new_with_shared_proplist() {
+        let mainloop = Mainloop::new().unwrap();
+        let mut proplist = Proplist::new().unwrap();
+        proplist.set_str(proplist::properties::APPLICATION_NAME, "test").unwrap();
+        let shared = &proplist;
+        let first = Context::new_with_proplist(&mainloop, "first", shared).unwrap();
+        let second = Context::new_with_proplist(&mainloop, "second", shared).unwrap();
+        // Still usable afterwards, and unchanged
+        assert_eq!(shared.get_str(proplist::properties::APPLICATION_NAME).unwrap(), "test");
+        assert_eq!(shared.len(), 1);
+        drop((first, second));
+    }
+
+    /// The event callback is given a view of the property list, of which a copy can be kept beyond
+    /// PA freeing the original (run under ASAN to catch any use-after-free).
+    #[test]
+    fn event_callback_proplist() {
+        use std::cell::RefCell;
+
+        let mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "test").unwrap();
+        let kept = Rc::new(RefCell::new(None));
+        let kept_ref = Rc::clone(&kept);
+        context.set_event_callback(Some(Box::new(move |name: String, list: ProplistRef| {
+            *kept_ref.borrow_mut() = Some((name, list.to_owned()));
+        })));
+
+        let mut original = Proplist::new().unwrap();
+        original.set_str(proplist::properties::EVENT_ID, "foo").unwrap();
+        let name = CString::new("some-event").unwrap();
+        let (_, cb_data) = context.cb_ptrs.event.get_capi_params(event_cb_proxy);
+        event_cb_proxy(context.ptr, name.as_ptr(), original.0.ptr, cb_data);
+        drop(original);
+
+        let (name, copy) = kept.borrow_mut().take().unwrap();
+        assert_eq!(name, "some-event");
+        assert_eq!(copy.get_str(proplist::properties::EVENT_ID).unwrap(), "foo");
+    }
+
+    #[test]
+    fn success_results() {
+        use std::cell::RefCell;
+        use crate::error::Code;
+
+        let mainloop = Mainloop::new().unwrap();
+        let context = Context::new(&mainloop, "test").unwrap();
+        let results = Rc::new(RefCell::new(Vec::new()));
+        let deliver = |success| {
+            let results_ref = Rc::clone(&results);
+            let callback = Box::new(move |r| results_ref.borrow_mut().push(r));
+            let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(callback);
+            success_cb_proxy(context.ptr, success, cb_data);
+        };
+
+        deliver(1);
+        // No error has been recorded by the unconnected context
+        deliver(0);
+        // Force a failure, which the context records, and which is then delivered
+        let mut introspect = context.introspect();
+        assert!(introspect.try_set_source_mute_by_name("nonexistent", true, None).is_err());
+        deliver(0);
+        assert_eq!(*results.borrow(),
+            vec![Ok(()), Err(PAErr::from(Code::Unknown)), Err(PAErr::from(Code::BadState))]);
+        // Each closure is destroyed after use
+        assert_eq!(Rc::strong_count(&results), 1);
+    }
+
+    #[test]
+    fn state_helpers() {
+        let states = [
+            (State::Unconnected, "unconnected", false, false, false),
+            (State::Connecting, "connecting", true, false, false),
+            (State::Authorizing, "authorizing", true, false, false),
+            (State::SettingName, "setting name", true, false, false),
+            (State::Ready, "ready", true, true, false),
+            (State::Failed, "failed", false, false, true),
+            (State::Terminated, "terminated", false, false, true),
+        ];
+        for &(state, name, good, ready, terminal) in states.iter() {
+            assert_eq!(state.to_string(), name);
+            assert_eq!(state.is_good(), good);
+            assert_eq!(state.is_ready(), ready);
+            assert_eq!(state.is_terminal(), terminal);
+            assert_eq!(State::from(state as i32), state);
+        }
+        // Unknown raw values are conservatively treated as failure
+        assert_eq!(State::from(State::Terminated as i32 + 1), State::Failed);
+        assert_eq!(State::from(-1), State::Failed);
+    }
+
+    #[test]
+    fn not_pending_when_unconnected() {
+        let mainloop = Mainloop::new().unwrap();
+        let context = Context::new(&mainloop, "test").unwrap();
+        assert_eq!(context.get_state(), State::Unconnected);
+        assert!(!context.is_pending());
+    }
+
+    #[test]
+    fn new() {
+        let mainloop = Mainloop::new().unwrap();
+        assert!(Context::new(&mainloop, "test").is_ok());
+        assert_eq!(Context::new(&mainloop, "te\0st").err(), Some(ContextError::InvalidName));
+
+        let proplist = Proplist::new().unwrap();
+        assert!(Context::new_with_proplist(&mainloop, "test", &proplist).is_ok());
+        assert_eq!(Context::new_with_proplist(&mainloop, "te\0st", &proplist).err(),
+            Some(ContextError::InvalidName));
+    }
+
+    #[test]
+    fn connect_noautospawn_with_spawn_api() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        static CALLED: AtomicBool = AtomicBool::new(false);
+        extern "C" fn hook() {
+            CALLED.store(true, Ordering::SeqCst);
+        }
+        let api = def::SpawnApi::new().prefork(hook).postfork(hook).atfork(hook);
+        assert!(api.prefork.is_some() && api.postfork.is_some() && api.atfork.is_some());
+        assert!(def::SpawnApi::default().prefork.is_none());
+
+        let mut mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "FooApp").unwrap();
+        // Whether or not a daemon is available, with autospawning disabled the hooks must never
+        // be used
+        let _ = context.connect(None, Flags::NOAUTOSPAWN, Some(&api));
+        for _ in 0..10 {
+            mainloop.iterate(false);
+        }
+        assert!(!CALLED.load(Ordering::SeqCst));
+        context.disconnect();
+    }
+
+    #[test]
+    fn connect_server_validation() {
+        let mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "FooApp").unwrap();
+        let invalid = Err(PAErr::from(crate::error::Code::Invalid));
+        assert_eq!(context.connect(Some(""), Flags::NOAUTOSPAWN, None), invalid);
+        assert_eq!(context.connect(Some("unix:/foo\0bar"), Flags::NOAUTOSPAWN, None), invalid);
+        // Rejected before reaching the C library, so the context is untouched
+        assert_eq!(context.get_state(), State::Unconnected);
+
+        // Connecting to a server which does not exist may or may not fail immediately, but the
+        // string itself is accepted
+        let result = context.connect(Some("unix:/nonexistent/pulse-binding-test/native"),
+            Flags::NOAUTOSPAWN, None);
+        assert_ne!(result, invalid);
+        context.disconnect();
+    }
+
+    #[test]
+    fn flags() {
+        assert_eq!(std::mem::size_of::<Flags>(), std::mem::size_of::<capi::pa_context_flags_t>());
+
+        let f = Flags::NOAUTOSPAWN | Flags::NOFAIL;
+        assert!(f.contains(Flags::NOAUTOSPAWN));
+        assert!(f.contains(Flags::NOFAIL));
+        assert!(f.contains(Flags::NOFLAGS));
+        assert_eq!(f.bits(), capi::PA_CONTEXT_NOAUTOSPAWN | capi::PA_CONTEXT_NOFAIL);
+        assert_eq!(f - Flags::NOFAIL, Flags::NOAUTOSPAWN);
+        assert!(Flags::NOFLAGS.is_empty());
+        assert_eq!(Flags::empty(), Flags::NOFLAGS);
+        assert_eq!(Flags::from_bits(0x1000), None);
+    }
+
+    #[test]
+    fn not_send_sync() {
+        assert_not_impl!(Context: Send);
+        assert_not_impl!(Context: Sync);
+    }
+}
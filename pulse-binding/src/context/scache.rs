@@ -65,6 +65,7 @@ use std::ffi::CString;
 use std::ptr::null;
 use super::{ContextInternal, Context};
 use crate::def;
+use crate::error::PAErr;
 use crate::callbacks::{box_closure_get_capi_ptr, get_su_capi_params, get_su_callback};
 use crate::{operation::Operation, volume::Volume, proplist::Proplist};
 
@@ -73,21 +74,22 @@ impl Context {
     ///
     /// Returns an operation object which may be used to cancel the operation while it is running.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn remove_sample<F>(&mut self, name: &str, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn remove_sample<F>(&mut self, name: &str, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
         let c_name = CString::new(name.clone()).unwrap();
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_remove_sample(self.ptr, c_name.as_ptr(),
             Some(super::success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Plays a sample from the sample cache to the specified device.
@@ -101,13 +103,15 @@ impl Context {
     /// * `volume`: Volume to play this sample with. Starting with 0.9.15 you may pass here
     ///   [`volume::VOLUME_INVALID`] which will leave the decision about the volume to the server
     ///   side which is a good idea.
-    /// * `callback`: Optional success callback. It must accept a `bool`, which indicates success.
+    /// * `callback`: Optional success callback. It must accept a `Result`, which gives the error
+    ///   upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     ///
     /// [`volume::VOLUME_INVALID`]: ../volume/constant.VOLUME_INVALID.html
     pub fn play_sample(&mut self, name: &str, dev: Option<&str>, volume: Volume,
-        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Operation<dyn FnMut(bool)>
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -124,7 +128,7 @@ impl Context {
         let ptr = unsafe { capi::pa_context_play_sample(self.ptr, c_name.as_ptr(), p_dev, volume.0,
             cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Plays a sample from the sample cache to the specified device, allowing specification of a
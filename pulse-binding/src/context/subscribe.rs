@@ -25,11 +25,13 @@
 //!
 //! The mask provided to [`context::Context::subscribe`] can be created by binary ORing a set of
 //! values, either produced with [`Facility::to_interest_mask`], or more simply with the provided
-//! constants in the [`subscription_masks`] submodule.
+//! constants in the [`subscription_masks`] submodule. Calling it again replaces the mask, and
+//! [`context::Context::unsubscribe_all`] disables notification entirely.
 //!
-//! The callback will be called with event type information representing the event that caused the
+//! The callback will be called with a [`SubscriptionEvent`] representing the event that caused the
 //! callback, detailing *facility* and *operation*, where for instance `Facility::Source` with
-//! `Operation::New` indicates that a new source was added.
+//! `Operation::New` indicates that a new source was added, along with the index of the object
+//! concerned.
 //!
 //! # Example
 //!
@@ -48,15 +50,19 @@
 //! ```
 //!
 //! [`Facility`]: enum.Facility.html
+//! [`SubscriptionEvent`]: struct.SubscriptionEvent.html
 //! [`Operation`]: enum.Operation.html
 //! [`Facility::to_interest_mask`]: enum.Facility.html#method.to_interest_mask
 //! [`context::Context::subscribe`]: ../struct.Context.html#method.subscribe
 //! [`context::Context::set_subscribe_callback`]: ../struct.Context.html#method.set_subscribe_callback
+//! [`context::Context::unsubscribe_all`]: ../struct.Context.html#method.unsubscribe_all
 //! [`subscription_masks`]: subscription_masks/index.html
 
+use std::cell::RefCell;
 use std::os::raw::c_void;
 use super::{ContextInternal, Context};
 use crate::operation;
+use crate::error::PAErr;
 use crate::callbacks::{box_closure_get_capi_ptr, MultiUseCallback};
 
 pub use capi::context::subscribe::pa_subscription_event_type_t as EventType;
@@ -163,7 +169,33 @@ fn get_operation(value: EventType) -> Option<Operation> {
     Operation::from_int((value & OPERATION_MASK) as u32)
 }
 
-pub(super) type Callback = MultiUseCallback<dyn FnMut(Option<Facility>, Option<Operation>, u32),
+/// A subscription event, decoded from the raw event type and index given by the C API.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SubscriptionEvent {
+    /// Facility component of the event type.
+    ///
+    /// This is `None` should the value given ever not map to a `Facility` variant.
+    pub facility: Option<Facility>,
+    /// Operation component of the event type.
+    ///
+    /// This is `None` should the value given ever not map to an `Operation` variant.
+    pub operation: Option<Operation>,
+    /// Index of the object concerned.
+    pub index: u32,
+}
+
+impl SubscriptionEvent {
+    /// Decodes an event from the raw event type and index values.
+    fn from_raw(event_type: EventType, index: u32) -> Self {
+        Self {
+            facility: get_facility(event_type),
+            operation: get_operation(event_type),
+            index: index,
+        }
+    }
+}
+
+pub(super) type Callback = MultiUseCallback<dyn FnMut(SubscriptionEvent),
     extern "C" fn(*mut ContextInternal, EventType, u32, *mut c_void)>;
 
 impl Context {
@@ -173,18 +205,47 @@ impl Context {
     /// modified about. Use [`set_subscribe_callback`](#method.set_subscribe_callback) to set the
     /// actual callback that will be called when an event occurs.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which indicates whether or not the server accepted the
+    /// subscription (it may for instance be refused by an older server), giving the error if not.
+    ///
+    /// The mask replaces any previously set mask, so this can be used to change the set of
+    /// facilities of interest at runtime (see also [`unsubscribe_all`](#method.unsubscribe_all)).
+    ///
+    /// # Ordering
+    ///
+    /// The server acknowledges a subscription request before sending any events resulting from it,
+    /// and messages from the server are dispatched in the order received. Thus no event for a newly
+    /// added facility is delivered to the subscribe callback before the success callback here has
+    /// been called. (Events for facilities that were already of interest, from a previous call, may
+    /// of course still arrive in the meantime).
     ///
     /// Panics if the underlying C function returns a null pointer.
     pub fn subscribe<F>(&mut self, mask: InterestMaskSet, callback: F)
-        -> operation::Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+        -> operation::Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_context_subscribe(self.ptr, mask, Some(super::success_cb_proxy),
             cb_data) };
         assert!(!ptr.is_null());
-        operation::Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        operation::Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
+    }
+
+    /// Disables event notification for all facilities.
+    ///
+    /// This is simply a shortcut for [`subscribe`](#method.subscribe) with an empty mask
+    /// ([`subscription_masks::NULL`]). Once the success callback has been called, no further events
+    /// will be delivered to the subscribe callback.
+    ///
+    /// Panics if the underlying C function returns a null pointer.
+    ///
+    /// [`subscription_masks::NULL`]: subscribe/subscription_masks/constant.NULL.html
+    #[inline]
+    pub fn unsubscribe_all<F>(&mut self, callback: F)
+        -> operation::Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
+    {
+        self.subscribe(subscription_masks::NULL, callback)
     }
 
     /// Sets the context specific call back function that is called whenever a subscribed-to event
@@ -193,19 +254,46 @@ impl Context {
     /// Use [`subscribe`](#method.subscribe) to set the facilities you are interested in receiving
     /// notifications for, and thus to start receiving notifications with the callback set here.
     ///
-    /// The callback must take three parameters. The first two are the facility and operation
-    /// components of the event type respectively (the underlying C API provides this information
-    /// combined into a single integer, here we extract the two component parts for you); these are
-    /// wrapped in `Option` wrappers should the given values ever not map to the enum variants, but
-    /// it’s probably safe to always just `unwrap()` them). The third parameter is an associated
-    /// index value.
+    /// The callback is given a [`SubscriptionEvent`], holding the facility and operation
+    /// components of the event type (the underlying C API provides this information combined into
+    /// a single integer, here we extract the two component parts for you), along with the
+    /// associated index value.
+    ///
+    /// The callback can be replaced (or cleared) at any time, independently of the interest mask,
+    /// including from within the callback itself. In that case the running callback is only
+    /// destroyed once it returns.
+    ///
+    /// Note that this deferral only covers replacing the callback. Dropping the `Context` itself
+    /// from within the callback still destroys the running closure, and must not be done. The same
+    /// applies to the other callbacks set on the context, such as the state and event callbacks,
+    /// which have no such deferral at all: neither replacing them nor dropping the `Context` from
+    /// within them is safe.
+    ///
+    /// [`SubscriptionEvent`]: subscribe/struct.SubscriptionEvent.html
     pub fn set_subscribe_callback(&mut self,
-        callback: Option<Box<dyn FnMut(Option<Facility>, Option<Operation>, u32) + 'static>>)
+        callback: Option<Box<dyn FnMut(SubscriptionEvent) + 'static>>)
     {
-        let saved = &mut self.cb_ptrs.subscribe;
-        *saved = Callback::new(callback);
-        let (cb_fn, cb_data) = saved.get_capi_params(cb_proxy);
+        let old = std::mem::replace(&mut self.cb_ptrs.subscribe, Callback::new(callback));
+        let (cb_fn, cb_data) = self.cb_ptrs.subscribe.get_capi_params(cb_proxy);
         unsafe { capi::pa_context_set_subscribe_callback(self.ptr, cb_fn, cb_data); }
+        release_callback(old);
+    }
+}
+
+thread_local! {
+    /// Closures of subscribe callbacks currently executing on this thread (outermost first).
+    static DISPATCHING: RefCell<Vec<*mut c_void>> = RefCell::new(Vec::new());
+    /// Replaced callbacks, whose destruction is deferred until dispatching has finished.
+    static DEFERRED: RefCell<Vec<Callback>> = RefCell::new(Vec::new());
+}
+
+/// Destroys a replaced callback, unless it is currently executing, in which case destruction is
+/// deferred until it returns.
+fn release_callback(old: Callback) {
+    let (_, ptr) = old.get_capi_params(cb_proxy);
+    let running = !ptr.is_null() && DISPATCHING.with(|d| d.borrow().contains(&ptr));
+    if running {
+        DEFERRED.with(|d| d.borrow_mut().push(old));
     }
 }
 
@@ -213,12 +301,228 @@ impl Context {
 ///
 /// Warning: This is for multi-use cases! It does **not** destroy the actual closure callback, which
 /// must be accomplished separately to avoid a memory leak.
-extern "C"
+pub(super) extern "C"
 fn cb_proxy(_: *mut ContextInternal, et: EventType, index: u32, userdata: *mut c_void) {
+    DISPATCHING.with(|d| d.borrow_mut().push(userdata));
     let _ = std::panic::catch_unwind(|| {
-        let facility = get_facility(et);
-        let operation = get_operation(et);
+        let event = SubscriptionEvent::from_raw(et, index);
         let callback = Callback::get_callback(userdata);
-        (callback)(facility, operation, index);
+        (callback)(event);
     });
+    let finished = DISPATCHING.with(|d| {
+        let mut d = d.borrow_mut();
+        d.pop();
+        d.is_empty()
+    });
+    if finished {
+        let deferred = DEFERRED.with(|d| std::mem::replace(&mut *d.borrow_mut(), Vec::new()));
+        drop(deferred);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use crate::context::{Flags, State};
+    use crate::mainloop::standard::Mainloop;
+
+    #[test]
+    fn decode_event() {
+        let event = SubscriptionEvent::from_raw(
+            capi::PA_SUBSCRIPTION_EVENT_SINK | capi::PA_SUBSCRIPTION_EVENT_NEW, 3);
+        assert_eq!(event, SubscriptionEvent {
+            facility: Some(Facility::Sink),
+            operation: Some(Operation::New),
+            index: 3,
+        });
+
+        let event = SubscriptionEvent::from_raw(
+            capi::PA_SUBSCRIPTION_EVENT_SINK_INPUT | capi::PA_SUBSCRIPTION_EVENT_CHANGE, 17);
+        assert_eq!(event, SubscriptionEvent {
+            facility: Some(Facility::SinkInput),
+            operation: Some(Operation::Changed),
+            index: 17,
+        });
+
+        let event = SubscriptionEvent::from_raw(
+            capi::PA_SUBSCRIPTION_EVENT_CLIENT | capi::PA_SUBSCRIPTION_EVENT_REMOVE, 42);
+        assert_eq!(event, SubscriptionEvent {
+            facility: Some(Facility::Client),
+            operation: Some(Operation::Removed),
+            index: 42,
+        });
+
+        // Obsoleted facility value
+        let event = SubscriptionEvent::from_raw(8 | capi::PA_SUBSCRIPTION_EVENT_NEW, 0);
+        assert_eq!(event.facility, None);
+        assert_eq!(event.operation, Some(Operation::New));
+    }
+
+    /// Sets a flag when dropped.
+    struct DropFlag(Rc<Cell<bool>>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    /// Simulates the C API delivering an event to the current callback.
+    fn deliver(context: &Rc<RefCell<Context>>, index: u32) {
+        let (ptr, (_, cb_data)) = {
+            let context = context.borrow();
+            (context.ptr, context.cb_ptrs.subscribe.get_capi_params(cb_proxy))
+        };
+        if !cb_data.is_null() {
+            cb_proxy(ptr, capi::PA_SUBSCRIPTION_EVENT_SINK | capi::PA_SUBSCRIPTION_EVENT_CHANGE,
+                index, cb_data);
+        }
+    }
+
+    #[test]
+    fn replace_callback_from_within() {
+        let mainloop = Mainloop::new().unwrap();
+        let context = Rc::new(RefCell::new(Context::new(&mainloop, "test").unwrap()));
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        // Each callback handles one event, then replaces itself with a fresh one, checking that its
+        // own captured state survives the replacement
+        fn install(context: &Rc<RefCell<Context>>, received: &Rc<RefCell<Vec<u32>>>,
+            drops: &Rc<RefCell<Vec<Rc<Cell<bool>>>>>, generation: u32)
+        {
+            let dropped = Rc::new(Cell::new(false));
+            drops.borrow_mut().push(Rc::clone(&dropped));
+            let drops_ref = Rc::clone(drops);
+            let guard = DropFlag(Rc::clone(&dropped));
+            let context_ref = Rc::downgrade(context);
+            let received_ref = Rc::clone(received);
+            context.borrow_mut().set_subscribe_callback(Some(Box::new(move |event| {
+                let _ = &guard;
+                received_ref.borrow_mut().push(event.index + 1000 * generation);
+                if let Some(context) = context_ref.upgrade() {
+                    match generation {
+                        49 => context.borrow_mut().set_subscribe_callback(None),
+                        _ => install(&context, &received_ref, &drops_ref, generation + 1),
+                    }
+                }
+                assert!(!dropped.get());
+            })));
+        }
+        let drops = Rc::new(RefCell::new(Vec::new()));
+        install(&context, &received, &drops, 0);
+
+        for i in 0..100 {
+            deliver(&context, i);
+        }
+        // Fifty generations handled one event each, then the callback was cleared
+        let expected: Vec<u32> = (0..50).map(|i| i + 1000 * i).collect();
+        assert_eq!(*received.borrow(), expected);
+        assert!(DEFERRED.with(|d| d.borrow().is_empty()));
+        assert_eq!(drops.borrow().len(), 50);
+        assert!(drops.borrow().iter().all(|d| d.get()));
+
+        // Replacing from outside of a callback destroys the old callback immediately
+        let dropped = Rc::new(Cell::new(false));
+        let guard = DropFlag(Rc::clone(&dropped));
+        context.borrow_mut().set_subscribe_callback(Some(Box::new(move |_| { let _ = &guard; })));
+        context.borrow_mut().set_subscribe_callback(None);
+        assert!(dropped.get());
+    }
+
+    /// Connects a context to the default server, or returns `None` if no server is available.
+    fn connect(mainloop: &mut Mainloop, name: &str) -> Option<Context> {
+        let mut context = Context::new(mainloop, name).unwrap();
+        context.connect(None, Flags::NOAUTOSPAWN, None).ok()?;
+        loop {
+            if !mainloop.iterate(true).is_success() {
+                return None;
+            }
+            match context.get_state() {
+                State::Ready => return Some(context),
+                State::Failed | State::Terminated => return None,
+                _ => {},
+            }
+        }
+    }
+
+    /// Iterates the mainloop until the condition is met.
+    fn iterate_until<F: FnMut() -> bool>(mainloop: &mut Mainloop, mut done: F) {
+        while !done() {
+            assert!(mainloop.iterate(true).is_success());
+        }
+    }
+
+    /// What the subscribe and success callbacks observed, in order.
+    #[derive(Debug, PartialEq)]
+    enum Logged {
+        Subscribed,
+        Event(Option<Facility>),
+    }
+
+    /// Subscribes with the given mask, iterating the mainloop until the server has acknowledged it.
+    fn resubscribe(mainloop: &mut Mainloop, context: &mut Context, mask: InterestMaskSet,
+        log: &Rc<RefCell<Vec<Logged>>>)
+    {
+        let done = Rc::new(Cell::new(false));
+        let done_ref = Rc::clone(&done);
+        let log_ref = Rc::clone(log);
+        let _op = context.subscribe(mask, move |result| {
+            assert!(result.is_ok());
+            log_ref.borrow_mut().push(Logged::Subscribed);
+            done_ref.set(true);
+        });
+        iterate_until(mainloop, || done.get());
+    }
+
+    /// Toggles the interest mask at runtime, against a live server. Skipped (passing) if no server
+    /// is available.
+    #[test]
+    fn toggle_mask() {
+        let mut mainloop = Mainloop::new().unwrap();
+        let mut context = match connect(&mut mainloop, "subscriber") {
+            Some(context) => context,
+            None => return,
+        };
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let log_ref = Rc::clone(&log);
+        context.set_subscribe_callback(Some(Box::new(move |event| {
+            log_ref.borrow_mut().push(Logged::Event(event.facility));
+        })));
+
+        // Nothing of interest yet; connecting a client must not notify us
+        resubscribe(&mut mainloop, &mut context, subscription_masks::NULL, &log);
+        let _first = connect(&mut mainloop, "first").unwrap();
+        resubscribe(&mut mainloop, &mut context, subscription_masks::NULL, &log);
+        assert!(!log.borrow().contains(&Logged::Event(Some(Facility::Client))));
+
+        // Request client events, and immediately cause one; it may only be delivered after the
+        // success callback
+        log.borrow_mut().clear();
+        let done = Rc::new(Cell::new(false));
+        let done_ref = Rc::clone(&done);
+        let log_ref = Rc::clone(&log);
+        let _op = context.subscribe(subscription_masks::CLIENT, move |result| {
+            assert!(result.is_ok());
+            log_ref.borrow_mut().push(Logged::Subscribed);
+            done_ref.set(true);
+        });
+        let _second = connect(&mut mainloop, "second").unwrap();
+        iterate_until(&mut mainloop, || {
+            log.borrow().contains(&Logged::Event(Some(Facility::Client)))
+        });
+        assert!(done.get());
+        let subscribed = log.borrow().iter().position(|l| *l == Logged::Subscribed).unwrap();
+        let first_event = log.borrow().iter()
+            .position(|l| *l == Logged::Event(Some(Facility::Client))).unwrap();
+        assert!(subscribed < first_event);
+
+        // And once unsubscribed, no more client events are delivered
+        resubscribe(&mut mainloop, &mut context, subscription_masks::NULL, &log);
+        log.borrow_mut().clear();
+        let _third = connect(&mut mainloop, "third").unwrap();
+        resubscribe(&mut mainloop, &mut context, subscription_masks::NULL, &log);
+        assert_eq!(*log.borrow(), vec![Logged::Subscribed]);
+    }
 }
@@ -39,12 +39,12 @@ pub struct BufferAttr {
     ///
     /// Setting this to `std::u32::MAX` will initialize this to the maximum value supported by the
     /// server, which is recommended. In strict low-latency playback scenarios you might want to set
-    /// this to a lower value, likely together with the [`stream::flags::ADJUST_LATENCY`] flag. If
+    /// this to a lower value, likely together with the [`stream::Flags::ADJUST_LATENCY`] flag. If
     /// you do so, you ensure that the latency doesn’t grow beyond what is acceptable for the use
     /// case, at the cost of getting more underruns if the latency is lower than what the server can
     /// reliably handle.
     ///
-    /// [`stream::flags::ADJUST_LATENCY`]: ../stream/flags/constant.ADJUST_LATENCY.html
+    /// [`stream::Flags::ADJUST_LATENCY`]: ../stream/struct.Flags.html#associatedconstant.ADJUST_LATENCY
     pub maxlength: u32,
 
     /// Target length of the buffer (playback only). The server tries to assure that at least
@@ -57,14 +57,14 @@ pub struct BufferAttr {
     /// applications that have specific latency requirements this value should be set to the maximum
     /// latency that the application can deal with.
     ///
-    /// When [`stream::flags::ADJUST_LATENCY`] is not set this value will influence only the
-    /// per-stream playback buffer size. When [`stream::flags::ADJUST_LATENCY`] is set, the overall
+    /// When [`stream::Flags::ADJUST_LATENCY`] is not set this value will influence only the
+    /// per-stream playback buffer size. When [`stream::Flags::ADJUST_LATENCY`] is set, the overall
     /// latency of the sink plus the playback buffer size is configured to this value. Set
-    /// [`stream::flags::ADJUST_LATENCY`] if you are interested in adjusting the overall latency.
+    /// [`stream::Flags::ADJUST_LATENCY`] if you are interested in adjusting the overall latency.
     /// Don’t set it if you are interested in configuring the server-side per-stream playback buffer
     /// size.
     ///
-    /// [`stream::flags::ADJUST_LATENCY`]: ../stream/flags/constant.ADJUST_LATENCY.html
+    /// [`stream::Flags::ADJUST_LATENCY`]: ../stream/struct.Flags.html#associatedconstant.ADJUST_LATENCY
     pub tlength: u32,
 
     /// Pre-buffering (playback only). The server does not start with playback before at least
@@ -75,7 +75,7 @@ pub struct BufferAttr {
     /// Initialize to `0` to enable manual start/stop control of the stream. This means that
     /// playback will not stop on underrun and playback will not start automatically, instead
     /// [`stream::Stream::cork`] needs to be called explicitly. If you set this value to `0` you
-    /// should also set [`stream::flags::START_CORKED`]. Should underrun occur, the read index of
+    /// should also set [`stream::Flags::START_CORKED`]. Should underrun occur, the read index of
     /// the output buffer overtakes the write index, and hence the fill level of the buffer is
     /// negative.
     ///
@@ -85,7 +85,7 @@ pub struct BufferAttr {
     ///
     /// [`stream::Stream::cork`]: ../stream/struct.Stream.html#method.cork
     /// [`stream::Stream::trigger`]: ../stream/struct.Stream.html#method.trigger
-    /// [`stream::flags::START_CORKED`]: ../stream/flags/constant.START_CORKED.html
+    /// [`stream::Flags::START_CORKED`]: ../stream/struct.Flags.html#associatedconstant.START_CORKED
     pub prebuf: u32,
 
     /// Minimum request (playback only). The server does not request less than `minreq` bytes from
@@ -104,10 +104,10 @@ pub struct BufferAttr {
     /// default to something like 2s; For applications that have specific latency requirements this
     /// value should be set to the maximum latency that the application can deal with.
     ///
-    /// If [`stream::flags::ADJUST_LATENCY`] is set the overall source latency will be adjusted
+    /// If [`stream::Flags::ADJUST_LATENCY`] is set the overall source latency will be adjusted
     /// according to this value. If it is not set the source latency is left unmodified.
     ///
-    /// [`stream::flags::ADJUST_LATENCY`]: ../stream/flags/constant.ADJUST_LATENCY.html
+    /// [`stream::Flags::ADJUST_LATENCY`]: ../stream/struct.Flags.html#associatedconstant.ADJUST_LATENCY
     pub fragsize: u32,
 }
 
@@ -247,6 +247,69 @@ fn timinginfo_compare_capi(){
     assert_eq!(std::mem::align_of::<TimingInfo>(), std::mem::align_of::<capi::pa_timing_info>());
 }
 
+impl TimingInfo {
+    /// Gets the [`sink_usec`](#structfield.sink_usec), or `None` should it be the
+    /// [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../time/constant.USEC_INVALID.html
+    #[inline]
+    pub fn reported_sink_usec(&self) -> Option<MicroSeconds> {
+        self.sink_usec.valid()
+    }
+
+    /// Gets the [`source_usec`](#structfield.source_usec), or `None` should it be the
+    /// [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../time/constant.USEC_INVALID.html
+    #[inline]
+    pub fn reported_source_usec(&self) -> Option<MicroSeconds> {
+        self.source_usec.valid()
+    }
+
+    /// Gets the [`transport_usec`](#structfield.transport_usec), or `None` should it be the
+    /// [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../time/constant.USEC_INVALID.html
+    #[inline]
+    pub fn reported_transport_usec(&self) -> Option<MicroSeconds> {
+        self.transport_usec.valid()
+    }
+
+    /// Gets the [`configured_sink_usec`](#structfield.configured_sink_usec), or `None` should it
+    /// be the [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../time/constant.USEC_INVALID.html
+    #[inline]
+    pub fn reported_configured_sink_usec(&self) -> Option<MicroSeconds> {
+        self.configured_sink_usec.valid()
+    }
+
+    /// Gets the [`configured_source_usec`](#structfield.configured_source_usec), or `None` should
+    /// it be the [`USEC_INVALID`] sentinel.
+    ///
+    /// [`USEC_INVALID`]: ../time/constant.USEC_INVALID.html
+    #[inline]
+    pub fn reported_configured_source_usec(&self) -> Option<MicroSeconds> {
+        self.configured_source_usec.valid()
+    }
+}
+
+#[test]
+fn timinginfo_reported_usecs(){
+    let mut raw: capi::pa_timing_info = unsafe { std::mem::zeroed() };
+    raw.sink_usec = 40_000;
+    raw.source_usec = crate::time::USEC_INVALID.0;
+    raw.transport_usec = 1_500;
+    raw.configured_sink_usec = crate::time::USEC_INVALID.0;
+    raw.configured_source_usec = 20_000;
+    let info: &TimingInfo = raw.as_ref();
+    assert_eq!(info.reported_sink_usec(), Some(MicroSeconds(40_000)));
+    assert_eq!(info.reported_source_usec(), None);
+    assert_eq!(info.reported_transport_usec(), Some(MicroSeconds(1_500)));
+    assert_eq!(info.reported_configured_sink_usec(), None);
+    assert_eq!(info.reported_configured_source_usec(), Some(MicroSeconds(20_000)));
+}
+
 impl AsRef<TimingInfo> for capi::pa_timing_info {
     #[inline]
     fn as_ref(&self) -> &TimingInfo {
@@ -261,9 +324,33 @@ impl AsRef<TimingInfo> for capi::pa_timing_info {
 /// the child’s PID. The spawn routine will not block or ignore SIGCHLD signals, since this cannot
 /// be done in a thread compatible way. You might have to do this in prefork/postfork.
 ///
+/// The hooks are only used should a daemon actually be autospawned; they are never called if
+/// autospawning is disabled with [`context::Flags::NOAUTOSPAWN`], or if a specific server is given.
+/// Most embedded users will want to simply use that flag, and need no spawn API at all.
+///
+/// The hooks may be set either directly, or with the builder style methods, for instance:
+///
+/// ```rust
+/// # extern crate libpulse_binding as pulse;
+/// # use pulse::def::SpawnApi;
+/// extern "C" fn prefork() { /* ... */ }
+///
+/// let api = SpawnApi::new().prefork(prefork);
+/// ```
+///
+/// # Why not closures?
+///
+/// The hooks are plain C functions, taking no userdata, thus there is simply nowhere to hold the
+/// state of a closure. More importantly, they run around the `fork()` call, the `atfork` hook in
+/// particular running in the child process of a possibly multi-threaded parent, where only
+/// async-signal-safe functions may be used. Allocating, locking (including the lock of a `Mutex`,
+/// or that taken by `println!`), and unwinding are all unsafe there, thus the hooks should be
+/// minimal, carefully written functions.
+///
 /// [`context::Context::connect`]: ../context/struct.Context.html#method.connect
+/// [`context::Flags::NOAUTOSPAWN`]: ../context/struct.Flags.html#associatedconstant.NOAUTOSPAWN
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Default, Copy, Clone)]
 pub struct SpawnApi {
     /* NOTE: This struct must be directly usable by the C API, thus same attributes/layout/etc */
 
@@ -280,6 +367,37 @@ pub struct SpawnApi {
     pub atfork: Option<extern "C" fn()>,
 }
 
+impl SpawnApi {
+    /// Creates a new spawn API, with no hooks set.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { prefork: None, postfork: None, atfork: None }
+    }
+
+    /// Sets the hook called just before the fork in the parent process.
+    #[inline]
+    pub fn prefork(mut self, hook: extern "C" fn()) -> Self {
+        self.prefork = Some(hook);
+        self
+    }
+
+    /// Sets the hook called immediately after the fork in the parent process.
+    #[inline]
+    pub fn postfork(mut self, hook: extern "C" fn()) -> Self {
+        self.postfork = Some(hook);
+        self
+    }
+
+    /// Sets the hook called immediately after the fork in the child process.
+    ///
+    /// See the [`atfork`](#structfield.atfork) field for important notes.
+    #[inline]
+    pub fn atfork(mut self, hook: extern "C" fn()) -> Self {
+        self.atfork = Some(hook);
+        self
+    }
+}
+
 /// Test size is equal to `sys` equivalent (duplicated here for different documentation)
 #[test]
 fn spawnapi_compare_capi(){
@@ -294,49 +412,150 @@ impl AsRef<capi::pa_spawn_api> for SpawnApi {
     }
 }
 
-pub type SinkFlagSet = capi::def::pa_sink_flags_t;
+flags_type! {
+    /// Special sink flags.
+    pub struct SinkFlagSet: capi::def::pa_sink_flags_t {
+        /// Flag to pass when no specific options are needed.
+        const NOFLAGS = capi::PA_SINK_NOFLAGS;
+
+        /// Supports hardware volume control. This is a dynamic flag and may change at runtime after
+        /// the sink has initialized.
+        const HW_VOLUME_CTRL = capi::PA_SINK_HW_VOLUME_CTRL;
+
+        /// Supports latency querying.
+        const LATENCY = capi::PA_SINK_LATENCY;
+
+        /// Is a hardware sink of some kind, in contrast to “virtual”/software sinks.
+        const HARDWARE = capi::PA_SINK_HARDWARE;
+
+        /// Is a networked sink of some kind.
+        const NETWORK = capi::PA_SINK_NETWORK;
+
+        /// Supports hardware mute control. This is a dynamic flag and may change at runtime after
+        /// the sink has initialized.
+        const HW_MUTE_CTRL = capi::PA_SINK_HW_MUTE_CTRL;
+
+        /// Volume can be translated to dB with [`volume::sw_volume_to_db`]. This is a dynamic flag
+        /// and may change at runtime after the sink has initialized.
+        ///
+        /// [`volume::sw_volume_to_db`]: ../volume/fn.sw_volume_to_db.html
+        const DECIBEL_VOLUME = capi::PA_SINK_DECIBEL_VOLUME;
+
+        /// This sink is in flat volume mode, i.e. always the maximum of the volume  of all
+        /// connected inputs.
+        const FLAT_VOLUME = capi::PA_SINK_FLAT_VOLUME;
+
+        /// The latency can be adjusted dynamically depending on the needs of the connected streams.
+        const DYNAMIC_LATENCY = capi::PA_SINK_DYNAMIC_LATENCY;
+
+        /// The sink allows setting what formats are supported by the connected hardware. The
+        /// actual functionality to do this might be provided by an extension.
+        const SET_FORMATS = capi::PA_SINK_SET_FORMATS;
+    }
+}
+
+impl SinkFlagSet {
+    /// Checks whether hardware volume control is supported.
+    #[inline]
+    pub fn supports_hardware_volume(self) -> bool {
+        self.contains(Self::HW_VOLUME_CTRL)
+    }
+
+    /// Checks whether hardware mute control is supported.
+    #[inline]
+    pub fn supports_hardware_mute(self) -> bool {
+        self.contains(Self::HW_MUTE_CTRL)
+    }
+
+    /// Checks whether the volume can be translated to dB.
+    #[inline]
+    pub fn has_decibel_volume(self) -> bool {
+        self.contains(Self::DECIBEL_VOLUME)
+    }
+
+    /// Checks whether latency querying is supported.
+    #[inline]
+    pub fn supports_latency(self) -> bool {
+        self.contains(Self::LATENCY)
+    }
+
+    /// Checks whether the latency can be adjusted dynamically.
+    #[inline]
+    pub fn has_dynamic_latency(self) -> bool {
+        self.contains(Self::DYNAMIC_LATENCY)
+    }
+
+    /// Checks whether the sink is in flat volume mode.
+    #[inline]
+    pub fn has_flat_volume(self) -> bool {
+        self.contains(Self::FLAT_VOLUME)
+    }
+
+    /// Checks whether the sink is a hardware device of some kind.
+    #[inline]
+    pub fn is_hardware(self) -> bool {
+        self.contains(Self::HARDWARE)
+    }
+
+    /// Checks whether the sink is a networked device of some kind.
+    #[inline]
+    pub fn is_network(self) -> bool {
+        self.contains(Self::NETWORK)
+    }
+
+    /// Checks whether setting what formats are supported by the connected hardware is allowed.
+    #[inline]
+    pub fn can_set_formats(self) -> bool {
+        self.contains(Self::SET_FORMATS)
+    }
+}
+
+#[test]
+fn sink_flags_retain_unknown(){
+    let flags = SinkFlagSet::from_bits_retain(capi::PA_SINK_DYNAMIC_LATENCY | 0x8000_0000);
+    assert!(flags.has_dynamic_latency());
+    assert!(!flags.supports_hardware_volume());
+    assert_eq!(flags.bits(), capi::PA_SINK_DYNAMIC_LATENCY | 0x8000_0000);
+    assert_eq!(SinkFlagSet::from_bits(flags.bits()), None);
+    assert_eq!(SinkFlagSet::from_bits_truncate(flags.bits()), SinkFlagSet::DYNAMIC_LATENCY);
+}
 
 /// Special sink flags.
+///
+/// These are deprecated aliases of the associated constants of
+/// [`SinkFlagSet`](../struct.SinkFlagSet.html).
 pub mod sink_flags {
-    use capi;
     use super::SinkFlagSet;
 
-    /// Flag to pass when no specific options are needed.
-    pub const NOFLAGS: SinkFlagSet = capi::PA_SINK_NOFLAGS;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::NOFLAGS` instead")]
+    pub const NOFLAGS: SinkFlagSet = SinkFlagSet::NOFLAGS;
 
-    /// Supports hardware volume control. This is a dynamic flag and may change at runtime after the
-    /// sink has initialized.
-    pub const HW_VOLUME_CTRL: SinkFlagSet = capi::PA_SINK_HW_VOLUME_CTRL;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::HW_VOLUME_CTRL` instead")]
+    pub const HW_VOLUME_CTRL: SinkFlagSet = SinkFlagSet::HW_VOLUME_CTRL;
 
-    /// Supports latency querying.
-    pub const LATENCY: SinkFlagSet = capi::PA_SINK_LATENCY;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::LATENCY` instead")]
+    pub const LATENCY: SinkFlagSet = SinkFlagSet::LATENCY;
 
-    /// Is a hardware sink of some kind, in contrast to “virtual”/software sinks.
-    pub const HARDWARE: SinkFlagSet = capi::PA_SINK_HARDWARE;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::HARDWARE` instead")]
+    pub const HARDWARE: SinkFlagSet = SinkFlagSet::HARDWARE;
 
-    /// Is a networked sink of some kind.
-    pub const NETWORK: SinkFlagSet = capi::PA_SINK_NETWORK;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::NETWORK` instead")]
+    pub const NETWORK: SinkFlagSet = SinkFlagSet::NETWORK;
 
-    /// Supports hardware mute control. This is a dynamic flag and may change at runtime after the
-    /// sink has initialized.
-    pub const HW_MUTE_CTRL: SinkFlagSet = capi::PA_SINK_HW_MUTE_CTRL;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::HW_MUTE_CTRL` instead")]
+    pub const HW_MUTE_CTRL: SinkFlagSet = SinkFlagSet::HW_MUTE_CTRL;
 
-    /// Volume can be translated to dB with [`volume::sw_volume_to_db`]. This is a dynamic flag
-    /// and may change at runtime after the sink has initialized.
-    ///
-    /// [`volume::sw_volume_to_db`]: ../../volume/fn.sw_volume_to_db.html
-    pub const DECIBEL_VOLUME: SinkFlagSet = capi::PA_SINK_DECIBEL_VOLUME;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::DECIBEL_VOLUME` instead")]
+    pub const DECIBEL_VOLUME: SinkFlagSet = SinkFlagSet::DECIBEL_VOLUME;
 
-    /// This sink is in flat volume mode, i.e. always the maximum of the volume  of all connected
-    /// inputs.
-    pub const FLAT_VOLUME: SinkFlagSet = capi::PA_SINK_FLAT_VOLUME;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::FLAT_VOLUME` instead")]
+    pub const FLAT_VOLUME: SinkFlagSet = SinkFlagSet::FLAT_VOLUME;
 
-    /// The latency can be adjusted dynamically depending on the needs of the connected streams.
-    pub const DYNAMIC_LATENCY: SinkFlagSet = capi::PA_SINK_DYNAMIC_LATENCY;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::DYNAMIC_LATENCY` instead")]
+    pub const DYNAMIC_LATENCY: SinkFlagSet = SinkFlagSet::DYNAMIC_LATENCY;
 
-    /// The sink allows setting what formats are supported by the connected hardware. The actual
-    /// functionality to do this might be provided by an extension.
-    pub const SET_FORMATS: SinkFlagSet = capi::PA_SINK_SET_FORMATS;
+    #[deprecated(since = "2.17.0", note = "use `SinkFlagSet::SET_FORMATS` instead")]
+    pub const SET_FORMATS: SinkFlagSet = SinkFlagSet::SET_FORMATS;
 }
 
 #[repr(C)]
@@ -377,6 +596,20 @@ impl From<capi::pa_sink_state_t> for SinkState {
         unsafe { std::mem::transmute(s) }
     }
 }
+/// Converts a raw integer state value.
+///
+/// Any value not known to this library (e.g. a state added by a newer version of PulseAudio) is
+/// converted to `Invalid`, rather than attempting an unsafe conversion.
+impl From<i32> for SinkState {
+    fn from(s: i32) -> Self {
+        match s {
+            0 => SinkState::Running,
+            1 => SinkState::Idle,
+            2 => SinkState::Suspended,
+            _ => SinkState::Invalid,
+        }
+    }
+}
 
 impl SinkState {
     /// Checks if sink is playing: running or idle.
@@ -393,47 +626,135 @@ impl SinkState {
     }
 }
 
-pub type SourceFlagSet = capi::def::pa_source_flags_t;
+flags_type! {
+    /// Special source flags.
+    pub struct SourceFlagSet: capi::def::pa_source_flags_t {
+        /// Flag to pass when no specific options are needed.
+        const NOFLAGS = capi::PA_SOURCE_NOFLAGS;
+
+        /// Supports hardware volume control. This is a dynamic flag and may change at runtime after
+        /// the source has initialized.
+        const HW_VOLUME_CTRL = capi::PA_SOURCE_HW_VOLUME_CTRL;
+
+        /// Supports latency querying.
+        const LATENCY = capi::PA_SOURCE_LATENCY;
+
+        /// Is a hardware source of some kind, in contrast to “virtual”/software source.
+        const HARDWARE = capi::PA_SOURCE_HARDWARE;
+
+        /// Is a networked source of some kind.
+        const NETWORK = capi::PA_SOURCE_NETWORK;
+
+        /// Supports hardware mute control. This is a dynamic flag and may change at runtime after
+        /// the source has initialized.
+        const HW_MUTE_CTRL = capi::PA_SOURCE_HW_MUTE_CTRL;
+
+        /// Volume can be translated to dB with [`volume::sw_volume_to_db`]. This is a dynamic flag
+        /// and may change at runtime after the source has initialized.
+        ///
+        /// [`volume::sw_volume_to_db`]: ../volume/fn.sw_volume_to_db.html
+        const DECIBEL_VOLUME = capi::PA_SOURCE_DECIBEL_VOLUME;
+
+        /// The latency can be adjusted dynamically depending on the needs of the connected streams.
+        const DYNAMIC_LATENCY = capi::PA_SOURCE_DYNAMIC_LATENCY;
+
+        /// This source is in flat volume mode, i.e. always the maximum of the volume of all
+        /// connected outputs.
+        const FLAT_VOLUME = capi::PA_SOURCE_FLAT_VOLUME;
+    }
+}
+
+impl SourceFlagSet {
+    /// Checks whether hardware volume control is supported.
+    #[inline]
+    pub fn supports_hardware_volume(self) -> bool {
+        self.contains(Self::HW_VOLUME_CTRL)
+    }
+
+    /// Checks whether hardware mute control is supported.
+    #[inline]
+    pub fn supports_hardware_mute(self) -> bool {
+        self.contains(Self::HW_MUTE_CTRL)
+    }
+
+    /// Checks whether the volume can be translated to dB.
+    #[inline]
+    pub fn has_decibel_volume(self) -> bool {
+        self.contains(Self::DECIBEL_VOLUME)
+    }
+
+    /// Checks whether latency querying is supported.
+    #[inline]
+    pub fn supports_latency(self) -> bool {
+        self.contains(Self::LATENCY)
+    }
+
+    /// Checks whether the latency can be adjusted dynamically.
+    #[inline]
+    pub fn has_dynamic_latency(self) -> bool {
+        self.contains(Self::DYNAMIC_LATENCY)
+    }
+
+    /// Checks whether the source is in flat volume mode.
+    #[inline]
+    pub fn has_flat_volume(self) -> bool {
+        self.contains(Self::FLAT_VOLUME)
+    }
+
+    /// Checks whether the source is a hardware device of some kind.
+    #[inline]
+    pub fn is_hardware(self) -> bool {
+        self.contains(Self::HARDWARE)
+    }
+
+    /// Checks whether the source is a networked device of some kind.
+    #[inline]
+    pub fn is_network(self) -> bool {
+        self.contains(Self::NETWORK)
+    }
+}
+
+#[test]
+fn source_flags_retain_unknown(){
+    let flags = SourceFlagSet::from_bits_retain(capi::PA_SOURCE_HW_MUTE_CTRL | 0x8000_0000);
+    assert!(flags.supports_hardware_mute());
+    assert!(!flags.is_network());
+    assert_eq!(flags.bits(), capi::PA_SOURCE_HW_MUTE_CTRL | 0x8000_0000);
+}
 
 /// Special source flags.
+///
+/// These are deprecated aliases of the associated constants of
+/// [`SourceFlagSet`](../struct.SourceFlagSet.html).
 pub mod source_flags {
-    use capi;
     use super::SourceFlagSet;
 
-    /// Flag to pass when no specific options are needed.
-    pub const NOFLAGS: SourceFlagSet = capi::PA_SOURCE_NOFLAGS;
+    #[deprecated(since = "2.17.0", note = "use `SourceFlagSet::NOFLAGS` instead")]
+    pub const NOFLAGS: SourceFlagSet = SourceFlagSet::NOFLAGS;
 
-    /// Supports hardware volume control. This is a dynamic flag and may change at runtime after the
-    /// source has initialized.
-    pub const HW_VOLUME_CTRL: SourceFlagSet = capi::PA_SOURCE_HW_VOLUME_CTRL;
+    #[deprecated(since = "2.17.0", note = "use `SourceFlagSet::HW_VOLUME_CTRL` instead")]
+    pub const HW_VOLUME_CTRL: SourceFlagSet = SourceFlagSet::HW_VOLUME_CTRL;
 
-    /// Supports latency querying.
-    pub const LATENCY: SourceFlagSet = capi::PA_SOURCE_LATENCY;
+    #[deprecated(since = "2.17.0", note = "use `SourceFlagSet::LATENCY` instead")]
+    pub const LATENCY: SourceFlagSet = SourceFlagSet::LATENCY;
 
-    /// Is a hardware source of some kind, in contrast to “virtual”/software source.
-    pub const HARDWARE: SourceFlagSet = capi::PA_SOURCE_HARDWARE;
+    #[deprecated(since = "2.17.0", note = "use `SourceFlagSet::HARDWARE` instead")]
+    pub const HARDWARE: SourceFlagSet = SourceFlagSet::HARDWARE;
 
-    /// Is a networked source of some kind.
-    pub const NETWORK: SourceFlagSet = capi::PA_SOURCE_NETWORK;
+    #[deprecated(since = "2.17.0", note = "use `SourceFlagSet::NETWORK` instead")]
+    pub const NETWORK: SourceFlagSet = SourceFlagSet::NETWORK;
 
-    /// Supports hardware mute control. This is a dynamic flag and may change at runtime after the
-    /// source has initialized.
-    pub const HW_MUTE_CTRL: SourceFlagSet = capi::PA_SOURCE_HW_MUTE_CTRL;
+    #[deprecated(since = "2.17.0", note = "use `SourceFlagSet::HW_MUTE_CTRL` instead")]
+    pub const HW_MUTE_CTRL: SourceFlagSet = SourceFlagSet::HW_MUTE_CTRL;
 
-    /// Volume can be translated to dB with [`volume::sw_volume_to_db`]. This is a dynamic flag and
-    /// may change at runtime after the sink has initialized. Volume can be translated to dB with
-    /// [`volume::sw_volume_to_db`]. This is a dynamic flag and may change at runtime after the
-    /// source has initialized.
-    ///
-    /// [`volume::sw_volume_to_db`]: ../../volume/fn.sw_volume_to_db.html
-    pub const DECIBEL_VOLUME: SourceFlagSet = capi::PA_SOURCE_DECIBEL_VOLUME;
+    #[deprecated(since = "2.17.0", note = "use `SourceFlagSet::DECIBEL_VOLUME` instead")]
+    pub const DECIBEL_VOLUME: SourceFlagSet = SourceFlagSet::DECIBEL_VOLUME;
 
-    /// The latency can be adjusted dynamically depending on the needs of the connected streams.
-    pub const DYNAMIC_LATENCY: SourceFlagSet = capi::PA_SOURCE_DYNAMIC_LATENCY;
+    #[deprecated(since = "2.17.0", note = "use `SourceFlagSet::DYNAMIC_LATENCY` instead")]
+    pub const DYNAMIC_LATENCY: SourceFlagSet = SourceFlagSet::DYNAMIC_LATENCY;
 
-    /// This source is in flat volume mode, i.e. always the maximum of the volume of all connected
-    /// outputs.
-    pub const FLAT_VOLUME: SourceFlagSet = capi::PA_SOURCE_FLAT_VOLUME;
+    #[deprecated(since = "2.17.0", note = "use `SourceFlagSet::FLAT_VOLUME` instead")]
+    pub const FLAT_VOLUME: SourceFlagSet = SourceFlagSet::FLAT_VOLUME;
 }
 
 /// Source state.
@@ -475,6 +796,30 @@ impl From<capi::pa_source_state_t> for SourceState {
         unsafe { std::mem::transmute(s) }
     }
 }
+/// Converts a raw integer state value.
+///
+/// Any value not known to this library (e.g. a state added by a newer version of PulseAudio) is
+/// converted to `Invalid`, rather than attempting an unsafe conversion.
+impl From<i32> for SourceState {
+    fn from(s: i32) -> Self {
+        match s {
+            0 => SourceState::Running,
+            1 => SourceState::Idle,
+            2 => SourceState::Suspended,
+            _ => SourceState::Invalid,
+        }
+    }
+}
+
+#[test]
+fn state_from_raw() {
+    assert_eq!(SinkState::from(0), SinkState::Running);
+    assert_eq!(SinkState::from(2), SinkState::Suspended);
+    assert_eq!(SinkState::from(-1), SinkState::Invalid);
+    assert_eq!(SinkState::from(1000), SinkState::Invalid);
+    assert_eq!(SourceState::from(1), SourceState::Idle);
+    assert_eq!(SourceState::from(-2), SourceState::Invalid);
+}
 
 impl SourceState {
     /// Checks if source is recording: running or idle.
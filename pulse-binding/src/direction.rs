@@ -11,35 +11,104 @@
 // Portions of documentation are copied from the LGPL 2.1+ licensed PulseAudio C headers on a
 // fair-use basis, as discussed in the overall project readme (available in the git repository).
 
-//! Utility functions for Direction.
+//! Direction type and utility functions.
 
 #[cfg(any(feature = "pa_v6", feature = "dox"))]
 use std::ffi::CStr;
 
-pub type FlagSet = capi::direction::pa_direction_t;
+flags_type! {
+    /// The direction of a port (or of a stream), as output, input, or both (bidirectional).
+    pub struct Direction: capi::direction::pa_direction_t {
+        /// Output direction.
+        const OUTPUT = capi::PA_DIRECTION_OUTPUT;
+        /// Input direction.
+        const INPUT = capi::PA_DIRECTION_INPUT;
+    }
+}
+
+impl Direction {
+    /// Checks whether the direction is valid (either input, output or bidirectional).
+    ///
+    /// Available since PA version 6.
+    #[inline]
+    #[cfg(any(feature = "pa_v6", feature = "dox"))]
+    pub fn is_valid(self) -> bool {
+        unsafe { capi::pa_direction_valid(self.0) != 0 }
+    }
+}
+
+/// Gives the textual representation of the direction, as provided by the C library (thus possibly
+/// translated).
+///
+/// Available since PA version 6.
+#[cfg(any(feature = "pa_v6", feature = "dox"))]
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = unsafe { CStr::from_ptr(capi::pa_direction_to_string(self.0)) };
+        f.write_str(&s.to_string_lossy())
+    }
+}
 
+/// Deprecated alias of [`Direction`](struct.Direction.html).
+#[deprecated(since = "2.17.0", note = "use `Direction` instead")]
+pub type FlagSet = Direction;
+
+/// Direction flags.
+///
+/// These are deprecated aliases of the associated constants of
+/// [`Direction`](../struct.Direction.html).
 pub mod flags {
-    use capi;
-    use super::FlagSet;
+    use super::Direction;
 
-    pub const OUTPUT: FlagSet = capi::PA_DIRECTION_OUTPUT;
-    pub const INPUT: FlagSet = capi::PA_DIRECTION_INPUT;
+    #[deprecated(since = "2.17.0", note = "use `Direction::OUTPUT` instead")]
+    pub const OUTPUT: Direction = Direction::OUTPUT;
+
+    #[deprecated(since = "2.17.0", note = "use `Direction::INPUT` instead")]
+    pub const INPUT: Direction = Direction::INPUT;
 }
 
 /// Checks whether direction is valid (either input, output or bidirectional).
 ///
 /// Available since PA version 6.
+#[deprecated(since = "2.17.0", note = "use `Direction::is_valid` instead")]
 #[inline]
 #[cfg(any(feature = "pa_v6", feature = "dox"))]
-pub fn is_valid(f: FlagSet) -> bool {
-    unsafe { capi::pa_direction_valid(f) != 0 }
+pub fn is_valid(f: Direction) -> bool {
+    f.is_valid()
 }
 
 /// Gets a textual representation of the direction.
 ///
 /// Available since PA version 6.
+#[deprecated(since = "2.17.0", note = "use the `Display` implementation of `Direction` instead")]
 #[inline]
 #[cfg(any(feature = "pa_v6", feature = "dox"))]
-pub fn to_string(f: FlagSet) -> String {
-    unsafe { CStr::from_ptr(capi::pa_direction_to_string(f)).to_string_lossy().into_owned() }
+pub fn to_string(f: Direction) -> String {
+    format!("{}", f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits() {
+        let both = Direction::OUTPUT | Direction::INPUT;
+        assert_eq!(both.bits(), capi::PA_DIRECTION_OUTPUT | capi::PA_DIRECTION_INPUT);
+        assert_eq!(Direction::all(), both);
+        assert_eq!(Direction::from_bits(0x4), None);
+        assert_eq!(Direction::from_bits_retain(0x5).bits(), 0x5);
+    }
+
+    #[test]
+    #[cfg(any(feature = "pa_v6", feature = "dox"))]
+    fn valid_and_display() {
+        assert!(Direction::OUTPUT.is_valid());
+        assert!((Direction::OUTPUT | Direction::INPUT).is_valid());
+        assert!(!Direction::empty().is_valid());
+        assert!(!Direction::from_bits_retain(0x4).is_valid());
+        let (output, input) = (Direction::OUTPUT.to_string(), Direction::INPUT.to_string());
+        assert!(!output.is_empty() && !input.is_empty());
+        assert_ne!(output, input);
+    }
 }
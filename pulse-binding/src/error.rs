@@ -13,12 +13,13 @@
 
 //! Error management.
 
+use std::convert::TryFrom;
 use std::ffi::CStr;
 
 type ErrorInt = i32;
 
 /// A wrapper around integer errors returned by PulseAudio. Can be converted to a `Code` variant for
-/// comparison purposes if desired.
+/// comparison purposes if desired (via `Code::try_from()`).
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct PAErr(pub ErrorInt);
@@ -95,17 +96,30 @@ impl PAErr {
             true => None,
         }
     }
+
+    /// Gets the error of the last failed operation of a context, as a (negative) error value, such
+    /// that it compares equal to the corresponding `Code` converted to a `PAErr`.
+    ///
+    /// Note, `pa_context_errno` gives back the error code as a positive value.
+    #[inline]
+    pub(crate) fn from_context(context: *const capi::pa_context) -> Self {
+        PAErr(-unsafe { capi::pa_context_errno(context) })
+    }
 }
 
 impl std::fmt::Display for PAErr {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Note, `pa_strerror` gives back NULL for codes it does not know about (e.g. those added by
+        // newer versions of the server), so we must fall back to something sensible here.
         match self.to_string() {
             Some(s) => write!(f, "{}", s),
-            None => write!(f, ""),
+            None => write!(f, "Unknown error code ({})", self.0),
         }
     }
 }
 
+impl std::error::Error for PAErr {}
+
 impl Code {
     /// Converts a `Code` to a human readable string.
     pub fn to_string(self) -> Option<String> {
@@ -113,6 +127,14 @@ impl Code {
     }
 }
 
+impl std::fmt::Display for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", PAErr::from(*self))
+    }
+}
+
+impl std::error::Error for Code {}
+
 impl From<Code> for PAErr {
     #[inline]
     fn from(c: Code) -> Self {
@@ -120,12 +142,16 @@ impl From<Code> for PAErr {
         PAErr(-(c as ErrorInt))
     }
 }
-impl From<PAErr> for Code {
-    fn from(e: PAErr) -> Self {
+impl TryFrom<PAErr> for Code {
+    /// The original error, for values not known to this library (e.g. new codes added by newer
+    /// versions of PulseAudio).
+    type Error = PAErr;
+
+    fn try_from(e: PAErr) -> Result<Self, Self::Error> {
         // Error codes are negative, `Code` enum variants are positive
         // Note, avoid transmute - likely different sizes!
         let abs = -(e.0);
-        match abs {
+        let code = match abs {
             x if x == Code::Ok as ErrorInt => Code::Ok,
             x if x == Code::Access as ErrorInt => Code::Access,
             x if x == Code::Command as ErrorInt => Code::Command,
@@ -153,7 +179,15 @@ impl From<PAErr> for Code {
             x if x == Code::Forked as ErrorInt => Code::Forked,
             x if x == Code::Io as ErrorInt => Code::Io,
             x if x == Code::Busy as ErrorInt => Code::Busy,
-            _ => Code::Unknown,
-        }
+            _ => return Err(e),
+        };
+        Ok(code)
     }
 }
+
+#[test]
+fn unknown_code_preserved() {
+    let e = PAErr(-1000);
+    assert_eq!(Code::try_from(e), Err(e));
+    assert_eq!(Code::try_from(PAErr::from(Code::Busy)), Ok(Code::Busy));
+}
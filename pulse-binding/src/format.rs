@@ -21,13 +21,15 @@
 //! [`Info`]: struct.Info.html
 //! [`set_encoding`]: struct.Info.html#method.set_encoding
 
-use std::os::raw::{c_char, c_void};
+use std::os::raw::c_char;
 use std::ffi::{CStr, CString};
 use std::ptr::{null, null_mut};
 use std::borrow::Cow;
+use std::marker::PhantomData;
 use crate::{sample, channelmap};
 use crate::error::PAErr;
 use crate::proplist::{Proplist, ProplistInternal};
+use crate::xmalloc::{PaAllocated, PaStringArray};
 
 pub use capi::pa_prop_type_t as PropType;
 
@@ -104,6 +106,7 @@ pub struct Info {
     weak: bool,
 }
 
+// Format info objects are not tied to any mainloop, and are only ever modified through `&mut self`.
 unsafe impl Send for Info {}
 unsafe impl Sync for Info {}
 
@@ -365,15 +368,9 @@ impl Info {
         if result != 0 {
             return None;
         }
-        // Clone each int in the array
-        let mut values: Vec<i32> = Vec::with_capacity(count as usize);
-        for i in 0..count {
-            values.push(unsafe { *p_ints.offset(i as isize) });
-        }
-        // Free the PA allocated array
-        unsafe { capi::pa_xfree(p_ints as *mut c_void) };
-        // Return vector of ints
-        Some(values)
+        // Copy the PA allocated array, which is freed upon drop
+        let ints = unsafe { PaAllocated::from_raw(p_ints) }?;
+        Some(unsafe { ints.to_vec(count as usize) })
     }
 
     /// Gets a string property.
@@ -384,14 +381,10 @@ impl Info {
         let mut p_str = null_mut::<c_char>();
         let result = unsafe { capi::pa_format_info_get_prop_string(
             self.ptr as *const capi::pa_format_info, c_key.as_ptr(), &mut p_str) };
-        if result != 0 || p_str.is_null() {
+        if result != 0 {
             return None;
         }
-        unsafe {
-            let ret = Some(CStr::from_ptr(p_str).to_string_lossy().into_owned());
-            capi::pa_xfree(p_str as *mut c_void);
-            ret
-        }
+        unsafe { PaAllocated::from_raw(p_str) }.map(|s| s.to_string_lossy())
     }
 
     /// Gets a string array property.
@@ -403,21 +396,11 @@ impl Info {
         let mut pp_str = null_mut::<*mut c_char>();
         let result = unsafe { capi::pa_format_info_get_prop_string_array(
             self.ptr as *const capi::pa_format_info, c_key.as_ptr(), &mut pp_str, &mut count) };
-        if result != 0 || pp_str.is_null() {
+        if result != 0 {
             return None;
         }
-        // Clone each string in the array to owned String
-        let mut values: Vec<String> = Vec::with_capacity(count as usize);
-        for i in 0..count {
-            let p_str = unsafe { *pp_str.offset(i as isize) };
-            if !p_str.is_null() {
-                values.push(unsafe { CStr::from_ptr(p_str).to_string_lossy().into_owned() });
-            }
-        }
-        // Free all PA internally allocated strings
-        unsafe { capi::pa_format_info_free_string_array(pp_str, count) };
-        // Return vector of Strings
-        Some(values)
+        // Copy each string, with all PA internally allocated strings freed upon drop
+        unsafe { PaStringArray::from_raw(pp_str, count as usize) }.map(|a| a.to_vec_lossy())
     }
 
     /// Gets the sample format stored in the format info.
@@ -609,3 +592,81 @@ impl Clone for Info {
         Self::from_raw(ptr as *mut InfoInternal)
     }
 }
+
+/// A borrowed view of a format info object, such as one owned by the C library and handed to a
+/// callback.
+///
+/// Lifetime `'a` ties the view to the validity of the underlying object, so it cannot be kept
+/// beyond that (e.g. beyond the callback it was given to). Read access is given through `Deref` to
+/// [`Info`]; use [`to_owned`](#method.to_owned) to obtain an independent copy that can be kept.
+///
+/// [`Info`]: struct.Info.html
+pub struct InfoRef<'a> {
+    /// Weak wrapper, or for `'static` views, possibly an owned one.
+    info: Info,
+    /// Use lifetime `'a`.
+    phantom: PhantomData<&'a InfoInternal>,
+}
+
+impl<'a> InfoRef<'a> {
+    /// Creates a view of the format info at `ptr`, which must remain valid for `'a`.
+    #[inline]
+    pub(crate) fn from_raw(ptr: *mut InfoInternal) -> Self {
+        Self { info: Info::from_raw_weak(ptr), phantom: PhantomData }
+    }
+
+    /// Gets an owned (deep) copy of the format info.
+    #[inline]
+    pub fn to_owned(&self) -> Info {
+        self.info.clone()
+    }
+}
+
+/// Wraps an owned format info object, for use where a `'static` view is needed, e.g. owned copies
+/// of introspection info.
+impl From<Info> for InfoRef<'static> {
+    #[inline]
+    fn from(info: Info) -> Self {
+        Self { info: info, phantom: PhantomData }
+    }
+}
+
+impl<'a> std::ops::Deref for InfoRef<'a> {
+    type Target = Info;
+
+    #[inline]
+    fn deref(&self) -> &Info {
+        &self.info
+    }
+}
+
+impl<'a> std::fmt::Debug for InfoRef<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.info.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A copy taken from a view remains valid once the underlying object is freed, as done by PA
+    /// after a callback returns (run under ASAN to catch any use-after-free).
+    #[test]
+    fn ref_to_owned() {
+        let mut original = Info::new().unwrap();
+        original.set_encoding(Encoding::AC3_IEC61937);
+        original.set_rate(48000);
+        let copy = {
+            let view = InfoRef::from_raw(original.ptr);
+            assert_eq!(view.get_encoding(), Encoding::AC3_IEC61937);
+            view.to_owned()
+        };
+        // Dropping the view does not free the object
+        assert!(original.is_valid());
+        drop(original);
+        assert_eq!(copy.get_encoding(), Encoding::AC3_IEC61937);
+        assert_eq!(copy.get_properties().get_str("format.rate").unwrap(), "48000");
+    }
+}
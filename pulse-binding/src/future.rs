@@ -0,0 +1,757 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! `Future` based async support.
+//!
+//! # Overview
+//!
+//! This module provides [`Future`] implementations for connecting a context and for operations,
+//! allowing them to be used from async Rust code. It is built upon the standard library’s
+//! `Future` and `Waker` only, and so is runtime-agnostic.
+//!
+//! * [`Context::connect_async`] connects a context, resolving once it is ready.
+//! * [`OperationFuture`] adapts any operation to a future, with [`Completer`] used within the
+//!   operation’s callback to provide the result.
+//! * [`Introspector::get_server_info_async`] is provided as a ready made example of the latter.
+//! * [`PlaybackSink`] and [`RecordStream`] provide async writing to and reading from streams.
+//!
+//! This module is only available with the `futures` Cargo feature enabled.
+//!
+//! # Driving the mainloop
+//!
+//! Note that these futures do nothing by themselves; they are resolved by callbacks, which are
+//! only executed when the mainloop is run. Something must therefore still drive the mainloop,
+//! independently of the executor polling the futures. There are two ways to pair them:
+//!
+//! * Use the [threaded mainloop], which runs in its own thread. The callbacks then run (and wake
+//!   the futures) in the event loop thread. Polling a future does not touch the PulseAudio objects
+//!   involved, so the executor does not need to hold the mainloop lock to do so. However, as with
+//!   any use of that mainloop, creating the futures (which calls into the PulseAudio library) and
+//!   dropping unfinished futures (which cancels the operation) must be done with the lock held.
+//! * Use the [standard mainloop], iterating it from the same thread as the executor, e.g. from a
+//!   task or in between polling the futures.
+//!
+//! # Cancellation
+//!
+//! Dropping an [`OperationFuture`] before it has resolved cancels the underlying operation.
+//!
+//! [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+//! [`Context::connect_async`]: ../context/struct.Context.html#method.connect_async
+//! [`Introspector::get_server_info_async`]: ../context/introspect/struct.Introspector.html#method.get_server_info_async
+//! [`OperationFuture`]: struct.OperationFuture.html
+//! [`Completer`]: struct.Completer.html
+//! [`PlaybackSink`]: struct.PlaybackSink.html
+//! [`RecordStream`]: struct.RecordStream.html
+//! [threaded mainloop]: ../mainloop/threaded/index.html
+//! [standard mainloop]: ../mainloop/standard/index.html
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll, Waker};
+use capi::pa_context as ContextInternal;
+use crate::context::{self, Context};
+use crate::context::introspect::{Introspector, ServerInfo};
+use crate::def;
+use crate::error::{PAErr, Code};
+use crate::operation::Operation;
+use crate::stream::{self, PeekResult, SeekMode, Stream};
+
+/// State shared between a future and the callbacks that resolve it.
+struct Shared<T> {
+    /// The result, once available.
+    result: Option<Result<T, PAErr>>,
+    /// Waker of the task last polling the future.
+    waker: Option<Waker>,
+}
+
+impl<T> Shared<T> {
+    fn new() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Shared { result: None, waker: None }))
+    }
+
+    /// Sets the result (if not already set) and wakes the waiting task.
+    fn resolve(&mut self, result: Result<T, PAErr>) {
+        if self.result.is_none() {
+            self.result = Some(result);
+        }
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn poll(&mut self, cx: &mut TaskContext) -> Poll<Result<T, PAErr>> {
+        match self.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                self.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+/// Future resolving once a context connection attempt completes, as returned by
+/// [`Context::connect_async`].
+///
+/// [`Context::connect_async`]: ../context/struct.Context.html#method.connect_async
+#[must_use = "futures do nothing unless polled"]
+pub struct ConnectFuture {
+    shared: Arc<Mutex<Shared<()>>>,
+}
+
+impl Future for ConnectFuture {
+    type Output = Result<(), PAErr>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<Self::Output> {
+        self.shared.lock().unwrap().poll(cx)
+    }
+}
+
+impl Context {
+    /// Connects the context to the specified server, returning a future that resolves once the
+    /// context is ready.
+    ///
+    /// The params are as for [`connect`]. The future resolves to an error if the connection
+    /// attempt fails, or if the context is terminated, in which case the error is
+    /// `ConnectionTerminated`.
+    ///
+    /// Note that this replaces any state callback set on the context. It is safe to set another
+    /// once the future has resolved.
+    ///
+    /// [`connect`]: #method.connect
+    pub fn connect_async(&mut self, server: Option<&str>, flags: context::Flags,
+        api: Option<&def::SpawnApi>) -> ConnectFuture
+    {
+        let shared = Shared::new();
+
+        let cb_shared = Arc::clone(&shared);
+        let ctx_ptr: *mut ContextInternal = self.ptr;
+        self.set_state_callback(Some(Box::new(move || {
+            let state: context::State = unsafe { capi::pa_context_get_state(ctx_ptr).into() };
+            let result = match state {
+                context::State::Ready => Ok(()),
+                context::State::Failed => Err(PAErr::from_context(ctx_ptr)),
+                context::State::Terminated => Err(PAErr::from(Code::ConnectionTerminated)),
+                _ => return,
+            };
+            cb_shared.lock().unwrap().resolve(result);
+        })));
+
+        if let Err(e) = self.connect(server, flags, api) {
+            shared.lock().unwrap().resolve(Err(e));
+        }
+
+        ConnectFuture { shared: shared }
+    }
+}
+
+/// Handle for providing the result of an operation to an [`OperationFuture`].
+///
+/// This is given to the closure starting the operation, and should be moved into the operation’s
+/// callback.
+///
+/// [`OperationFuture`]: struct.OperationFuture.html
+pub struct Completer<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> Completer<T> {
+    /// Provides the result of the operation, resolving the future.
+    ///
+    /// Only the first result provided is used.
+    pub fn complete(&self, value: T) {
+        self.shared.lock().unwrap().resolve(Ok(value));
+    }
+
+    /// Resolves the future with the given error.
+    ///
+    /// This is not normally needed, since the future resolves with the context’s error should the
+    /// operation complete without a result having been provided.
+    pub fn fail(&self, error: PAErr) {
+        self.shared.lock().unwrap().resolve(Err(error));
+    }
+}
+
+/// Adapter turning an [`Operation`] into a future.
+///
+/// The future resolves with the value given to the [`Completer`], or with the context’s error
+/// should the operation finish (or be cancelled) without one having been given. Dropping the
+/// future before it has resolved cancels the operation.
+///
+/// For instance, obtaining the index of a sink:
+///
+/// ```rust,ignore
+/// let introspector = context.introspect();
+/// let index = OperationFuture::new(&context, |completer| {
+///     introspector.get_sink_info_by_name("foo", move |result| match result {
+///         ListResult::Item(info) => completer.complete(info.index),
+///         _ => {},
+///     })
+/// }).await?;
+/// ```
+///
+/// [`Operation`]: ../operation/struct.Operation.html
+/// [`Completer`]: struct.Completer.html
+#[must_use = "futures do nothing unless polled"]
+pub struct OperationFuture<T, ClosureProto: ?Sized> {
+    op: Operation<ClosureProto>,
+    shared: Arc<Mutex<Shared<T>>>,
+    /// Set once the future has resolved.
+    done: bool,
+}
+
+impl<T: 'static, ClosureProto: ?Sized> OperationFuture<T, ClosureProto> {
+    /// Creates a new future for an operation.
+    ///
+    /// The `start` closure is given a [`Completer`], and must start the operation, returning the
+    /// resulting [`Operation`] object. The context the operation is performed on must be provided,
+    /// for obtaining the error in case of failure.
+    ///
+    /// Note that this sets the state callback of the operation.
+    ///
+    /// [`Operation`]: ../operation/struct.Operation.html
+    /// [`Completer`]: struct.Completer.html
+    pub fn new<S>(context: &Context, start: S) -> Self
+        where S: FnOnce(Completer<T>) -> Operation<ClosureProto>
+    {
+        Self::new_internal(context.ptr, start)
+    }
+
+    pub(crate) fn new_internal<S>(context: *mut ContextInternal, start: S) -> Self
+        where S: FnOnce(Completer<T>) -> Operation<ClosureProto>
+    {
+        let shared = Shared::new();
+        let mut op = start(Completer { shared: Arc::clone(&shared) });
+
+        // The operation’s callback provides the result (if any) before the operation completes,
+        // thus if no result is available upon completion, it has failed.
+        let cb_shared = Arc::clone(&shared);
+        op.set_state_callback(Some(Box::new(move || {
+            let err = PAErr::from_context(context);
+            cb_shared.lock().unwrap().resolve(Err(err));
+        })));
+
+        Self { op: op, shared: shared, done: false }
+    }
+}
+
+impl<T, ClosureProto: ?Sized> Future for OperationFuture<T, ClosureProto> {
+    type Output = Result<T, PAErr>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let result = this.shared.lock().unwrap().poll(cx);
+        if result.is_ready() {
+            this.done = true;
+        }
+        result
+    }
+}
+
+impl<T, ClosureProto: ?Sized> Drop for OperationFuture<T, ClosureProto> {
+    fn drop(&mut self) {
+        if !self.done && self.op.get_state() == crate::operation::State::Running {
+            self.op.cancel();
+        }
+    }
+}
+
+impl Introspector {
+    /// Gets some information about the server, asynchronously.
+    ///
+    /// This is the `Future` based equivalent of [`get_server_info`].
+    ///
+    /// [`get_server_info`]: #method.get_server_info
+    pub fn get_server_info_async(&self)
+        -> OperationFuture<ServerInfo<'static>, dyn FnMut(&ServerInfo)>
+    {
+        OperationFuture::new_internal(self.context, |completer| {
+            self.get_server_info(move |info| completer.complete(info.to_owned()))
+        })
+    }
+}
+
+/// Waker storage shared between a stream adapter and the stream’s callbacks.
+///
+/// The stored waker is taken upon being woken, thus a task is woken only once per readiness
+/// change, and must poll again (re-registering) to be woken again.
+#[derive(Default)]
+struct WakerSlot(Option<Waker>);
+
+impl WakerSlot {
+    fn wake(&mut self) {
+        if let Some(waker) = self.0.take() {
+            waker.wake();
+        }
+    }
+
+    fn register(&mut self, cx: &mut TaskContext) {
+        match self.0 {
+            Some(ref w) if w.will_wake(cx.waker()) => {},
+            _ => self.0 = Some(cx.waker().clone()),
+        }
+    }
+}
+
+/// Sets up the state callback of a stream to wake the given slot, returning the slot.
+fn watch_state(stream: &mut Stream) -> Arc<Mutex<WakerSlot>> {
+    let slot = Arc::new(Mutex::new(WakerSlot::default()));
+    let cb_slot = Arc::clone(&slot);
+    stream.set_state_callback(Some(Box::new(move || cb_slot.lock().unwrap().wake())));
+    slot
+}
+
+/// Async writer for playback streams.
+///
+/// This wraps a playback [`Stream`], providing `poll_write`, `poll_flush` and `poll_close` methods,
+/// matching those of the `AsyncWrite` traits of the `futures` and `tokio` crates, such that
+/// implementing those traits for a wrapper type is trivial (this crate does not depend upon either
+/// of them).
+///
+/// Unlike the futures above, polling here calls into the PulseAudio library, thus with the
+/// threaded mainloop the lock must be held whilst doing so.
+///
+/// Note that this replaces the state and write callbacks of the stream.
+///
+/// [`Stream`]: ../stream/struct.Stream.html
+pub struct PlaybackSink {
+    stream: Stream,
+    waker: Arc<Mutex<WakerSlot>>,
+}
+
+impl PlaybackSink {
+    /// Creates a new writer for the given playback stream.
+    ///
+    /// The stream may be connected either before or after creating the writer; writing is pending
+    /// until the stream is ready.
+    pub fn new(mut stream: Stream) -> Self {
+        let waker = watch_state(&mut stream);
+        let cb_waker = Arc::clone(&waker);
+        stream.set_write_callback(Some(Box::new(move |_| cb_waker.lock().unwrap().wake())));
+        Self { stream: stream, waker: waker }
+    }
+
+    /// Gets a reference to the underlying stream.
+    #[inline]
+    pub fn get_ref(&self) -> &Stream {
+        &self.stream
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// Beware that replacing the state or write callbacks will break the writer.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Stream {
+        &mut self.stream
+    }
+
+    /// Attempts to write data to the stream.
+    ///
+    /// Writes as much of `buf` as the server currently accepts (see [`Stream::writable_size`]),
+    /// returning the number of bytes written. If no more data is currently accepted, the task is
+    /// woken once the server requests more.
+    ///
+    /// Fails with `BadState` should the stream fail or be terminated.
+    ///
+    /// [`Stream::writable_size`]: ../stream/struct.Stream.html#method.writable_size
+    pub fn poll_write(&mut self, cx: &mut TaskContext, buf: &[u8]) -> Poll<Result<usize, PAErr>> {
+        match self.stream.get_state() {
+            stream::State::Ready => {},
+            stream::State::Failed | stream::State::Terminated => {
+                return Poll::Ready(Err(PAErr::from(Code::BadState)));
+            },
+            _ => {
+                self.waker.lock().unwrap().register(cx);
+                return Poll::Pending;
+            },
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        match self.stream.writable_size() {
+            None => Poll::Ready(Err(PAErr::from(Code::BadState))),
+            Some(0) => {
+                self.waker.lock().unwrap().register(cx);
+                Poll::Pending
+            },
+            Some(size) => {
+                let len = std::cmp::min(size, buf.len());
+                let result = self.stream.write_copy(&buf[..len], 0, SeekMode::Relative);
+                Poll::Ready(result.map(|_| len).map_err(PAErr::from))
+            },
+        }
+    }
+
+    /// Flushes the writer.
+    ///
+    /// Data is handed over to the server as it is written, thus this completes immediately. Note
+    /// that this does not wait for playback to complete; use [`Stream::drain`] for that.
+    ///
+    /// [`Stream::drain`]: ../stream/struct.Stream.html#method.drain
+    pub fn poll_flush(&mut self, _cx: &mut TaskContext) -> Poll<Result<(), PAErr>> {
+        Poll::Ready(Ok(()))
+    }
+
+    /// Closes the writer, disconnecting the stream.
+    pub fn poll_close(&mut self, _cx: &mut TaskContext) -> Poll<Result<(), PAErr>> {
+        match self.stream.get_state() {
+            stream::State::Ready | stream::State::Creating => {
+                Poll::Ready(self.stream.disconnect())
+            },
+            _ => Poll::Ready(Ok(())),
+        }
+    }
+}
+
+/// Async stream of captured data, for record streams.
+///
+/// This wraps a record [`Stream`], providing a `poll_next` method matching that of the `Stream`
+/// trait of the `futures` crate, yielding chunks of captured data, such that implementing that
+/// trait for a wrapper type is trivial (this crate does not depend upon it).
+///
+/// Data is not buffered here; each chunk is copied straight out of the stream’s own buffer when
+/// polled, and nothing is read from the server whilst not being polled. Memory use is thus bounded
+/// by the stream’s buffer attributes, with the server dropping data should the consumer fall
+/// behind (as reported by the overflow callback).
+///
+/// Unlike the futures above, polling here calls into the PulseAudio library, thus with the
+/// threaded mainloop the lock must be held whilst doing so.
+///
+/// Note that this replaces the state and read callbacks of the stream.
+///
+/// [`Stream`]: ../stream/struct.Stream.html
+pub struct RecordStream {
+    stream: Stream,
+    waker: Arc<Mutex<WakerSlot>>,
+    /// Set once the end (or an error) has been yielded.
+    finished: bool,
+}
+
+impl RecordStream {
+    /// Creates a new stream of captured data for the given record stream.
+    ///
+    /// The stream may be connected either before or after creating this; reading is pending until
+    /// the stream is ready.
+    pub fn new(mut stream: Stream) -> Self {
+        let waker = watch_state(&mut stream);
+        let cb_waker = Arc::clone(&waker);
+        stream.set_read_callback(Some(Box::new(move |_| cb_waker.lock().unwrap().wake())));
+        Self { stream: stream, waker: waker, finished: false }
+    }
+
+    /// Gets a reference to the underlying stream.
+    #[inline]
+    pub fn get_ref(&self) -> &Stream {
+        &self.stream
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// Beware that replacing the state or read callbacks will break this.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Stream {
+        &mut self.stream
+    }
+
+    /// Attempts to get the next chunk of captured data.
+    ///
+    /// If no data is currently available, the task is woken once more arrives. Holes in the data
+    /// are skipped. Once the stream is terminated, `None` is returned; should the stream fail, a
+    /// `BadState` error is returned, followed by `None`.
+    pub fn poll_next(&mut self, cx: &mut TaskContext) -> Poll<Option<Result<Vec<u8>, PAErr>>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+        match self.stream.get_state() {
+            stream::State::Ready => {},
+            stream::State::Terminated => {
+                self.finished = true;
+                return Poll::Ready(None);
+            },
+            stream::State::Failed => {
+                self.finished = true;
+                return Poll::Ready(Some(Err(PAErr::from(Code::BadState))));
+            },
+            _ => {
+                self.waker.lock().unwrap().register(cx);
+                return Poll::Pending;
+            },
+        }
+        loop {
+            let chunk = match self.stream.peek() {
+                Err(e) => return Poll::Ready(Some(Err(e))),
+                Ok(PeekResult::Empty) => {
+                    self.waker.lock().unwrap().register(cx);
+                    return Poll::Pending;
+                },
+                Ok(PeekResult::Hole(_)) => None,
+                Ok(PeekResult::Data(data)) => Some(data.to_vec()),
+            };
+            if let Err(e) = self.stream.discard() {
+                return Poll::Ready(Some(Err(e)));
+            }
+            if let Some(data) = chunk {
+                return Poll::Ready(Some(Ok(data)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{RawWaker, RawWakerVTable};
+    use crate::mainloop::standard::Mainloop;
+    use crate::sample;
+
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+    }
+
+    /// Gets a waker counting the number of times it is woken.
+    fn counting_waker(wakes: &Arc<AtomicUsize>) -> Waker {
+        unsafe fn clone(p: *const ()) -> RawWaker {
+            let wakes = Arc::from_raw(p as *const AtomicUsize);
+            let copy = Arc::into_raw(Arc::clone(&wakes));
+            std::mem::forget(wakes);
+            RawWaker::new(copy as *const (), &VTABLE)
+        }
+        unsafe fn wake(p: *const ()) {
+            wake_by_ref(p);
+            drop_waker(p);
+        }
+        unsafe fn wake_by_ref(p: *const ()) {
+            (*(p as *const AtomicUsize)).fetch_add(1, Ordering::SeqCst);
+        }
+        unsafe fn drop_waker(p: *const ()) {
+            drop(Arc::from_raw(p as *const AtomicUsize));
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_waker);
+        let p = Arc::into_raw(Arc::clone(wakes)) as *const ();
+        unsafe { Waker::from_raw(RawWaker::new(p, &VTABLE)) }
+    }
+
+    /// Connects a context to the default server, giving `None` if there is no server available, in
+    /// which case tests requiring one are skipped.
+    fn connect() -> Option<(Mainloop, Context)> {
+        let mut mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "FooApp").unwrap();
+        context.connect(None, context::Flags::NOAUTOSPAWN, None).ok()?;
+        loop {
+            if !mainloop.iterate(true).is_success() {
+                return None;
+            }
+            match context.get_state() {
+                context::State::Ready => return Some((mainloop, context)),
+                context::State::Failed | context::State::Terminated => return None,
+                _ => {},
+            }
+        }
+    }
+
+    /// Iterates the mainloop until the condition is met.
+    fn iterate_until<F: FnMut() -> bool>(mainloop: &mut Mainloop, mut done: F) {
+        while !done() {
+            assert!(mainloop.iterate(true).is_success());
+        }
+    }
+
+    #[test]
+    fn waker_slot_wakes_once() {
+        static WAKES: AtomicUsize = AtomicUsize::new(0);
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        fn wake(_: *const ()) {
+            WAKES.fetch_add(1, Ordering::SeqCst);
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, noop);
+        let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+        let mut cx = TaskContext::from_waker(&waker);
+
+        let mut slot = WakerSlot::default();
+        slot.wake();
+        assert_eq!(WAKES.load(Ordering::SeqCst), 0);
+        slot.register(&mut cx);
+        slot.register(&mut cx);
+        slot.wake();
+        slot.wake();
+        assert_eq!(WAKES.load(Ordering::SeqCst), 1);
+        slot.register(&mut cx);
+        slot.wake();
+        assert_eq!(WAKES.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn connect_async_bad_state() {
+        let mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "FooApp").unwrap();
+
+        // Whether or not the first attempt can succeed, the context has left the unconnected
+        // state, thus a second attempt must fail immediately.
+        let _first = context.connect_async(None, context::Flags::NOAUTOSPAWN, None);
+        let mut second = context.connect_async(None, context::Flags::NOAUTOSPAWN, None);
+
+        let waker = noop_waker();
+        let mut cx = TaskContext::from_waker(&waker);
+        assert_eq!(Pin::new(&mut second).poll(&mut cx),
+            Poll::Ready(Err(PAErr::from(Code::BadState))));
+    }
+
+    #[test]
+    fn operation_future_cancel_on_drop() {
+        let (mut mainloop, context) = match connect() {
+            Some(c) => c,
+            None => return,
+        };
+        let introspector = context.introspect();
+        let waker = noop_waker();
+        let mut cx = TaskContext::from_waker(&waker);
+
+        // Dropped before resolving, the operation is cancelled, and its closure freed
+        let held = Arc::new(());
+        let held_ref = Arc::clone(&held);
+        let mut future = OperationFuture::new(&context, |completer| {
+            introspector.get_server_info(move |info| {
+                let _ = &held_ref;
+                completer.complete(info.to_owned())
+            })
+        });
+        assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+        assert_eq!(Arc::strong_count(&held), 2);
+        drop(future);
+        assert_eq!(Arc::strong_count(&held), 1);
+        // The reply, should it arrive, goes nowhere
+        for _ in 0..10 {
+            assert!(mainloop.iterate(false).is_success());
+        }
+
+        // Resolved, the closure is freed upon completion, and dropping has no effect
+        let held_ref = Arc::clone(&held);
+        let mut future = OperationFuture::new(&context, |completer| {
+            introspector.get_server_info(move |info| {
+                let _ = &held_ref;
+                completer.complete(info.to_owned())
+            })
+        });
+        let mut result = None;
+        iterate_until(&mut mainloop, || {
+            if let Poll::Ready(r) = Pin::new(&mut future).poll(&mut cx) {
+                result = Some(r);
+            }
+            result.is_some()
+        });
+        assert!(result.unwrap().unwrap().server_name.is_some());
+        assert_eq!(Arc::strong_count(&held), 1);
+        drop(future);
+        assert_eq!(Arc::strong_count(&held), 1);
+    }
+
+    #[test]
+    fn stream_adapters_pending_until_ready() {
+        let mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "FooApp").unwrap();
+        let spec = sample::Spec { format: sample::Format::S16le, channels: 2, rate: 44100 };
+        let wakes = Arc::new(AtomicUsize::new(0));
+        let waker = counting_waker(&wakes);
+        let mut cx = TaskContext::from_waker(&waker);
+
+        // Not yet connected, so writing and reading wait, without waking
+        let stream = Stream::new(&mut context, "Playback", &spec, None).unwrap();
+        let mut sink = PlaybackSink::new(stream);
+        assert!(sink.poll_write(&mut cx, &[0; 4]).is_pending());
+        assert!(sink.poll_write(&mut cx, &[]).is_pending());
+        assert_eq!(sink.poll_flush(&mut cx), Poll::Ready(Ok(())));
+        let stream = Stream::new(&mut context, "Capture", &spec, None).unwrap();
+        let mut record = RecordStream::new(stream);
+        assert!(record.poll_next(&mut cx).is_pending());
+        assert_eq!(wakes.load(Ordering::SeqCst), 0);
+
+        // The wakers are registered for the next readiness change (as given by the callbacks)
+        sink.waker.lock().unwrap().wake();
+        record.waker.lock().unwrap().wake();
+        assert_eq!(wakes.load(Ordering::SeqCst), 2);
+
+        // Closing a stream never connected has nothing to do
+        assert_eq!(sink.poll_close(&mut cx), Poll::Ready(Ok(())));
+    }
+
+    #[test]
+    fn stream_adapters_loopback() {
+        let (mut mainloop, mut context) = match connect() {
+            Some(c) => c,
+            None => return,
+        };
+        let mut introspector = context.introspect();
+        let spec = sample::Spec { format: sample::Format::S16le, channels: 2, rate: 44100 };
+        let sink_name = "pulse_binding_future_loopback";
+
+        // Play to a null sink, recording from its monitor
+        let module = Arc::new(Mutex::new(None));
+        let module_ref = Arc::clone(&module);
+        let _op = introspector.load_module("module-null-sink",
+            &format!("sink_name={}", sink_name),
+            move |index| *module_ref.lock().unwrap() = Some(index));
+        iterate_until(&mut mainloop, || module.lock().unwrap().is_some());
+        let module = module.lock().unwrap().unwrap();
+        if module == def::INVALID_INDEX {
+            return;
+        }
+
+        let mut stream = Stream::new(&mut context, "Capture", &spec, None).unwrap();
+        stream.connect_record_monitor(sink_name, None, stream::Flags::NOFLAGS).unwrap();
+        let mut record = RecordStream::new(stream);
+        let mut stream = Stream::new(&mut context, "Playback", &spec, None).unwrap();
+        stream.connect_playback(Some(sink_name), None, stream::Flags::NOFLAGS, None, None).unwrap();
+        let mut sink = PlaybackSink::new(stream);
+
+        let wakes = Arc::new(AtomicUsize::new(0));
+        let waker = counting_waker(&wakes);
+        let mut cx = TaskContext::from_waker(&waker);
+        let data = [0x11u8; 4096];
+        let (mut written, mut received) = (0, 0);
+        iterate_until(&mut mainloop, || {
+            while written < data.len() * 16 {
+                match sink.poll_write(&mut cx, &data) {
+                    Poll::Ready(r) => written += r.unwrap(),
+                    Poll::Pending => break,
+                }
+            }
+            while let Poll::Ready(chunk) = record.poll_next(&mut cx) {
+                received += chunk.unwrap().unwrap().len();
+            }
+            written >= data.len() * 16 && received > 0
+        });
+        // Pending polls were woken by the callbacks
+        assert!(wakes.load(Ordering::SeqCst) > 0);
+
+        // Once disconnected, writing fails and the record stream ends
+        assert_eq!(sink.poll_close(&mut cx), Poll::Ready(Ok(())));
+        assert_eq!(sink.poll_write(&mut cx, &data), Poll::Ready(Err(PAErr::from(Code::BadState))));
+        record.get_mut().disconnect().unwrap();
+        assert_eq!(record.poll_next(&mut cx), Poll::Ready(None));
+        assert_eq!(record.poll_next(&mut cx), Poll::Ready(None));
+
+        let unloaded = Arc::new(Mutex::new(None));
+        let unloaded_ref = Arc::clone(&unloaded);
+        let _op = introspector.unload_module(module,
+            move |r| *unloaded_ref.lock().unwrap() = Some(r));
+        iterate_until(&mut mainloop, || unloaded.lock().unwrap().is_some());
+        assert_eq!(unloaded.lock().unwrap().unwrap(), Ok(()));
+    }
+}
@@ -61,6 +61,19 @@
 //! The included main loop implementation is also not thread safe. Take care to make sure event
 //! objects are not manipulated when any other code is using the main loop.
 //!
+//! In terms of the `Send` and `Sync` markers of this binding:
+//!
+//!  * The mainloop objects (standard, threaded and glib) are neither `Send` nor `Sync`. To lock
+//!    and signal a threaded mainloop from another thread, use a [`SendableHandle`].
+//!  * `Context`, `Stream`, `Operation`, `Introspector` and the extension objects are neither `Send`
+//!    nor `Sync`, since they hold `Rc` references and callbacks that need not be `Send`. With the
+//!    threaded mainloop, they must thus only be used from the thread that created them (with the
+//!    mainloop lock held) and from within callbacks run by the mainloop thread.
+//!  * `Proplist`, `format::Info` and plain value types such as `sample::Spec`, `channelmap::Map`
+//!    and `volume::ChannelVolumes` are `Send` and `Sync`, since they are not tied to any mainloop.
+//!
+//! [`SendableHandle`]: mainloop/threaded/struct.SendableHandle.html
+//!
 //! ## Logging
 //!
 //! You can configure different logging parameters for the PulseAudio client libraries. The
@@ -118,6 +131,9 @@
 extern crate libc;
 extern crate libpulse_sys as capi;
 
+#[macro_use]
+mod macros;
+
 pub mod callbacks;
 pub mod channelmap;
 pub mod context;
@@ -125,6 +141,8 @@ pub mod def;
 pub mod direction;
 pub mod error;
 pub mod format;
+#[cfg(any(feature = "futures", feature = "dox"))]
+pub mod future;
 pub mod mainloop;
 pub mod operation;
 pub mod proplist;
@@ -135,3 +153,8 @@ pub mod utf8;
 pub mod util;
 pub mod version;
 pub mod volume;
+
+mod xmalloc;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
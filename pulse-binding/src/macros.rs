@@ -0,0 +1,219 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Internal macros.
+
+/// Defines a typed set of bit flags, wrapping the C flag type.
+///
+/// The resulting type has the same layout as the C type (being `repr(transparent)`), such that it
+/// can be passed directly to the C API, and provides the typical set operations (`|`, `&`, `^`,
+/// `-`, `!`), along with methods for checking membership.
+macro_rules! flags_type {
+    (
+        $(#[$outer:meta])*
+        pub struct $name:ident: $t:ty {
+            $(
+                $(#[$inner:meta])*
+                const $flag:ident = $value:expr;
+            )*
+        }
+    ) => {
+        $(#[$outer])*
+        #[repr(transparent)]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+        pub struct $name($t);
+
+        impl $name {
+            $(
+                $(#[$inner])*
+                pub const $flag: $name = $name($value);
+            )*
+
+            /// Gets an empty set of flags.
+            #[inline]
+            pub const fn empty() -> Self {
+                $name(0)
+            }
+
+            /// Gets the set of all known flags.
+            #[inline]
+            pub const fn all() -> Self {
+                $name(0 $(| $value)*)
+            }
+
+            /// Gets the raw value of the flags.
+            #[inline]
+            pub const fn bits(&self) -> $t {
+                self.0
+            }
+
+            /// Converts from a raw value, returning `None` if it contains any unknown bits.
+            #[inline]
+            pub fn from_bits(bits: $t) -> Option<Self> {
+                match bits & !Self::all().0 {
+                    0 => Some($name(bits)),
+                    _ => None,
+                }
+            }
+
+            /// Converts from a raw value, dropping any unknown bits.
+            #[inline]
+            pub const fn from_bits_truncate(bits: $t) -> Self {
+                $name(bits & Self::all().0)
+            }
+
+            /// Converts from a raw value, retaining any unknown bits (e.g. those given by a newer
+            /// version of PulseAudio), such that they are kept through to [`bits`](#method.bits).
+            #[inline]
+            pub const fn from_bits_retain(bits: $t) -> Self {
+                $name(bits)
+            }
+
+            /// Checks if no flags are set.
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// Checks if all of the flags in `other` are set.
+            #[inline]
+            pub const fn contains(&self, other: Self) -> bool {
+                (self.0 & other.0) == other.0
+            }
+
+            /// Checks if any of the flags in `other` are set.
+            #[inline]
+            pub const fn intersects(&self, other: Self) -> bool {
+                (self.0 & other.0) != 0
+            }
+
+            /// Sets the flags in `other`.
+            #[inline]
+            pub fn insert(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+
+            /// Clears the flags in `other`.
+            #[inline]
+            pub fn remove(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+
+            /// Toggles the flags in `other`.
+            #[inline]
+            pub fn toggle(&mut self, other: Self) {
+                self.0 ^= other.0;
+            }
+
+            /// Sets or clears the flags in `other`, per `value`.
+            #[inline]
+            pub fn set(&mut self, other: Self, value: bool) {
+                match value {
+                    true => self.insert(other),
+                    false => self.remove(other),
+                }
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = Self;
+            #[inline]
+            fn bitor(self, other: Self) -> Self {
+                $name(self.0 | other.0)
+            }
+        }
+
+        impl std::ops::BitOrAssign for $name {
+            #[inline]
+            fn bitor_assign(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+        }
+
+        impl std::ops::BitAnd for $name {
+            type Output = Self;
+            #[inline]
+            fn bitand(self, other: Self) -> Self {
+                $name(self.0 & other.0)
+            }
+        }
+
+        impl std::ops::BitAndAssign for $name {
+            #[inline]
+            fn bitand_assign(&mut self, other: Self) {
+                self.0 &= other.0;
+            }
+        }
+
+        impl std::ops::BitXor for $name {
+            type Output = Self;
+            #[inline]
+            fn bitxor(self, other: Self) -> Self {
+                $name(self.0 ^ other.0)
+            }
+        }
+
+        impl std::ops::BitXorAssign for $name {
+            #[inline]
+            fn bitxor_assign(&mut self, other: Self) {
+                self.0 ^= other.0;
+            }
+        }
+
+        impl std::ops::Sub for $name {
+            type Output = Self;
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                $name(self.0 & !other.0)
+            }
+        }
+
+        impl std::ops::SubAssign for $name {
+            #[inline]
+            fn sub_assign(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+        }
+
+        impl std::ops::Not for $name {
+            type Output = Self;
+            #[inline]
+            fn not(self) -> Self {
+                Self::from_bits_truncate(!self.0)
+            }
+        }
+
+        impl From<$name> for $t {
+            #[inline]
+            fn from(f: $name) -> Self {
+                f.0
+            }
+        }
+    };
+}
+
+/// Asserts, at compile time, that a type does not implement the given trait, such as `Send`.
+///
+/// Should the type implement the trait, the call to `check` is ambiguous between the two blanket
+/// implementations, thus compilation fails. Unlike a `compile_fail` doctest, this cannot pass due
+/// to some unrelated failure (such as the C library not being available to link against).
+#[cfg(test)]
+macro_rules! assert_not_impl {
+    ($t:ty: $tr:path) => {{
+        trait AmbiguousIfImpl<A> {
+            fn check() {}
+        }
+        impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+        #[allow(dead_code)]
+        struct Invalid;
+        impl<T: ?Sized + $tr> AmbiguousIfImpl<Invalid> for T {}
+        <$t as AmbiguousIfImpl<_>>::check();
+    }};
+}
@@ -13,15 +13,16 @@
 
 //! Main loop abstraction layer API.
 
+use std::any::Any;
 use std::os::raw::c_void;
 use std::rc::Rc;
 use libc::timeval;
 use crate::def;
 use super::events;
 use super::events::io::{IoEvent, IoEventRef, IoEventInternal, IoEventFlagSet};
-use super::events::timer::{TimeEvent, TimeEventRef, TimeEventInternal};
+use super::events::timer::{TimeEvent, TimeEventRef, TimeEventInternal, PeriodicTimer};
 use super::events::deferred::{DeferEvent, DeferEventRef, DeferEventInternal};
-use crate::time::{UnixTs, MonotonicTs, Timeval, USEC_INVALID};
+use crate::time::{UnixTs, MonotonicTs, MicroSeconds, Timeval, USEC_INVALID};
 use crate::callbacks::{get_su_capi_params, get_su_callback};
 
 pub(crate) use capi::pa_mainloop_api as ApiInternal;
@@ -239,6 +240,35 @@ pub trait Mainloop {
         }
     }
 
+    /// Schedules a callback to be called repeatedly, every `interval`, starting one interval from
+    /// now.
+    ///
+    /// This is built upon a monotonic-based timer event (see [`new_timer_event_rt`]), restarted
+    /// from within its own callback after each firing. Returns `None` should the timer event not be
+    /// created.
+    ///
+    /// **Note**: As with other events, the returned object must be kept alive for as long as you
+    /// want the callback to be called; dropping it (or calling its `cancel` method) stops the
+    /// timer.
+    ///
+    /// Asserts that `interval` is not zero.
+    ///
+    /// [`new_timer_event_rt`]: #method.new_timer_event_rt
+    fn schedule_periodic(&mut self, interval: std::time::Duration,
+        mut callback: Box<dyn FnMut() + 'static>) -> Option<PeriodicTimer<Self::MI>>
+        where Self::MI: 'static
+    {
+        let interval = MicroSeconds::from(interval);
+        assert_ne!(interval.0, 0);
+        let mut deadline = MonotonicTs::now() + interval;
+        self.new_timer_event_rt(deadline, Box::new(move |mut event| {
+            callback();
+            deadline = events::timer::next_periodic_deadline(deadline, interval,
+                MonotonicTs::now());
+            event.restart_rt(deadline);
+        })).map(PeriodicTimer::new)
+    }
+
     /// Creates a new deferred event.
     ///
     /// **Note**: You must ensure that the returned event object lives for as long as you want its
@@ -287,6 +317,21 @@ pub trait Mainloop {
         unsafe { capi::pa_mainloop_api_once(api.as_ref(), cb_fn, cb_data) };
     }
 
+    /// Runs the specified one-shot callback on the next iteration of the main loop.
+    ///
+    /// This is the same as [`once_event`](#method.once_event), but taking an `FnOnce` closure,
+    /// e.g. for “posting” work to be done by the mainloop, such as moving values into it. The
+    /// anonymous defer event used disables and frees itself after firing, thus the callback runs
+    /// exactly once. The same rules regarding threads apply.
+    fn defer_once(&mut self, callback: Box<dyn FnOnce() + 'static>) {
+        let mut callback = Some(callback);
+        self.once_event(Box::new(move || {
+            if let Some(callback) = callback.take() {
+                callback();
+            }
+        }));
+    }
+
     /// Calls quit
     fn quit(&mut self, retval: def::Retval) {
         let inner = self.inner();
@@ -296,6 +341,111 @@ pub trait Mainloop {
     }
 }
 
+/// An object-safe view of a mainloop, of any implementation.
+///
+/// The [`Mainloop`] trait cannot be used as a trait object, since it is generic over the inner
+/// wrapper type of each implementation. This trait is implemented for all mainloops (standard,
+/// threaded and GLib), allowing code to work with `&dyn AnyMainloop` regardless of which
+/// implementation is in use. The [`ErasedMainloop`] handle obtained through it gives access to
+/// event creation and `quit`, since it itself implements [`Mainloop`], and can be given to
+/// `Context::new` and `Context::new_with_proplist`.
+///
+/// [`Mainloop`]: trait.Mainloop.html
+/// [`ErasedMainloop`]: struct.ErasedMainloop.html
+pub trait AnyMainloop {
+    /// Gets a type erased handle to the mainloop.
+    ///
+    /// The handle keeps the inner wrapper of the mainloop alive, just as event objects do.
+    fn erased(&self) -> ErasedMainloop;
+}
+
+impl<M> AnyMainloop for M
+    where M: Mainloop, M::MI: 'static
+{
+    fn erased(&self) -> ErasedMainloop {
+        let inner = self.inner();
+        ErasedMainloop {
+            inner: Rc::new(ErasedInner {
+                ptr: inner.get_ptr() as *mut ErasedInternal,
+                api: inner.get_api(),
+                supports_rtclock: inner.supports_rtclock(),
+                _inner: inner,
+            }),
+        }
+    }
+}
+
+/// A stand-in for the opaque main loop object of an [`ErasedInner`], of whichever implementation.
+///
+/// [`ErasedInner`]: struct.ErasedInner.html
+pub enum ErasedInternal {}
+
+impl MainloopInternalType for ErasedInternal {}
+
+/// Type erased mainloop inner wrapper, as used by [`ErasedMainloop`] and its events.
+///
+/// This holds a ref-counted copy of the inner wrapper of the actual mainloop, keeping it alive.
+///
+/// [`ErasedMainloop`]: struct.ErasedMainloop.html
+pub struct ErasedInner {
+    ptr: *mut ErasedInternal,
+    api: *const MainloopApi,
+    supports_rtclock: bool,
+    _inner: Rc<dyn Any>,
+}
+
+impl MainloopInnerType for ErasedInner {
+    type I = ErasedInternal;
+
+    fn get_ptr(&self) -> *mut ErasedInternal {
+        self.ptr
+    }
+
+    fn get_api(&self) -> &MainloopApi {
+        assert!(!self.api.is_null());
+        unsafe { &*self.api }
+    }
+
+    fn supports_rtclock(&self) -> bool {
+        self.supports_rtclock
+    }
+}
+
+/// A handle to a mainloop, of any implementation, with its type erased.
+///
+/// Obtained with [`AnyMainloop::erased`]. Through its implementation of [`Mainloop`], events can be
+/// created upon the mainloop, and it can be quit.
+///
+/// [`AnyMainloop::erased`]: trait.AnyMainloop.html#tymethod.erased
+/// [`Mainloop`]: trait.Mainloop.html
+#[derive(Clone)]
+pub struct ErasedMainloop {
+    inner: Rc<ErasedInner>,
+}
+
+impl Mainloop for ErasedMainloop {
+    type MI = ErasedInner;
+
+    #[inline]
+    fn inner(&self) -> Rc<ErasedInner> {
+        Rc::clone(&self.inner)
+    }
+}
+
+impl ErasedMainloop {
+    /// Creates a handle from an existing inner wrapper.
+    #[inline]
+    pub(crate) fn from_inner(inner: Rc<ErasedInner>) -> Self {
+        Self { inner: inner }
+    }
+
+    /// Gets the main loop abstraction layer vtable for this main loop.
+    #[inline]
+    pub fn get_api(&self) -> &MainloopApi {
+        self.inner.get_api()
+    }
+}
+
 /// An IO event callback prototype.
 pub type IoEventCb = extern "C" fn(a: *const MainloopApi, e: *mut IoEventInternal, fd: i32,
     events: IoEventFlagSet, userdata: *mut c_void);
@@ -364,11 +514,22 @@ pub struct MainloopApi {
     pub quit: Option<extern "C" fn(a: *const MainloopApi, retval: def::RetvalActual)>,
 }
 
-/// Test size is equal to `sys` equivalent (duplicated here for different documentation)
-#[test]
-fn api_compare_capi(){
-    assert_eq!(std::mem::size_of::<ApiInternal>(), std::mem::size_of::<capi::pa_mainloop_api>());
-    assert_eq!(std::mem::align_of::<ApiInternal>(), std::mem::align_of::<capi::pa_mainloop_api>());
+// Statically assert that the size and alignment are equal to the `sys` equivalent (duplicated here
+// for different documentation), upon which the pointer casts between the two rely. Field offsets
+// are checked by the `api_layout` test.
+const _: () = [()][(std::mem::size_of::<MainloopApi>() != std::mem::size_of::<ApiInternal>())
+    as usize];
+const _: () = [()][(std::mem::align_of::<MainloopApi>() != std::mem::align_of::<ApiInternal>())
+    as usize];
+
+impl MainloopApi {
+    /// Converts a pointer to the `sys` crate’s API vtable into one to this equivalent.
+    ///
+    /// For use by mainloop implementations. The two are layout-compatible.
+    #[inline]
+    pub fn from_capi_ptr(ptr: *const capi::pa_mainloop_api) -> *const Self {
+        ptr as *const Self
+    }
 }
 
 impl AsRef<capi::pa_mainloop_api> for MainloopApi {
@@ -381,13 +542,13 @@ impl AsRef<capi::pa_mainloop_api> for MainloopApi {
 impl<'a> From<*const ApiInternal> for &'a MainloopApi {
     #[inline]
     fn from(a: *const ApiInternal) -> Self {
-        unsafe { std::mem::transmute(a) }
+        unsafe { &*MainloopApi::from_capi_ptr(a) }
     }
 }
 impl<'a> From<&'a MainloopApi> for *const ApiInternal {
     #[inline]
     fn from(a: &'a MainloopApi) -> Self {
-        unsafe { std::mem::transmute(a) }
+        a as *const MainloopApi as *const ApiInternal
     }
 }
 
@@ -402,3 +563,217 @@ fn once_cb_proxy(_: *const ApiInternal, userdata: *mut c_void) {
         (callback)();
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use crate::context::Context;
+    use crate::mainloop::{standard, threaded};
+
+    /// Library code written against any mainloop implementation, quitting it once the event fires.
+    fn quit_deferred(mainloop: &dyn AnyMainloop, fired: Rc<Cell<bool>>)
+        -> Option<DeferEvent<ErasedInner>>
+    {
+        let mut erased = mainloop.erased();
+        let mut for_callback = erased.clone();
+        erased.new_deferred_event(Box::new(move |_| {
+            fired.set(true);
+            for_callback.quit(def::Retval(3));
+        }))
+    }
+
+    /// Checks that every field of the API vtable is at the same offset as in the `sys` equivalent,
+    /// upon which the pointer casts between the two rely.
+    #[test]
+    fn api_layout() {
+        let ours: MainloopApi = unsafe { std::mem::zeroed() };
+        let theirs: ApiInternal = unsafe { std::mem::zeroed() };
+        macro_rules! assert_offsets {
+            ($($field:ident),*) => {$(
+                assert_eq!(
+                    &ours.$field as *const _ as usize - &ours as *const _ as usize,
+                    &theirs.$field as *const _ as usize - &theirs as *const _ as usize,
+                    stringify!($field));
+            )*};
+        }
+        assert_offsets!(userdata, io_new, io_enable, io_free, io_set_destroy, time_new,
+            time_restart, time_free, time_set_destroy, defer_new, defer_enable, defer_free,
+            defer_set_destroy, quit);
+    }
+
+    #[test]
+    fn erased_mainloop() {
+        let mut mainloop = standard::Mainloop::new().unwrap();
+        let fired = Rc::new(Cell::new(false));
+        let event = quit_deferred(&mainloop, Rc::clone(&fired));
+        assert!(event.is_some());
+        assert_eq!(mainloop.run(), Ok(def::Retval(3)));
+        assert!(fired.get());
+
+        // The handle keeps the mainloop internals alive
+        let inner = Rc::downgrade(&mainloop._inner);
+        let erased = mainloop.erased();
+        drop((event, mainloop));
+        assert!(inner.upgrade().is_some());
+        drop(erased);
+        assert!(inner.upgrade().is_none());
+    }
+
+    #[test]
+    fn defer_once() {
+        let mut mainloop = standard::Mainloop::new().unwrap();
+        let count = Rc::new(Cell::new(0));
+        let posted = vec![1, 2, 3];
+        let count_ref = Rc::clone(&count);
+        mainloop.defer_once(Box::new(move || {
+            count_ref.set(count_ref.get() + posted.len());
+            drop(posted);
+        }));
+        assert_eq!(count.get(), 0);
+        for _ in 0..2 {
+            match mainloop.iterate(false) {
+                standard::IterateResult::Success(_) => {},
+                _ => panic!("iteration failed"),
+            }
+        }
+        assert_eq!(count.get(), 3);
+        // The closure, and what it captured, has been freed
+        assert_eq!(Rc::strong_count(&count), 1);
+    }
+
+    #[test]
+    fn context_from_trait_object() {
+        let standard = standard::Mainloop::new().unwrap();
+        let threaded = threaded::Mainloop::new().unwrap();
+        let mainloops: [&dyn AnyMainloop; 2] = [&standard, &threaded];
+        for mainloop in mainloops.iter() {
+            assert!(Context::new(*mainloop, "test").is_ok());
+        }
+    }
+
+    /// A stand-in mainloop object for the stub API.
+    enum StubInternal {}
+
+    impl MainloopInternalType for StubInternal {}
+
+    /// The calls made to the stub API, and the timer callback registered with it.
+    #[derive(Default)]
+    struct StubTimer {
+        callback: Option<TimeEventCb>,
+        userdata: usize,
+        started: Vec<(i64, i64)>,
+        restarted: Vec<(i64, i64)>,
+        freed: bool,
+    }
+
+    thread_local! {
+        static STUB_TIMER: std::cell::RefCell<StubTimer> = Default::default();
+    }
+
+    extern "C" fn stub_time_new(_: *const MainloopApi, tv: *const timeval, cb: Option<TimeEventCb>,
+        userdata: *mut c_void) -> *mut TimeEventInternal
+    {
+        STUB_TIMER.with(|t| {
+            let mut t = t.borrow_mut();
+            let tv = unsafe { &*tv };
+            t.started.push((tv.tv_sec as i64, tv.tv_usec as i64));
+            t.callback = cb;
+            t.userdata = userdata as usize;
+        });
+        std::ptr::NonNull::dangling().as_ptr()
+    }
+
+    extern "C" fn stub_time_restart(_: *mut TimeEventInternal, tv: *const timeval) {
+        let tv = unsafe { &*tv };
+        STUB_TIMER.with(|t| t.borrow_mut().restarted.push((tv.tv_sec as i64, tv.tv_usec as i64)));
+    }
+
+    extern "C" fn stub_time_free(_: *mut TimeEventInternal) {
+        STUB_TIMER.with(|t| t.borrow_mut().freed = true);
+    }
+
+    /// A mainloop implementation supporting only timer events, through the stub API.
+    struct StubMainloop {
+        inner: Rc<MainloopInner<StubInternal>>,
+    }
+
+    impl Mainloop for StubMainloop {
+        type MI = MainloopInner<StubInternal>;
+
+        fn inner(&self) -> Rc<MainloopInner<StubInternal>> {
+            Rc::clone(&self.inner)
+        }
+    }
+
+    impl StubMainloop {
+        fn new() -> Self {
+            let api = Box::new(MainloopApi {
+                userdata: std::ptr::null_mut(),
+                io_new: None,
+                io_enable: None,
+                io_free: None,
+                io_set_destroy: None,
+                time_new: Some(stub_time_new),
+                time_restart: Some(stub_time_restart),
+                time_free: Some(stub_time_free),
+                time_set_destroy: None,
+                defer_new: None,
+                defer_enable: None,
+                defer_free: None,
+                defer_set_destroy: None,
+                quit: None,
+            });
+            Self {
+                inner: Rc::new(MainloopInner::<StubInternal> {
+                    ptr: std::ptr::null_mut(),
+                    api: Box::into_raw(api),
+                    dropfn: |inner| drop(unsafe { Box::from_raw(inner.api as *mut MainloopApi) }),
+                    supports_rtclock: true,
+                }),
+            }
+        }
+    }
+
+    /// Fires the timer registered with the stub API.
+    fn fire_stub_timer() {
+        let (callback, userdata) = STUB_TIMER.with(|t| {
+            let t = t.borrow();
+            (t.callback.unwrap(), t.userdata as *mut c_void)
+        });
+        let tv = Timeval::new_zero();
+        callback(std::ptr::null(), std::ptr::NonNull::dangling().as_ptr(), &tv.0, userdata);
+    }
+
+    #[test]
+    fn periodic_timer_reschedules() {
+        let mut mainloop = StubMainloop::new();
+        let fired = Rc::new(Cell::new(0));
+        let fired_ref = Rc::clone(&fired);
+        let mut timer = mainloop.schedule_periodic(std::time::Duration::from_millis(20),
+            Box::new(move || fired_ref.set(fired_ref.get() + 1))).unwrap();
+        assert!(timer.is_active());
+        STUB_TIMER.with(|t| {
+            let t = t.borrow();
+            assert_eq!(t.started.len(), 1);
+            assert!(t.restarted.is_empty());
+        });
+
+        // Each firing calls the callback, then restarts the timer for a later deadline
+        fire_stub_timer();
+        assert_eq!(fired.get(), 1);
+        fire_stub_timer();
+        assert_eq!(fired.get(), 2);
+        STUB_TIMER.with(|t| {
+            let t = t.borrow();
+            assert_eq!(t.restarted.len(), 2);
+            assert!(t.restarted[0] > t.started[0]);
+            assert!(t.restarted[1] > t.restarted[0]);
+            assert!(!t.freed);
+        });
+
+        timer.cancel();
+        assert!(!timer.is_active());
+        assert!(STUB_TIMER.with(|t| t.borrow().freed));
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Blocking convenience helpers for use with the threaded mainloop.
+//!
+//! # Overview
+//!
+//! Users of the [threaded mainloop] very commonly want to simply perform some operation and wait
+//! for it to complete, such as connecting a context and waiting until it is ready. Doing so
+//! correctly requires careful use of the mainloop lock, along with [`wait`] and [`signal`], as
+//! described in the documentation of that module. The functions in this module take care of that
+//! for a few common cases.
+//!
+//! All functions here take an optional timeout, and return an [`Error`] giving the reason for
+//! failure.
+//!
+//! # Locking
+//!
+//! The mainloop must have been started (see [`Mainloop::start`]) and must **not** be locked when
+//! calling these functions. They take the lock themselves, holding it around every call into the
+//! PulseAudio library and releasing it while waiting. The lock is released again upon return.
+//! These functions must not be called from within the event loop thread.
+//!
+//! This module is only available with the `blocking` Cargo feature enabled.
+//!
+//! [threaded mainloop]: ../threaded/index.html
+//! [`wait`]: ../threaded/struct.Mainloop.html#method.wait
+//! [`signal`]: ../threaded/struct.Mainloop.html#method.signal
+//! [`Mainloop::start`]: ../threaded/struct.Mainloop.html#method.start
+//! [`Error`]: enum.Error.html
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::context::{self, Context};
+use crate::error::PAErr;
+use crate::mainloop::api::Mainloop as MainloopTrait;
+use crate::mainloop::threaded::{Mainloop, MainloopInternal};
+use crate::operation::Operation;
+use crate::stream::{self, Stream};
+use crate::time::{MicroSeconds, MonotonicTs};
+
+/// Reasons for failure of a blocking helper.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The timeout expired before completion.
+    Timeout,
+    /// The operation failed, with the given error.
+    Failed(PAErr),
+    /// The context or stream was terminated.
+    Terminated,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Error::Timeout => write!(f, "timed out"),
+            Error::Failed(e) => write!(f, "failed: {}", e),
+            Error::Terminated => write!(f, "terminated"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Wakes up a thread waiting on the mainloop.
+///
+/// This is for use from within callbacks, which run in the event loop thread with the lock held.
+#[inline]
+fn signal_raw(ptr: *mut MainloopInternal) {
+    unsafe { capi::pa_threaded_mainloop_signal(ptr, 0) };
+}
+
+/// Waits until `check` gives a result, or until the timeout expires.
+///
+/// Must be called with the mainloop locked, and something must signal the mainloop whenever the
+/// condition being checked may have changed.
+fn wait_until<F>(mainloop: &mut Mainloop, timeout: Option<MicroSeconds>, mut check: F)
+    -> Result<(), Error>
+    where F: FnMut() -> Option<Result<(), Error>>
+{
+    let timed_out = Arc::new(AtomicBool::new(false));
+
+    // Note, the timer event must be kept alive until we are done waiting, then destroyed (as it is
+    // when going out of scope) whilst the lock is still held.
+    let _timer = match timeout.and_then(|t| MonotonicTs::now().checked_add(t)) {
+        Some(deadline) => {
+            let flag = Arc::clone(&timed_out);
+            let ml_ptr = (*mainloop._inner).ptr;
+            mainloop.new_timer_event_rt(deadline, Box::new(move |_| {
+                flag.store(true, Ordering::SeqCst);
+                signal_raw(ml_ptr);
+            }))
+        },
+        None => None,
+    };
+
+    loop {
+        if let Some(result) = check() {
+            return result;
+        }
+        if timed_out.load(Ordering::SeqCst) {
+            return Err(Error::Timeout);
+        }
+        mainloop.wait();
+    }
+}
+
+/// Connects a context to the server, waiting until it is ready (or fails).
+///
+/// The `server` and `flags` params are as for [`Context::connect`].
+///
+/// Note that this replaces any state callback set on the context, and leaves it unset upon
+/// return.
+///
+/// [`Context::connect`]: ../../context/struct.Context.html#method.connect
+pub fn connect_context_blocking(mainloop: &mut Mainloop, context: &mut Context,
+    server: Option<&str>, flags: context::Flags, timeout: Option<MicroSeconds>)
+    -> Result<(), Error>
+{
+    mainloop.lock();
+
+    let ml_ptr = (*mainloop._inner).ptr;
+    context.set_state_callback(Some(Box::new(move || signal_raw(ml_ptr))));
+
+    let result = match context.connect(server, flags, None) {
+        Err(e) => Err(Error::Failed(e)),
+        Ok(()) => {
+            let ctx = &*context;
+            wait_until(mainloop, timeout, || match ctx.get_state() {
+                context::State::Ready => Some(Ok(())),
+                context::State::Failed => Some(Err(Error::Failed(ctx.errno()))),
+                context::State::Terminated => Some(Err(Error::Terminated)),
+                _ => None,
+            })
+        },
+    };
+
+    context.set_state_callback(None);
+    mainloop.unlock();
+    result
+}
+
+/// Connects a stream, waiting until it is ready (or fails).
+///
+/// The actual connection is performed by the `connect` closure, which is given the stream, and
+/// should call the appropriate connection method, e.g. [`Stream::connect_playback`]. The context
+/// the stream belongs to must be provided, for obtaining the error in case of failure.
+///
+/// Note that this replaces any state callback set on the stream, and leaves it unset upon return.
+///
+/// [`Stream::connect_playback`]: ../../stream/struct.Stream.html#method.connect_playback
+pub fn connect_stream_blocking<F>(mainloop: &mut Mainloop, context: &Context, stream: &mut Stream,
+    connect: F, timeout: Option<MicroSeconds>) -> Result<(), Error>
+    where F: FnOnce(&mut Stream) -> Result<(), PAErr>
+{
+    mainloop.lock();
+
+    let ml_ptr = (*mainloop._inner).ptr;
+    stream.set_state_callback(Some(Box::new(move || signal_raw(ml_ptr))));
+
+    let result = match connect(stream) {
+        Err(e) => Err(Error::Failed(e)),
+        Ok(()) => {
+            let s = &*stream;
+            wait_until(mainloop, timeout, || match s.get_state() {
+                stream::State::Ready => Some(Ok(())),
+                stream::State::Failed => Some(Err(Error::Failed(context.errno()))),
+                stream::State::Terminated => Some(Err(Error::Terminated)),
+                _ => None,
+            })
+        },
+    };
+
+    stream.set_state_callback(None);
+    mainloop.unlock();
+    result
+}
+
+/// Callback for the result of a list operation, as given by [`collect_list_blocking`].
+///
+/// [`collect_list_blocking`]: fn.collect_list_blocking.html
+pub type ListCallback<T> = Box<dyn FnOnce(Result<Vec<T>, PAErr>)>;
+
+/// Runs a list operation, such as an introspection query, waiting for and returning the collected
+/// items.
+///
+/// The `start` closure is given a [`ListCallback`], and must start the operation, returning the
+/// resulting [`Operation`] object. The callback is designed to be passed straight to one of the
+/// `_collected` list variants of the [`Introspector`], which take care of collecting the items,
+/// and of reporting the end of the list or an error. For instance:
+///
+/// ```rust,ignore
+/// let introspector = context.introspect();
+/// let sinks = collect_list_blocking(&mut mainloop, None, |callback| {
+///     introspector.get_sink_info_list_collected(callback)
+/// })?;
+/// ```
+///
+/// If the timeout expires, the operation is cancelled.
+///
+/// [`ListCallback`]: type.ListCallback.html
+/// [`Operation`]: ../../operation/struct.Operation.html
+/// [`Introspector`]: ../../context/introspect/struct.Introspector.html
+pub fn collect_list_blocking<T, S, ClosureProto>(mainloop: &mut Mainloop,
+    timeout: Option<MicroSeconds>, start: S) -> Result<Vec<T>, Error>
+    where S: FnOnce(ListCallback<T>) -> Operation<ClosureProto>,
+          T: 'static,
+          ClosureProto: ?Sized
+{
+    mainloop.lock();
+
+    let state = Arc::new(Mutex::new(None));
+    let cb_state = Arc::clone(&state);
+    let ml_ptr = (*mainloop._inner).ptr;
+    let callback: ListCallback<T> = Box::new(move |result| {
+        *cb_state.lock().unwrap() = Some(result);
+        signal_raw(ml_ptr);
+    });
+
+    let mut op = start(callback);
+
+    let mut items = Vec::new();
+    let result = wait_until(mainloop, timeout, || match state.lock().unwrap().take() {
+        Some(Ok(collected)) => {
+            items = collected;
+            Some(Ok(()))
+        },
+        Some(Err(e)) => Some(Err(Error::Failed(e))),
+        None => None,
+    });
+    if result == Err(Error::Timeout) {
+        op.cancel();
+    }
+    drop(op);
+
+    mainloop.unlock();
+
+    result.map(|_| items)
+}
@@ -26,7 +26,7 @@ use std::os::raw::c_void;
 use std::rc::Rc;
 use libc::timeval;
 use crate::mainloop::api::{MainloopApi, MainloopInnerType};
-use crate::time::{UnixTs, MonotonicTs, Timeval, USEC_INVALID};
+use crate::time::{UnixTs, MonotonicTs, MicroSeconds, Timeval, USEC_INVALID};
 use crate::callbacks::MultiUseCallback;
 
 pub use capi::pa_time_event as TimeEventInternal;
@@ -124,6 +124,62 @@ impl<T> Drop for TimeEvent<T>
     }
 }
 
+/// A timer that fires repeatedly, at a fixed interval.
+///
+/// Created with [`Mainloop::schedule_periodic`]. The timer reschedules itself after each firing
+/// (by restarting the underlying timer event from within its own callback), until cancelled with
+/// [`cancel`](#method.cancel) or dropped.
+///
+/// Deadlines are kept at multiples of the interval from the first, such that the timer does not
+/// drift. Should the mainloop fall behind by more than an interval, missed firings are skipped.
+///
+/// [`Mainloop::schedule_periodic`]: ../../api/trait.Mainloop.html#method.schedule_periodic
+pub struct PeriodicTimer<T>
+    where T: MainloopInnerType
+{
+    event: Option<TimeEvent<T>>,
+}
+
+impl<T> PeriodicTimer<T>
+    where T: MainloopInnerType
+{
+    #[inline]
+    pub(crate) fn new(event: TimeEvent<T>) -> Self {
+        Self { event: Some(event) }
+    }
+
+    /// Stops the timer. The callback will not be called again.
+    ///
+    /// This is equivalent to dropping the timer.
+    #[inline]
+    pub fn cancel(&mut self) {
+        self.event = None;
+    }
+
+    /// Checks whether the timer is still scheduled, i.e. has not been cancelled (returns `true` if
+    /// so).
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.event.is_some()
+    }
+}
+
+/// Calculates the deadline following `previous` for a periodic timer firing every `interval`.
+///
+/// Should `now` already be past the next deadline, those missed are skipped.
+pub(crate) fn next_periodic_deadline(previous: MonotonicTs, interval: MicroSeconds,
+    now: MonotonicTs) -> MonotonicTs
+{
+    let next = previous + interval;
+    match next < now {
+        false => next,
+        true => {
+            let missed = ((now.0).0 - (next.0).0) / interval.0.max(1) + 1;
+            next + MicroSeconds(missed * interval.0)
+        },
+    }
+}
+
 /// Proxy for the event callback.
 ///
 /// Warning: This is for multi-use cases! It does **not** destroy the actual closure callback, which
@@ -138,3 +194,28 @@ fn event_cb_proxy(_: *const MainloopApi, e: *mut TimeEventInternal, _: *const ti
         (callback)(e);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn periodic_deadlines() {
+        let interval = MicroSeconds(20_000);
+        let start = MonotonicTs(MicroSeconds(1_000_000));
+
+        // On time (or early)
+        assert_eq!(next_periodic_deadline(start, interval, start),
+            MonotonicTs(MicroSeconds(1_020_000)));
+        assert_eq!(next_periodic_deadline(start, interval, MonotonicTs(MicroSeconds(1_015_000))),
+            MonotonicTs(MicroSeconds(1_020_000)));
+        // Late by less than an interval, keeping to the schedule
+        assert_eq!(next_periodic_deadline(start, interval, MonotonicTs(MicroSeconds(1_020_000))),
+            MonotonicTs(MicroSeconds(1_020_000)));
+        // Missed several, which are skipped
+        assert_eq!(next_periodic_deadline(start, interval, MonotonicTs(MicroSeconds(1_065_000))),
+            MonotonicTs(MicroSeconds(1_080_000)));
+        assert_eq!(next_periodic_deadline(start, interval, MonotonicTs(MicroSeconds(1_060_001))),
+            MonotonicTs(MicroSeconds(1_080_000)));
+    }
+}
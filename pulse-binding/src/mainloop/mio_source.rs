@@ -0,0 +1,359 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Integration of the standard mainloop with a `mio` event loop.
+//!
+//! # Overview
+//!
+//! Rather than running the [standard mainloop] itself, the file descriptors it wishes to poll can
+//! be driven from an application’s own [`mio::Poll`]. Each iteration consists of:
+//!
+//!  1. Calling [`MioSource::prepare`], which runs the mainloop’s prepare step, (re-)registers its
+//!     current set of file descriptors with the poll registry, and returns the maximum time to wait
+//!     for (`None` meaning block indefinitely), as is the time until the next timer event is due.
+//!  2. Polling the [`mio::Poll`], with that timeout (or a shorter one).
+//!  3. Calling [`MioSource::dispatch`] with the events received (even if there are none, such that
+//!     timer and deferred events are dispatched), which marks the corresponding file descriptors as
+//!     ready, and dispatches the mainloop’s events.
+//!
+//! The file descriptors are registered using tokens from a range beginning with the base token
+//! given to [`MioSource::new`], which thus must not overlap with any others used with the same
+//! poll. Whether an event belongs to the mainloop can be checked with [`MioSource::owns`].
+//!
+//! Note that the set of file descriptors may change between iterations, and that they are
+//! re-registered upon every prepare step, such that any left ready by the previous iteration are
+//! reported again, despite `mio` being edge triggered.
+//!
+//! See also [`Mainloop::prepare_ext`], upon which this is built, for other event loops.
+//!
+//! This module is only available on Unix, with the `mio` Cargo feature enabled.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! extern crate libpulse_binding as pulse;
+//! extern crate mio;
+//!
+//! use mio::{Events, Poll, Token};
+//! use pulse::mainloop::mio_source::MioSource;
+//! use pulse::mainloop::standard::Mainloop;
+//!
+//! let mut mainloop = Mainloop::new().unwrap();
+//! let mut poll = Poll::new().unwrap();
+//! let mut events = Events::with_capacity(64);
+//! let mut source = MioSource::new(Token(1000));
+//!
+//! loop {
+//!     let timeout = source.prepare(&mut mainloop, poll.registry(), None).unwrap();
+//!     poll.poll(&mut events, timeout).unwrap();
+//!     source.dispatch(&mut mainloop, &events).unwrap();
+//!     // Handle any other events here
+//! }
+//! ```
+//!
+//! [standard mainloop]: ../standard/index.html
+//! [`mio::Poll`]: https://docs.rs/mio/0.7/mio/struct.Poll.html
+//! [`MioSource::new`]: struct.MioSource.html#method.new
+//! [`MioSource::prepare`]: struct.MioSource.html#method.prepare
+//! [`MioSource::dispatch`]: struct.MioSource.html#method.dispatch
+//! [`MioSource::owns`]: struct.MioSource.html#method.owns
+//! [`Mainloop::prepare_ext`]: ../standard/struct.Mainloop.html#method.prepare_ext
+
+use std::io;
+use std::os::raw::c_short;
+use std::os::unix::io::RawFd;
+use std::time::Duration;
+use libc::pollfd;
+use mio::{Events, Interest, Registry, Token};
+use mio::unix::SourceFd;
+use crate::error::PAErr;
+use crate::mainloop::standard::Mainloop;
+
+/// Reasons for failure of a `mio` integration step.
+#[derive(Debug)]
+pub enum Error {
+    /// The mainloop failed (or quit was requested), with the given error.
+    Mainloop(PAErr),
+    /// Registering a file descriptor with the poll registry failed.
+    Io(io::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Error::Mainloop(e) => write!(f, "mainloop error: {}", e),
+            Error::Io(ref e) => write!(f, "registration error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<PAErr> for Error {
+    #[inline]
+    fn from(e: PAErr) -> Self {
+        Error::Mainloop(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    #[inline]
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Drives the file descriptors of a standard mainloop from a `mio` poll.
+///
+/// See the [module level documentation](index.html) for usage.
+#[derive(Debug)]
+pub struct MioSource {
+    /// The first token used for registrations.
+    base: Token,
+    /// The set of file descriptors handed out by the current prepare step.
+    fds: Vec<pollfd>,
+    /// The distinct file descriptors currently registered, each with the token `base + index`.
+    registered: Vec<RawFd>,
+}
+
+impl MioSource {
+    /// Creates a new source, which registers file descriptors with tokens starting from `base`.
+    pub fn new(base: Token) -> Self {
+        Self { base: base, fds: Vec::new(), registered: Vec::new() }
+    }
+
+    /// Runs the mainloop’s prepare step, and (re-)registers its current set of file descriptors
+    /// with `registry`, deregistering any no longer in use.
+    ///
+    /// `timeout` specifies a maximum timeout for the subsequent poll, or `None` for no limit. On
+    /// success returns the timeout to use for the poll, or `None` to block indefinitely.
+    pub fn prepare(&mut self, mainloop: &mut Mainloop, registry: &Registry, timeout: Option<i32>)
+        -> Result<Option<Duration>, Error>
+    {
+        let (fds, timeout) = mainloop.prepare_ext(timeout)?;
+        self.fds = fds;
+
+        let mut current: Vec<RawFd> = Vec::with_capacity(self.fds.len());
+        for p in &self.fds {
+            if interest(p.events).is_some() && !current.contains(&p.fd) {
+                current.push(p.fd);
+            }
+        }
+
+        // Deregister those no longer in use. Failure is ignored, since the file descriptor may
+        // already have been closed, which implicitly removes it.
+        for fd in self.registered.iter().filter(|fd| !current.contains(fd)) {
+            let _ = registry.deregister(&mut SourceFd(fd));
+        }
+
+        for (i, fd) in current.iter().enumerate() {
+            let events = self.fds.iter().filter(|p| p.fd == *fd).fold(0, |acc, p| acc | p.events);
+            let interests = interest(events).unwrap();
+            let token = Token(self.base.0 + i);
+            // Note, a file descriptor previously registered may have been closed and replaced by
+            // another of the same number, thus if one form fails, the other is attempted.
+            let result = match self.registered.contains(fd) {
+                true => registry.reregister(&mut SourceFd(fd), token, interests)
+                    .or_else(|_| registry.register(&mut SourceFd(fd), token, interests)),
+                false => registry.register(&mut SourceFd(fd), token, interests)
+                    .or_else(|_| registry.reregister(&mut SourceFd(fd), token, interests)),
+            };
+            if let Err(e) = result {
+                self.registered = current;
+                return Err(e.into());
+            }
+        }
+        self.registered = current;
+        Ok(timeout)
+    }
+
+    /// Marks the file descriptors for which `events` reports readiness as ready, and dispatches
+    /// the mainloop’s events.
+    ///
+    /// This must be called after each poll following [`prepare`](#method.prepare), even if no
+    /// events were received, such that timer and deferred events are dispatched. Events for
+    /// tokens not belonging to this source are ignored.
+    ///
+    /// On success returns the number of sources dispatched.
+    pub fn dispatch(&mut self, mainloop: &mut Mainloop, events: &Events) -> Result<u32, Error> {
+        for p in self.fds.iter_mut() {
+            p.revents = 0;
+        }
+        for event in events.iter() {
+            let fd = match self.token_fd(event.token()) {
+                Some(fd) => fd,
+                None => continue,
+            };
+            let mut revents = 0;
+            if event.is_readable() {
+                revents |= libc::POLLIN;
+            }
+            if event.is_priority() {
+                revents |= libc::POLLPRI;
+            }
+            if event.is_writable() {
+                revents |= libc::POLLOUT;
+            }
+            if event.is_error() {
+                revents |= libc::POLLERR;
+            }
+            if event.is_read_closed() || event.is_write_closed() {
+                revents |= libc::POLLHUP;
+            }
+            for p in self.fds.iter_mut().filter(|p| p.fd == fd) {
+                p.revents |= revents & (p.events | libc::POLLERR | libc::POLLHUP);
+            }
+        }
+        Ok(mainloop.apply_and_dispatch(&self.fds)?)
+    }
+
+    /// Deregisters all file descriptors currently registered with `registry`.
+    ///
+    /// This should be used if the mainloop is to no longer be driven by the poll. Failure is
+    /// ignored (as may happen if a file descriptor has since been closed).
+    pub fn deregister(&mut self, registry: &Registry) {
+        for fd in &self.registered {
+            let _ = registry.deregister(&mut SourceFd(fd));
+        }
+        self.registered.clear();
+        self.fds.clear();
+    }
+
+    /// Checks whether the token belongs to one of the file descriptors currently registered.
+    #[inline]
+    pub fn owns(&self, token: Token) -> bool {
+        self.token_fd(token).is_some()
+    }
+
+    /// Gets the set of file descriptors handed out by the most recent prepare step, which is empty
+    /// before the first.
+    #[inline]
+    pub fn pollfds(&self) -> &[pollfd] {
+        &self.fds
+    }
+
+    fn token_fd(&self, token: Token) -> Option<RawFd> {
+        match token.0.checked_sub(self.base.0) {
+            Some(i) => self.registered.get(i).cloned(),
+            None => None,
+        }
+    }
+}
+
+/// Converts `poll()` event flags to the equivalent `mio` interest, if any.
+fn interest(events: c_short) -> Option<Interest> {
+    let readable = events & (libc::POLLIN | libc::POLLPRI) != 0;
+    let writable = events & libc::POLLOUT != 0;
+    match (readable, writable) {
+        (true, true) => Some(Interest::READABLE | Interest::WRITABLE),
+        (true, false) => Some(Interest::READABLE),
+        (false, true) => Some(Interest::WRITABLE),
+        (false, false) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::os::raw::c_void;
+    use std::rc::Rc;
+    use mio::Poll;
+    use crate::mainloop::api::Mainloop as MainloopTrait;
+    use crate::mainloop::events::io::flags as io_flags;
+
+    #[test]
+    fn prepare_and_dispatch() {
+        let mut mainloop = Mainloop::new().unwrap();
+        let mut poll = Poll::new().unwrap();
+        let mut events = Events::with_capacity(16);
+        let mut source = MioSource::new(Token(100));
+        assert!(source.pollfds().is_empty());
+        assert!(!source.owns(Token(100)));
+
+        let mut pipe_fds = [0i32; 2];
+        assert_eq!(unsafe { libc::pipe(pipe_fds.as_mut_ptr()) }, 0);
+
+        let fired = Rc::new(Cell::new(false));
+        let fired_ref = Rc::clone(&fired);
+        let _io = mainloop.new_io_event(pipe_fds[0], io_flags::INPUT,
+            Box::new(move |_, _, _| fired_ref.set(true))).unwrap();
+
+        // Nothing is ready yet, so the poll times out, and nothing is fired
+        let timeout = source.prepare(&mut mainloop, poll.registry(), Some(5)).unwrap();
+        assert!(timeout.unwrap() <= Duration::from_millis(5));
+        let entry = source.pollfds().iter().find(|p| p.fd == pipe_fds[0]).unwrap();
+        assert_eq!(entry.events & libc::POLLIN, libc::POLLIN);
+        poll.poll(&mut events, Some(Duration::from_millis(0))).unwrap();
+        assert!(source.dispatch(&mut mainloop, &events).is_ok());
+        assert!(!fired.get());
+
+        // Once readable, the event for the pipe is received and dispatched
+        assert_eq!(unsafe { libc::write(pipe_fds[1], [1u8].as_ptr() as *const c_void, 1) }, 1);
+        let timeout = source.prepare(&mut mainloop, poll.registry(), Some(5)).unwrap();
+        poll.poll(&mut events, timeout).unwrap();
+        assert!(events.iter().any(|e| source.owns(e.token())));
+        assert!(source.dispatch(&mut mainloop, &events).unwrap() >= 1);
+        assert!(fired.get());
+
+        // The pipe was not drained, so it is reported again, despite being edge triggered
+        fired.set(false);
+        let timeout = source.prepare(&mut mainloop, poll.registry(), Some(5)).unwrap();
+        poll.poll(&mut events, timeout).unwrap();
+        assert!(source.dispatch(&mut mainloop, &events).unwrap() >= 1);
+        assert!(fired.get());
+
+        source.deregister(poll.registry());
+        assert!(!source.owns(Token(100)));
+
+        unsafe {
+            libc::close(pipe_fds[0]);
+            libc::close(pipe_fds[1]);
+        }
+    }
+
+    #[test]
+    fn prepare_and_dispatch_deferred() {
+        let mut mainloop = Mainloop::new().unwrap();
+        let mut poll = Poll::new().unwrap();
+        let mut events = Events::with_capacity(16);
+        let mut source = MioSource::new(Token(100));
+
+        let count = Rc::new(Cell::new(0));
+        let count_ref = Rc::clone(&count);
+        let _defer = mainloop.new_deferred_event(Box::new(move |mut e| {
+            count_ref.set(count_ref.get() + 1);
+            if count_ref.get() == 2 {
+                e.disable();
+            }
+        })).unwrap();
+
+        // Whilst the deferred event is enabled, the mainloop skips its poll step, handing out no
+        // file descriptors, yet still dispatches
+        for expected in 1..3 {
+            let timeout = source.prepare(&mut mainloop, poll.registry(), None).unwrap();
+            assert_eq!(timeout, Some(Duration::from_millis(0)));
+            assert!(source.pollfds().is_empty());
+            poll.poll(&mut events, timeout).unwrap();
+            assert_eq!(source.dispatch(&mut mainloop, &events).unwrap(), 1);
+            assert_eq!(count.get(), expected);
+        }
+
+        // Once disabled, the mainloop's own file descriptors are registered again
+        let timeout = source.prepare(&mut mainloop, poll.registry(), Some(0)).unwrap();
+        assert!(!source.pollfds().is_empty());
+        assert!(source.owns(Token(100)));
+        poll.poll(&mut events, timeout).unwrap();
+        assert!(source.dispatch(&mut mainloop, &events).is_ok());
+        assert_eq!(count.get(), 2);
+
+        source.deregister(poll.registry());
+    }
+}
@@ -93,7 +93,11 @@
 //! [`in_thread`]: threaded/struct.Mainloop.html#method.in_thread
 
 pub mod api;
+#[cfg(any(feature = "blocking", feature = "dox"))]
+pub mod blocking;
 pub mod events;
+#[cfg(all(feature = "mio", unix))]
+pub mod mio_source;
 pub mod signal;
 pub mod standard;
 pub mod threaded;
@@ -43,6 +43,9 @@
 //! When using the main loop, the application can either execute each iteration, one at a time,
 //! using [`Mainloop::iterate`], or let the library iterate automatically using [`Mainloop::run`].
 //!
+//! To integrate with an external event loop, which is to perform the poll step itself, use
+//! [`Mainloop::prepare_ext`] and [`Mainloop::apply_and_dispatch`].
+//!
 //! # Threads
 //!
 //! The main loop functions are designed to be thread safe, but the objects are not. What this means
@@ -64,7 +67,6 @@
 //! use pulse::stream::Stream;
 //! use pulse::proplist::Proplist;
 //! use pulse::mainloop::standard::IterateResult;
-//! use pulse::def::Retval;
 //!
 //! fn main() {
 //!     let spec = pulse::sample::Spec {
@@ -87,7 +89,7 @@
 //!         &proplist
 //!         ).expect("Failed to create new context")));
 //!
-//!     context.borrow_mut().connect(None, pulse::context::flags::NOFLAGS, None)
+//!     context.borrow_mut().connect(None, pulse::context::Flags::NOFLAGS, None)
 //!         .expect("Failed to connect context");
 //!
 //!     // Wait for context to be ready
@@ -100,14 +102,13 @@
 //!             },
 //!             IterateResult::Success(_) => {},
 //!         }
-//!         match context.borrow().get_state() {
-//!             pulse::context::State::Ready => { break; },
-//!             pulse::context::State::Failed |
-//!             pulse::context::State::Terminated => {
-//!                 eprintln!("Context state failed/terminated, quitting...");
-//!                 return;
-//!             },
-//!             _ => {},
+//!         let state = context.borrow().get_state();
+//!         if state.is_ready() {
+//!             break;
+//!         }
+//!         if !state.is_good() {
+//!             eprintln!("Context state failed/terminated, quitting...");
+//!             return;
 //!         }
 //!     }
 //!
@@ -118,7 +119,7 @@
 //!         None
 //!         ).expect("Failed to create new stream")));
 //!
-//!     stream.borrow_mut().connect_playback(None, None, pulse::stream::flags::START_CORKED,
+//!     stream.borrow_mut().connect_playback(None, None, pulse::stream::Flags::START_CORKED,
 //!         None, None).expect("Failed to connect playback");
 //!
 //!     // Wait for stream to be ready
@@ -131,14 +132,13 @@
 //!             },
 //!             IterateResult::Success(_) => {},
 //!         }
-//!         match stream.borrow().get_state() {
-//!             pulse::stream::State::Ready => { break; },
-//!             pulse::stream::State::Failed |
-//!             pulse::stream::State::Terminated => {
-//!                 eprintln!("Stream state failed/terminated, quitting...");
-//!                 return;
-//!             },
-//!             _ => {},
+//!         let state = stream.borrow().get_state();
+//!         if state.is_ready() {
+//!             break;
+//!         }
+//!         if !state.is_good() {
+//!             eprintln!("Stream state failed/terminated, quitting...");
+//!             return;
 //!         }
 //!     }
 //!
@@ -164,7 +164,7 @@
 //!         // Wait for our data to be played
 //!         let _o = {
 //!             let drain_state_ref = Rc::clone(&drained);
-//!             stream.borrow_mut().drain(Some(Box::new(move |_success: bool| {
+//!             stream.borrow_mut().drain(Some(Box::new(move |_result| {
 //!                 drain_state_ref.store(true, atomic::Ordering::Relaxed);
 //!             })))
 //!         };
@@ -188,9 +188,9 @@
 //! #       }
 //!     }
 //!
-//!     // Clean shutdown
-//!     mainloop.borrow_mut().quit(Retval(0)); // uncertain whether this is necessary
+//!     // Clean shutdown, letting any outstanding operations complete before disconnecting
 //!     stream.borrow_mut().disconnect().unwrap();
+//!     let _ = context.borrow_mut().shutdown(&mut mainloop.borrow_mut(), 100);
 //! }
 //! ```
 //!
@@ -199,17 +199,20 @@
 //! [`Mainloop::new`]: struct.Mainloop.html#method.new
 //! [`Mainloop::get_api`]: struct.Mainloop.html#method.get_api
 //! [`Mainloop::iterate`]: struct.Mainloop.html#method.iterate
+//! [`Mainloop::prepare_ext`]: struct.Mainloop.html#method.prepare_ext
+//! [`Mainloop::apply_and_dispatch`]: struct.Mainloop.html#method.apply_and_dispatch
 //! [`Mainloop::run`]: struct.Mainloop.html#method.run
 
 use std::os::raw::{c_ulong, c_void};
 use std::rc::Rc;
 use std::ptr::null_mut;
+use std::time::Duration;
 #[cfg(not(windows))]
 use libc::pollfd;
 #[cfg(windows)]
 use winapi::um::winsock2::WSAPOLLFD as pollfd;
 use crate::def;
-use crate::error::PAErr;
+use crate::error::{PAErr, Code};
 use crate::mainloop::api::{MainloopInternalType, MainloopInner, MainloopApi, Mainloop as MainloopTrait};
 use crate::mainloop::signal::MainloopSignals;
 
@@ -221,6 +224,8 @@ impl MainloopInternalType for MainloopInternal {}
 pub type PollFn = extern "C" fn(ufds: *mut pollfd, nfds: c_ulong, timeout: i32,
     userdata: *mut c_void) -> i32;
 
+type PollCb = Box<dyn FnMut(&mut [pollfd], Option<i32>) -> i32 + 'static>;
+
 /// Return type for [`Mainloop::iterate`](struct.Mainloop.html#method.iterate).
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum IterateResult {
@@ -268,9 +273,40 @@ impl IterateResult {
 /// the inner ref counted mainloop object to them. Giving this to events serves two purposes,
 /// firstly because they need the API pointer, secondly, it ensures that event objects do not
 /// outlive the mainloop object.
+///
+/// Note, this is neither `Send` nor `Sync`, since it holds an `Rc`; it must only ever be used from
+/// the thread that created it.
 pub struct Mainloop {
     /// The ref-counted inner data.
     pub _inner: Rc<MainloopInner<MainloopInternal>>,
+    /// Poll callback, and snapshot of the poll parameters from the last poll step.
+    poll_state: Box<PollState>,
+    /// Poll step handed out by `prepare_ext`, awaiting results via `apply_and_dispatch`.
+    ext_poll: Box<ExtPollState>,
+}
+
+/// State of the poll function installed by this binding.
+///
+/// This wraps that set with [`Mainloop::set_poll_callback`], or the system `poll()` by default,
+/// recording a snapshot of the parameters given to it.
+#[derive(Default)]
+struct PollState {
+    callback: Option<PollCb>,
+    fds: Vec<pollfd>,
+    timeout: Option<i32>,
+}
+
+/// State of a poll step handed out to an external event loop.
+#[derive(Default)]
+struct ExtPollState {
+    /// The mainloop’s own file descriptor array, along with its length, as given to the poll
+    /// function. This remains valid until the next prepare step. Should the library have skipped
+    /// the poll step (as it does whilst any deferred events are enabled), this is an empty array.
+    pending: Option<(*mut pollfd, usize)>,
+    /// Copy of the array for handing out.
+    fds: Vec<pollfd>,
+    /// The timeout given to the poll function.
+    timeout: Option<i32>,
 }
 
 impl MainloopTrait for Mainloop {
@@ -301,18 +337,31 @@ impl Mainloop {
         }
         let api_ptr = unsafe { capi::pa_mainloop_get_api(ptr) };
         assert!(!api_ptr.is_null());
-        Some(
-            Self {
-                _inner: Rc::new(
-                    MainloopInner::<MainloopInternal> {
-                        ptr: ptr,
-                        api: unsafe { std::mem::transmute(api_ptr) },
-                        dropfn: MainloopInner::<MainloopInternal>::drop_actual,
-                        supports_rtclock: true,
-                    }
-                ),
-            }
-        )
+        let mut mainloop = Self {
+            _inner: Rc::new(
+                MainloopInner::<MainloopInternal> {
+                    ptr: ptr,
+                    api: MainloopApi::from_capi_ptr(api_ptr),
+                    dropfn: MainloopInner::<MainloopInternal>::drop_actual,
+                    supports_rtclock: true,
+                }
+            ),
+            poll_state: Default::default(),
+            ext_poll: Default::default(),
+        };
+        mainloop.install_poll_proxy();
+        Some(mainloop)
+    }
+
+    /// Installs the poll function wrapping the poll callback (or the system `poll()`).
+    ///
+    /// The state is boxed, so the pointer remains valid if we are moved.
+    fn install_poll_proxy(&mut self) {
+        let state_ptr: *mut PollState = &mut *self.poll_state;
+        unsafe {
+            capi::pa_mainloop_set_poll_func((*self._inner).ptr, Some(poll_proxy),
+                state_ptr as *mut c_void);
+        }
     }
 
     /// Prepares for a single iteration of the main loop.
@@ -323,6 +372,8 @@ impl Mainloop {
     /// behaviour. Only positive values should be provided, negative values will have the same
     /// effect as `None`.
     pub fn prepare(&mut self, timeout: Option<i32>) -> Result<(), PAErr> {
+        // The library may rebuild its file descriptor array, invalidating any handed out
+        self.ext_poll.pending = None;
         let t = timeout.unwrap_or(-1);
         match unsafe { capi::pa_mainloop_prepare((*self._inner).ptr, t) } {
             0 => Ok(()),
@@ -368,6 +419,7 @@ impl Mainloop {
     /// * If exit was requested, returns `IterateResult::Quit` containing quit’s retval.
     /// * On error, returns `IterateResult::Err` containing error value.
     pub fn iterate(&mut self, block: bool) -> IterateResult {
+        self.ext_poll.pending = None;
         let mut retval: i32 = 0;
         match unsafe { capi::pa_mainloop_iterate((*self._inner).ptr, block as i32, &mut retval) } {
             r if r >= 0 => IterateResult::Success(r as u32),
@@ -379,9 +431,12 @@ impl Mainloop {
     /// Runs unlimited iterations of the main loop object until the main loop’s
     /// [`quit`](#method.quit) routine is called.
     ///
-    /// On success, returns `Ok` containing quit’s return value. On error returns `Err` containing a
-    /// tuple of the error value and quit’s return value.
+    /// On success, i.e. once [`quit`](#method.quit) has been called (which may have happened
+    /// before this function was called), returns `Ok` containing quit’s return value. On error
+    /// (e.g. a failed poll), returns `Err` containing a tuple of the error value and quit’s return
+    /// value.
     pub fn run(&mut self) -> Result<def::Retval, (PAErr, def::Retval)> {
+        self.ext_poll.pending = None;
         let mut retval: i32 = 0;
         match unsafe { capi::pa_mainloop_run((*self._inner).ptr, &mut retval) } {
             r if r >= 0 => Ok(def::Retval(retval)),
@@ -421,4 +476,414 @@ impl Mainloop {
     pub fn set_poll_func(&mut self, poll_cb: (PollFn, *mut c_void)) {
         unsafe { capi::pa_mainloop_set_poll_func((*self._inner).ptr, Some(poll_cb.0), poll_cb.1); }
     }
+
+    /// Changes the poll() implementation to the given closure, or restores the default if `None`.
+    ///
+    /// This is a safe alternative to [`set_poll_func`], and is the hook needed for integrating the
+    /// mainloop with an external event loop (such as one built upon `mio`). Having called
+    /// [`prepare`], a call to [`poll`] will execute the closure, giving it the mainloop’s current
+    /// set of file descriptors, along with the maximum time to wait in milliseconds (`None` meaning
+    /// block indefinitely). The closure should wait for readiness of any of those file descriptors
+    /// (e.g. by registering them with the external loop and polling it), fill in the `revents`
+    /// attribute of each, and return what `poll()` would, i.e. the number of ready descriptors,
+    /// zero on timeout, or a negative value on error. A subsequent call to [`dispatch`] will then
+    /// dispatch the events for whichever descriptors were marked as ready.
+    ///
+    /// Note that the set of file descriptors may change between iterations, and thus should be
+    /// re-examined on every call.
+    ///
+    /// [`set_poll_func`]: #method.set_poll_func
+    /// [`prepare`]: #method.prepare
+    /// [`poll`]: #method.poll
+    /// [`dispatch`]: #method.dispatch
+    pub fn set_poll_callback(&mut self,
+        callback: Option<Box<dyn FnMut(&mut [pollfd], Option<i32>) -> i32 + 'static>>)
+    {
+        self.poll_state.callback = callback;
+        // In case replaced with `set_poll_func`
+        self.install_poll_proxy();
+    }
+
+    /// Gets the set of file descriptors the mainloop polled in its most recent poll step.
+    ///
+    /// Note that the underlying library only provides this set to the poll function, which is run
+    /// by [`poll`] (or [`iterate`] or [`run`]), so this is a snapshot taken from within that,
+    /// taken after polling (and thus including the `revents` filled in), whether by the system
+    /// `poll()` or the closure set with [`set_poll_callback`], or handed out by [`prepare_ext`].
+    /// It is therefore empty until a poll step has been executed. The set is rebuilt by the
+    /// library in [`prepare`] whenever IO events change.
+    ///
+    /// No snapshot is taken while a poll function set with [`set_poll_func`] is in use.
+    ///
+    /// [`poll`]: #method.poll
+    /// [`iterate`]: #method.iterate
+    /// [`run`]: #method.run
+    /// [`prepare`]: #method.prepare
+    /// [`prepare_ext`]: #method.prepare_ext
+    /// [`set_poll_func`]: #method.set_poll_func
+    /// [`set_poll_callback`]: #method.set_poll_callback
+    #[inline]
+    pub fn get_pollfds(&self) -> &[pollfd] {
+        &self.poll_state.fds
+    }
+
+    /// Gets the timeout given to the most recent poll step, or `None` if it was to block
+    /// indefinitely (or no poll step has yet been executed).
+    ///
+    /// This is the time until the next timer event is due (or the timeout given to [`prepare`], if
+    /// shorter). As with [`get_pollfds`], it is a snapshot taken from within the poll step.
+    ///
+    /// [`prepare`]: #method.prepare
+    /// [`get_pollfds`]: #method.get_pollfds
+    #[inline]
+    pub fn get_next_timeout(&self) -> Option<Duration> {
+        self.poll_state.timeout.map(|t| Duration::from_millis(t as u64))
+    }
+
+    /// Prepares for an iteration of the main loop to be polled by an external event loop.
+    ///
+    /// This is an alternative to [`set_poll_callback`], for integrating the mainloop with an
+    /// external event loop (such as one built upon `mio`, `calloop` or `tokio`), where the poll
+    /// step cannot be performed from within a closure. It runs the prepare and poll steps, with the
+    /// latter handing out the mainloop’s current set of file descriptors, along with the maximum
+    /// time to wait (`None` meaning block indefinitely), which are returned, rather than actually
+    /// polling.
+    ///
+    /// The caller should then wait for readiness of any of those file descriptors, or until the
+    /// timeout expires, fill in the `revents` attribute of each, and pass them back to
+    /// [`apply_and_dispatch`]. Note that the set of file descriptors may change between
+    /// iterations, and thus should be re-examined on every call.
+    ///
+    /// Whilst any deferred events are enabled, the library skips the poll step, since they are to
+    /// be dispatched straight away. In this case an empty set is returned, with a zero timeout, and
+    /// [`apply_and_dispatch`] is to be given an empty set.
+    ///
+    /// `timeout` is as for [`prepare`].
+    ///
+    /// Note that any poll function set with [`set_poll_func`] is replaced by the default upon
+    /// return (one set with [`set_poll_callback`] is kept).
+    ///
+    /// [`apply_and_dispatch`]: #method.apply_and_dispatch
+    /// [`prepare`]: #method.prepare
+    /// [`set_poll_func`]: #method.set_poll_func
+    /// [`set_poll_callback`]: #method.set_poll_callback
+    pub fn prepare_ext(&mut self, timeout: Option<i32>)
+        -> Result<(Vec<pollfd>, Option<Duration>), PAErr>
+    {
+        self.prepare(timeout)?;
+
+        // Clear the results of any previous iteration, since the poll function is not called at
+        // all should the library skip the poll step
+        self.ext_poll.fds.clear();
+        self.ext_poll.timeout = None;
+
+        let ml_ptr = (*self._inner).ptr;
+        let state_ptr: *mut ExtPollState = &mut *self.ext_poll;
+        unsafe {
+            capi::pa_mainloop_set_poll_func(ml_ptr, Some(ext_poll_proxy), state_ptr as *mut c_void);
+        }
+        let result = self.poll();
+
+        // Restore the poll function wrapping the poll callback (or the default)
+        self.install_poll_proxy();
+
+        result?;
+
+        if self.ext_poll.pending.is_none() {
+            // The poll step was skipped, thus there is nothing to wait for before dispatching
+            self.ext_poll.pending = Some((null_mut::<pollfd>(), 0));
+            self.ext_poll.timeout = Some(0);
+        }
+
+        let state = &*self.ext_poll;
+        self.poll_state.fds.clear();
+        self.poll_state.fds.extend_from_slice(&state.fds);
+        self.poll_state.timeout = state.timeout;
+        Ok((state.fds.clone(), state.timeout.map(|t| Duration::from_millis(t as u64))))
+    }
+
+    /// Applies the results of polling the file descriptors handed out by [`prepare_ext`], and
+    /// dispatches the events.
+    ///
+    /// `revents` must be the set of file descriptors returned by [`prepare_ext`], in the same
+    /// order, with the `revents` attribute of each filled in. Only that attribute is applied.
+    ///
+    /// On success returns the number of sources dispatched. Returns a `BadState` error if there is
+    /// no poll step from [`prepare_ext`] pending, or an `Invalid` error if `revents` does not match
+    /// the set handed out (which is empty should the poll step have been skipped), in which case
+    /// the poll step remains pending.
+    ///
+    /// [`prepare_ext`]: #method.prepare_ext
+    pub fn apply_and_dispatch(&mut self, revents: &[pollfd]) -> Result<u32, PAErr> {
+        let (ufds, nfds) = self.ext_poll.pending.ok_or(PAErr::from(Code::BadState))?;
+        let fds = match nfds {
+            0 => &mut [],
+            n => unsafe { std::slice::from_raw_parts_mut(ufds, n) },
+        };
+        if revents.len() != fds.len() || fds.iter().zip(revents).any(|(a, b)| a.fd != b.fd) {
+            return Err(PAErr::from(Code::Invalid));
+        }
+        for (fd, result) in fds.iter_mut().zip(revents) {
+            fd.revents = result.revents;
+        }
+        self.ext_poll.pending = None;
+        self.dispatch()
+    }
+}
+
+/// Proxy for the poll step handed out by `prepare_ext`.
+///
+/// Rather than polling, this records the parameters for handing out. Note that the number of
+/// descriptors is returned, since the library only dispatches IO events should this be positive;
+/// it checks the `revents` attribute of each to find those that are ready, which will have been
+/// filled in by the time the dispatch step is run.
+extern "C"
+fn ext_poll_proxy(ufds: *mut pollfd, nfds: c_ulong, timeout: i32, userdata: *mut c_void) -> i32 {
+    let state = unsafe { &mut *(userdata as *mut ExtPollState) };
+    let fds = match nfds {
+        0 => &[][..],
+        n => unsafe { std::slice::from_raw_parts(ufds, n as usize) },
+    };
+    state.fds.clear();
+    state.fds.extend(fds.iter().map(|p| pollfd { revents: 0, ..*p }));
+    state.timeout = match timeout { t if t < 0 => None, t => Some(t) };
+    state.pending = Some((ufds, nfds as usize));
+    nfds as i32
+}
+
+/// Proxy for the poll step.
+///
+/// This runs the poll callback, if one is set, otherwise the system `poll()`, and then records a
+/// snapshot of the parameters for `get_pollfds` and `get_next_timeout`.
+extern "C"
+fn poll_proxy(ufds: *mut pollfd, nfds: c_ulong, timeout: i32, userdata: *mut c_void) -> i32 {
+    let ret = std::panic::catch_unwind(|| {
+        let state = unsafe { &mut *(userdata as *mut PollState) };
+        let fds = match nfds {
+            0 => &mut [],
+            n => unsafe { std::slice::from_raw_parts_mut(ufds, n as usize) },
+        };
+        let timeout_actual = match timeout { t if t < 0 => None, t => Some(t) };
+        let ret = match state.callback {
+            Some(ref mut callback) => (callback)(fds, timeout_actual),
+            None => sys_poll(fds, timeout),
+        };
+        state.fds.clear();
+        state.fds.extend_from_slice(fds);
+        state.timeout = timeout_actual;
+        ret
+    });
+    ret.unwrap_or(-1)
+}
+
+/// Polls with the system `poll()`, as the library does in the absence of a poll function.
+#[cfg(not(windows))]
+#[inline]
+fn sys_poll(fds: &mut [pollfd], timeout: i32) -> i32 {
+    unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout) }
+}
+
+/// Polls with `WSAPoll()`, the system equivalent of `poll()`.
+#[cfg(windows)]
+#[inline]
+fn sys_poll(fds: &mut [pollfd], timeout: i32) -> i32 {
+    unsafe { winapi::um::winsock2::WSAPoll(fds.as_mut_ptr(), fds.len() as u32, timeout) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn poll_callback() {
+        let mut mainloop = Mainloop::new().unwrap();
+
+        let seen_fds = Rc::new(Cell::new(None));
+        let seen_fds_ref = Rc::clone(&seen_fds);
+        mainloop.set_poll_callback(Some(Box::new(move |fds, timeout| {
+            seen_fds_ref.set(Some((fds.len(), timeout)));
+            for fd in fds.iter_mut() {
+                fd.revents = 0;
+            }
+            0
+        })));
+
+        mainloop.prepare(Some(0)).unwrap();
+        assert_eq!(mainloop.poll(), Ok(0));
+        assert!(mainloop.dispatch().is_ok());
+
+        // There is always at least the mainloop’s own wakeup pipe
+        let (nfds, timeout) = seen_fds.get().unwrap();
+        assert!(nfds >= 1);
+        assert_eq!(timeout, Some(0));
+
+        // Restore the default implementation
+        mainloop.set_poll_callback(None);
+        mainloop.prepare(Some(0)).unwrap();
+        assert!(mainloop.poll().is_ok());
+        assert!(mainloop.dispatch().is_ok());
+    }
+
+    #[test]
+    fn run_quit() {
+        let mut mainloop = Mainloop::new().unwrap();
+        mainloop.quit(def::Retval(7));
+        assert_eq!(mainloop.run(), Ok(def::Retval(7)));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn run_poll_error() {
+        let mut mainloop = Mainloop::new().unwrap();
+        // A poll implementation which always fails (for a reason other than being interrupted)
+        mainloop.set_poll_callback(Some(Box::new(|_, _| {
+            unsafe { *libc::__errno_location() = libc::EBADF; }
+            -1
+        })));
+        let result = mainloop.run();
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().1, def::Retval(0));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn pollfds_snapshot() {
+        use crate::mainloop::events::io::flags as io_flags;
+
+        let mut mainloop = Mainloop::new().unwrap();
+        assert!(mainloop.get_pollfds().is_empty());
+        assert_eq!(mainloop.get_next_timeout(), None);
+
+        let mut pipe_fds = [0i32; 2];
+        assert_eq!(unsafe { libc::pipe(pipe_fds.as_mut_ptr()) }, 0);
+
+        let _io = mainloop.new_io_event(pipe_fds[0], io_flags::INPUT,
+            Box::new(|_, _, _| {})).unwrap();
+
+        // With the default poll implementation
+        mainloop.prepare(Some(5)).unwrap();
+        assert_eq!(mainloop.poll(), Ok(0));
+        assert!(mainloop.dispatch().is_ok());
+
+        let fds = mainloop.get_pollfds();
+        let entry = fds.iter().find(|p| p.fd == pipe_fds[0]).unwrap();
+        assert_eq!(entry.events & libc::POLLIN, libc::POLLIN);
+        assert_eq!(entry.revents, 0);
+        assert_eq!(mainloop.get_next_timeout(), Some(Duration::from_millis(5)));
+
+        // Readiness as found by the system `poll()` is included
+        assert_eq!(unsafe { libc::write(pipe_fds[1], [1u8].as_ptr() as *const c_void, 1) }, 1);
+        assert!(mainloop.iterate(false).is_success());
+        let entry = mainloop.get_pollfds().iter().find(|p| p.fd == pipe_fds[0]).unwrap();
+        assert_eq!(entry.revents & libc::POLLIN, libc::POLLIN);
+        assert_eq!(mainloop.get_next_timeout(), Some(Duration::from_millis(0)));
+
+        // And with a poll callback
+        mainloop.set_poll_callback(Some(Box::new(|_, _| 0)));
+        mainloop.prepare(Some(7)).unwrap();
+        assert_eq!(mainloop.poll(), Ok(0));
+        assert!(mainloop.dispatch().is_ok());
+        assert!(mainloop.get_pollfds().iter().any(|p| p.fd == pipe_fds[0]));
+        assert_eq!(mainloop.get_next_timeout(), Some(Duration::from_millis(7)));
+
+        unsafe {
+            libc::close(pipe_fds[0]);
+            libc::close(pipe_fds[1]);
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn external_poll() {
+        use std::cell::Cell;
+        use crate::mainloop::events::io::flags as io_flags;
+
+        let mut mainloop = Mainloop::new().unwrap();
+        assert_eq!(mainloop.apply_and_dispatch(&[]), Err(PAErr::from(Code::BadState)));
+
+        let mut pipe_fds = [0i32; 2];
+        assert_eq!(unsafe { libc::pipe(pipe_fds.as_mut_ptr()) }, 0);
+
+        let fired = Rc::new(Cell::new(false));
+        let fired_ref = Rc::clone(&fired);
+        let _io = mainloop.new_io_event(pipe_fds[0], io_flags::INPUT,
+            Box::new(move |_, _, _| fired_ref.set(true))).unwrap();
+
+        assert_eq!(unsafe { libc::write(pipe_fds[1], [1u8].as_ptr() as *const c_void, 1) }, 1);
+
+        let (mut fds, timeout) = mainloop.prepare_ext(Some(5)).unwrap();
+        assert!(timeout.unwrap() <= Duration::from_millis(5));
+        assert!(fds.iter().any(|p| p.fd == pipe_fds[0]));
+
+        // A mismatched set is rejected
+        assert_eq!(mainloop.apply_and_dispatch(&fds[1..]), Err(PAErr::from(Code::Invalid)));
+
+        // Poll externally, then feed the results back
+        let ready = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, 0) };
+        assert!(ready >= 1);
+        assert!(mainloop.apply_and_dispatch(&fds).unwrap() >= 1);
+        assert!(fired.get());
+
+        // The handed out poll step has been consumed
+        assert_eq!(mainloop.apply_and_dispatch(&fds), Err(PAErr::from(Code::BadState)));
+
+        unsafe {
+            libc::close(pipe_fds[0]);
+            libc::close(pipe_fds[1]);
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn external_poll_deferred() {
+        use std::cell::Cell;
+        use crate::mainloop::events::io::flags as io_flags;
+
+        let mut mainloop = Mainloop::new().unwrap();
+        let mut pipe_fds = [0i32; 2];
+        assert_eq!(unsafe { libc::pipe(pipe_fds.as_mut_ptr()) }, 0);
+        let _io = mainloop.new_io_event(pipe_fds[0], io_flags::INPUT, Box::new(|_, _, _| {}))
+            .unwrap();
+
+        // A first iteration leaves a set of file descriptors behind
+        let (fds, _) = mainloop.prepare_ext(Some(0)).unwrap();
+        assert!(fds.iter().any(|p| p.fd == pipe_fds[0]));
+        assert_eq!(mainloop.apply_and_dispatch(&fds), Ok(0));
+
+        // Whilst a deferred event is enabled, the poll step is skipped, yet dispatching proceeds
+        let count = Rc::new(Cell::new(0));
+        let count_ref = Rc::clone(&count);
+        let _defer = mainloop.new_deferred_event(Box::new(move |mut e| {
+            count_ref.set(count_ref.get() + 1);
+            if count_ref.get() == 2 {
+                e.disable();
+            }
+        })).unwrap();
+        for expected in 1..3 {
+            let (fds, timeout) = mainloop.prepare_ext(None).unwrap();
+            assert!(fds.is_empty());
+            assert_eq!(timeout, Some(Duration::from_millis(0)));
+            assert_eq!(mainloop.apply_and_dispatch(&[]), Ok(1));
+            assert_eq!(count.get(), expected);
+        }
+
+        // Once disabled, the file descriptors are handed out again
+        let (fds, _) = mainloop.prepare_ext(Some(0)).unwrap();
+        assert!(fds.iter().any(|p| p.fd == pipe_fds[0]));
+        assert_eq!(mainloop.apply_and_dispatch(&[]), Err(PAErr::from(Code::Invalid)));
+        assert_eq!(mainloop.apply_and_dispatch(&fds), Ok(0));
+        assert_eq!(count.get(), 2);
+
+        unsafe {
+            libc::close(pipe_fds[0]);
+            libc::close(pipe_fds[1]);
+        }
+    }
+
+    #[test]
+    fn not_send_sync() {
+        assert_not_impl!(Mainloop: Send);
+        assert_not_impl!(Mainloop: Sync);
+    }
 }
@@ -108,7 +108,7 @@
 //!     // Drain
 //!     let o = {
 //!         let ml_ref = Rc::clone(&m);
-//!         s.borrow_mut().drain(Some(Box::new(move |_success: bool| {
+//!         s.borrow_mut().drain(Some(Box::new(move |_result| {
 //!             unsafe { (*ml_ref.as_ptr()).signal(false); }
 //!         })))
 //!     };
@@ -153,9 +153,10 @@
 //! use std::sync::atomic::{AtomicBool, Ordering};
 //! use pulse::mainloop::threaded::Mainloop;
 //! use pulse::stream::Stream;
+//! use pulse::error::PAErr;
 //!
-//! // A data structure to capture all our data in (currently just a pointer to a bool)
-//! struct DrainCbData(*mut bool);
+//! // A data structure to capture all our data in (currently just a pointer to the result)
+//! struct DrainCbData(*mut Result<(), PAErr>);
 //!
 //! fn drain_stream(m: Rc<RefCell<Mainloop>>, s: Rc<RefCell<Stream>>) {
 //!     m.borrow_mut().lock();
@@ -171,9 +172,9 @@
 //!         let ml_ref = Rc::clone(&m);
 //!         let guard_ref = Rc::clone(&guard);
 //!         let data_ref = Rc::clone(&data);
-//!         s.borrow_mut().drain(Some(Box::new(move |mut success: bool| {
+//!         s.borrow_mut().drain(Some(Box::new(move |mut result: Result<(), PAErr>| {
 //!             unsafe {
-//!                 *data_ref.as_ptr() = Some(DrainCbData(&mut success));
+//!                 *data_ref.as_ptr() = Some(DrainCbData(&mut result));
 //!                 (*guard_ref.as_ptr()).store(false, Ordering::Release);
 //!                 (*ml_ref.as_ptr()).signal(true);
 //!             }
@@ -184,14 +185,14 @@
 //!     }
 //!
 //!     assert!(!data.borrow().is_none());
-//!     let success = unsafe { *(data.borrow_mut().take().unwrap().0) };
+//!     let result = unsafe { *(data.borrow_mut().take().unwrap().0) };
 //!
 //!     // Allow callback to continue now
 //!     m.borrow_mut().accept();
 //!
-//!     match success {
-//!         false => { println!("Bitter defeat..."); },
-//!         true => { println!("Success!"); },
+//!     match result {
+//!         Err(e) => { println!("Bitter defeat: {}", e); },
+//!         Ok(()) => { println!("Success!"); },
 //!     }
 //!
 //!     m.borrow_mut().unlock();
@@ -199,7 +200,7 @@
 //! ```
 //!
 //! The example is a bit silly as it would have been more simple to just copy the contents of
-//! `success`, but for larger data structures this can be wasteful.
+//! `result`, but for larger data structures this can be wasteful.
 //!
 //! The difference here compared to the basic callback is the value `true` passed to
 //! [`Mainloop::signal`] and the call to [`Mainloop::accept`]. What will happen is that
@@ -266,18 +267,13 @@
 //!         let context_ref = Rc::clone(&context);
 //!         context.borrow_mut().set_state_callback(Some(Box::new(move || {
 //!             let state = unsafe { (*context_ref.as_ptr()).get_state() };
-//!             match state {
-//!                 pulse::context::State::Ready |
-//!                 pulse::context::State::Failed |
-//!                 pulse::context::State::Terminated => {
-//!                     unsafe { (*ml_ref.as_ptr()).signal(false); }
-//!                 },
-//!                 _ => {},
+//!             if state.is_ready() || state.is_terminal() {
+//!                 unsafe { (*ml_ref.as_ptr()).signal(false); }
 //!             }
 //!         })));
 //!     }
 //!
-//!     context.borrow_mut().connect(None, pulse::context::flags::NOFLAGS, None)
+//!     context.borrow_mut().connect(None, pulse::context::Flags::NOFLAGS, None)
 //!         .expect("Failed to connect context");
 //!
 //!     mainloop.borrow_mut().lock();
@@ -285,17 +281,17 @@
 //!
 //!     // Wait for context to be ready
 //!     loop {
-//!         match context.borrow().get_state() {
-//!             pulse::context::State::Ready => { break; },
-//!             pulse::context::State::Failed |
-//!             pulse::context::State::Terminated => {
-//!                 eprintln!("Context state failed/terminated, quitting...");
-//!                 mainloop.borrow_mut().unlock();
-//!                 mainloop.borrow_mut().stop();
-//!                 return;
-//!             },
-//!             _ => { mainloop.borrow_mut().wait(); },
+//!         let state = context.borrow().get_state();
+//!         if state.is_ready() {
+//!             break;
+//!         }
+//!         if !state.is_good() {
+//!             eprintln!("Context state failed/terminated, quitting...");
+//!             mainloop.borrow_mut().unlock();
+//!             mainloop.borrow_mut().stop();
+//!             return;
 //!         }
+//!         mainloop.borrow_mut().wait();
 //!     }
 //!     context.borrow_mut().set_state_callback(None);
 //!
@@ -312,33 +308,28 @@
 //!         let stream_ref = Rc::clone(&stream);
 //!         stream.borrow_mut().set_state_callback(Some(Box::new(move || {
 //!             let state = unsafe { (*stream_ref.as_ptr()).get_state() };
-//!             match state {
-//!                 pulse::stream::State::Ready |
-//!                 pulse::stream::State::Failed |
-//!                 pulse::stream::State::Terminated => {
-//!                     unsafe { (*ml_ref.as_ptr()).signal(false); }
-//!                 },
-//!                 _ => {},
+//!             if state.is_ready() || state.is_terminal() {
+//!                 unsafe { (*ml_ref.as_ptr()).signal(false); }
 //!             }
 //!         })));
 //!     }
 //!
-//!     stream.borrow_mut().connect_playback(None, None, pulse::stream::flags::START_CORKED,
+//!     stream.borrow_mut().connect_playback(None, None, pulse::stream::Flags::START_CORKED,
 //!         None, None).expect("Failed to connect playback");
 //!
 //!     // Wait for stream to be ready
 //!     loop {
-//!         match stream.borrow().get_state() {
-//!             pulse::stream::State::Ready => { break; },
-//!             pulse::stream::State::Failed |
-//!             pulse::stream::State::Terminated => {
-//!                 eprintln!("Stream state failed/terminated, quitting...");
-//!                 mainloop.borrow_mut().unlock();
-//!                 mainloop.borrow_mut().stop();
-//!                 return;
-//!             },
-//!             _ => { mainloop.borrow_mut().wait(); },
+//!         let state = stream.borrow().get_state();
+//!         if state.is_ready() {
+//!             break;
 //!         }
+//!         if !state.is_good() {
+//!             eprintln!("Stream state failed/terminated, quitting...");
+//!             mainloop.borrow_mut().unlock();
+//!             mainloop.borrow_mut().stop();
+//!             return;
+//!         }
+//!         mainloop.borrow_mut().wait();
 //!     }
 //!     stream.borrow_mut().set_state_callback(None);
 //!
@@ -358,7 +349,7 @@
 //!         // Drain
 //!         let o = {
 //!             let ml_ref = Rc::clone(&mainloop);
-//!             stream.borrow_mut().drain(Some(Box::new(move |_success: bool| {
+//!             stream.borrow_mut().drain(Some(Box::new(move |_result| {
 //!                 unsafe { (*ml_ref.as_ptr()).signal(false); }
 //!             })))
 //!         };
@@ -398,11 +389,14 @@
 //! [`Mainloop::accept`]: struct.Mainloop.html#method.accept
 
 use std::rc::Rc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
 #[cfg(any(feature = "pa_v5", feature = "dox"))]
 use std::ffi::CString;
+#[cfg(any(feature = "pa_v13", feature = "dox"))]
+use std::os::raw::c_void;
 use std::ptr::null_mut;
 use crate::def;
-use crate::error::PAErr;
+use crate::error::{Code, PAErr};
 use crate::mainloop::api::{MainloopInternalType, MainloopInner, MainloopApi, Mainloop as MainloopTrait};
 use crate::mainloop::signal::MainloopSignals;
 
@@ -417,9 +411,81 @@ impl MainloopInternalType for MainloopInternal {}
 /// the inner ref counted mainloop object to them. Giving this to events serves two purposes,
 /// firstly because they need the API pointer, secondly, it ensures that event objects do not
 /// outlive the mainloop object.
+///
+/// Note, this is neither `Send` nor `Sync`, since it holds an `Rc`. Use a [`SendableHandle`] to
+/// interact with the event loop from other threads.
+///
+/// [`SendableHandle`]: struct.SendableHandle.html
 pub struct Mainloop {
     /// The ref-counted inner data.
     pub _inner: Rc<MainloopInner<MainloopInternal>>,
+    /// State shared with any sendable handles, invalidated on drop.
+    handle: Arc<HandleShared>,
+}
+
+/// The mainloop pointer shared between a mainloop and its sendable handles.
+///
+/// The pointer is set to null when the mainloop is dropped. Handle operations register themselves
+/// as users whilst calling into the C library, with the mutex released, since some of those calls
+/// block (`pa_threaded_mainloop_lock`) and others must be able to proceed meanwhile (the matching
+/// unlock, or a signal from the event loop thread). Dropping the mainloop waits for any such calls
+/// in progress to finish, thus the pointer can never be freed whilst in use.
+struct HandleShared {
+    state: Mutex<HandleState>,
+    /// Notified whenever the number of users drops to zero.
+    idle: Condvar,
+}
+
+struct HandleState {
+    ptr: *mut MainloopInternal,
+    /// The number of handle operations currently making use of the pointer.
+    users: usize,
+}
+
+impl HandleShared {
+    fn lock_state(&self) -> MutexGuard<'_, HandleState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Registration of a handle operation as a user of the pointer, released upon drop (including upon
+/// unwinding).
+struct HandleUser<'a> {
+    shared: &'a HandleShared,
+}
+
+impl<'a> Drop for HandleUser<'a> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock_state();
+        state.users -= 1;
+        if state.users == 0 {
+            self.shared.idle.notify_all();
+        }
+    }
+}
+
+// The pointer is only ever changed under the mutex, is never freed whilst registered users remain,
+// and the operations performed upon it through a handle are those that the C API documents as safe
+// to call from any thread.
+unsafe impl Send for HandleShared {}
+unsafe impl Sync for HandleShared {}
+
+/// A cheap, cloneable handle to a threaded mainloop that can be sent to other threads.
+///
+/// Only those operations that are safe to call from any thread are exposed. Once the [`Mainloop`]
+/// it was obtained from has been dropped, all operations fail with [`Code::BadState`]. Dropping the
+/// mainloop waits for any handle operations in progress, including a [`lock`] waiting for the lock
+/// to be released, thus the mainloop must not be dropped whilst another thread holds the lock.
+///
+/// [`Mainloop`]: struct.Mainloop.html
+/// [`Code::BadState`]: ../../error/enum.Code.html#variant.BadState
+/// [`lock`]: #method.lock
+#[derive(Clone)]
+pub struct SendableHandle {
+    shared: Arc<HandleShared>,
 }
 
 impl MainloopTrait for Mainloop {
@@ -457,11 +523,15 @@ impl Mainloop {
                 _inner: Rc::new(
                     MainloopInner::<MainloopInternal> {
                         ptr: ptr,
-                        api: unsafe { std::mem::transmute(api_ptr) },
+                        api: MainloopApi::from_capi_ptr(api_ptr),
                         dropfn: MainloopInner::<MainloopInternal>::drop_actual,
                         supports_rtclock: true,
                     }
                 ),
+                handle: Arc::new(HandleShared {
+                    state: Mutex::new(HandleState { ptr: ptr, users: 0 }),
+                    idle: Condvar::new(),
+                }),
             }
         )
     }
@@ -573,4 +643,203 @@ impl Mainloop {
         let c_name = CString::new(name.clone()).unwrap();
         unsafe { capi::pa_threaded_mainloop_set_name((*self._inner).ptr, c_name.as_ptr()); }
     }
+
+    /// Gets a handle that can be sent to other threads, for locking and signalling this mainloop.
+    #[inline]
+    pub fn sendable_handle(&self) -> SendableHandle {
+        SendableHandle { shared: Arc::clone(&self.handle) }
+    }
+}
+
+impl Drop for Mainloop {
+    fn drop(&mut self) {
+        let mut state = self.handle.lock_state();
+        while state.users != 0 {
+            state = match self.handle.idle.wait(state) {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+        }
+        state.ptr = null_mut::<MainloopInternal>();
+    }
+}
+
+impl SendableHandle {
+    /// Runs `f` with the mainloop pointer, provided the mainloop still exists.
+    ///
+    /// The mutex is not held whilst `f` runs, only the registration as a user, which keeps the
+    /// mainloop from being freed.
+    fn with_ptr<F: FnOnce(*mut MainloopInternal)>(&self, f: F) -> Result<(), PAErr> {
+        let ptr = {
+            let mut state = self.shared.lock_state();
+            if state.ptr.is_null() {
+                return Err(PAErr::from(Code::BadState));
+            }
+            state.users += 1;
+            state.ptr
+        };
+        let _user = HandleUser { shared: &self.shared };
+        f(ptr);
+        Ok(())
+    }
+
+    /// Locks the event loop object, as with [`Mainloop::lock`].
+    ///
+    /// Must not be called from within the event loop thread.
+    ///
+    /// [`Mainloop::lock`]: struct.Mainloop.html#method.lock
+    pub fn lock(&self) -> Result<(), PAErr> {
+        self.with_ptr(|ptr| {
+            assert!(unsafe { capi::pa_threaded_mainloop_in_thread(ptr) } == 0,
+                "lock() can not be called from within the event loop thread!");
+            unsafe { capi::pa_threaded_mainloop_lock(ptr); }
+        })
+    }
+
+    /// Unlocks the event loop object, inverse of [`lock`](#method.lock).
+    pub fn unlock(&self) -> Result<(), PAErr> {
+        self.with_ptr(|ptr| unsafe { capi::pa_threaded_mainloop_unlock(ptr); })
+    }
+
+    /// Signals all threads waiting for a signalling event in [`Mainloop::wait`].
+    ///
+    /// Unlike [`Mainloop::signal`], this never waits for the signal to be accepted.
+    ///
+    /// [`Mainloop::wait`]: struct.Mainloop.html#method.wait
+    /// [`Mainloop::signal`]: struct.Mainloop.html#method.signal
+    pub fn signal(&self) -> Result<(), PAErr> {
+        self.with_ptr(|ptr| unsafe { capi::pa_threaded_mainloop_signal(ptr, 0); })
+    }
+
+    /// Runs the given callback once, in the event loop thread, without the lock held.
+    ///
+    /// Available since PA version 13.
+    #[cfg(any(feature = "pa_v13", feature = "dox"))]
+    pub fn once_unlocked(&self, callback: Box<dyn FnOnce() + Send + 'static>) -> Result<(), PAErr>
+    {
+        self.with_ptr(|ptr| {
+            let cb_data = Box::into_raw(Box::new(callback)) as *mut c_void;
+            unsafe { capi::pa_threaded_mainloop_once_unlocked(ptr, once_unlocked_cb_proxy,
+                cb_data); }
+        })
+    }
+}
+
+/// Proxy for the “once unlocked” callback.
+#[cfg(any(feature = "pa_v13", feature = "dox"))]
+extern "C"
+fn once_unlocked_cb_proxy(_: *mut MainloopInternal, userdata: *mut c_void) {
+    let _ = std::panic::catch_unwind(|| {
+        assert!(!userdata.is_null());
+        // Note, destroys closure callback after use - restoring outer box means it gets dropped
+        let callback = unsafe {
+            Box::from_raw(userdata as *mut Box<dyn FnOnce() + Send + 'static>)
+        };
+        (callback)();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn sendable_handle_is_send() {
+        assert_send_sync::<SendableHandle>();
+    }
+
+    #[test]
+    fn sendable_handle_outliving_mainloop() {
+        let handle = {
+            let mainloop = Mainloop::new().unwrap();
+            let handle = mainloop.sendable_handle();
+            assert!(handle.signal().is_ok());
+            handle
+        };
+        let result = std::thread::spawn(move || handle.signal()).join().unwrap();
+        assert_eq!(result, Err(PAErr::from(Code::BadState)));
+    }
+
+    #[test]
+    fn sendable_handle_clone() {
+        let mainloop = Mainloop::new().unwrap();
+        let a = mainloop.sendable_handle();
+        let b = a.clone();
+        assert!(Arc::ptr_eq(&a.shared, &b.shared));
+        drop(mainloop);
+        assert!(a.lock().is_err());
+        assert!(b.unlock().is_err());
+    }
+
+    #[test]
+    fn sendable_handle_lock_contended() {
+        let mainloop = Mainloop::new().unwrap();
+        let a = mainloop.sendable_handle();
+        let b = a.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        // Whilst `b` waits for the lock in the other thread, `a` must still be able to release it
+        a.lock().unwrap();
+        let thread = std::thread::spawn(move || {
+            tx.send(()).unwrap();
+            b.lock().unwrap();
+            b.unlock().unwrap();
+        });
+        rx.recv().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        a.unlock().unwrap();
+        thread.join().unwrap();
+
+        // And both threads can go on taking turns
+        let c = a.clone();
+        let thread = std::thread::spawn(move || {
+            for _ in 0..100 {
+                c.lock().unwrap();
+                c.unlock().unwrap();
+            }
+        });
+        for _ in 0..100 {
+            a.lock().unwrap();
+            a.unlock().unwrap();
+        }
+        thread.join().unwrap();
+        drop(mainloop);
+        assert_eq!(a.lock(), Err(PAErr::from(Code::BadState)));
+    }
+
+    #[test]
+    fn sendable_handle_signal_from_event_loop() {
+        let mut mainloop = Mainloop::new().unwrap();
+        mainloop.start().unwrap();
+        let handle = mainloop.sendable_handle();
+        let waiter = handle.clone();
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+
+        // Signal from the event loop thread, with the lock held, whilst another thread waits for
+        // the lock through a handle
+        mainloop.lock();
+        mainloop.defer_once(Box::new(move || {
+            started_rx.recv().unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            done_tx.send(handle.signal()).unwrap();
+        }));
+        mainloop.unlock();
+        let thread = std::thread::spawn(move || {
+            started_tx.send(()).unwrap();
+            waiter.lock().unwrap();
+            waiter.unlock().unwrap();
+        });
+        assert_eq!(done_rx.recv().unwrap(), Ok(()));
+        thread.join().unwrap();
+        mainloop.stop();
+    }
+
+    #[test]
+    fn not_send_sync() {
+        assert_not_impl!(Mainloop: Send);
+        assert_not_impl!(Mainloop: Sync);
+    }
 }
@@ -23,6 +23,10 @@ pub use capi::pa_operation_state_t as State;
 /// An asynchronous operation object.
 ///
 /// Note: Saves a copy of active multi-use closure callbacks, which it frees on drop.
+///
+/// Note, this is neither `Send` nor `Sync`, since the callbacks it holds need not be `Send`, and
+/// since cancelling it, or setting its state callback, are not safe to do concurrently with the
+/// mainloop.
 pub struct Operation<ClosureProto: ?Sized> {
     /// The actual C object.
     ptr: *mut OperationInternal,
@@ -36,9 +40,6 @@ pub struct Operation<ClosureProto: ?Sized> {
     state_cb: NotifyCb,
 }
 
-unsafe impl<ClosureProto: ?Sized> Send for Operation<ClosureProto> {}
-unsafe impl<ClosureProto: ?Sized> Sync for Operation<ClosureProto> {}
-
 type NotifyCb = callbacks::MultiUseCallback<dyn FnMut(),
     extern "C" fn(*mut OperationInternal, *mut c_void)>;
 
@@ -54,8 +55,8 @@ impl<ClosureProto: ?Sized> Operation<ClosureProto> {
     {
         assert_eq!(false, ptr.is_null());
         let saved_cb_actual = match saved_cb.is_null() {
-            true => Some(saved_cb),
-            false => None,
+            false => Some(saved_cb),
+            true => None,
         };
         Self { ptr: ptr, saved_cb: saved_cb_actual, state_cb: Default::default() }
     }
@@ -70,17 +71,14 @@ impl<ClosureProto: ?Sized> Operation<ClosureProto> {
     /// execution of that callback itself. This should go without saying, since it makes absolutely
     /// no sense to try and do this, but be aware that this is not supported by the C API and
     /// **will** break things.
+    ///
+    /// If the operation was still running, the closure callback associated with it is destroyed
+    /// here. Cancelling an operation that has already finished does nothing more than change its
+    /// state, since its callback will already have been run (and destroyed).
     pub fn cancel(&mut self) {
+        let running = self.get_state() == State::Running;
         unsafe { capi::pa_operation_cancel(self.ptr); }
-        // Release the memory allocated for the closure.
-        // Note, we `take()` here to help avoid issues if this function is mistakenly called more
-        // than once.
-        let callback = self.saved_cb.take();
-        if let Some(ptr) = callback {
-            if !ptr.is_null() {
-                drop(unsafe { Box::from_raw(ptr as *mut Box<ClosureProto>) });
-            }
-        }
+        release_saved_cb(&mut self.saved_cb, running);
     }
 
     /// Gets the current status of the operation.
@@ -112,6 +110,21 @@ impl<ClosureProto: ?Sized> Drop for Operation<ClosureProto> {
     }
 }
 
+/// Releases the memory allocated for an operation’s closure callback, if it is still owned, i.e. if
+/// the operation was still running (once finished, the callback proxy will have destroyed it).
+///
+/// Note, we `take()` here to help avoid issues if cancellation is mistakenly attempted more than
+/// once.
+fn release_saved_cb<ClosureProto: ?Sized>(saved_cb: &mut Option<*mut Box<ClosureProto>>,
+    owned: bool)
+{
+    if let Some(ptr) = saved_cb.take() {
+        if owned && !ptr.is_null() {
+            drop(unsafe { Box::from_raw(ptr) });
+        }
+    }
+}
+
 /// Proxy for notification callbacks.
 ///
 /// Warning: This is for multi-use cases! It does **not** destroy the actual closure callback, which
@@ -123,3 +136,56 @@ fn notify_cb_proxy(_: *mut OperationInternal, userdata: *mut c_void) {
         (callback)();
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use crate::error::PAErr;
+    use std::rc::Rc;
+
+    struct DropFlag(Rc<Cell<bool>>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    fn boxed_closure(dropped: &Rc<Cell<bool>>) -> *mut Box<dyn FnOnce(Result<(), PAErr>)> {
+        let flag = DropFlag(Rc::clone(dropped));
+        let closure: Box<dyn FnOnce(Result<(), PAErr>)> = Box::new(move |_| { let _ = &flag; });
+        Box::into_raw(Box::new(closure))
+    }
+
+    #[test]
+    fn cancel_releases_closure() {
+        // Still running, thus still owned, so released, exactly once
+        let dropped = Rc::new(Cell::new(false));
+        let mut saved = Some(boxed_closure(&dropped));
+        release_saved_cb(&mut saved, true);
+        assert!(dropped.get());
+        assert!(saved.is_none());
+        release_saved_cb(&mut saved, true);
+
+        // Already finished, thus already destroyed by the callback proxy, which we emulate here
+        let dropped = Rc::new(Cell::new(false));
+        let ptr = boxed_closure(&dropped);
+        let mut saved = Some(ptr);
+        release_saved_cb(&mut saved, false);
+        assert!(!dropped.get());
+        assert!(saved.is_none());
+        drop(unsafe { Box::from_raw(ptr) });
+        assert!(dropped.get());
+
+        // No callback
+        let mut saved: Option<*mut Box<dyn FnOnce(Result<(), PAErr>)>> = None;
+        release_saved_cb(&mut saved, true);
+    }
+
+    #[test]
+    fn not_send_sync() {
+        assert_not_impl!(Operation<dyn FnOnce(Result<(), PAErr>)>: Send);
+        assert_not_impl!(Operation<dyn FnOnce(Result<(), PAErr>)>: Sync);
+    }
+}
@@ -14,10 +14,13 @@
 //! Property list constants and functions.
 
 use std::os::raw::{c_char, c_void};
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
 use std::ptr::{null, null_mut};
 use std::marker::PhantomData;
-use crate::error::PAErr;
+use crate::error::{PAErr, Code};
+use crate::xmalloc::PaAllocated;
 
 pub(crate) use capi::pa_proplist as ProplistInternal;
 pub use capi::pa_update_mode_t as UpdateMode;
@@ -129,12 +132,419 @@ pub mod properties {
     /// For PCM formats: the channel map of the stream as returned by
     /// [`channelmap::Map::print`](../../channelmap/struct.Map.html#method.print).
     pub const FORMAT_CHANNEL_MAP: &str = capi::PA_PROP_FORMAT_CHANNEL_MAP;
+
+
+    /* Grouped by subject, as an alternative to the above, e.g. `properties::media::ROLE` */
+
+    /// Properties of media streams, named without the `MEDIA_` prefix.
+    pub mod media {
+        pub use super::MEDIA_NAME as NAME;
+        pub use super::MEDIA_TITLE as TITLE;
+        pub use super::MEDIA_ARTIST as ARTIST;
+        pub use super::MEDIA_COPYRIGHT as COPYRIGHT;
+        pub use super::MEDIA_SOFTWARE as SOFTWARE;
+        pub use super::MEDIA_LANGUAGE as LANGUAGE;
+        pub use super::MEDIA_FILENAME as FILENAME;
+        pub use super::MEDIA_ICON as ICON;
+        pub use super::MEDIA_ICON_NAME as ICON_NAME;
+        pub use super::MEDIA_ROLE as ROLE;
+    }
+
+    /// Properties of filter requests of streams, named without the `FILTER_` prefix.
+    pub mod filter {
+        pub use super::FILTER_WANT as WANT;
+        pub use super::FILTER_APPLY as APPLY;
+        pub use super::FILTER_SUPPRESS as SUPPRESS;
+    }
+
+    /// Properties of event sound streams, named without the `EVENT_` prefix.
+    pub mod event {
+        pub use super::EVENT_ID as ID;
+        pub use super::EVENT_DESCRIPTION as DESCRIPTION;
+        pub use super::EVENT_MOUSE_X as MOUSE_X;
+        pub use super::EVENT_MOUSE_Y as MOUSE_Y;
+        pub use super::EVENT_MOUSE_HPOS as MOUSE_HPOS;
+        pub use super::EVENT_MOUSE_VPOS as MOUSE_VPOS;
+        pub use super::EVENT_MOUSE_BUTTON as MOUSE_BUTTON;
+    }
+
+    /// Properties of the windows of applications, named without the `WINDOW_` prefix.
+    pub mod window {
+        pub use super::WINDOW_NAME as NAME;
+        pub use super::WINDOW_ID as ID;
+        pub use super::WINDOW_ICON as ICON;
+        pub use super::WINDOW_ICON_NAME as ICON_NAME;
+        pub use super::WINDOW_X as X;
+        pub use super::WINDOW_Y as Y;
+        pub use super::WINDOW_WIDTH as WIDTH;
+        pub use super::WINDOW_HEIGHT as HEIGHT;
+        pub use super::WINDOW_HPOS as HPOS;
+        pub use super::WINDOW_VPOS as VPOS;
+        pub use super::WINDOW_DESKTOP as DESKTOP;
+        pub use super::WINDOW_X11_DISPLAY as X11_DISPLAY;
+        pub use super::WINDOW_X11_SCREEN as X11_SCREEN;
+        pub use super::WINDOW_X11_MONITOR as X11_MONITOR;
+        pub use super::WINDOW_X11_XID as X11_XID;
+    }
+
+    /// Properties of applications, named without the `APPLICATION_` prefix.
+    pub mod application {
+        pub use super::APPLICATION_NAME as NAME;
+        pub use super::APPLICATION_ID as ID;
+        pub use super::APPLICATION_VERSION as VERSION;
+        pub use super::APPLICATION_ICON as ICON;
+        pub use super::APPLICATION_ICON_NAME as ICON_NAME;
+        pub use super::APPLICATION_LANGUAGE as LANGUAGE;
+        pub use super::APPLICATION_PROCESS_ID as PROCESS_ID;
+        pub use super::APPLICATION_PROCESS_BINARY as PROCESS_BINARY;
+        pub use super::APPLICATION_PROCESS_USER as PROCESS_USER;
+        pub use super::APPLICATION_PROCESS_HOST as PROCESS_HOST;
+        pub use super::APPLICATION_PROCESS_MACHINE_ID as PROCESS_MACHINE_ID;
+        pub use super::APPLICATION_PROCESS_SESSION_ID as PROCESS_SESSION_ID;
+    }
+
+    /// Properties of devices, named without the `DEVICE_` prefix.
+    pub mod device {
+        pub use super::DEVICE_STRING as STRING;
+        pub use super::DEVICE_API as API;
+        pub use super::DEVICE_DESCRIPTION as DESCRIPTION;
+        pub use super::DEVICE_BUS_PATH as BUS_PATH;
+        pub use super::DEVICE_SERIAL as SERIAL;
+        pub use super::DEVICE_VENDOR_ID as VENDOR_ID;
+        pub use super::DEVICE_VENDOR_NAME as VENDOR_NAME;
+        pub use super::DEVICE_PRODUCT_ID as PRODUCT_ID;
+        pub use super::DEVICE_PRODUCT_NAME as PRODUCT_NAME;
+        pub use super::DEVICE_CLASS as CLASS;
+        pub use super::DEVICE_FORM_FACTOR as FORM_FACTOR;
+        pub use super::DEVICE_BUS as BUS;
+        pub use super::DEVICE_ICON as ICON;
+        pub use super::DEVICE_ICON_NAME as ICON_NAME;
+        pub use super::DEVICE_ACCESS_MODE as ACCESS_MODE;
+        pub use super::DEVICE_MASTER_DEVICE as MASTER_DEVICE;
+        pub use super::DEVICE_BUFFERING_BUFFER_SIZE as BUFFERING_BUFFER_SIZE;
+        pub use super::DEVICE_BUFFERING_FRAGMENT_SIZE as BUFFERING_FRAGMENT_SIZE;
+        pub use super::DEVICE_PROFILE_NAME as PROFILE_NAME;
+        pub use super::DEVICE_INTENDED_ROLES as INTENDED_ROLES;
+        pub use super::DEVICE_PROFILE_DESCRIPTION as PROFILE_DESCRIPTION;
+    }
+
+    /// Properties of modules, named without the `MODULE_` prefix.
+    pub mod module {
+        pub use super::MODULE_AUTHOR as AUTHOR;
+        pub use super::MODULE_DESCRIPTION as DESCRIPTION;
+        pub use super::MODULE_USAGE as USAGE;
+        pub use super::MODULE_VERSION as VERSION;
+    }
+
+    /// Properties of formats, named without the `FORMAT_` prefix.
+    pub mod format {
+        pub use super::FORMAT_SAMPLE_FORMAT as SAMPLE_FORMAT;
+        pub use super::FORMAT_RATE as RATE;
+        pub use super::FORMAT_CHANNELS as CHANNELS;
+        pub use super::FORMAT_CHANNEL_MAP as CHANNEL_MAP;
+    }
+}
+
+/// Well known property list keys, as an alternative to the string constants of the [`properties`]
+/// submodule, such that misspelt keys are caught at compile time.
+///
+/// Use with [`Proplist::set_wellknown`], or convert to the key string with [`as_str`].
+///
+/// [`properties`]: properties/index.html
+/// [`Proplist::set_wellknown`]: struct.Proplist.html#method.set_wellknown
+/// [`as_str`]: #method.as_str
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum WellKnownKey {
+    /// [`properties::MEDIA_NAME`](properties/constant.MEDIA_NAME.html).
+    MediaName,
+    /// [`properties::MEDIA_TITLE`](properties/constant.MEDIA_TITLE.html).
+    MediaTitle,
+    /// [`properties::MEDIA_ARTIST`](properties/constant.MEDIA_ARTIST.html).
+    MediaArtist,
+    /// [`properties::MEDIA_COPYRIGHT`](properties/constant.MEDIA_COPYRIGHT.html).
+    MediaCopyright,
+    /// [`properties::MEDIA_SOFTWARE`](properties/constant.MEDIA_SOFTWARE.html).
+    MediaSoftware,
+    /// [`properties::MEDIA_LANGUAGE`](properties/constant.MEDIA_LANGUAGE.html).
+    MediaLanguage,
+    /// [`properties::MEDIA_FILENAME`](properties/constant.MEDIA_FILENAME.html).
+    MediaFilename,
+    /// [`properties::MEDIA_ICON`](properties/constant.MEDIA_ICON.html).
+    MediaIcon,
+    /// [`properties::MEDIA_ICON_NAME`](properties/constant.MEDIA_ICON_NAME.html).
+    MediaIconName,
+    /// [`properties::MEDIA_ROLE`](properties/constant.MEDIA_ROLE.html).
+    MediaRole,
+    /// [`properties::FILTER_WANT`](properties/constant.FILTER_WANT.html).
+    FilterWant,
+    /// [`properties::EVENT_ID`](properties/constant.EVENT_ID.html).
+    EventId,
+    /// [`properties::EVENT_DESCRIPTION`](properties/constant.EVENT_DESCRIPTION.html).
+    EventDescription,
+    /// [`properties::EVENT_MOUSE_X`](properties/constant.EVENT_MOUSE_X.html).
+    EventMouseX,
+    /// [`properties::EVENT_MOUSE_Y`](properties/constant.EVENT_MOUSE_Y.html).
+    EventMouseY,
+    /// [`properties::EVENT_MOUSE_HPOS`](properties/constant.EVENT_MOUSE_HPOS.html).
+    EventMouseHpos,
+    /// [`properties::EVENT_MOUSE_VPOS`](properties/constant.EVENT_MOUSE_VPOS.html).
+    EventMouseVpos,
+    /// [`properties::EVENT_MOUSE_BUTTON`](properties/constant.EVENT_MOUSE_BUTTON.html).
+    EventMouseButton,
+    /// [`properties::WINDOW_NAME`](properties/constant.WINDOW_NAME.html).
+    WindowName,
+    /// [`properties::WINDOW_ID`](properties/constant.WINDOW_ID.html).
+    WindowId,
+    /// [`properties::WINDOW_ICON`](properties/constant.WINDOW_ICON.html).
+    WindowIcon,
+    /// [`properties::WINDOW_ICON_NAME`](properties/constant.WINDOW_ICON_NAME.html).
+    WindowIconName,
+    /// [`properties::WINDOW_X`](properties/constant.WINDOW_X.html).
+    WindowX,
+    /// [`properties::WINDOW_Y`](properties/constant.WINDOW_Y.html).
+    WindowY,
+    /// [`properties::WINDOW_WIDTH`](properties/constant.WINDOW_WIDTH.html).
+    WindowWidth,
+    /// [`properties::WINDOW_HEIGHT`](properties/constant.WINDOW_HEIGHT.html).
+    WindowHeight,
+    /// [`properties::WINDOW_HPOS`](properties/constant.WINDOW_HPOS.html).
+    WindowHpos,
+    /// [`properties::WINDOW_VPOS`](properties/constant.WINDOW_VPOS.html).
+    WindowVpos,
+    /// [`properties::WINDOW_DESKTOP`](properties/constant.WINDOW_DESKTOP.html).
+    WindowDesktop,
+    /// [`properties::WINDOW_X11_DISPLAY`](properties/constant.WINDOW_X11_DISPLAY.html).
+    WindowX11Display,
+    /// [`properties::WINDOW_X11_SCREEN`](properties/constant.WINDOW_X11_SCREEN.html).
+    WindowX11Screen,
+    /// [`properties::WINDOW_X11_MONITOR`](properties/constant.WINDOW_X11_MONITOR.html).
+    WindowX11Monitor,
+    /// [`properties::WINDOW_X11_XID`](properties/constant.WINDOW_X11_XID.html).
+    WindowX11Xid,
+    /// [`properties::APPLICATION_NAME`](properties/constant.APPLICATION_NAME.html).
+    ApplicationName,
+    /// [`properties::APPLICATION_ID`](properties/constant.APPLICATION_ID.html).
+    ApplicationId,
+    /// [`properties::APPLICATION_VERSION`](properties/constant.APPLICATION_VERSION.html).
+    ApplicationVersion,
+    /// [`properties::APPLICATION_ICON`](properties/constant.APPLICATION_ICON.html).
+    ApplicationIcon,
+    /// [`properties::APPLICATION_ICON_NAME`](properties/constant.APPLICATION_ICON_NAME.html).
+    ApplicationIconName,
+    /// [`properties::APPLICATION_LANGUAGE`](properties/constant.APPLICATION_LANGUAGE.html).
+    ApplicationLanguage,
+    /// [`properties::APPLICATION_PROCESS_ID`](properties/constant.APPLICATION_PROCESS_ID.html).
+    ApplicationProcessId,
+    /// [`properties::APPLICATION_PROCESS_BINARY`](properties/constant.APPLICATION_PROCESS_BINARY.html).
+    ApplicationProcessBinary,
+    /// [`properties::APPLICATION_PROCESS_USER`](properties/constant.APPLICATION_PROCESS_USER.html).
+    ApplicationProcessUser,
+    /// [`properties::APPLICATION_PROCESS_HOST`](properties/constant.APPLICATION_PROCESS_HOST.html).
+    ApplicationProcessHost,
+    /// [`properties::APPLICATION_PROCESS_MACHINE_ID`](properties/constant.APPLICATION_PROCESS_MACHINE_ID.html).
+    ApplicationProcessMachineId,
+    /// [`properties::APPLICATION_PROCESS_SESSION_ID`](properties/constant.APPLICATION_PROCESS_SESSION_ID.html).
+    ApplicationProcessSessionId,
+    /// [`properties::DEVICE_STRING`](properties/constant.DEVICE_STRING.html).
+    DeviceString,
+    /// [`properties::DEVICE_API`](properties/constant.DEVICE_API.html).
+    DeviceApi,
+    /// [`properties::DEVICE_DESCRIPTION`](properties/constant.DEVICE_DESCRIPTION.html).
+    DeviceDescription,
+    /// [`properties::DEVICE_BUS_PATH`](properties/constant.DEVICE_BUS_PATH.html).
+    DeviceBusPath,
+    /// [`properties::DEVICE_SERIAL`](properties/constant.DEVICE_SERIAL.html).
+    DeviceSerial,
+    /// [`properties::DEVICE_VENDOR_ID`](properties/constant.DEVICE_VENDOR_ID.html).
+    DeviceVendorId,
+    /// [`properties::DEVICE_VENDOR_NAME`](properties/constant.DEVICE_VENDOR_NAME.html).
+    DeviceVendorName,
+    /// [`properties::DEVICE_PRODUCT_ID`](properties/constant.DEVICE_PRODUCT_ID.html).
+    DeviceProductId,
+    /// [`properties::DEVICE_PRODUCT_NAME`](properties/constant.DEVICE_PRODUCT_NAME.html).
+    DeviceProductName,
+    /// [`properties::DEVICE_CLASS`](properties/constant.DEVICE_CLASS.html).
+    DeviceClass,
+    /// [`properties::DEVICE_FORM_FACTOR`](properties/constant.DEVICE_FORM_FACTOR.html).
+    DeviceFormFactor,
+    /// [`properties::DEVICE_BUS`](properties/constant.DEVICE_BUS.html).
+    DeviceBus,
+    /// [`properties::DEVICE_ICON`](properties/constant.DEVICE_ICON.html).
+    DeviceIcon,
+    /// [`properties::DEVICE_ICON_NAME`](properties/constant.DEVICE_ICON_NAME.html).
+    DeviceIconName,
+    /// [`properties::DEVICE_ACCESS_MODE`](properties/constant.DEVICE_ACCESS_MODE.html).
+    DeviceAccessMode,
+    /// [`properties::DEVICE_MASTER_DEVICE`](properties/constant.DEVICE_MASTER_DEVICE.html).
+    DeviceMasterDevice,
+    /// [`properties::DEVICE_BUFFERING_BUFFER_SIZE`](properties/constant.DEVICE_BUFFERING_BUFFER_SIZE.html).
+    DeviceBufferingBufferSize,
+    /// [`properties::DEVICE_BUFFERING_FRAGMENT_SIZE`](properties/constant.DEVICE_BUFFERING_FRAGMENT_SIZE.html).
+    DeviceBufferingFragmentSize,
+    /// [`properties::DEVICE_PROFILE_NAME`](properties/constant.DEVICE_PROFILE_NAME.html).
+    DeviceProfileName,
+    /// [`properties::DEVICE_PROFILE_DESCRIPTION`](properties/constant.DEVICE_PROFILE_DESCRIPTION.html).
+    DeviceProfileDescription,
+    /// [`properties::MODULE_AUTHOR`](properties/constant.MODULE_AUTHOR.html).
+    ModuleAuthor,
+    /// [`properties::MODULE_DESCRIPTION`](properties/constant.MODULE_DESCRIPTION.html).
+    ModuleDescription,
+    /// [`properties::MODULE_USAGE`](properties/constant.MODULE_USAGE.html).
+    ModuleUsage,
+    /// [`properties::MODULE_VERSION`](properties/constant.MODULE_VERSION.html).
+    ModuleVersion,
+    /// [`properties::FORMAT_RATE`](properties/constant.FORMAT_RATE.html).
+    FormatRate,
+    /// [`properties::FORMAT_CHANNELS`](properties/constant.FORMAT_CHANNELS.html).
+    FormatChannels,
+    /// [`properties::FILTER_APPLY`](properties/constant.FILTER_APPLY.html).
+    FilterApply,
+    /// [`properties::FILTER_SUPPRESS`](properties/constant.FILTER_SUPPRESS.html).
+    FilterSuppress,
+    /// [`properties::DEVICE_INTENDED_ROLES`](properties/constant.DEVICE_INTENDED_ROLES.html).
+    DeviceIntendedRoles,
+    /// [`properties::FORMAT_SAMPLE_FORMAT`](properties/constant.FORMAT_SAMPLE_FORMAT.html).
+    FormatSampleFormat,
+    /// [`properties::FORMAT_CHANNEL_MAP`](properties/constant.FORMAT_CHANNEL_MAP.html).
+    FormatChannelMap,
+}
+
+impl WellKnownKey {
+    /// Gets the key string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WellKnownKey::MediaName => properties::MEDIA_NAME,
+            WellKnownKey::MediaTitle => properties::MEDIA_TITLE,
+            WellKnownKey::MediaArtist => properties::MEDIA_ARTIST,
+            WellKnownKey::MediaCopyright => properties::MEDIA_COPYRIGHT,
+            WellKnownKey::MediaSoftware => properties::MEDIA_SOFTWARE,
+            WellKnownKey::MediaLanguage => properties::MEDIA_LANGUAGE,
+            WellKnownKey::MediaFilename => properties::MEDIA_FILENAME,
+            WellKnownKey::MediaIcon => properties::MEDIA_ICON,
+            WellKnownKey::MediaIconName => properties::MEDIA_ICON_NAME,
+            WellKnownKey::MediaRole => properties::MEDIA_ROLE,
+            WellKnownKey::FilterWant => properties::FILTER_WANT,
+            WellKnownKey::EventId => properties::EVENT_ID,
+            WellKnownKey::EventDescription => properties::EVENT_DESCRIPTION,
+            WellKnownKey::EventMouseX => properties::EVENT_MOUSE_X,
+            WellKnownKey::EventMouseY => properties::EVENT_MOUSE_Y,
+            WellKnownKey::EventMouseHpos => properties::EVENT_MOUSE_HPOS,
+            WellKnownKey::EventMouseVpos => properties::EVENT_MOUSE_VPOS,
+            WellKnownKey::EventMouseButton => properties::EVENT_MOUSE_BUTTON,
+            WellKnownKey::WindowName => properties::WINDOW_NAME,
+            WellKnownKey::WindowId => properties::WINDOW_ID,
+            WellKnownKey::WindowIcon => properties::WINDOW_ICON,
+            WellKnownKey::WindowIconName => properties::WINDOW_ICON_NAME,
+            WellKnownKey::WindowX => properties::WINDOW_X,
+            WellKnownKey::WindowY => properties::WINDOW_Y,
+            WellKnownKey::WindowWidth => properties::WINDOW_WIDTH,
+            WellKnownKey::WindowHeight => properties::WINDOW_HEIGHT,
+            WellKnownKey::WindowHpos => properties::WINDOW_HPOS,
+            WellKnownKey::WindowVpos => properties::WINDOW_VPOS,
+            WellKnownKey::WindowDesktop => properties::WINDOW_DESKTOP,
+            WellKnownKey::WindowX11Display => properties::WINDOW_X11_DISPLAY,
+            WellKnownKey::WindowX11Screen => properties::WINDOW_X11_SCREEN,
+            WellKnownKey::WindowX11Monitor => properties::WINDOW_X11_MONITOR,
+            WellKnownKey::WindowX11Xid => properties::WINDOW_X11_XID,
+            WellKnownKey::ApplicationName => properties::APPLICATION_NAME,
+            WellKnownKey::ApplicationId => properties::APPLICATION_ID,
+            WellKnownKey::ApplicationVersion => properties::APPLICATION_VERSION,
+            WellKnownKey::ApplicationIcon => properties::APPLICATION_ICON,
+            WellKnownKey::ApplicationIconName => properties::APPLICATION_ICON_NAME,
+            WellKnownKey::ApplicationLanguage => properties::APPLICATION_LANGUAGE,
+            WellKnownKey::ApplicationProcessId => properties::APPLICATION_PROCESS_ID,
+            WellKnownKey::ApplicationProcessBinary => properties::APPLICATION_PROCESS_BINARY,
+            WellKnownKey::ApplicationProcessUser => properties::APPLICATION_PROCESS_USER,
+            WellKnownKey::ApplicationProcessHost => properties::APPLICATION_PROCESS_HOST,
+            WellKnownKey::ApplicationProcessMachineId => properties::APPLICATION_PROCESS_MACHINE_ID,
+            WellKnownKey::ApplicationProcessSessionId => properties::APPLICATION_PROCESS_SESSION_ID,
+            WellKnownKey::DeviceString => properties::DEVICE_STRING,
+            WellKnownKey::DeviceApi => properties::DEVICE_API,
+            WellKnownKey::DeviceDescription => properties::DEVICE_DESCRIPTION,
+            WellKnownKey::DeviceBusPath => properties::DEVICE_BUS_PATH,
+            WellKnownKey::DeviceSerial => properties::DEVICE_SERIAL,
+            WellKnownKey::DeviceVendorId => properties::DEVICE_VENDOR_ID,
+            WellKnownKey::DeviceVendorName => properties::DEVICE_VENDOR_NAME,
+            WellKnownKey::DeviceProductId => properties::DEVICE_PRODUCT_ID,
+            WellKnownKey::DeviceProductName => properties::DEVICE_PRODUCT_NAME,
+            WellKnownKey::DeviceClass => properties::DEVICE_CLASS,
+            WellKnownKey::DeviceFormFactor => properties::DEVICE_FORM_FACTOR,
+            WellKnownKey::DeviceBus => properties::DEVICE_BUS,
+            WellKnownKey::DeviceIcon => properties::DEVICE_ICON,
+            WellKnownKey::DeviceIconName => properties::DEVICE_ICON_NAME,
+            WellKnownKey::DeviceAccessMode => properties::DEVICE_ACCESS_MODE,
+            WellKnownKey::DeviceMasterDevice => properties::DEVICE_MASTER_DEVICE,
+            WellKnownKey::DeviceBufferingBufferSize => properties::DEVICE_BUFFERING_BUFFER_SIZE,
+            WellKnownKey::DeviceBufferingFragmentSize => properties::DEVICE_BUFFERING_FRAGMENT_SIZE,
+            WellKnownKey::DeviceProfileName => properties::DEVICE_PROFILE_NAME,
+            WellKnownKey::DeviceProfileDescription => properties::DEVICE_PROFILE_DESCRIPTION,
+            WellKnownKey::ModuleAuthor => properties::MODULE_AUTHOR,
+            WellKnownKey::ModuleDescription => properties::MODULE_DESCRIPTION,
+            WellKnownKey::ModuleUsage => properties::MODULE_USAGE,
+            WellKnownKey::ModuleVersion => properties::MODULE_VERSION,
+            WellKnownKey::FormatRate => properties::FORMAT_RATE,
+            WellKnownKey::FormatChannels => properties::FORMAT_CHANNELS,
+            WellKnownKey::FilterApply => properties::FILTER_APPLY,
+            WellKnownKey::FilterSuppress => properties::FILTER_SUPPRESS,
+            WellKnownKey::DeviceIntendedRoles => properties::DEVICE_INTENDED_ROLES,
+            WellKnownKey::FormatSampleFormat => properties::FORMAT_SAMPLE_FORMAT,
+            WellKnownKey::FormatChannelMap => properties::FORMAT_CHANNEL_MAP,
+        }
+    }
+}
+
+/// The well known values of the [`properties::MEDIA_ROLE`] property, such that misspelt roles are
+/// caught at compile time.
+///
+/// Use with [`Proplist::set_media_role`], or convert to the value string with [`as_str`].
+///
+/// [`properties::MEDIA_ROLE`]: properties/constant.MEDIA_ROLE.html
+/// [`Proplist::set_media_role`]: struct.Proplist.html#method.set_media_role
+/// [`as_str`]: #method.as_str
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MediaRole {
+    /// Video playback, e.g. a movie player.
+    Video,
+    /// Music playback.
+    Music,
+    /// Game audio.
+    Game,
+    /// Event sounds, e.g. notification and alert sounds.
+    Event,
+    /// Telephony, e.g. VoIP calls.
+    Phone,
+    /// Animation sounds, e.g. of user interface transitions.
+    Animation,
+    /// Audio production, e.g. a digital audio workstation.
+    Production,
+    /// Accessibility, e.g. a screen reader.
+    A11y,
+    /// Test sounds, e.g. speaker tests.
+    Test,
+}
+
+impl MediaRole {
+    /// Gets the property value string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MediaRole::Video => "video",
+            MediaRole::Music => "music",
+            MediaRole::Game => "game",
+            MediaRole::Event => "event",
+            MediaRole::Phone => "phone",
+            MediaRole::Animation => "animation",
+            MediaRole::Production => "production",
+            MediaRole::A11y => "a11y",
+            MediaRole::Test => "test",
+        }
+    }
 }
 
 /// A property list object. Basically a dictionary with ASCII strings as keys and arbitrary data as
 /// values.
 pub struct Proplist(pub(crate) ProplistInner);
 
+// A property list is not tied to any mainloop, and is only ever modified through `&mut self`.
 unsafe impl Send for Proplist {}
 unsafe impl Sync for Proplist {}
 
@@ -213,6 +623,153 @@ impl PartialEq for Proplist {
     }
 }
 
+/// A borrowed view of a property list, such as one owned by the C library and handed to a
+/// callback.
+///
+/// Lifetime `'a` ties the view to the validity of the underlying object, so it cannot be kept
+/// beyond that (e.g. beyond the callback it was given to). Read access is given through `Deref` to
+/// [`Proplist`]; use [`to_owned`](#method.to_owned) to obtain an independent copy that can be kept.
+///
+/// [`Proplist`]: struct.Proplist.html
+pub struct ProplistRef<'a> {
+    /// Weak wrapper, or for `'static` views, possibly an owned one.
+    list: Proplist,
+    /// Use lifetime `'a`.
+    phantom: PhantomData<&'a ProplistInternal>,
+}
+
+impl<'a> ProplistRef<'a> {
+    /// Creates a view of the property list at `ptr`, which must remain valid for `'a`.
+    #[inline]
+    pub(crate) fn from_raw(ptr: *mut ProplistInternal) -> Self {
+        Self { list: Proplist::from_raw_weak(ptr), phantom: PhantomData }
+    }
+
+    /// Gets an owned (deep) copy of the property list.
+    #[inline]
+    pub fn to_owned(&self) -> Proplist {
+        self.list.clone()
+    }
+}
+
+/// Wraps an owned property list, for use where a `'static` view is needed, e.g. owned copies of
+/// introspection info.
+impl From<Proplist> for ProplistRef<'static> {
+    #[inline]
+    fn from(list: Proplist) -> Self {
+        Self { list: list, phantom: PhantomData }
+    }
+}
+
+impl<'a> std::ops::Deref for ProplistRef<'a> {
+    type Target = Proplist;
+
+    #[inline]
+    fn deref(&self) -> &Proplist {
+        &self.list
+    }
+}
+
+impl<'a> std::fmt::Debug for ProplistRef<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+/// Errors from building a property list from a set of key-value pairs, identifying the key of the
+/// offending entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProplistError {
+    /// The key is not valid (e.g. is empty, or contains a nul byte).
+    InvalidKey(String),
+    /// The string value for the key contains a nul byte.
+    NulInValue(String),
+    /// The underlying library failed to create the property list.
+    CreationFailed,
+}
+
+impl std::fmt::Display for ProplistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ProplistError::InvalidKey(ref key) => write!(f, "invalid property key {:?}", key),
+            ProplistError::NulInValue(ref key) => {
+                write!(f, "value of property {:?} contains a nul byte", key)
+            },
+            ProplistError::CreationFailed => write!(f, "failed to create property list"),
+        }
+    }
+}
+
+impl std::error::Error for ProplistError {}
+
+/// Values which can be used with the key-value pair based property list building functions, such
+/// as [`Proplist::from_pairs`].
+///
+/// Strings are set as string entries (as with [`Proplist::set_str`]), and byte slices as arbitrary
+/// data entries (as with [`Proplist::set`]).
+///
+/// [`Proplist::from_pairs`]: struct.Proplist.html#method.from_pairs
+/// [`Proplist::set_str`]: struct.Proplist.html#method.set_str
+/// [`Proplist::set`]: struct.Proplist.html#method.set
+pub trait PropValue {
+    /// Sets this value for the given key in the property list.
+    fn set_in(&self, list: &mut Proplist, key: &str) -> Result<(), ProplistError>;
+}
+
+impl PropValue for str {
+    fn set_in(&self, list: &mut Proplist, key: &str) -> Result<(), ProplistError> {
+        let c_key = checked_key(key)?;
+        let c_value = CString::new(self).or(Err(ProplistError::NulInValue(key.to_string())))?;
+        match unsafe { capi::pa_proplist_sets(list.0.ptr, c_key.as_ptr(), c_value.as_ptr()) } {
+            0 => Ok(()),
+            _ => Err(ProplistError::InvalidKey(key.to_string())),
+        }
+    }
+}
+
+impl PropValue for String {
+    #[inline]
+    fn set_in(&self, list: &mut Proplist, key: &str) -> Result<(), ProplistError> {
+        self.as_str().set_in(list, key)
+    }
+}
+
+impl PropValue for [u8] {
+    fn set_in(&self, list: &mut Proplist, key: &str) -> Result<(), ProplistError> {
+        let c_key = checked_key(key)?;
+        match unsafe { capi::pa_proplist_set(list.0.ptr, c_key.as_ptr(),
+            self.as_ptr() as *mut c_void, self.len()) }
+        {
+            0 => Ok(()),
+            _ => Err(ProplistError::InvalidKey(key.to_string())),
+        }
+    }
+}
+
+impl PropValue for Vec<u8> {
+    #[inline]
+    fn set_in(&self, list: &mut Proplist, key: &str) -> Result<(), ProplistError> {
+        self.as_slice().set_in(list, key)
+    }
+}
+
+impl<'a, T: PropValue + ?Sized> PropValue for &'a T {
+    #[inline]
+    fn set_in(&self, list: &mut Proplist, key: &str) -> Result<(), ProplistError> {
+        (**self).set_in(list, key)
+    }
+}
+
+/// Converts a key for use with the C API, checking that it is valid.
+fn checked_key(key: &str) -> Result<CString, ProplistError> {
+    let c_key = CString::new(key).or(Err(ProplistError::InvalidKey(key.to_string())))?;
+    match unsafe { capi::pa_proplist_key_valid(c_key.as_ptr()) } {
+        0 => Err(ProplistError::InvalidKey(key.to_string())),
+        _ => Ok(c_key),
+    }
+}
+
 impl Proplist {
     /// Allocates a property list.
     pub fn new() -> Option<Self> {
@@ -221,10 +778,21 @@ impl Proplist {
     }
 
     /// Allocates a new property list and assigns key/value from a human readable string.
+    ///
+    /// This is equivalent to [`from_string`](#method.from_string).
+    #[inline]
     pub fn new_from_string(s: &str) -> Option<Self> {
+        Self::from_string(s)
+    }
+
+    /// Parses a property list from its human readable form, as produced by
+    /// [`to_string`](#method.to_string).
+    ///
+    /// Returns `None` should the string fail to parse, including should it contain a nul byte.
+    pub fn from_string(s: &str) -> Option<Self> {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
-        let c_str = CString::new(s.clone()).unwrap();
+        let c_str = CString::new(s).ok()?;
         let ptr = unsafe { capi::pa_proplist_from_string(c_str.as_ptr()) };
         match ptr.is_null() { false => Some(Self::from_raw(ptr)), true => None }
     }
@@ -259,30 +827,57 @@ impl Proplist {
     /// entry with the same key.
     ///
     /// An internal copy is made of the provided string.
-    pub fn set_str(&mut self, key: &str, value: &str) -> Result<(), ()> {
+    ///
+    /// Returns a reference to `self` on success, allowing calls to be chained. An `Invalid` error
+    /// is returned if the key is not valid, or if either string contains a nul byte.
+    pub fn set_str(&mut self, key: &str, value: &str) -> Result<&mut Self, PAErr> {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
-        let c_key = CString::new(key.clone()).unwrap();
-        let c_value = CString::new(value.clone()).unwrap();
+        let c_key = CString::new(key).or(Err(PAErr::from(Code::Invalid)))?;
+        let c_value = CString::new(value).or(Err(PAErr::from(Code::Invalid)))?;
         match unsafe { capi::pa_proplist_sets(self.0.ptr, c_key.as_ptr(), c_value.as_ptr()) } {
-            0 => Ok(()),
-            _ => Err(()),
+            0 => Ok(self),
+            _ => Err(PAErr::from(Code::Invalid)),
         }
     }
 
+    /// Appends a new string entry for a well known key to the property list, possibly overwriting
+    /// an already existing entry with the same key.
+    ///
+    /// This is the same as [`set_str`](#method.set_str), except that using a [`WellKnownKey`]
+    /// rather than a string prevents misspelling the key.
+    ///
+    /// [`WellKnownKey`]: enum.WellKnownKey.html
+    #[inline]
+    pub fn set_wellknown(&mut self, key: WellKnownKey, value: &str) -> Result<&mut Self, PAErr> {
+        self.set_str(key.as_str(), value)
+    }
+
+    /// Sets the [`properties::MEDIA_ROLE`] property, possibly overwriting an already existing
+    /// entry.
+    ///
+    /// [`properties::MEDIA_ROLE`]: properties/constant.MEDIA_ROLE.html
+    #[inline]
+    pub fn set_media_role(&mut self, role: MediaRole) -> Result<&mut Self, PAErr> {
+        self.set_str(properties::MEDIA_ROLE, role.as_str())
+    }
+
     /// Appends a new string entry to the property list, possibly overwriting an already existing
     /// entry with the same key.
     ///
-    /// This is similar to [`set_strs`](#method.set_str), however here the provided key and value
+    /// This is similar to [`set_str`](#method.set_str), however here the provided key and value
     /// are combined into a single string, separated by an `=`. An internal copy is made of the
     /// provided string.
-    pub fn set_pl(&mut self, pair: &str) -> Result<(), ()> {
+    ///
+    /// Returns a reference to `self` on success, allowing calls to be chained. An `Invalid` error
+    /// is returned if the pair is malformed, the key is not valid, or it contains a nul byte.
+    pub fn set_pl(&mut self, pair: &str) -> Result<&mut Self, PAErr> {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
-        let c_pair = CString::new(pair.clone()).unwrap();
+        let c_pair = CString::new(pair).or(Err(PAErr::from(Code::Invalid)))?;
         match unsafe { capi::pa_proplist_setp(self.0.ptr, c_pair.as_ptr()) } {
-            0 => Ok(()),
-            _ => Err(()),
+            0 => Ok(self),
+            _ => Err(PAErr::from(Code::Invalid)),
         }
     }
 
@@ -290,21 +885,72 @@ impl Proplist {
     /// existing entry with the same key.
     ///
     /// An internal copy of the provided data is made.
-    pub fn set(&mut self, key: &str, data: &[u8]) -> Result<(), ()> {
+    ///
+    /// Returns a reference to `self` on success, allowing calls to be chained. An `Invalid` error
+    /// is returned if the key is not valid, or if it contains a nul byte.
+    pub fn set(&mut self, key: &str, data: &[u8]) -> Result<&mut Self, PAErr> {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
-        //  as_ptr() giving dangling pointers!
-        let c_key = CString::new(key.clone()).unwrap();
+        // as_ptr() giving dangling pointers!
+        let c_key = CString::new(key).or(Err(PAErr::from(Code::Invalid)))?;
         match unsafe { capi::pa_proplist_set(self.0.ptr, c_key.as_ptr(),
             data.as_ptr() as *mut c_void, data.len()) }
         {
-            0 => Ok(()),
-            _ => Err(()),
+            0 => Ok(self),
+            _ => Err(PAErr::from(Code::Invalid)),
         }
     }
 
+    /// Creates a property list from a set of key-value pairs.
+    ///
+    /// Values may be strings or byte slices (see [`PropValue`]). Entries are set in order, thus
+    /// later ones overwrite earlier ones with the same key. An error identifying the offending key
+    /// is returned if any key is invalid or any string value contains a nul byte.
+    ///
+    /// ```rust
+    /// # extern crate libpulse_binding as pulse;
+    /// # use pulse::proplist::{properties, Proplist};
+    /// let props = Proplist::from_pairs(vec![
+    ///     (properties::APPLICATION_NAME, "FooApp"),
+    ///     (properties::APPLICATION_ICON_NAME, "foo-app"),
+    ///     (properties::MEDIA_ROLE, "music"),
+    /// ]).unwrap();
+    /// assert_eq!(props.len(), 3);
+    /// ```
+    ///
+    /// [`PropValue`]: trait.PropValue.html
+    pub fn from_pairs<I, K, V>(pairs: I) -> Result<Self, ProplistError>
+        where I: IntoIterator<Item = (K, V)>, K: AsRef<str>, V: PropValue
+    {
+        let mut list = Self::new().ok_or(ProplistError::CreationFailed)?;
+        list.try_extend(pairs)?;
+        Ok(list)
+    }
+
+    /// Sets the entries of a set of key-value pairs in the property list, overwriting any existing
+    /// entries with the same keys.
+    ///
+    /// As with [`from_pairs`], an error identifying the offending key is returned if any key is
+    /// invalid or any string value contains a nul byte, in which case the entries preceding it
+    /// will have been set.
+    ///
+    /// [`from_pairs`]: #method.from_pairs
+    pub fn try_extend<I, K, V>(&mut self, pairs: I) -> Result<(), ProplistError>
+        where I: IntoIterator<Item = (K, V)>, K: AsRef<str>, V: PropValue
+    {
+        for (key, value) in pairs {
+            value.set_in(self, key.as_ref())?;
+        }
+        Ok(())
+    }
+
     /// Gets a string entry for the specified key.
     ///
-    /// Will return `None` if the key does not exist or if data is not valid UTF-8.
+    /// The string is copied, since the C library’s copy is only valid until the property list is
+    /// next modified, thus the value returned is unaffected by subsequent changes to the list, or
+    /// by it being dropped. Use [`get`](#method.get) to access the data without copying.
+    ///
+    /// Will return `None` if the key does not exist or if the entry is not a string. (Strings are
+    /// always valid UTF-8, this being enforced by the C library upon setting them).
     pub fn get_str(&self, key: &str) -> Option<String> {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -318,10 +964,23 @@ impl Proplist {
 
     /// Gets the value for the specified key.
     ///
-    /// For string entries, the value store will be NUL-terminated. The caller should make a copy of
-    /// the data before the property list is accessed again.
+    /// For string entries, the value store will be NUL-terminated.
+    ///
+    /// Returns a slice formed from the data pointer and the length of the data, borrowed from the
+    /// property list, since the data is owned by it and only valid until it is next modified. As
+    /// all modification requires mutable access, the borrow checker thus ensures the slice cannot
+    /// be used after the data is invalidated:
+    ///
+    /// ```compile_fail
+    /// # extern crate libpulse_binding as pulse;
+    /// # use pulse::proplist::Proplist;
+    /// let mut props = Proplist::new().unwrap();
+    /// props.set_str("foo", "bar").unwrap();
+    /// let value = props.get("foo").unwrap();
+    /// props.set_str("foo", "baz").unwrap(); // Modifying while borrowed should not compile!
+    /// assert_eq!(value, b"bar\0");
+    /// ```
     ///
-    /// Returns a slice formed from the data pointer and the length of the data.
     /// Returns `None` if key does not exist.
     pub fn get(&self, key: &str) -> Option<&[u8]> {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
@@ -414,16 +1073,9 @@ impl Proplist {
     /// separator and appends one final one.
     pub fn to_string(&self) -> Option<String> {
         let ptr = unsafe { capi::pa_proplist_to_string(self.0.ptr) };
-        if ptr.is_null() {
-            return None;
-        }
         // Note, copying string on behalf of user here, and freeing that returned by PA, as
         // documentation instructs, saving the user from having to remember.
-        unsafe {
-            let ret = Some(CStr::from_ptr(ptr).to_string_lossy().into_owned());
-            capi::pa_xfree(ptr as *mut c_void);
-            ret
-        }
+        unsafe { PaAllocated::from_raw(ptr) }.map(|s| s.to_string_lossy())
     }
 
     /// Formats the property list nicely as a human readable string, choosing the separator used.
@@ -432,16 +1084,9 @@ impl Proplist {
         // as_ptr() giving dangling pointers!
         let c_sep = CString::new(sep.clone()).unwrap();
         let ptr = unsafe { capi::pa_proplist_to_string_sep(self.0.ptr, c_sep.as_ptr()) };
-        if ptr.is_null() {
-            return None;
-        }
         // Note, copying string on behalf of user here, and freeing that returned by PA, as
         // documentation instructs, saving the user from having to remember.
-        unsafe {
-            let ret = Some(CStr::from_ptr(ptr).to_string_lossy().into_owned());
-            capi::pa_xfree(ptr as *mut c_void);
-            ret
-        }
+        unsafe { PaAllocated::from_raw(ptr) }.map(|s| s.to_string_lossy())
     }
 
     /// Checks if this contains an entry with the given key.
@@ -474,7 +1119,7 @@ impl Proplist {
     /// Checks if the proplist is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        unsafe { capi::pa_proplist_isempty(self.0.ptr) == 0 }
+        unsafe { capi::pa_proplist_isempty(self.0.ptr) != 0 }
     }
 }
 
@@ -497,6 +1142,38 @@ impl Clone for Proplist {
     }
 }
 
+/// **Panics** upon an invalid pair; use [`Proplist::from_pairs`] to handle such errors.
+///
+/// [`Proplist::from_pairs`]: struct.Proplist.html#method.from_pairs
+impl<K: AsRef<str>, V: PropValue> std::iter::FromIterator<(K, V)> for Proplist {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(pairs: I) -> Self {
+        match Self::from_pairs(pairs) {
+            Ok(list) => list,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+/// **Panics** upon an invalid pair; use [`Proplist::try_extend`] to handle such errors.
+///
+/// [`Proplist::try_extend`]: struct.Proplist.html#method.try_extend
+impl<K: AsRef<str>, V: PropValue> Extend<(K, V)> for Proplist {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, pairs: I) {
+        if let Err(e) = self.try_extend(pairs) {
+            panic!("{}", e);
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a HashMap<String, String>> for Proplist {
+    type Error = ProplistError;
+
+    #[inline]
+    fn try_from(map: &'a HashMap<String, String>) -> Result<Self, Self::Error> {
+        Self::from_pairs(map)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -517,6 +1194,238 @@ mod tests {
         }
     }
 
+    #[test]
+    fn set_chaining() {
+        fn build() -> Result<Proplist, PAErr> {
+            let mut my_props = Proplist::new().unwrap();
+            my_props
+                .set_str(properties::APPLICATION_NAME, "FooApp")?
+                .set_str(properties::MEDIA_ROLE, "music")?
+                .set_pl("media.title=Bar")?;
+            Ok(my_props)
+        }
+        let my_props = build().unwrap();
+        assert_eq!(my_props.get_str(properties::APPLICATION_NAME).unwrap(), "FooApp");
+        assert_eq!(my_props.get_str(properties::MEDIA_ROLE).unwrap(), "music");
+        assert_eq!(my_props.get_str(properties::MEDIA_TITLE).unwrap(), "Bar");
+
+        let mut my_props = Proplist::new().unwrap();
+        assert_eq!(my_props.set_str("", "foo").err(), Some(PAErr::from(Code::Invalid)));
+        assert_eq!(my_props.set_str("foo\0", "bar").err(), Some(PAErr::from(Code::Invalid)));
+        assert_eq!(my_props.set_pl("no equals").err(), Some(PAErr::from(Code::Invalid)));
+    }
+
+    #[test]
+    fn from_string() {
+        let mut props = Proplist::new().unwrap();
+        props.set_str(properties::APPLICATION_NAME, "Test \"quoted\" app").unwrap();
+        props.set_str(properties::MEDIA_ROLE, "music").unwrap();
+        props.set(properties::APPLICATION_ICON, &[0x00, 0xff]).unwrap();
+        let text = props.to_string().unwrap();
+        let parsed = Proplist::from_string(&text).unwrap();
+        assert!(props == parsed);
+        assert_eq!(parsed.get_str(properties::APPLICATION_NAME).unwrap(), "Test \"quoted\" app");
+        assert_eq!(parsed.get(properties::APPLICATION_ICON).unwrap(), &[0x00, 0xff]);
+
+        assert!(Proplist::from_string("").unwrap().is_empty());
+        for malformed in &["foo", "foo=", "foo=\"unterminated", "foo=hex:0", "foo=\"a\"\0", "\0"] {
+            assert!(Proplist::from_string(malformed).is_none(), "{:?}", malformed);
+        }
+    }
+
+    #[test]
+    fn from_pairs() {
+        let props = Proplist::from_pairs(vec![
+            (properties::APPLICATION_NAME, "FooApp"),
+            (properties::APPLICATION_VERSION, "1.0"),
+            (properties::MEDIA_ROLE, "music"),
+            (properties::MEDIA_ROLE, "video"),
+        ]).unwrap();
+        assert_eq!(props.len(), 3);
+        assert_eq!(props.get_str(properties::APPLICATION_NAME).unwrap(), "FooApp");
+        assert_eq!(props.get_str(properties::APPLICATION_VERSION).unwrap(), "1.0");
+        // Later entries win
+        assert_eq!(props.get_str(properties::MEDIA_ROLE).unwrap(), "video");
+
+        // Arbitrary data, which may contain nul bytes
+        let data: &[u8] = &[1, 0, 2];
+        let props = Proplist::from_pairs(vec![("foo.data", data)]).unwrap();
+        assert_eq!(props.get("foo.data").unwrap(), data);
+
+        // Errors identify the key
+        assert_eq!(Proplist::from_pairs(vec![("good", "a"), ("", "b")]).err(),
+            Some(ProplistError::InvalidKey(String::new())));
+        assert_eq!(Proplist::from_pairs(vec![("foo\0bar", "a")]).err(),
+            Some(ProplistError::InvalidKey("foo\0bar".to_string())));
+        assert_eq!(Proplist::from_pairs(vec![("good", "a"), ("bad", "b\0c")]).err(),
+            Some(ProplistError::NulInValue("bad".to_string())));
+
+        // The entries preceding an error are set
+        let mut props = Proplist::new().unwrap();
+        assert!(props.try_extend(vec![("first", "a"), ("bad", "b\0c"), ("last", "d")]).is_err());
+        assert_eq!(props.get_str("first").unwrap(), "a");
+        assert_eq!(props.contains("last"), Some(false));
+    }
+
+    #[test]
+    fn from_iterator_and_extend() {
+        let mut props: Proplist = vec![(properties::APPLICATION_NAME, "FooApp")].into_iter()
+            .collect();
+        props.extend(vec![(properties::MEDIA_ROLE, "music")]);
+        let data: &[u8] = b"icon";
+        props.extend(vec![(properties::APPLICATION_ICON, data)]);
+        assert_eq!(props.len(), 3);
+        assert_eq!(props.get_str(properties::MEDIA_ROLE).unwrap(), "music");
+        assert_eq!(props.get(properties::APPLICATION_ICON).unwrap(), data);
+
+        let mut map = HashMap::new();
+        map.insert(properties::APPLICATION_NAME.to_string(), "FooApp".to_string());
+        map.insert(properties::MEDIA_ROLE.to_string(), "music".to_string());
+        let from_map = Proplist::try_from(&map).unwrap();
+        assert_eq!(from_map.len(), 2);
+        assert_eq!(from_map.get_str(properties::MEDIA_ROLE).unwrap(), "music");
+
+        map.insert("bad".to_string(), "a\0b".to_string());
+        assert_eq!(Proplist::try_from(&map).err(), Some(ProplistError::NulInValue("bad".into())));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid property key")]
+    fn collect_invalid() {
+        let _: Proplist = vec![("", "foo")].into_iter().collect();
+    }
+
+    #[test]
+    fn property_names() {
+        // Each must match the C header exactly
+        let props = [
+            (properties::MEDIA_NAME, "media.name"),
+            (properties::MEDIA_TITLE, "media.title"),
+            (properties::MEDIA_ARTIST, "media.artist"),
+            (properties::MEDIA_COPYRIGHT, "media.copyright"),
+            (properties::MEDIA_SOFTWARE, "media.software"),
+            (properties::MEDIA_LANGUAGE, "media.language"),
+            (properties::MEDIA_FILENAME, "media.filename"),
+            (properties::MEDIA_ICON, "media.icon"),
+            (properties::MEDIA_ICON_NAME, "media.icon_name"),
+            (properties::MEDIA_ROLE, "media.role"),
+            (properties::FILTER_WANT, "filter.want"),
+            (properties::FILTER_APPLY, "filter.apply"),
+            (properties::FILTER_SUPPRESS, "filter.suppress"),
+            (properties::EVENT_ID, "event.id"),
+            (properties::EVENT_DESCRIPTION, "event.description"),
+            (properties::EVENT_MOUSE_X, "event.mouse.x"),
+            (properties::EVENT_MOUSE_Y, "event.mouse.y"),
+            (properties::EVENT_MOUSE_HPOS, "event.mouse.hpos"),
+            (properties::EVENT_MOUSE_VPOS, "event.mouse.vpos"),
+            (properties::EVENT_MOUSE_BUTTON, "event.mouse.button"),
+            (properties::WINDOW_NAME, "window.name"),
+            (properties::WINDOW_ID, "window.id"),
+            (properties::WINDOW_ICON, "window.icon"),
+            (properties::WINDOW_ICON_NAME, "window.icon_name"),
+            (properties::WINDOW_X, "window.x"),
+            (properties::WINDOW_Y, "window.y"),
+            (properties::WINDOW_WIDTH, "window.width"),
+            (properties::WINDOW_HEIGHT, "window.height"),
+            (properties::WINDOW_HPOS, "window.hpos"),
+            (properties::WINDOW_VPOS, "window.vpos"),
+            (properties::WINDOW_DESKTOP, "window.desktop"),
+            (properties::WINDOW_X11_DISPLAY, "window.x11.display"),
+            (properties::WINDOW_X11_SCREEN, "window.x11.screen"),
+            (properties::WINDOW_X11_MONITOR, "window.x11.monitor"),
+            (properties::WINDOW_X11_XID, "window.x11.xid"),
+            (properties::APPLICATION_NAME, "application.name"),
+            (properties::APPLICATION_ID, "application.id"),
+            (properties::APPLICATION_VERSION, "application.version"),
+            (properties::APPLICATION_ICON, "application.icon"),
+            (properties::APPLICATION_ICON_NAME, "application.icon_name"),
+            (properties::APPLICATION_LANGUAGE, "application.language"),
+            (properties::APPLICATION_PROCESS_ID, "application.process.id"),
+            (properties::APPLICATION_PROCESS_BINARY, "application.process.binary"),
+            (properties::APPLICATION_PROCESS_USER, "application.process.user"),
+            (properties::APPLICATION_PROCESS_HOST, "application.process.host"),
+            (properties::APPLICATION_PROCESS_MACHINE_ID, "application.process.machine_id"),
+            (properties::APPLICATION_PROCESS_SESSION_ID, "application.process.session_id"),
+            (properties::DEVICE_STRING, "device.string"),
+            (properties::DEVICE_API, "device.api"),
+            (properties::DEVICE_DESCRIPTION, "device.description"),
+            (properties::DEVICE_BUS_PATH, "device.bus_path"),
+            (properties::DEVICE_SERIAL, "device.serial"),
+            (properties::DEVICE_VENDOR_ID, "device.vendor.id"),
+            (properties::DEVICE_VENDOR_NAME, "device.vendor.name"),
+            (properties::DEVICE_PRODUCT_ID, "device.product.id"),
+            (properties::DEVICE_PRODUCT_NAME, "device.product.name"),
+            (properties::DEVICE_CLASS, "device.class"),
+            (properties::DEVICE_FORM_FACTOR, "device.form_factor"),
+            (properties::DEVICE_BUS, "device.bus"),
+            (properties::DEVICE_ICON, "device.icon"),
+            (properties::DEVICE_ICON_NAME, "device.icon_name"),
+            (properties::DEVICE_ACCESS_MODE, "device.access_mode"),
+            (properties::DEVICE_MASTER_DEVICE, "device.master_device"),
+            (properties::DEVICE_BUFFERING_BUFFER_SIZE, "device.buffering.buffer_size"),
+            (properties::DEVICE_BUFFERING_FRAGMENT_SIZE, "device.buffering.fragment_size"),
+            (properties::DEVICE_PROFILE_NAME, "device.profile.name"),
+            (properties::DEVICE_INTENDED_ROLES, "device.intended_roles"),
+            (properties::DEVICE_PROFILE_DESCRIPTION, "device.profile.description"),
+            (properties::MODULE_AUTHOR, "module.author"),
+            (properties::MODULE_DESCRIPTION, "module.description"),
+            (properties::MODULE_USAGE, "module.usage"),
+            (properties::MODULE_VERSION, "module.version"),
+            (properties::FORMAT_SAMPLE_FORMAT, "format.sample_format"),
+            (properties::FORMAT_RATE, "format.rate"),
+            (properties::FORMAT_CHANNELS, "format.channels"),
+            (properties::FORMAT_CHANNEL_MAP, "format.channel_map"),
+        ];
+        for &(constant, expected) in props.iter() {
+            assert_eq!(constant, expected);
+        }
+
+        assert_eq!(properties::media::ROLE, properties::MEDIA_ROLE);
+        assert_eq!(properties::filter::APPLY, "filter.apply");
+        assert_eq!(properties::window::X11_XID, properties::WINDOW_X11_XID);
+        assert_eq!(properties::application::PROCESS_ID, properties::APPLICATION_PROCESS_ID);
+        assert_eq!(properties::device::INTENDED_ROLES, "device.intended_roles");
+        assert_eq!(properties::format::CHANNEL_MAP, "format.channel_map");
+    }
+
+    #[test]
+    fn set_media_role() {
+        let mut my_props = Proplist::new().unwrap();
+        my_props.set_media_role(MediaRole::Music).unwrap();
+        assert_eq!(my_props.get_str(properties::MEDIA_ROLE).unwrap(), "music");
+        my_props.set_media_role(MediaRole::A11y).unwrap();
+        assert_eq!(my_props.get_str(properties::MEDIA_ROLE).unwrap(), "a11y");
+
+        let roles = [MediaRole::Video, MediaRole::Music, MediaRole::Game, MediaRole::Event,
+            MediaRole::Phone, MediaRole::Animation, MediaRole::Production, MediaRole::A11y,
+            MediaRole::Test];
+        let names: Vec<_> = roles.iter().map(|r| r.as_str()).collect();
+        assert_eq!(names, ["video", "music", "game", "event", "phone", "animation", "production",
+            "a11y", "test"]);
+    }
+
+    #[test]
+    fn set_wellknown() {
+        let mut my_props = Proplist::new().unwrap();
+        my_props
+            .set_wellknown(WellKnownKey::ApplicationName, "FooApp").unwrap()
+            .set_wellknown(WellKnownKey::MediaRole, "music").unwrap()
+            .set_wellknown(WellKnownKey::ApplicationIconName, "foo-app").unwrap();
+        assert_eq!(my_props.get_str("application.name").unwrap(), "FooApp");
+        assert_eq!(my_props.get_str(properties::MEDIA_ROLE).unwrap(), "music");
+        assert_eq!(my_props.get_str("application.icon_name").unwrap(), "foo-app");
+        assert_eq!(WellKnownKey::WindowX11Display.as_str(), "window.x11.display");
+        assert_eq!(WellKnownKey::FormatChannelMap.as_str(), properties::FORMAT_CHANNEL_MAP);
+    }
+
+    #[test]
+    fn empty() {
+        let mut my_props = Proplist::new().unwrap();
+        assert!(my_props.is_empty());
+        my_props.set_str(properties::MEDIA_ROLE, "music").unwrap();
+        assert!(!my_props.is_empty());
+    }
+
     /// Test that you can however return an iterator if you convert the `Proplist` into one
     #[test]
     fn proplist_iter_lifetime_conv() {
@@ -530,4 +1439,47 @@ mod tests {
             println!("key: {}", key);
         }
     }
+
+    /// Values obtained are unaffected by subsequent changes to the list (see also the
+    /// `compile_fail` example of `get`, covering borrowed data).
+    #[test]
+    fn get_after_modification() {
+        let mut my_props = Proplist::new().unwrap();
+        my_props.set_str(properties::MEDIA_TITLE, "Foo").unwrap();
+        my_props.set(properties::MEDIA_ICON, &[1, 2, 3]).unwrap();
+
+        let title = my_props.get_str(properties::MEDIA_TITLE).unwrap();
+        let icon = my_props.get(properties::MEDIA_ICON).unwrap().to_vec();
+        my_props.set_str(properties::MEDIA_TITLE, "Bar").unwrap();
+        my_props.unset(properties::MEDIA_ICON).unwrap();
+        my_props.clear();
+        drop(my_props);
+        assert_eq!(title, "Foo");
+        assert_eq!(icon, [1, 2, 3]);
+
+        let mut my_props = Proplist::new().unwrap();
+        my_props.set_str(properties::MEDIA_TITLE, "Foo").unwrap();
+        assert_eq!(my_props.get(properties::MEDIA_TITLE), Some(&b"Foo\0"[..]));
+        // Binary data is not a string entry
+        my_props.set(properties::MEDIA_ICON, &[1, 2, 3]).unwrap();
+        assert_eq!(my_props.get_str(properties::MEDIA_ICON), None);
+        assert_eq!(my_props.get_str("nonexistent"), None);
+    }
+
+    /// A copy taken from a view remains valid once the underlying list is freed, as done by PA
+    /// after a callback returns (run under ASAN to catch any use-after-free).
+    #[test]
+    fn ref_to_owned() {
+        let mut original = Proplist::new().unwrap();
+        original.set_str(properties::MEDIA_TITLE, "Foo").unwrap();
+        let copy = {
+            let view = ProplistRef::from_raw(original.0.ptr);
+            assert_eq!(view.get_str(properties::MEDIA_TITLE).unwrap(), "Foo");
+            view.to_owned()
+        };
+        // Dropping the view does not free the list
+        assert_eq!(original.len(), 1);
+        drop(original);
+        assert_eq!(copy.get_str(properties::MEDIA_TITLE).unwrap(), "Foo");
+    }
 }
@@ -246,6 +246,13 @@ impl From<capi::pa_sample_spec> for Spec {
     }
 }
 
+impl Default for Spec {
+    /// Creates an initialised, but invalid, sample spec, as per [`init`](#method.init).
+    fn default() -> Self {
+        Self { format: Format::Invalid, rate: 0, channels: 0 }
+    }
+}
+
 impl PartialEq for Spec {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
@@ -288,6 +295,21 @@ impl Spec {
         unsafe { capi::pa_sample_size(self.as_ref()) }
     }
 
+    /// Gets the byte value that, repeated, makes up silence in the spec’s format.
+    ///
+    /// This is `0x80` for unsigned 8-bit PCM, `0xd5` for a-Law, `0xff` for µ-Law, and zero for all
+    /// other formats (signed PCM, including the packed 24-bit formats, and floating point). Since
+    /// silence is a single repeated byte in every format, buffers of any whole number of frames
+    /// may simply be filled with it. Zero is also given for an invalid format.
+    pub fn silence_value(&self) -> u8 {
+        match self.format {
+            Format::U8 => 0x80,
+            Format::ALaw => 0xd5,
+            Format::ULaw => 0xff,
+            _ => 0,
+        }
+    }
+
     /// Calculates the time it would take to play a buffer of the specified size.
     ///
     /// The return value will always be rounded down for non-integral return values.
@@ -364,6 +386,40 @@ impl Format {
         unsafe { capi::pa_sample_size_of_format((*self).into()) }
     }
 
+    /// Checks whether the format is floating point (i.e. `F32le` or `F32be`).
+    pub fn is_float(&self) -> bool {
+        match self {
+            Format::F32le | Format::F32be => true,
+            _ => false,
+        }
+    }
+
+    /// Checks whether the format is linear integer PCM (signed, or unsigned 8-bit).
+    ///
+    /// This is `false` for the companded a-Law and µ-Law formats, as well as floating point.
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Format::U8 | Format::S16le | Format::S16be | Format::S32le | Format::S32be |
+            Format::S24le | Format::S24be | Format::S24_32le | Format::S24_32be => true,
+            _ => false,
+        }
+    }
+
+    /// Gets the number of significant bits per sample of a linear format, or `None` for the
+    /// companded a-Law and µ-Law formats (and `Invalid`).
+    ///
+    /// Note that for the `S24_32` formats this is 24, with each sample being carried in 32 bits,
+    /// as per [`size`](#method.size).
+    pub fn bit_depth(&self) -> Option<u8> {
+        match self {
+            Format::U8 => Some(8),
+            Format::S16le | Format::S16be => Some(16),
+            Format::S24le | Format::S24be | Format::S24_32le | Format::S24_32be => Some(24),
+            Format::S32le | Format::S32be | Format::F32le | Format::F32be => Some(32),
+            Format::ALaw | Format::ULaw | Format::Invalid => None,
+        }
+    }
+
     /// Gets a descriptive string for the specified sample format.
     pub fn to_string(&self) -> Option<Cow<'static, str>> {
         let ptr = unsafe { capi::pa_sample_format_to_string((*self).into()) };
@@ -425,4 +481,165 @@ impl Format {
     pub fn is_re(&self) -> Option<bool> {
         self.is_ne().and_then(|b| Some(!b))
     }
+
+    /// Gets the little endian variant of the format.
+    ///
+    /// Formats to which endianness does not apply are returned unchanged.
+    pub fn to_le(self) -> Self {
+        match self {
+            Format::S16be => Format::S16le,
+            Format::F32be => Format::F32le,
+            Format::S32be => Format::S32le,
+            Format::S24be => Format::S24le,
+            Format::S24_32be => Format::S24_32le,
+            f => f,
+        }
+    }
+
+    /// Gets the big endian variant of the format.
+    ///
+    /// Formats to which endianness does not apply are returned unchanged.
+    pub fn to_be(self) -> Self {
+        match self {
+            Format::S16le => Format::S16be,
+            Format::F32le => Format::F32be,
+            Format::S32le => Format::S32be,
+            Format::S24le => Format::S24be,
+            Format::S24_32le => Format::S24_32be,
+            f => f,
+        }
+    }
+
+    /// Gets the native endian variant of the format.
+    ///
+    /// For example, on a little endian system, both [`S16le`] and [`S16be`] resolve to [`S16le`].
+    /// Formats to which endianness does not apply are returned unchanged.
+    ///
+    /// [`S16le`]: enum.Format.html#variant.S16le
+    /// [`S16be`]: enum.Format.html#variant.S16be
+    #[inline]
+    pub fn to_native_endian(self) -> Self {
+        #[cfg(target_endian = "big")]
+        { self.to_be() }
+        #[cfg(target_endian = "little")]
+        { self.to_le() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plain value types can be freely shared between threads.
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Spec>();
+    }
+
+    #[test]
+    fn default_spec() {
+        let spec = Spec::default();
+        assert!(!spec.is_valid());
+
+        let mut initialised = Spec { format: SAMPLE_S16NE, rate: 44100, channels: 2 };
+        initialised.init();
+        assert_eq!(initialised.format, spec.format);
+        assert_eq!(initialised.rate, spec.rate);
+        assert_eq!(initialised.channels, spec.channels);
+    }
+
+    #[test]
+    fn silence_values() {
+        let table = [
+            (Format::U8, 0x80),
+            (Format::ALaw, 0xd5),
+            (Format::ULaw, 0xff),
+            (Format::S16le, 0),
+            (Format::S16be, 0),
+            (Format::F32le, 0),
+            (Format::F32be, 0),
+            (Format::S32le, 0),
+            (Format::S32be, 0),
+            (Format::S24le, 0),
+            (Format::S24be, 0),
+            (Format::S24_32le, 0),
+            (Format::S24_32be, 0),
+            (Format::Invalid, 0),
+        ];
+        for &(format, silence) in &table {
+            // Exhaustive, so that new formats cannot be missed here
+            match format {
+                Format::U8 | Format::ALaw | Format::ULaw | Format::S16le | Format::S16be |
+                Format::F32le | Format::F32be | Format::S32le | Format::S32be | Format::S24le |
+                Format::S24be | Format::S24_32le | Format::S24_32be | Format::Invalid => {},
+            }
+            let spec = Spec { format: format, rate: 44100, channels: 2 };
+            assert_eq!(spec.silence_value(), silence, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn format_groups() {
+        // Format, float, integer, bit depth
+        let table = [
+            (Format::U8, false, true, Some(8)),
+            (Format::ALaw, false, false, None),
+            (Format::ULaw, false, false, None),
+            (Format::S16le, false, true, Some(16)),
+            (Format::S16be, false, true, Some(16)),
+            (Format::F32le, true, false, Some(32)),
+            (Format::F32be, true, false, Some(32)),
+            (Format::S32le, false, true, Some(32)),
+            (Format::S32be, false, true, Some(32)),
+            (Format::S24le, false, true, Some(24)),
+            (Format::S24be, false, true, Some(24)),
+            (Format::S24_32le, false, true, Some(24)),
+            (Format::S24_32be, false, true, Some(24)),
+            (Format::Invalid, false, false, None),
+        ];
+        for &(format, float, integer, depth) in &table {
+            assert_eq!(format.is_float(), float, "{:?}", format);
+            assert_eq!(format.is_integer(), integer, "{:?}", format);
+            assert_eq!(format.bit_depth(), depth, "{:?}", format);
+            if format != Format::Invalid {
+                // Significant bits fit within the sample size
+                let bits = format.bit_depth().unwrap_or(8) as usize;
+                assert!(bits <= format.size() * 8, "{:?}", format);
+            }
+        }
+    }
+
+    #[test]
+    fn endian_conversion() {
+        assert_eq!(Format::S16be.to_le(), Format::S16le);
+        assert_eq!(Format::S16le.to_le(), Format::S16le);
+        assert_eq!(Format::S24_32le.to_be(), Format::S24_32be);
+        assert_eq!(Format::F32be.to_be(), Format::F32be);
+        for f in &[Format::U8, Format::ALaw, Format::ULaw, Format::Invalid] {
+            assert_eq!(f.to_le(), *f);
+            assert_eq!(f.to_be(), *f);
+            assert_eq!(f.to_native_endian(), *f);
+        }
+    }
+
+    #[test]
+    fn native_endian_resolution() {
+        for &f in &[Format::S16le, Format::S16be] {
+            assert_eq!(f.to_native_endian(), SAMPLE_S16NE);
+            assert_eq!(f.to_native_endian().is_ne(), Some(true));
+        }
+        for &f in &[Format::F32le, Format::F32be] {
+            assert_eq!(f.to_native_endian(), SAMPLE_FLOAT32NE);
+        }
+        for &f in &[Format::S32le, Format::S32be] {
+            assert_eq!(f.to_native_endian(), SAMPLE_S32NE);
+        }
+        for &f in &[Format::S24le, Format::S24be] {
+            assert_eq!(f.to_native_endian(), SAMPLE_S24NE);
+        }
+        for &f in &[Format::S24_32le, Format::S24_32be] {
+            assert_eq!(f.to_native_endian(), SAMPLE_S24_32NE);
+        }
+    }
 }
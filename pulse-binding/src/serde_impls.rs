@@ -0,0 +1,454 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Serde support for value types, enabled with the `serde` feature.
+//!
+//! Sample formats, channel positions and channel maps are represented by their PulseAudio string
+//! forms (e.g. `s16le`, `front-left`, and `front-left,front-right`), keeping such data human
+//! readable; channel maps may also be given as a sequence of positions when deserializing.
+//!
+//! Volumes are represented as (floating point) percentages of `VOLUME_NORM`, as per
+//! `Volume::to_percent`, i.e. the same figures as displayed by `pactl` and friends, and channel
+//! volumes by a sequence of such values. Sample specs and buffer attributes are represented as
+//! structures of their fields.
+//!
+//! Deserialization validates the data, producing an error for unknown names, invalid sample specs,
+//! out of range volumes, or too many channels.
+
+use std::fmt;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{self, Visitor, MapAccess, SeqAccess};
+use serde::ser::{SerializeSeq, SerializeStruct};
+use crate::channelmap::{Map, Position};
+use crate::def::BufferAttr;
+use crate::sample::{self, Format, Spec};
+use crate::volume::{ChannelVolumes, Volume, VOLUME_MAX};
+
+/// Deserializes a string, rejecting those which cannot be passed to the C API.
+fn deserialize_c_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    match s.contains('\0') {
+        false => Ok(s),
+        true => Err(de::Error::custom("string contains a nul byte")),
+    }
+}
+
+impl Serialize for Format {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.to_string() {
+            Some(s) => serializer.serialize_str(&s),
+            None => Err(serde::ser::Error::custom("invalid sample format")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Format {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = deserialize_c_string(deserializer)?;
+        match Format::parse(&s) {
+            Format::Invalid => Err(de::Error::custom(format!("unknown sample format `{}`", s))),
+            f => Ok(f),
+        }
+    }
+}
+
+impl Serialize for Position {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match (*self, Position::to_string(*self)) {
+            (Position::Invalid, _) | (_, None) => {
+                Err(serde::ser::Error::custom("invalid channel position"))
+            },
+            (_, Some(s)) => serializer.serialize_str(&s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Position {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = deserialize_c_string(deserializer)?;
+        match Position::from_string(&s) {
+            Position::Invalid => {
+                Err(de::Error::custom(format!("unknown channel position `{}`", s)))
+            },
+            p => Ok(p),
+        }
+    }
+}
+
+impl Serialize for Map {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.is_valid() {
+            true => serializer.serialize_str(&self.print()),
+            false => Err(serde::ser::Error::custom("invalid channel map")),
+        }
+    }
+}
+
+/// Accepts either the string form, or a sequence of channel positions.
+impl<'de> Deserialize<'de> for Map {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MapVisitor;
+
+        impl<'de> Visitor<'de> for MapVisitor {
+            type Value = Map;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a channel map string, or a sequence of 1 to {} channel positions",
+                    sample::CHANNELS_MAX)
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                if s.contains('\0') {
+                    return Err(E::custom("string contains a nul byte"));
+                }
+                match Map::new_from_string(s) {
+                    Ok(map) if map.is_valid() => Ok(map),
+                    _ => Err(E::custom(format!("invalid channel map `{}`", s))),
+                }
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut map = Map::default();
+                let mut channels: u8 = 0;
+                while let Some(p) = seq.next_element::<Position>()? {
+                    if channels as usize == sample::CHANNELS_MAX {
+                        return Err(de::Error::invalid_length(channels as usize + 1, &self));
+                    }
+                    channels += 1;
+                    map.set_len(channels);
+                    map.get_mut()[channels as usize - 1] = p;
+                }
+                match channels {
+                    0 => Err(de::Error::invalid_length(0, &self)),
+                    _ => Ok(map),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(MapVisitor)
+    }
+}
+
+impl Serialize for Volume {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_percent())
+    }
+}
+
+/// Accepts percentages from zero up to that of `VOLUME_MAX`, rounding to the nearest volume step.
+impl<'de> Deserialize<'de> for Volume {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let percent = f64::deserialize(deserializer)?;
+        if percent.is_nan() || percent < 0.0 {
+            return Err(de::Error::custom(format!("invalid volume percentage {}", percent)));
+        }
+        match Volume::from_percent_unchecked(percent) {
+            v if v <= VOLUME_MAX => Ok(v),
+            _ => Err(de::Error::custom(format!("volume {}% exceeds the maximum", percent))),
+        }
+    }
+}
+
+impl Serialize for ChannelVolumes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let values = self.get();
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for v in values {
+            seq.serialize_element(v)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ChannelVolumes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ChannelVolumesVisitor;
+
+        impl<'de> Visitor<'de> for ChannelVolumesVisitor {
+            type Value = ChannelVolumes;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a sequence of 1 to {} volumes", sample::CHANNELS_MAX)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut cv = ChannelVolumes::default();
+                let mut channels: u8 = 0;
+                while let Some(v) = seq.next_element::<Volume>()? {
+                    if channels as usize == sample::CHANNELS_MAX {
+                        return Err(de::Error::invalid_length(channels as usize + 1, &self));
+                    }
+                    channels += 1;
+                    cv.set_len(channels);
+                    cv.get_mut()[channels as usize - 1] = v;
+                }
+                match channels {
+                    0 => Err(de::Error::invalid_length(0, &self)),
+                    _ => Ok(cv),
+                }
+            }
+        }
+
+        deserializer.deserialize_seq(ChannelVolumesVisitor)
+    }
+}
+
+impl Serialize for Spec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Spec", 3)?;
+        state.serialize_field("format", &self.format)?;
+        state.serialize_field("rate", &self.rate)?;
+        state.serialize_field("channels", &self.channels)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Spec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const FIELDS: &[&str] = &["format", "rate", "channels"];
+
+        struct SpecVisitor;
+
+        impl<'de> Visitor<'de> for SpecVisitor {
+            type Value = Spec;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sample spec")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let (mut format, mut rate, mut channels) = (None, None, None);
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "format" => format = Some(map.next_value()?),
+                        "rate" => rate = Some(map.next_value()?),
+                        "channels" => channels = Some(map.next_value()?),
+                        _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+                    }
+                }
+                let spec = Spec {
+                    format: format.ok_or_else(|| de::Error::missing_field("format"))?,
+                    rate: rate.ok_or_else(|| de::Error::missing_field("rate"))?,
+                    channels: channels.ok_or_else(|| de::Error::missing_field("channels"))?,
+                };
+                match spec.is_valid() {
+                    true => Ok(spec),
+                    false => Err(de::Error::custom("invalid sample spec")),
+                }
+            }
+        }
+
+        deserializer.deserialize_struct("Spec", FIELDS, SpecVisitor)
+    }
+}
+
+impl Serialize for BufferAttr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("BufferAttr", 5)?;
+        state.serialize_field("maxlength", &self.maxlength)?;
+        state.serialize_field("tlength", &self.tlength)?;
+        state.serialize_field("prebuf", &self.prebuf)?;
+        state.serialize_field("minreq", &self.minreq)?;
+        state.serialize_field("fragsize", &self.fragsize)?;
+        state.end()
+    }
+}
+
+/// Note that any fields missing are set to `std::u32::MAX`, i.e. the server default.
+impl<'de> Deserialize<'de> for BufferAttr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const FIELDS: &[&str] = &["maxlength", "tlength", "prebuf", "minreq", "fragsize"];
+
+        struct BufferAttrVisitor;
+
+        impl<'de> Visitor<'de> for BufferAttrVisitor {
+            type Value = BufferAttr;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("buffer attributes")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut attr = BufferAttr {
+                    maxlength: std::u32::MAX,
+                    tlength: std::u32::MAX,
+                    prebuf: std::u32::MAX,
+                    minreq: std::u32::MAX,
+                    fragsize: std::u32::MAX,
+                };
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "maxlength" => attr.maxlength = map.next_value()?,
+                        "tlength" => attr.tlength = map.next_value()?,
+                        "prebuf" => attr.prebuf = map.next_value()?,
+                        "minreq" => attr.minreq = map.next_value()?,
+                        "fragsize" => attr.fragsize = map.next_value()?,
+                        _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+                    }
+                }
+                Ok(attr)
+            }
+        }
+
+        deserializer.deserialize_struct("BufferAttr", FIELDS, BufferAttrVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use crate::volume::{VOLUME_MUTED, VOLUME_NORM};
+
+    /// Round-trips a value through JSON.
+    fn round_trip<T>(value: T) -> T
+        where T: Serialize + for<'de> Deserialize<'de>
+    {
+        let s = serde_json::to_string(&value).unwrap();
+        serde_json::from_str(&s).unwrap()
+    }
+
+    fn from_json<T>(s: &str) -> Result<T, serde_json::Error>
+        where T: for<'de> Deserialize<'de>
+    {
+        serde_json::from_str(s)
+    }
+
+    /// Round-trips a value through TOML, as the value of a key (a TOML document must be a table).
+    fn round_trip_toml<T>(value: T) -> T
+        where T: Serialize + for<'de> Deserialize<'de>
+    {
+        let mut table = BTreeMap::new();
+        table.insert("value", value);
+        let s = toml::to_string(&table).unwrap();
+        let mut table: BTreeMap<String, T> = toml::from_str(&s).unwrap();
+        table.remove("value").unwrap()
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(round_trip(Format::S16le), Format::S16le);
+        assert_eq!(round_trip(Format::F32be), Format::F32be);
+        assert_eq!(from_json::<Format>("\"s16le\"").unwrap(), Format::S16le);
+        assert!(from_json::<Format>("\"bogus\"").is_err());
+        assert!(from_json::<Format>("\"s16\\u0000le\"").is_err());
+    }
+
+    #[test]
+    fn spec() {
+        let spec = Spec { format: Format::S16le, rate: 44100, channels: 2 };
+        assert_eq!(round_trip(spec), spec);
+        assert!(serde_json::to_string(&spec).unwrap().contains("\"format\":\"s16le\""));
+
+        assert!(from_json::<Spec>(r#"{"format":"bogus","rate":44100,"channels":2}"#).is_err());
+        assert!(from_json::<Spec>(r#"{"format":"s16le","rate":44100,"channels":33}"#).is_err());
+        assert!(from_json::<Spec>(r#"{"format":"s16le","rate":44100}"#).is_err());
+    }
+
+    #[test]
+    fn channel_map() {
+        let mut map = Map::default();
+        map.init_stereo();
+        assert_eq!(round_trip(map), map);
+        assert_eq!(round_trip(Position::RearCenter), Position::RearCenter);
+        assert_eq!(from_json::<Map>("\"front-left,front-right\"").unwrap(), map);
+        assert!(from_json::<Map>("\"front-left,bogus\"").is_err());
+        assert!(from_json::<Position>("\"bogus\"").is_err());
+
+        // Over the maximum number of channels
+        let too_many = vec!["mono"; sample::CHANNELS_MAX + 1].join(",");
+        assert!(from_json::<Map>(&format!("\"{}\"", too_many)).is_err());
+
+        // Position lists
+        assert_eq!(from_json::<Map>(r#"["front-left", "front-right"]"#).unwrap(), map);
+        assert!(from_json::<Map>("[]").is_err());
+        assert!(from_json::<Map>(r#"["front-left", "bogus"]"#).is_err());
+        let too_many = vec!["\"mono\""; sample::CHANNELS_MAX + 1].join(",");
+        assert!(from_json::<Map>(&format!("[{}]", too_many)).is_err());
+    }
+
+    #[test]
+    fn volumes() {
+        let mut cv = ChannelVolumes::default();
+        cv.set(2, Volume(0x8000));
+        assert_eq!(round_trip(cv), cv);
+        assert_eq!(serde_json::to_string(&cv).unwrap(), "[50.0,50.0]");
+        assert_eq!(round_trip(Volume(0x1234)), Volume(0x1234));
+        assert_eq!(round_trip(VOLUME_MAX), VOLUME_MAX);
+        assert_eq!(serde_json::to_string(&VOLUME_NORM).unwrap(), "100.0");
+        assert_eq!(from_json::<Volume>("100").unwrap(), VOLUME_NORM);
+        assert_eq!(from_json::<Volume>("0").unwrap(), VOLUME_MUTED);
+        assert!(from_json::<Volume>("-1").is_err());
+        assert!(from_json::<Volume>(&format!("{}", VOLUME_MAX.to_percent() + 1.0)).is_err());
+        assert!(from_json::<ChannelVolumes>("[]").is_err());
+
+        let too_many = vec!["100"; sample::CHANNELS_MAX + 1].join(",");
+        assert!(from_json::<ChannelVolumes>(&format!("[{}]", too_many)).is_err());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        // As for a config file describing an audio format
+        let spec = Spec { format: Format::F32le, rate: 48000, channels: 2 };
+        let mut map = Map::default();
+        map.init_stereo();
+        let volume = Volume::from_percent(75.0).unwrap();
+
+        let json = serde_json::to_string(&(spec, map, volume)).unwrap();
+        assert_eq!(json,
+            r#"[{"format":"float32le","rate":48000,"channels":2},"front-left,front-right",75.0]"#);
+        let (spec2, map2, volume2): (Spec, Map, Volume) = serde_json::from_str(&json).unwrap();
+        assert_eq!(spec2, spec);
+        assert_eq!(map2, map);
+        assert_eq!(volume2, volume);
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        let spec = Spec { format: Format::F32le, rate: 48000, channels: 2 };
+        let mut map = Map::default();
+        map.init_stereo();
+        let volume = Volume::from_percent(75.0).unwrap();
+        let mut cv = ChannelVolumes::default();
+        cv.set(2, volume);
+        let attr = BufferAttr { maxlength: 1, tlength: 2, prebuf: 3, minreq: 4, fragsize: 5 };
+
+        assert_eq!(round_trip_toml(Format::S16le), Format::S16le);
+        assert_eq!(round_trip_toml(Position::RearCenter), Position::RearCenter);
+        assert_eq!(round_trip_toml(spec), spec);
+        assert_eq!(round_trip_toml(map), map);
+        assert_eq!(round_trip_toml(volume), volume);
+        assert_eq!(round_trip_toml(cv), cv);
+        assert_eq!(round_trip_toml(attr), attr);
+
+        // As for a config file describing an audio format
+        assert_eq!(toml::to_string(&spec).unwrap(),
+            "format = \"float32le\"\nrate = 48000\nchannels = 2\n");
+        let config = "format = \"float32le\"\nrate = 48000\nchannels = 2\n";
+        assert_eq!(toml::from_str::<Spec>(config).unwrap(), spec);
+        let config = "map = [\"front-left\", \"front-right\"]\nvolume = 75.0\n";
+        let table: BTreeMap<String, toml::Value> = toml::from_str(config).unwrap();
+        assert_eq!(table["map"].clone().try_into::<Map>().unwrap(), map);
+        assert_eq!(table["volume"].clone().try_into::<Volume>().unwrap(), volume);
+        assert!(toml::from_str::<Spec>("format = \"bogus\"\nrate = 48000\nchannels = 2\n")
+            .is_err());
+    }
+
+    #[test]
+    fn buffer_attr() {
+        let attr = BufferAttr { maxlength: 1, tlength: 2, prebuf: 3, minreq: 4, fragsize: 5 };
+        assert_eq!(round_trip(attr), attr);
+
+        let partial = from_json::<BufferAttr>(r#"{"tlength":4096}"#).unwrap();
+        assert_eq!(partial.tlength, 4096);
+        assert_eq!(partial.maxlength, std::u32::MAX);
+        assert_eq!(partial.fragsize, std::u32::MAX);
+    }
+}
@@ -66,12 +66,12 @@
 //! The buffer metrics may be controlled by the application. They are described with a
 //! [`def::BufferAttr`] structure.
 //!
-//! If [`flags::ADJUST_LATENCY`] is set, then the `tlength`/`fragsize` parameters of this structure
+//! If [`Flags::ADJUST_LATENCY`] is set, then the `tlength`/`fragsize` parameters of this structure
 //! will be interpreted slightly differently than otherwise when passed to
 //! [`Stream::connect_record`] and [`Stream::connect_playback`]: the overall latency that is
 //! comprised of both the server side playback buffer length, the hardware playback buffer length
 //! and additional latencies will be adjusted in a way that it matches `tlength` resp. `fragsize`.
-//! Set [`flags::ADJUST_LATENCY`] if you want to control the overall playback latency for your
+//! Set [`Flags::ADJUST_LATENCY`] if you want to control the overall playback latency for your
 //! stream. Unset it if you want to control only the latency induced by the server-side, rewritable
 //! playback buffer. The server will try to fulfill the client’s latency requests as good as
 //! possible. However if the underlying hardware cannot change the hardware buffer length or only in
@@ -80,7 +80,7 @@
 //! via [`Stream::get_latency`] or a similar call, and not make any assumptions about the latency
 //! available. The function [`Stream::get_buffer_attr`] will always return the actual size of the
 //! server-side per-stream buffer in `tlength`/`fragsize`, regardless whether
-//! [`flags::ADJUST_LATENCY`] is set or not.
+//! [`Flags::ADJUST_LATENCY`] is set or not.
 //!
 //! The server-side per-stream playback buffers are indexed by a write and a read index. The
 //! application writes to the write index and the sound device reads from the read index. The read
@@ -158,7 +158,7 @@
 //! This structure is updated every time a [`Stream::update_timing_info`] operation is executed.
 //! (i.e. before the first call to this function the timing information structure is not available!)
 //! Since it is a lot of work to keep this structure up-to-date manually, PulseAudio can do that
-//! automatically for you: if [`flags::AUTO_TIMING_UPDATE`] is passed when connecting the stream
+//! automatically for you: if [`Flags::AUTO_TIMING_UPDATE`] is passed when connecting the stream
 //! PulseAudio will automatically update the structure every 100ms and every time a function is
 //! called that might invalidate the previously known timing data (such as [`Stream::write`] or
 //! [`Stream::flush`]). Please note however, that there always is a short time window when the data
@@ -175,10 +175,10 @@
 //!
 //! Since updating the timing info structure usually requires a full network round trip and some
 //! applications monitor the timing very often PulseAudio offers a timing interpolation system. If
-//! [`flags::INTERPOLATE_TIMING`] is passed when connecting the stream, [`Stream::get_time`] and
+//! [`Flags::INTERPOLATE_TIMING`] is passed when connecting the stream, [`Stream::get_time`] and
 //! [`Stream::get_latency`] will try to interpolate the current playback time/latency by estimating
 //! the number of samples that have been played back by the hardware since the last regular timing
-//! update. It is especially useful to combine this option with [`flags::AUTO_TIMING_UPDATE`], which
+//! update. It is especially useful to combine this option with [`Flags::AUTO_TIMING_UPDATE`], which
 //! will enable you to monitor the current playback time/latency very precisely and very frequently
 //! without requiring a network round trip every time.
 //!
@@ -186,7 +186,9 @@
 //!
 //! Even with the best precautions, buffers will sometime over - or underflow. To handle this
 //! gracefully, the application can be notified when this happens. Callbacks are registered using
-//! [`Stream::set_overflow_callback`] and [`Stream::set_underflow_callback`].
+//! [`Stream::set_overflow_callback`] and [`Stream::set_underflow_callback`]. Alternatively, the
+//! number of occurrences since the stream was created can be polled with
+//! [`Stream::get_overflow_count`] and [`Stream::get_underflow_count`].
 //!
 //! # Synchronizing Multiple Playback Streams
 //!
@@ -197,7 +199,7 @@
 //!
 //! To synchronize a stream to another, just pass the “master” stream as the last argument to
 //! [`Stream::connect_playback`]. To make sure that the freshly created stream doesn’t start
-//! playback right-away, make sure to pass [`flags::START_CORKED`] and, after all streams have been
+//! playback right-away, make sure to pass [`Flags::START_CORKED`] and, after all streams have been
 //! created, uncork them all with a single call to [`Stream::uncork`] for the master stream.
 //!
 //! To make sure that a particular stream doesn’t stop to play when a server side buffer underrun
@@ -213,10 +215,10 @@
 //! [`context::scache`]: ../context/scache/index.html
 //! [`def::BufferAttr`]: ../def/struct.BufferAttr.html
 //! [`def::TimingInfo`]: ../def/struct.TimingInfo.html
-//! [`flags::ADJUST_LATENCY`]: flags/constant.ADJUST_LATENCY.html
-//! [`flags::AUTO_TIMING_UPDATE`]: flags/constant.AUTO_TIMING_UPDATE.html
-//! [`flags::INTERPOLATE_TIMING`]: flags/constant.INTERPOLATE_TIMING.html
-//! [`flags::START_CORKED`]: flags/constant.START_CORKED.html
+//! [`Flags::ADJUST_LATENCY`]: struct.Flags.html#associatedconstant.ADJUST_LATENCY
+//! [`Flags::AUTO_TIMING_UPDATE`]: struct.Flags.html#associatedconstant.AUTO_TIMING_UPDATE
+//! [`Flags::INTERPOLATE_TIMING`]: struct.Flags.html#associatedconstant.INTERPOLATE_TIMING
+//! [`Flags::START_CORKED`]: struct.Flags.html#associatedconstant.START_CORKED
 //! [`operation::Operation`]: ../operation/struct.Operation.html
 //! [`SeekMode::Absolute`]: enum.SeekMode.html#Absolute.v
 //! [`SeekMode::Relative`]: enum.SeekMode.html#Relative.v
@@ -243,6 +245,8 @@
 //! [`Stream::set_read_callback`]: struct.Stream.html#method.set_read_callback
 //! [`Stream::set_state_callback`]: struct.Stream.html#method.set_state_callback
 //! [`Stream::set_underflow_callback`]: struct.Stream.html#method.set_underflow_callback
+//! [`Stream::get_overflow_count`]: struct.Stream.html#method.get_overflow_count
+//! [`Stream::get_underflow_count`]: struct.Stream.html#method.get_underflow_count
 //! [`Stream::set_write_callback`]: struct.Stream.html#method.set_write_callback
 //! [`Stream::trigger`]: struct.Stream.html#method.trigger
 //! [`Stream::uncork`]: struct.Stream.html#method.uncork
@@ -253,14 +257,22 @@
 use std::os::raw::{c_char, c_void};
 use std::ffi::{CStr, CString};
 use std::ptr::{null, null_mut};
+use std::any::Any;
 use std::borrow::Cow;
+#[cfg(any(feature = "pa_v6", feature = "dox"))]
+use std::ops::Range;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use capi::pa_stream as StreamInternal;
 use crate::{channelmap, format, def, proplist, sample};
 use crate::callbacks::{self, box_closure_get_capi_ptr, get_su_capi_params, get_su_callback};
 use crate::error::{self, PAErr};
 use crate::format::InfoInternal;
-use crate::proplist::{Proplist, ProplistInternal};
+use crate::proplist::{Proplist, ProplistInternal, ProplistRef};
 use crate::{context::Context, volume::ChannelVolumes, operation::Operation, time::MicroSeconds};
+use crate::mainloop::standard;
 
 pub use capi::pa_seek_mode_t as SeekMode;
 pub use capi::pa_stream_direction_t as Direction;
@@ -268,15 +280,33 @@ pub use capi::pa_stream_direction_t as Direction;
 /// An opaque stream for playback or recording.
 ///
 /// Note: Saves a copy of active multi-use closure callbacks, which it frees on drop.
+///
+/// Note, as with [`Context`], this is neither `Send` nor `Sync`, since it holds `Rc`s (keeping the
+/// mainloop alive, and sharing its event callbacks), and since its callbacks need not be `Send`.
+///
+/// [`Context`]: ../context/struct.Context.html
 pub struct Stream {
     /// The actual C object.
     ptr: *mut StreamInternal,
     /// Multi-use callback closure pointers.
     cb_ptrs: CallbackPointers,
+    /// Overflow and underflow counters, incremented by the internal callback wrappers.
+    xrun_counts: Arc<XrunCounts>,
+    /// User event callbacks, shared with the dispatching closure given to the C API.
+    event_handlers: Rc<RefCell<EventHandlers>>,
+    /// Keeps the mainloop alive for as long as the stream exists.
+    _mainloop: Rc<dyn Any>,
+    /// Whether the user has disconnected the stream, which the state does not reflect until the
+    /// server has responded.
+    disconnected: bool,
 }
 
-unsafe impl Send for Stream {}
-unsafe impl Sync for Stream {}
+/// Counts of buffer overflows and underflows since stream creation.
+#[derive(Default)]
+struct XrunCounts {
+    overflow: AtomicU64,
+    underflow: AtomicU64,
+}
 
 /// Holds copies of callback closure pointers, for those that are “multi-use” (may be fired multiple
 /// times), for freeing at the appropriate time.
@@ -301,9 +331,21 @@ type RequestCb = callbacks::MultiUseCallback<dyn FnMut(usize),
 type NotifyCb = callbacks::MultiUseCallback<dyn FnMut(),
     extern "C" fn(*mut StreamInternal, *mut c_void)>;
 
-type EventCb = callbacks::MultiUseCallback<dyn FnMut(String, Proplist),
+type EventCb = callbacks::MultiUseCallback<dyn FnMut(String, ProplistRef),
     extern "C" fn(*mut StreamInternal, name: *const c_char, pl: *mut ProplistInternal, *mut c_void)>;
 
+/// The user callbacks between which events are dispatched.
+///
+/// These are reference counted such that one can be called without holding a borrow, thus the
+/// callbacks can themselves be (re)set from within a callback.
+#[derive(Default)]
+struct EventHandlers {
+    generic: Option<Rc<RefCell<dyn FnMut(String, ProplistRef) + 'static>>>,
+    cork_request: Option<Rc<RefCell<dyn FnMut(CorkRequest) + 'static>>>,
+    /// Whether an event is being dispatched, during which the dispatcher must not be replaced.
+    dispatching: bool,
+}
+
 /// The state of a stream.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -336,10 +378,26 @@ impl From<State> for capi::pa_stream_state_t {
         unsafe { std::mem::transmute(s) }
     }
 }
+/// Checked, as with the conversion from a raw value, in case of a state value unknown to us.
 impl From<capi::pa_stream_state_t> for State {
     #[inline]
     fn from(s: capi::pa_stream_state_t) -> Self {
-        unsafe { std::mem::transmute(s) }
+        State::from(s as i32)
+    }
+}
+
+/// Converts a raw state value, as given by the C API, conservatively treating unknown values (e.g.
+/// a state added by a newer version of PulseAudio) as `Failed`.
+impl From<i32> for State {
+    fn from(s: i32) -> Self {
+        match s {
+            0 => State::Unconnected,
+            1 => State::Creating,
+            2 => State::Ready,
+            3 => State::Failed,
+            4 => State::Terminated,
+            _ => State::Failed,
+        }
     }
 }
 
@@ -349,179 +407,281 @@ impl State {
     pub fn is_good(self) -> bool {
         self == State::Creating || self == State::Ready
     }
+
+    /// Checks if the passed state is a terminal state, i.e. `Failed` or `Terminated`, from which
+    /// the stream cannot recover.
+    #[inline]
+    pub fn is_terminal(self) -> bool {
+        self == State::Failed || self == State::Terminated
+    }
+
+    /// Checks if the passed state is the `Ready` state.
+    #[inline]
+    pub fn is_ready(self) -> bool {
+        self == State::Ready
+    }
+}
+
+impl std::fmt::Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match *self {
+            State::Unconnected => "unconnected",
+            State::Creating => "creating",
+            State::Ready => "ready",
+            State::Failed => "failed",
+            State::Terminated => "terminated",
+        };
+        f.write_str(s)
+    }
+}
+
+flags_type! {
+    /// Flags for stream connections.
+    pub struct Flags: capi::pa_stream_flags_t {
+        /// Flag to pass when no specific options are needed.
+        const NOFLAGS = capi::PA_STREAM_NOFLAGS;
+
+        /// Create the stream corked, requiring an explicit [`Stream::uncork`] call to uncork it.
+        ///
+        /// [`Stream::uncork`]: struct.Stream.html#method.uncork
+        const START_CORKED = capi::PA_STREAM_START_CORKED;
+
+        /// Interpolate the latency for this stream. When enabled, [`Stream::get_latency`] and
+        /// [`Stream::get_time`] will try to estimate the current record/playback time based on the
+        /// local time that passed since the last timing info update. Using this option has the
+        /// advantage of not requiring a whole round trip when the current playback/recording time
+        /// is needed. Consider using this option when requesting latency information frequently.
+        /// This is especially useful on long latency network connections. It makes a lot of sense
+        /// to combine this option with
+        /// [`AUTO_TIMING_UPDATE`](#associatedconstant.AUTO_TIMING_UPDATE).
+        ///
+        /// [`Stream::get_latency`]: struct.Stream.html#method.get_latency
+        /// [`Stream::get_time`]: struct.Stream.html#method.get_time
+        const INTERPOLATE_TIMING = capi::PA_STREAM_INTERPOLATE_TIMING;
+
+        /// Don’t force the time to increase monotonically. If this option is enabled,
+        /// [`Stream::get_time`] will not necessarily return always monotonically increasing time
+        /// values on each call. This may confuse applications which cannot deal with time going
+        /// ‘backwards’, but has the advantage that bad transport latency estimations that caused
+        /// the time to jump ahead can be corrected quickly, without the need to wait.
+        ///
+        /// [`Stream::get_time`]: struct.Stream.html#method.get_time
+        const NOT_MONOTONIC = capi::PA_STREAM_NOT_MONOTONIC;
+
+        /// If set timing update requests are issued periodically automatically. Combined with
+        /// [`INTERPOLATE_TIMING`] you will be able to query the current time and latency with
+        /// [`Stream::get_time`] and [`Stream::get_latency`] at all times without a packet round
+        /// trip.
+        ///
+        /// [`INTERPOLATE_TIMING`]: #associatedconstant.INTERPOLATE_TIMING
+        /// [`Stream::get_time`]: struct.Stream.html#method.get_time
+        /// [`Stream::get_latency`]: struct.Stream.html#method.get_latency
+        const AUTO_TIMING_UPDATE = capi::PA_STREAM_AUTO_TIMING_UPDATE;
+
+        /// Don’t remap channels by their name, instead map them simply by their index. Implies
+        /// [`NO_REMIX_CHANNELS`](#associatedconstant.NO_REMIX_CHANNELS).
+        const NO_REMAP_CHANNELS = capi::PA_STREAM_NO_REMAP_CHANNELS;
+
+        /// When remapping channels by name, don’t upmix or downmix them to related channels. Copy
+        /// them into matching channels of the device 1:1.
+        const NO_REMIX_CHANNELS = capi::PA_STREAM_NO_REMIX_CHANNELS;
+
+        /// Use the sample format of the sink/device this stream is being connected to, and possibly
+        /// ignore the format the sample spec contains -- but you still have to pass a valid value
+        /// in it as a hint to PulseAudio what would suit your stream best. If this is used you
+        /// should query the used sample format after creating the stream by using
+        /// [`Stream::get_sample_spec`]. Also, if you specified manual buffer metrics it is
+        /// recommended to update them with [`Stream::set_buffer_attr`] to compensate for the
+        /// changed frame sizes.
+        ///
+        /// When creating streams with [`Stream::new_extended`], this flag has no effect. If you
+        /// specify a format with PCM encoding, and you want the server to choose the sample format,
+        /// then you should leave the sample format unspecified in the [`format::Info`] object. This
+        /// also means that you can’t use [`format::Info::new_from_sample_spec`], because that
+        /// function always sets the sample format.
+        ///
+        /// [`Stream::get_sample_spec`]: struct.Stream.html#method.get_sample_spec
+        /// [`Stream::set_buffer_attr`]: struct.Stream.html#method.set_buffer_attr
+        /// [`Stream::new_extended`]: struct.Stream.html#method.new_extended
+        /// [`format::Info`]: ../format/struct.Info.html
+        /// [`format::Info::new_from_sample_spec`]: ../format/struct.Info.html#method.new_from_sample_spec
+        const FIX_FORMAT = capi::PA_STREAM_FIX_FORMAT;
+
+        /// Use the sample rate of the sink, and possibly ignore the rate the sample spec contains.
+        /// Usage similar to [`FIX_FORMAT`].
+        ///
+        /// When creating streams with [`Stream::new_extended`], this flag has no effect. If you
+        /// specify a format with PCM encoding, and you want the server to choose the sample rate,
+        /// then you should leave the rate unspecified in the [`format::Info`] object. This also
+        /// means that you can’t use [`format::Info::new_from_sample_spec`], because that function
+        /// always sets the sample rate.
+        ///
+        /// [`FIX_FORMAT`]: #associatedconstant.FIX_FORMAT
+        /// [`Stream::new_extended`]: struct.Stream.html#method.new_extended
+        /// [`format::Info`]: ../format/struct.Info.html
+        /// [`format::Info::new_from_sample_spec`]: ../format/struct.Info.html#method.new_from_sample_spec
+        const FIX_RATE = capi::PA_STREAM_FIX_RATE;
+
+        /// Use the number of channels and the channel map of the sink, and possibly ignore the
+        /// number of channels and the map the sample spec and the passed channel map contains.
+        /// Usage similar to [`FIX_FORMAT`].
+        ///
+        /// When creating streams with [`Stream::new_extended`], this flag has no effect. If you
+        /// specify a format with PCM encoding, and you want the server to choose the channel count
+        /// and/or channel map, then you should leave the channels and/or the channel map
+        /// unspecified in the [`format::Info`] object. This also means that you can’t use
+        /// [`format::Info::new_from_sample_spec`], because that function always sets the channel
+        /// count (but if you only want to leave the channel map unspecified, then
+        /// [`format::Info::new_from_sample_spec`] works, because the channel map parameter is
+        /// optional).
+        ///
+        /// [`FIX_FORMAT`]: #associatedconstant.FIX_FORMAT
+        /// [`Stream::new_extended`]: struct.Stream.html#method.new_extended
+        /// [`format::Info`]: ../format/struct.Info.html
+        /// [`format::Info::new_from_sample_spec`]: ../format/struct.Info.html#method.new_from_sample_spec
+        const FIX_CHANNELS = capi::PA_STREAM_FIX_CHANNELS;
+
+        /// Don’t allow moving of this stream to another sink/device. Useful if you use any of the
+        /// `Fix*` flags and want to make sure that resampling never takes place -- which might
+        /// happen if the stream is moved to another sink/source with a different sample
+        /// spec/channel map.
+        const DONT_MOVE = capi::PA_STREAM_DONT_MOVE;
+
+        /// Allow dynamic changing of the sampling rate during playback with
+        /// [`Stream::update_sample_rate`].
+        ///
+        /// [`Stream::update_sample_rate`]: struct.Stream.html#method.update_sample_rate
+        const VARIABLE_RATE = capi::PA_STREAM_VARIABLE_RATE;
+
+        /// Find peaks instead of resampling.
+        const PEAK_DETECT = capi::PA_STREAM_PEAK_DETECT;
+
+        /// Create in muted state. If neither [`START_UNMUTED`] nor this is specified, it is left to
+        /// the server to decide whether to create the stream in muted or in un-muted state.
+        ///
+        /// [`START_UNMUTED`]: #associatedconstant.START_UNMUTED
+        const START_MUTED = capi::PA_STREAM_START_MUTED;
+
+        /// Try to adjust the latency of the sink/source based on the requested buffer metrics and
+        /// adjust buffer metrics accordingly. Also see [`def::BufferAttr`]. This option may not be
+        /// specified at the same time as [`EARLY_REQUESTS`](#associatedconstant.EARLY_REQUESTS).
+        ///
+        /// [`def::BufferAttr`]: ../def/struct.BufferAttr.html
+        const ADJUST_LATENCY = capi::PA_STREAM_ADJUST_LATENCY;
+
+        /// Enable compatibility mode for legacy clients that rely on a “classic” hardware device
+        /// fragment-style playback model. If this option is set, the `minreq` value of the buffer
+        /// metrics gets a new meaning: instead of just specifying that no requests asking for less
+        /// new data than this value will be made to the client it will also guarantee that requests
+        /// are generated as early as this limit is reached. This flag should only be set in very
+        /// few situations where compatibility with a fragment-based playback model needs to be kept
+        /// and the client applications cannot deal with data requests that are delayed to the
+        /// latest moment possible. (Usually these are programs that use usleep() or a similar call
+        /// in their playback loops instead of sleeping on the device itself.) Also see
+        /// [`def::BufferAttr`]. This option may not be specified at the same time as
+        /// [`ADJUST_LATENCY`](#associatedconstant.ADJUST_LATENCY).
+        ///
+        /// [`def::BufferAttr`]: ../def/struct.BufferAttr.html
+        const EARLY_REQUESTS = capi::PA_STREAM_EARLY_REQUESTS;
+
+        /// If set this stream won’t be taken into account when it is checked whether the device
+        /// this stream is connected to should auto-suspend.
+        const DONT_INHIBIT_AUTO_SUSPEND = capi::PA_STREAM_DONT_INHIBIT_AUTO_SUSPEND;
+
+        /// Create in unmuted state. If neither [`START_MUTED`] nor this is specified, it is left to
+        /// the server to decide whether to create the stream in muted or in unmuted state.
+        ///
+        /// [`START_MUTED`]: #associatedconstant.START_MUTED
+        const START_UNMUTED = capi::PA_STREAM_START_UNMUTED;
+
+        /// If the sink/source this stream is connected to is suspended during the creation of this
+        /// stream, cause it to fail. If the sink/source is being suspended during creation of this
+        /// stream, make sure this stream is terminated.
+        const FAIL_ON_SUSPEND = capi::PA_STREAM_FAIL_ON_SUSPEND;
+
+        /// If a volume is passed when this stream is created, consider it relative to the sink’s
+        /// current volume, never as absolute device volume. If this is not specified the volume
+        /// will be consider absolute when the sink is in flat volume mode, relative otherwise.
+        const RELATIVE_VOLUME = capi::PA_STREAM_RELATIVE_VOLUME;
+
+        /// Used to tag content that will be rendered by passthrough sinks. The data will be left as
+        /// is and not reformatted, resampled.
+        const PASSTHROUGH = capi::PA_STREAM_PASSTHROUGH;
+    }
 }
 
-pub type FlagSet = capi::pa_stream_flags_t;
+/// Deprecated alias of [`Flags`](struct.Flags.html).
+#[deprecated(since = "2.17.0", note = "use `Flags` instead")]
+pub type FlagSet = Flags;
 
 /// Some special flags for stream connections.
+///
+/// These are deprecated aliases of the associated constants of [`Flags`](../struct.Flags.html).
 pub mod flags {
-    use capi;
-    use super::FlagSet;
-
-    /// Flag to pass when no specific options are needed.
-    pub const NOFLAGS: FlagSet = capi::PA_STREAM_NOFLAGS;
-
-    /// Create the stream corked, requiring an explicit [`Stream::uncork`] call to uncork it.
-    ///
-    /// [`Stream::uncork`]: ../struct.Stream.html#method.uncork
-    pub const START_CORKED: FlagSet = capi::PA_STREAM_START_CORKED;
-
-    /// Interpolate the latency for this stream. When enabled, [`Stream::get_latency`] and
-    /// [`Stream::get_time`] will try to estimate the current record/playback time based on the
-    /// local time that passed since the last timing info update. Using this option has the
-    /// advantage of not requiring a whole round trip when the current playback/recording time is
-    /// needed. Consider using this option when requesting latency information frequently. This is
-    /// especially useful on long latency network connections. It makes a lot of sense to combine
-    /// this option with [`AUTO_TIMING_UPDATE`](constant.AUTO_TIMING_UPDATE.html).
-    ///
-    /// [`Stream::get_latency`]: ../struct.Stream.html#method.get_latency
-    /// [`Stream::get_time`]: ../struct.Stream.html#method.get_time
-    pub const INTERPOLATE_TIMING: FlagSet = capi::PA_STREAM_INTERPOLATE_TIMING;
-
-    /// Don’t force the time to increase monotonically. If this option is enabled,
-    /// [`Stream::get_time`] will not necessarily return always monotonically increasing time
-    /// values on each call. This may confuse applications which cannot deal with time going
-    /// ‘backwards’, but has the advantage that bad transport latency estimations that caused the
-    /// time to jump ahead can be corrected quickly, without the need to wait.
-    ///
-    /// [`Stream::get_time`]: ../struct.Stream.html#method.get_time
-    pub const NOT_MONOTONIC: FlagSet = capi::PA_STREAM_NOT_MONOTONIC;
-
-    /// If set timing update requests are issued periodically automatically. Combined with
-    /// [`INTERPOLATE_TIMING`] you will be able to query the current time and latency with
-    /// [`Stream::get_time`] and [`Stream::get_latency`] at all times without a packet round trip.
-    ///
-    /// [`INTERPOLATE_TIMING`]: constant.INTERPOLATE_TIMING.html
-    /// [`Stream::get_time`]: ../struct.Stream.html#method.get_time
-    /// [`Stream::get_latency`]: ../struct.Stream.html#method.get_latency
-    pub const AUTO_TIMING_UPDATE: FlagSet = capi::PA_STREAM_AUTO_TIMING_UPDATE;
-
-    /// Don’t remap channels by their name, instead map them simply by their index. Implies
-    /// [`NO_REMIX_CHANNELS`](constant.NO_REMIX_CHANNELS.html).
-    pub const NO_REMAP_CHANNELS: FlagSet = capi::PA_STREAM_NO_REMAP_CHANNELS;
-
-    /// When remapping channels by name, don’t upmix or downmix them to related channels. Copy them
-    /// into matching channels of the device 1:1.
-    pub const NO_REMIX_CHANNELS: FlagSet = capi::PA_STREAM_NO_REMIX_CHANNELS;
-
-    /// Use the sample format of the sink/device this stream is being connected to, and possibly
-    /// ignore the format the sample spec contains -- but you still have to pass a valid value in it
-    /// as a hint to PulseAudio what would suit your stream best. If this is used you should query
-    /// the used sample format after creating the stream by using [`Stream::get_sample_spec`]. Also,
-    /// if you specified manual buffer metrics it is recommended to update them with
-    /// [`Stream::set_buffer_attr`] to compensate for the changed frame sizes.
-    ///
-    /// When creating streams with [`Stream::new_extended`], this flag has no effect. If you specify
-    /// a format with PCM encoding, and you want the server to choose the sample format, then you
-    /// should leave the sample format unspecified in the [`format::Info`] object. This also means
-    /// that you can’t use [`format::Info::new_from_sample_spec`], because that function always sets
-    /// the sample format.
-    ///
-    /// [`Stream::get_sample_spec`]: ../struct.Stream.html#method.get_sample_spec
-    /// [`Stream::set_buffer_attr`]: ../struct.Stream.html#method.set_buffer_attr
-    /// [`Stream::new_extended`]: ../struct.Stream.html#method.new_extended
-    /// [`format::Info`]: ../../format/struct.Info.html
-    /// [`format::Info::new_from_sample_spec`]: ../../format/struct.Info.html#method.new_from_sample_spec
-    pub const FIX_FORMAT: FlagSet = capi::PA_STREAM_FIX_FORMAT;
-
-    /// Use the sample rate of the sink, and possibly ignore the rate the sample spec contains.
-    /// Usage similar to [`FIX_FORMAT`].
-    ///
-    /// When creating streams with [`Stream::new_extended`], this flag has no effect. If you specify
-    /// a format with PCM encoding, and you want the server to choose the sample rate, then you
-    /// should leave the rate unspecified in the [`format::Info`] object. This also means that you
-    /// can’t use [`format::Info::new_from_sample_spec`], because that function always sets the
-    /// sample rate.
-    ///
-    /// [`FIX_FORMAT`]: constant.FIX_FORMAT.html
-    /// [`Stream::new_extended`]: ../struct.Stream.html#method.new_extended
-    /// [`format::Info`]: ../../format/struct.Info.html
-    /// [`format::Info::new_from_sample_spec`]: ../../format/struct.Info.html#method.new_from_sample_spec
-    pub const FIX_RATE: FlagSet = capi::PA_STREAM_FIX_RATE;
-
-    /// Use the number of channels and the channel map of the sink, and possibly ignore the number
-    /// of channels and the map the sample spec and the passed channel map contains. Usage similar
-    /// to [`FIX_FORMAT`].
-    ///
-    /// When creating streams with [`Stream::new_extended`], this flag has no effect. If you specify
-    /// a format with PCM encoding, and you want the server to choose the channel count and/or
-    /// channel map, then you should leave the channels and/or the channel map unspecified in the
-    /// [`format::Info`] object. This also means that you can’t use
-    /// [`format::Info::new_from_sample_spec`], because that function always sets the channel
-    /// count (but if you only want to leave the channel map unspecified, then
-    /// [`format::Info::new_from_sample_spec`] works, because the channel map parameter is
-    /// optional).
-    ///
-    /// [`FIX_FORMAT`]: constant.FIX_FORMAT.html
-    /// [`Stream::new_extended`]: ../struct.Stream.html#method.new_extended
-    /// [`format::Info`]: ../../format/struct.Info.html
-    /// [`format::Info::new_from_sample_spec`]: ../../format/struct.Info.html#method.new_from_sample_spec
-    pub const FIX_CHANNELS: FlagSet = capi::PA_STREAM_FIX_CHANNELS;
-
-    /// Don’t allow moving of this stream to another sink/device. Useful if you use any of the
-    /// `Fix*` flags and want to make sure that resampling never takes place -- which might happen
-    /// if the stream is moved to another sink/source with a different sample spec/channel map.
-    pub const DONT_MOVE: FlagSet = capi::PA_STREAM_DONT_MOVE;
-
-    /// Allow dynamic changing of the sampling rate during playback with
-    /// [`Stream::update_sample_rate`].
-    ///
-    /// [`Stream::update_sample_rate`]: ../struct.Stream.html#method.update_sample_rate
-    pub const VARIABLE_RATE: FlagSet = capi::PA_STREAM_VARIABLE_RATE;
-
-    /// Find peaks instead of resampling.
-    pub const PEAK_DETECT: FlagSet = capi::PA_STREAM_PEAK_DETECT;
-
-    /// Create in muted state. If neither [`START_UNMUTED`] nor this is specified, it is left to the
-    /// server to decide whether to create the stream in muted or in un-muted state.
-    ///
-    /// [`START_UNMUTED`]: constant.START_UNMUTED.html
-    pub const START_MUTED: FlagSet = capi::PA_STREAM_START_MUTED;
-
-    /// Try to adjust the latency of the sink/source based on the requested buffer metrics and
-    /// adjust buffer metrics accordingly. Also see [`def::BufferAttr`]. This option may not be
-    /// specified at the same time as [`EARLY_REQUESTS`](constant.EARLY_REQUESTS.html).
-    ///
-    /// [`def::BufferAttr`]: ../../def/struct.BufferAttr.html
-    pub const ADJUST_LATENCY: FlagSet = capi::PA_STREAM_ADJUST_LATENCY;
-
-    /// Enable compatibility mode for legacy clients that rely on a “classic” hardware device
-    /// fragment-style playback model. If this option is set, the `minreq` value of the buffer
-    /// metrics gets a new meaning: instead of just specifying that no requests asking for less new
-    /// data than this value will be made to the client it will also guarantee that requests are
-    /// generated as early as this limit is reached. This flag should only be set in very few
-    /// situations where compatibility with a fragment-based playback model needs to be kept and the
-    /// client applications cannot deal with data requests that are delayed to the latest moment
-    /// possible. (Usually these are programs that use usleep() or a similar call in their playback
-    /// loops instead of sleeping on the device itself.) Also see [`def::BufferAttr`]. This option
-    /// may not be specified at the same time as [`ADJUST_LATENCY`](constant.ADJUST_LATENCY.html).
-    ///
-    /// [`def::BufferAttr`]: ../../def/struct.BufferAttr.html
-    pub const EARLY_REQUESTS: FlagSet = capi::PA_STREAM_EARLY_REQUESTS;
-
-    /// If set this stream won’t be taken into account when it is checked whether the device this
-    /// stream is connected to should auto-suspend.
-    pub const DONT_INHIBIT_AUTO_SUSPEND: FlagSet = capi::PA_STREAM_DONT_INHIBIT_AUTO_SUSPEND;
-
-    /// Create in unmuted state. If neither [`START_MUTED`] nor this is specified, it is left to the
-    /// server to decide whether to create the stream in muted or in unmuted state.
-    ///
-    /// [`START_MUTED`]: constant.START_MUTED.html
-    pub const START_UNMUTED: FlagSet = capi::PA_STREAM_START_UNMUTED;
-
-    /// If the sink/source this stream is connected to is suspended during the creation of this
-    /// stream, cause it to fail. If the sink/source is being suspended during creation of this
-    /// stream, make sure this stream is terminated.
-    pub const FAIL_ON_SUSPEND: FlagSet = capi::PA_STREAM_FAIL_ON_SUSPEND;
-
-    /// If a volume is passed when this stream is created, consider it relative to the sink’s
-    /// current volume, never as absolute device volume. If this is not specified the volume will be
-    /// consider absolute when the sink is in flat volume mode, relative otherwise.
-    pub const RELATIVE_VOLUME: FlagSet = capi::PA_STREAM_RELATIVE_VOLUME;
-
-    /// Used to tag content that will be rendered by passthrough sinks. The data will be left as is
-    /// and not reformatted, resampled.
-    pub const PASSTHROUGH: FlagSet = capi::PA_STREAM_PASSTHROUGH;
+    use super::Flags;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::NOFLAGS` instead")]
+    pub const NOFLAGS: Flags = Flags::NOFLAGS;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::START_CORKED` instead")]
+    pub const START_CORKED: Flags = Flags::START_CORKED;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::INTERPOLATE_TIMING` instead")]
+    pub const INTERPOLATE_TIMING: Flags = Flags::INTERPOLATE_TIMING;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::NOT_MONOTONIC` instead")]
+    pub const NOT_MONOTONIC: Flags = Flags::NOT_MONOTONIC;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::AUTO_TIMING_UPDATE` instead")]
+    pub const AUTO_TIMING_UPDATE: Flags = Flags::AUTO_TIMING_UPDATE;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::NO_REMAP_CHANNELS` instead")]
+    pub const NO_REMAP_CHANNELS: Flags = Flags::NO_REMAP_CHANNELS;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::NO_REMIX_CHANNELS` instead")]
+    pub const NO_REMIX_CHANNELS: Flags = Flags::NO_REMIX_CHANNELS;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::FIX_FORMAT` instead")]
+    pub const FIX_FORMAT: Flags = Flags::FIX_FORMAT;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::FIX_RATE` instead")]
+    pub const FIX_RATE: Flags = Flags::FIX_RATE;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::FIX_CHANNELS` instead")]
+    pub const FIX_CHANNELS: Flags = Flags::FIX_CHANNELS;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::DONT_MOVE` instead")]
+    pub const DONT_MOVE: Flags = Flags::DONT_MOVE;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::VARIABLE_RATE` instead")]
+    pub const VARIABLE_RATE: Flags = Flags::VARIABLE_RATE;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::PEAK_DETECT` instead")]
+    pub const PEAK_DETECT: Flags = Flags::PEAK_DETECT;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::START_MUTED` instead")]
+    pub const START_MUTED: Flags = Flags::START_MUTED;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::ADJUST_LATENCY` instead")]
+    pub const ADJUST_LATENCY: Flags = Flags::ADJUST_LATENCY;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::EARLY_REQUESTS` instead")]
+    pub const EARLY_REQUESTS: Flags = Flags::EARLY_REQUESTS;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::DONT_INHIBIT_AUTO_SUSPEND` instead")]
+    pub const DONT_INHIBIT_AUTO_SUSPEND: Flags = Flags::DONT_INHIBIT_AUTO_SUSPEND;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::START_UNMUTED` instead")]
+    pub const START_UNMUTED: Flags = Flags::START_UNMUTED;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::FAIL_ON_SUSPEND` instead")]
+    pub const FAIL_ON_SUSPEND: Flags = Flags::FAIL_ON_SUSPEND;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::RELATIVE_VOLUME` instead")]
+    pub const RELATIVE_VOLUME: Flags = Flags::RELATIVE_VOLUME;
+
+    #[deprecated(since = "2.17.0", note = "use `Flags::PASSTHROUGH` instead")]
+    pub const PASSTHROUGH: Flags = Flags::PASSTHROUGH;
 }
 
 /// Common event names supplied to the [`set_event_callback`] callback.
@@ -542,6 +702,36 @@ pub mod event_names {
     pub const EVENT_FORMAT_LOST: &str = capi::PA_STREAM_EVENT_FORMAT_LOST;
 }
 
+/// A cork/uncork request, as given to the [`set_cork_request_callback`] callback.
+///
+/// Such requests are sent by policy modules, for instance when _module-role-cork_ corks music
+/// streams upon a phone stream starting. Applications should cork or uncork themselves
+/// accordingly (for instance pausing their playback).
+///
+/// [`set_cork_request_callback`]: struct.Stream.html#method.set_cork_request_callback
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CorkRequest {
+    /// Requested to cork ([`event_names::EVENT_REQUEST_CORK`]).
+    ///
+    /// [`event_names::EVENT_REQUEST_CORK`]: event_names/constant.EVENT_REQUEST_CORK.html
+    Cork,
+    /// Requested to uncork ([`event_names::EVENT_REQUEST_UNCORK`]).
+    ///
+    /// [`event_names::EVENT_REQUEST_UNCORK`]: event_names/constant.EVENT_REQUEST_UNCORK.html
+    Uncork,
+}
+
+impl CorkRequest {
+    /// Gets the request corresponding to an event name, if any.
+    fn from_event_name(name: &str) -> Option<Self> {
+        match name {
+            event_names::EVENT_REQUEST_CORK => Some(CorkRequest::Cork),
+            event_names::EVENT_REQUEST_UNCORK => Some(CorkRequest::Uncork),
+            _ => None,
+        }
+    }
+}
+
 /// Result type for the [`Stream::Peek`](struct.Stream.html#method.peek) method. See documentation
 /// of the method itself for more information.
 #[derive(Debug)]
@@ -554,6 +744,17 @@ pub enum PeekResult<'a> {
     Data(&'a [u8]),
 }
 
+/// How [`Stream::read_all`](struct.Stream.html#method.read_all) handles holes in the data.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HolePolicy {
+    /// Appends silence (for the stream’s sample format) in place of the hole.
+    Silence,
+    /// Skips over the hole, appending nothing.
+    Skip,
+    /// Fails with [`error::Code::NoData`](../error/enum.Code.html#variant.NoData).
+    Fail,
+}
+
 /// Result type for [`Stream::get_latency`](struct.Stream.html#method.get_latency).
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Latency {
@@ -562,6 +763,212 @@ pub enum Latency {
     Negative(MicroSeconds),
 }
 
+/// Timing facts for synchronising audio playback with video.
+///
+/// Returned by [`Stream::get_av_sync_info`](struct.Stream.html#method.get_av_sync_info).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AvSync {
+    /// The current playback time of the stream (see `Stream::get_time`).
+    pub play_time: MicroSeconds,
+    /// The total stream latency (see `Stream::get_latency`), i.e. how long it will take for data
+    /// written now to be heard. This is zero should the latency be negative (possible only for
+    /// monitoring streams).
+    pub sink_latency: MicroSeconds,
+}
+
+/// A snapshot of various stream facts.
+///
+/// Returned by [`Stream::status`](struct.Stream.html#method.status).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Status {
+    /// Whether or not the stream has been corked.
+    pub corked: bool,
+    /// Whether or not the sink or source the stream is connected to has been suspended.
+    pub suspended: bool,
+    /// Index of the sink or source the stream is connected to, if known.
+    pub device_index: Option<u32>,
+    /// Number of bytes requested by the server that have not yet been written, if known.
+    pub writable_size: Option<usize>,
+    /// Number of bytes that may be read with `peek`, if known.
+    pub readable_size: Option<usize>,
+    /// Number of buffer overflows since stream creation.
+    pub overflow_count: u64,
+    /// Number of buffer underflows since stream creation.
+    pub underflow_count: u64,
+    /// Whether or not data is being passed on to the device (not underrun), from the timing info.
+    /// (Only for playback streams).
+    ///
+    /// `None` if no timing info has been received yet.
+    pub playing: Option<bool>,
+    /// Bytes handed to the sink since the last underrun, or since playback started again after it,
+    /// from the timing info. (Only for playback streams).
+    ///
+    /// `None` if no timing info has been received yet.
+    pub since_underrun: Option<i64>,
+}
+
+/// Timing configuration for a stream, a builder for the flags and callback of the standard timing
+/// setup, as applied with [`Stream::apply_timing_config`].
+///
+/// By default, both [`Flags::AUTO_TIMING_UPDATE`] and [`Flags::INTERPOLATE_TIMING`] are enabled,
+/// i.e. the server is automatically asked for timing updates, and the time is interpolated in
+/// between them, giving a smoothly progressing time (see [`Stream::playback_position`]).
+///
+/// [`Stream::apply_timing_config`]: struct.Stream.html#method.apply_timing_config
+/// [`Stream::playback_position`]: struct.Stream.html#method.playback_position
+/// [`Flags::AUTO_TIMING_UPDATE`]: struct.Flags.html#associatedconstant.AUTO_TIMING_UPDATE
+/// [`Flags::INTERPOLATE_TIMING`]: struct.Flags.html#associatedconstant.INTERPOLATE_TIMING
+pub struct TimingConfig {
+    flags: Flags,
+    latency_update: Option<Box<dyn FnMut() + 'static>>,
+}
+
+impl Default for TimingConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimingConfig {
+    /// Creates the standard timing configuration, with automatic updates and interpolation.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            flags: Flags::AUTO_TIMING_UPDATE | Flags::INTERPOLATE_TIMING,
+            latency_update: None,
+        }
+    }
+
+    fn set_flag(mut self, flag: Flags, enable: bool) -> Self {
+        self.flags.set(flag, enable);
+        self
+    }
+
+    /// Sets whether or not to interpolate the time between timing updates
+    /// ([`Flags::INTERPOLATE_TIMING`]).
+    ///
+    /// [`Flags::INTERPOLATE_TIMING`]: struct.Flags.html#associatedconstant.INTERPOLATE_TIMING
+    #[inline]
+    pub fn interpolate(self, enable: bool) -> Self {
+        self.set_flag(Flags::INTERPOLATE_TIMING, enable)
+    }
+
+    /// Sets whether or not the server is automatically asked for timing updates
+    /// ([`Flags::AUTO_TIMING_UPDATE`]).
+    ///
+    /// [`Flags::AUTO_TIMING_UPDATE`]: struct.Flags.html#associatedconstant.AUTO_TIMING_UPDATE
+    #[inline]
+    pub fn auto_update(self, enable: bool) -> Self {
+        self.set_flag(Flags::AUTO_TIMING_UPDATE, enable)
+    }
+
+    /// Sets whether or not the time is guaranteed to increase monotonically (the default), with
+    /// disabling this setting [`Flags::NOT_MONOTONIC`].
+    ///
+    /// [`Flags::NOT_MONOTONIC`]: struct.Flags.html#associatedconstant.NOT_MONOTONIC
+    #[inline]
+    pub fn monotonic(self, enable: bool) -> Self {
+        self.set_flag(Flags::NOT_MONOTONIC, !enable)
+    }
+
+    /// Sets a callback to be called upon each timing update (see
+    /// [`Stream::set_latency_update_callback`]).
+    ///
+    /// [`Stream::set_latency_update_callback`]:
+    /// struct.Stream.html#method.set_latency_update_callback
+    pub fn on_latency_update<F>(mut self, callback: F) -> Self
+        where F: FnMut() + 'static
+    {
+        self.latency_update = Some(Box::new(callback));
+        self
+    }
+
+    /// Gets the stream flags of the configuration.
+    #[inline]
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+}
+
+/// Reasons for failure of checked stream creation, with [`Stream::try_new`].
+///
+/// [`Stream::try_new`]: struct.Stream.html#method.try_new
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConnectError {
+    /// The name contained a nul byte, and thus could not be passed to the C API.
+    InvalidName,
+    /// The channel map has a different number of channels to the sample spec.
+    ChannelMismatch {
+        /// Number of channels of the sample spec.
+        spec: u8,
+        /// Number of channels of the channel map.
+        map: u8,
+    },
+    /// The underlying library failed to create the stream, with the context’s error.
+    CreationFailed(PAErr),
+}
+
+impl std::fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ConnectError::InvalidName => write!(f, "invalid stream name (contains a nul byte)"),
+            ConnectError::ChannelMismatch { spec, map } => write!(f,
+                "channel map has {} channels, but the sample spec has {}", map, spec),
+            ConnectError::CreationFailed(e) => write!(f, "failed to create stream: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConnectError {}
+
+/// Reasons for failure of a write, with [`Stream::write`] and its variants.
+///
+/// This converts into a [`PAErr`] (giving an `Invalid` error for the checks performed by this
+/// binding), for use with the `?` operator in functions returning such errors.
+///
+/// [`Stream::write`]: struct.Stream.html#method.write
+/// [`PAErr`]: ../error/struct.PAErr.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WriteError {
+    /// The length of the data is not a multiple of the stream’s sample spec frame size.
+    Misaligned {
+        /// Length of the data, in bytes.
+        len: usize,
+        /// Frame size of the stream’s sample spec, in bytes.
+        frame_size: usize,
+    },
+    /// The range of data to write is empty, or extends beyond the end of the buffer.
+    InvalidRange,
+    /// The underlying library refused the write, with the given error.
+    Failed(PAErr),
+}
+
+impl std::fmt::Display for WriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            WriteError::Misaligned { len, frame_size } => write!(f,
+                "length of {} bytes is not a multiple of the frame size of {} bytes", len,
+                frame_size),
+            WriteError::InvalidRange => write!(f, "invalid range of data to write"),
+            WriteError::Failed(e) => write!(f, "failed to write: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+impl From<WriteError> for PAErr {
+    fn from(e: WriteError) -> Self {
+        match e {
+            WriteError::Misaligned { .. } | WriteError::InvalidRange => {
+                PAErr::from(error::Code::Invalid)
+            },
+            WriteError::Failed(e) => e,
+        }
+    }
+}
+
 impl Stream {
     /// Creates a new, unconnected stream with the specified name and sample type.
     ///
@@ -584,7 +991,32 @@ impl Stream {
         let p_map = map.map_or(null::<capi::pa_channel_map>(), |m| m.as_ref());
 
         let ptr = unsafe { capi::pa_stream_new(ctx.ptr, c_name.as_ptr(), ss.as_ref(), p_map) };
-        match ptr.is_null() { false => Some(Self::from_raw(ptr)), true => None }
+        match ptr.is_null() { false => Some(Self::from_raw(ptr, ctx)), true => None }
+    }
+
+    /// Creates a new, unconnected stream, as with [`new`](#method.new), but checking the
+    /// parameters first, and giving the reason for any failure.
+    ///
+    /// Should both a channel map and sample spec be given, their number of channels must agree,
+    /// otherwise [`ConnectError::ChannelMismatch`] is returned, rather than leaving the server to
+    /// reject the combination with an obscure error.
+    ///
+    /// [`ConnectError::ChannelMismatch`]: enum.ConnectError.html#variant.ChannelMismatch
+    pub fn try_new(ctx: &mut Context, name: &str, ss: &sample::Spec,
+        map: Option<&channelmap::Map>) -> Result<Self, ConnectError>
+    {
+        check_channels(ss, map)?;
+        // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
+        // as_ptr() giving dangling pointers!
+        let c_name = CString::new(name).or(Err(ConnectError::InvalidName))?;
+
+        let p_map = map.map_or(null::<capi::pa_channel_map>(), |m| m.as_ref());
+
+        let ptr = unsafe { capi::pa_stream_new(ctx.ptr, c_name.as_ptr(), ss.as_ref(), p_map) };
+        match ptr.is_null() {
+            false => Ok(Self::from_raw(ptr, ctx)),
+            true => Err(ConnectError::CreationFailed(ctx.errno())),
+        }
     }
 
     /// Creates a new, unconnected stream with the specified name and sample type, and specify the
@@ -597,12 +1029,22 @@ impl Stream {
     /// * `ss`: The desired sample format
     /// * `map`: The desired channel map, or `None` for default
     /// * `proplist`: The initial property list
+    ///
+    /// Returns `None` on failure, including if `name` contains a nul byte, or if the sample spec
+    /// or channel map is invalid (see [`Context::errno`] for the reason in that case).
+    ///
+    /// The property list itself is not checked here, since a [`Proplist`] cannot hold an invalid
+    /// entry: keys and values are already validated as they are set (see [`Proplist::set_str`]).
+    ///
+    /// [`Context::errno`]: ../context/struct.Context.html#method.errno
+    /// [`Proplist`]: ../proplist/struct.Proplist.html
+    /// [`Proplist::set_str`]: ../proplist/struct.Proplist.html#method.set_str
     pub fn new_with_proplist(ctx: &mut Context, name: &str, ss: &sample::Spec,
-        map: Option<&channelmap::Map>, proplist: &mut Proplist) -> Option<Self>
+        map: Option<&channelmap::Map>, proplist: &Proplist) -> Option<Self>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
-        let c_name = CString::new(name.clone()).unwrap();
+        let c_name = CString::new(name).ok()?;
 
         let p_map = map.map_or(null::<capi::pa_channel_map>(), |m| m.as_ref());
 
@@ -610,7 +1052,7 @@ impl Stream {
             capi::pa_stream_new_with_proplist(ctx.ptr, c_name.as_ptr(), ss.as_ref(),
                 p_map, proplist.0.ptr)
         };
-        match ptr.is_null() { false => Some(Self::from_raw(ptr)), true => None }
+        match ptr.is_null() { false => Some(Self::from_raw(ptr, ctx)), true => None }
     }
 
     /// Creates a new, unconnected stream with the specified name, the set of formats this client
@@ -625,38 +1067,72 @@ impl Stream {
     /// * `name`: A name for this stream
     /// * `formats`: The list of formats that can be provided
     /// * `proplist`: The initial property list
+    ///
+    /// The formats are only borrowed, the caller remains responsible for them; the server copies
+    /// what it needs. Servers too old to support format negotiation (protocol version < 21) only
+    /// accept a single PCM format, which is then used as a plain sample spec (and channel map).
+    ///
+    /// Returns `None` on failure, including if `name` contains a nul byte, if `formats` is empty,
+    /// or if the formats are not supported (see [`Context::errno`] for the reason in that case).
+    ///
+    /// [`Context::errno`]: ../context/struct.Context.html#method.errno
     pub fn new_extended(ctx: &mut Context, name: &str, formats: &[&format::Info],
-        proplist: &mut Proplist) -> Option<Self>
+        proplist: &Proplist) -> Option<Self>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
-        let c_name = CString::new(name.clone()).unwrap();
+        let c_name = CString::new(name).ok()?;
+
+        // The C API asserts upon an empty list
+        if formats.is_empty() {
+            return None;
+        }
 
         // Create array of format::InfoInternal pointers from provided array of format::Info
         // pointers.
-        let mut info_ptrs: Vec<*const capi::pa_format_info> = Vec::with_capacity(formats.len());
-        for format in formats {
-            info_ptrs.push(format.ptr as *const capi::pa_format_info);
-        }
+        let info_ptrs: Vec<*const capi::pa_format_info> = formats.iter()
+            .map(|f| f.ptr as *const capi::pa_format_info)
+            .collect();
 
         let ptr = unsafe {
             capi::pa_stream_new_extended(ctx.ptr, c_name.as_ptr(), info_ptrs.as_ptr(),
                 info_ptrs.len() as u32, proplist.0.ptr)
         };
-        match ptr.is_null() { false => Some(Self::from_raw(ptr)), true => None }
+        match ptr.is_null() { false => Some(Self::from_raw(ptr, ctx)), true => None }
     }
 
     /// Creates a new `Stream` from an existing [`StreamInternal`](enum.StreamInternal.html) pointer.
     #[inline]
-    fn from_raw(ptr: *mut StreamInternal) -> Self {
+    fn from_raw(ptr: *mut StreamInternal, ctx: &Context) -> Self {
         assert_eq!(false, ptr.is_null());
-        Self { ptr: ptr, cb_ptrs: Default::default() }
+        let mut stream = Self { ptr: ptr, cb_ptrs: Default::default(),
+            xrun_counts: Default::default(), event_handlers: Default::default(),
+            _mainloop: Rc::clone(&ctx.mainloop), disconnected: false };
+        // Install the counting wrappers, even without user callbacks
+        stream.set_overflow_callback(None);
+        stream.set_underflow_callback(None);
+        stream
+    }
+
+    /// Gets the context the stream belongs to.
+    ///
+    /// The returned object holds its own reference on the underlying context, thus dropping it
+    /// does not affect any other `Context` object for the context, and it may be compared with
+    /// these to check for being the same context. Note that multi-use callbacks set through it are
+    /// cleared upon it being dropped, see [`Context`].
+    ///
+    /// [`Context`]: ../context/struct.Context.html
+    pub fn get_context(&self) -> Context {
+        let ptr = unsafe { capi::pa_stream_get_context(self.ptr) };
+        assert!(!ptr.is_null());
+        unsafe { crate::capi::pa_context_ref(ptr) };
+        Context::from_raw(ptr, Rc::clone(&self._mainloop))
     }
 
     /// Gets the current state of the stream.
     #[inline]
     pub fn get_state(&self) -> State {
-        unsafe { capi::pa_stream_get_state(self.ptr).into() }
+        unsafe { capi::pa_stream_get_state(self.ptr) }.into()
     }
 
     /// Gets the sink input resp. source output index this stream is identified in the server with.
@@ -735,10 +1211,43 @@ impl Stream {
         }
     }
 
+    /// Gets a snapshot of various stream facts in one go.
+    ///
+    /// This is a shortcut for calling [`is_corked`], [`is_suspended`], [`get_device_index`],
+    /// [`writable_size`], [`readable_size`], [`get_overflow_count`], [`get_underflow_count`] and
+    /// [`get_timing_info`] individually, with a single error path. The timing info based fields
+    /// are `None` until a timing info update has completed (see [`get_timing_info`]).
+    ///
+    /// Returns an error if the stream is not connected (is not in the [`State::Ready`] state).
+    ///
+    /// [`is_corked`]: #method.is_corked
+    /// [`is_suspended`]: #method.is_suspended
+    /// [`get_device_index`]: #method.get_device_index
+    /// [`writable_size`]: #method.writable_size
+    /// [`readable_size`]: #method.readable_size
+    /// [`get_overflow_count`]: #method.get_overflow_count
+    /// [`get_underflow_count`]: #method.get_underflow_count
+    /// [`get_timing_info`]: #method.get_timing_info
+    /// [`State::Ready`]: enum.State.html#variant.Ready
+    pub fn status(&self) -> Result<Status, PAErr> {
+        let timing = unsafe { capi::pa_stream_get_timing_info(self.ptr).as_ref() };
+        Ok(Status {
+            corked: self.is_corked()?,
+            suspended: self.is_suspended()?,
+            device_index: self.get_device_index(),
+            writable_size: self.writable_size(),
+            readable_size: self.readable_size(),
+            overflow_count: self.get_overflow_count(),
+            underflow_count: self.get_underflow_count(),
+            playing: timing.map(|t| t.playing != 0),
+            since_underrun: timing.map(|t| t.since_underrun),
+        })
+    }
+
     /// Connects the stream to a sink.
     ///
     /// It is strongly recommended to pass `None` in both `dev` and `volume` and to set neither
-    /// [`flags::START_MUTED`] nor [`flags::START_UNMUTED`] -- unless these options are directly
+    /// [`Flags::START_MUTED`] nor [`Flags::START_UNMUTED`] -- unless these options are directly
     /// dependent on user input or configuration.
     ///
     /// If you follow this rule then the sound server will have the full flexibility to choose the
@@ -767,14 +1276,81 @@ impl Stream {
     /// * `sync_stream`: Synchronize this stream with the specified one, or
     ///   `None` for a standalone stream.
     ///
-    /// [`flags::START_MUTED`]: flags/constant.START_MUTED.html
-    /// [`flags::START_UNMUTED`]: flags/constant.START_UNMUTED.html
+    /// [`Flags::START_MUTED`]: struct.Flags.html#associatedconstant.START_MUTED
+    /// [`Flags::START_UNMUTED`]: struct.Flags.html#associatedconstant.START_UNMUTED
     /// [`context::introspect::Introspector::set_sink_input_volume`]:
     /// ../context/struct.Context.html#method.set_sink_input_volume
     /// [`context::introspect::Introspector::get_sink_info_by_name`]:
     /// ../context/struct.Context.html#method.get_sink_info_by_name
     pub fn connect_playback(&mut self, dev: Option<&str>, attr: Option<&def::BufferAttr>,
-        flags: FlagSet, volume: Option<&ChannelVolumes>, sync_stream: Option<&mut Self>)
+        flags: Flags, volume: Option<&ChannelVolumes>, sync_stream: Option<&mut Self>)
+        -> Result<(), PAErr>
+    {
+        let p_sync = sync_stream.map_or(null_mut::<StreamInternal>(), |s| s.ptr);
+        self.connect_playback_inner(dev, attr, flags, volume, p_sync)
+    }
+
+    /// Connects the stream to a sink, sample-synchronized with another playback stream.
+    ///
+    /// This is equivalent to [`connect_playback`] with a `sync_stream`, but checks the constraints
+    /// on the stream to synchronize with (the master): it must belong to the same context as this
+    /// stream, giving an [`Invalid`] error otherwise, and it must already be connected, giving a
+    /// [`BadState`] error otherwise. Synchronized streams are typically connected corked (see
+    /// [`Flags::START_CORKED`]), then uncorked together once all are ready.
+    ///
+    /// # Params
+    ///
+    /// * `master`: The playback stream to synchronize with
+    /// * `dev`: Name of the sink to connect to, or `None` to let the server decide
+    /// * `attr`: Buffering attributes, or `None` for default
+    /// * `flags`: Additional flags, or `0` for default
+    /// * `volume`: Initial volume, or `None` for default
+    ///
+    /// [`connect_playback`]: #method.connect_playback
+    /// [`Invalid`]: ../error/enum.Code.html#variant.Invalid
+    /// [`BadState`]: ../error/enum.Code.html#variant.BadState
+    /// [`Flags::START_CORKED`]: struct.Flags.html#associatedconstant.START_CORKED
+    pub fn connect_playback_synced_to(&mut self, master: &Self, dev: Option<&str>,
+        attr: Option<&def::BufferAttr>, flags: Flags, volume: Option<&ChannelVolumes>)
+        -> Result<(), PAErr>
+    {
+        let same_context = unsafe {
+            capi::pa_stream_get_context(self.ptr) == capi::pa_stream_get_context(master.ptr)
+        };
+        if !same_context {
+            return Err(PAErr::from(error::Code::Invalid));
+        }
+        if !master.get_state().is_good() {
+            return Err(PAErr::from(error::Code::BadState));
+        }
+        self.connect_playback_inner(dev, attr, flags, volume, master.ptr)
+    }
+
+    /// Connects the stream to a sink, requesting the given overall latency.
+    ///
+    /// This is a convenience wrapper around [`connect_playback`], requesting a target buffer length
+    /// (`tlength`) equivalent to `latency`, leaving all other buffer attributes to the server, and
+    /// adding [`Flags::ADJUST_LATENCY`] to `extra_flags`, such that the server configures the
+    /// overall latency (the sink latency plus the buffer) to match.
+    ///
+    /// Note, this is a request, not a guarantee. The server may choose different attributes, for
+    /// instance if the latency requested is lower than the sink supports. Once the stream is
+    /// ready, use [`get_buffer_attr`] to obtain the attributes actually negotiated.
+    ///
+    /// [`connect_playback`]: #method.connect_playback
+    /// [`get_buffer_attr`]: #method.get_buffer_attr
+    /// [`Flags::ADJUST_LATENCY`]: struct.Flags.html#associatedconstant.ADJUST_LATENCY
+    pub fn connect_playback_with_latency(&mut self, dev: Option<&str>, latency: MicroSeconds,
+        extra_flags: Flags, volume: Option<&ChannelVolumes>) -> Result<(), PAErr>
+    {
+        let spec = self.get_sample_spec().ok_or(PAErr::from(error::Code::BadState))?;
+        let attr = latency_buffer_attr(spec, latency, Direction::Playback);
+        self.connect_playback_inner(dev, Some(&attr), extra_flags | Flags::ADJUST_LATENCY, volume,
+            null_mut::<StreamInternal>())
+    }
+
+    fn connect_playback_inner(&mut self, dev: Option<&str>, attr: Option<&def::BufferAttr>,
+        flags: Flags, volume: Option<&ChannelVolumes>, p_sync: *mut StreamInternal)
         -> Result<(), PAErr>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
@@ -786,11 +1362,10 @@ impl Stream {
 
         let p_attr = attr.map_or(null::<capi::pa_buffer_attr>(), |a| a.as_ref());
         let p_vol = volume.map_or(null::<capi::pa_cvolume>(), |v| v.as_ref());
-        let p_sync = sync_stream.map_or(null_mut::<StreamInternal>(), |s| s.ptr);
         let p_dev = dev.map_or(null::<c_char>(), |_| c_dev.as_ptr() as *const c_char);
 
         let r = unsafe {
-            capi::pa_stream_connect_playback(self.ptr, p_dev, p_attr, flags, p_vol, p_sync)
+            capi::pa_stream_connect_playback(self.ptr, p_dev, p_attr, flags.bits(), p_vol, p_sync)
         };
         match r {
             0 => Ok(()),
@@ -806,7 +1381,7 @@ impl Stream {
     /// * `attr`: Buffering attributes, or `None` for default
     /// * `flags`: Additional flags, or `0` for default
     pub fn connect_record(&mut self, dev: Option<&str>, attr: Option<&def::BufferAttr>,
-        flags: FlagSet) -> Result<(), PAErr>
+        flags: Flags) -> Result<(), PAErr>
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -818,12 +1393,51 @@ impl Stream {
         let p_attr = attr.map_or(null::<capi::pa_buffer_attr>(), |a| a.as_ref());
         let p_dev = dev.map_or(null::<c_char>(), |_| c_dev.as_ptr() as *const c_char);
 
-        match unsafe { capi::pa_stream_connect_record(self.ptr, p_dev, p_attr, flags) } {
+        match unsafe { capi::pa_stream_connect_record(self.ptr, p_dev, p_attr, flags.bits()) } {
             0 => Ok(()),
             e => Err(PAErr(e)),
         }
     }
 
+    /// Connects the stream to a source, requesting the given latency.
+    ///
+    /// This is the record equivalent of [`connect_playback_with_latency`], requesting a fragment
+    /// size (`fragsize`) equivalent to `latency`, with [`Flags::ADJUST_LATENCY`] added to
+    /// `extra_flags`. As there, this is a request, not a guarantee; use [`get_buffer_attr`] once
+    /// the stream is ready to obtain the attributes actually negotiated.
+    ///
+    /// [`connect_playback_with_latency`]: #method.connect_playback_with_latency
+    /// [`get_buffer_attr`]: #method.get_buffer_attr
+    /// [`Flags::ADJUST_LATENCY`]: struct.Flags.html#associatedconstant.ADJUST_LATENCY
+    pub fn connect_record_with_latency(&mut self, dev: Option<&str>, latency: MicroSeconds,
+        extra_flags: Flags) -> Result<(), PAErr>
+    {
+        let spec = self.get_sample_spec().ok_or(PAErr::from(error::Code::BadState))?;
+        let attr = latency_buffer_attr(spec, latency, Direction::Record);
+        self.connect_record(dev, Some(&attr), extra_flags | Flags::ADJUST_LATENCY)
+    }
+
+    /// Connects the stream to the monitor source of a sink, for recording what the sink plays.
+    ///
+    /// This is a convenience wrapper around [`connect_record`], connecting to the source named by
+    /// appending `.monitor` to the sink name, per the naming convention used by the server for
+    /// monitor sources. (The exact monitor source name of a sink can also be obtained from its
+    /// [`SinkInfo`]).
+    ///
+    /// # Params
+    ///
+    /// * `sink_name`: Name of the sink whose monitor source to connect to
+    /// * `attr`: Buffering attributes, or `None` for default
+    /// * `flags`: Additional flags, or `0` for default
+    ///
+    /// [`connect_record`]: #method.connect_record
+    /// [`SinkInfo`]: ../context/introspect/struct.SinkInfo.html
+    pub fn connect_record_monitor(&mut self, sink_name: &str, attr: Option<&def::BufferAttr>,
+        flags: Flags) -> Result<(), PAErr>
+    {
+        self.connect_record(Some(&monitor_source_name(sink_name)), attr, flags)
+    }
+
     /// Makes this stream a sample upload stream.
     ///
     /// (See [`scache`](../context/scache/index.html)).
@@ -847,7 +1461,10 @@ impl Stream {
     /// Disconnects a stream from a source/sink.
     pub fn disconnect(&mut self) -> Result<(), PAErr> {
         match unsafe { capi::pa_stream_disconnect(self.ptr) } {
-            0 => Ok(()),
+            0 => {
+                self.disconnected = true;
+                Ok(())
+            },
             e => Err(PAErr(e)),
         }
     }
@@ -948,7 +1565,7 @@ impl Stream {
     /// # Params
     ///
     /// * `data`: The data to write. The length must be in multiples of the stream’s sample spec
-    ///   frame size.
+    ///   frame size, otherwise [`WriteError::Misaligned`] is returned.
     /// * `free_cb`: A cleanup routine for the data or `None` to request an internal copy of the
     ///   data.
     /// * `offset`: Offset for seeking. Must be `0` for upload streams. Must be in multiples of the
@@ -958,18 +1575,18 @@ impl Stream {
     /// [`SeekMode::Relative`]: enum.SeekMode.html#Relative.v
     /// [`begin_write`]: #method.begin_write
     /// [`write`]: #method.write
+    /// [`WriteError::Misaligned`]: enum.WriteError.html#variant.Misaligned
     pub fn write(&mut self, data: &[u8], free_cb: Option<def::FreeCb>, offset: i64,
-        seek: SeekMode) -> Result<(), PAErr>
+        seek: SeekMode) -> Result<(), WriteError>
     {
-        debug_assert_eq!(0, data.len().checked_rem(self.get_sample_spec().unwrap().frame_size())
-            .unwrap());
+        self.check_frame_aligned(data.len())?;
         let r = unsafe {
             capi::pa_stream_write(self.ptr, data.as_ptr() as *const c_void, data.len(), free_cb,
                 offset, seek)
         };
         match r {
             0 => Ok(()),
-            e => Err(PAErr(e)),
+            e => Err(WriteError::Failed(PAErr(e))),
         }
     }
 
@@ -990,10 +1607,76 @@ impl Stream {
     /// [`begin_write`]: #method.begin_write
     /// [`write`]: #method.write
     #[inline(always)]
-    pub fn write_copy(&mut self, data: &[u8], offset: i64, seek: SeekMode) -> Result<(), PAErr> {
+    pub fn write_copy(&mut self, data: &[u8], offset: i64, seek: SeekMode)
+        -> Result<(), WriteError>
+    {
         self.write(data, None, offset, seek)
     }
 
+    /// Writes all of the given data to the server (for playback streams), blocking until done.
+    ///
+    /// This is a convenience for simple “play this whole buffer” use. It repeatedly writes (copies)
+    /// as much of the remaining data as the server will currently accept (per [`writable_size`]),
+    /// iterating the (standard) mainloop, blocking, whenever the server will accept no more, until
+    /// all of the data has been written. Note, this thus dispatches any other events of the
+    /// mainloop in the meantime, including running callbacks.
+    ///
+    /// `seek` applies to the first chunk written only, subsequent chunks follow on from it (i.e.
+    /// use [`SeekMode::Relative`]). As with [`write`], the length of `data` must be in multiples of
+    /// the stream’s sample spec frame size.
+    ///
+    /// Returns an error if writing fails, if the stream is not connected, or if iterating the
+    /// mainloop fails. Should the mainloop be quit while waiting, [`error::Code::Killed`] is
+    /// returned. In these cases some of the data may already have been written.
+    ///
+    /// [`writable_size`]: #method.writable_size
+    /// [`write`]: #method.write
+    /// [`SeekMode::Relative`]: enum.SeekMode.html#Relative.v
+    /// [`error::Code::Killed`]: ../error/enum.Code.html#variant.Killed
+    pub fn write_all(&mut self, data: &[u8], mainloop: &mut standard::Mainloop, seek: SeekMode)
+        -> Result<(), PAErr>
+    {
+        let ptr = self.ptr;
+        write_all_chunked(data, seek,
+            || match unsafe { capi::pa_stream_writable_size(ptr) } {
+                std::usize::MAX => None,
+                r => Some(r),
+            },
+            |chunk, seek| self.write_copy(chunk, 0, seek).map_err(PAErr::from),
+            || match mainloop.iterate(true) {
+                standard::IterateResult::Success(_) => Ok(()),
+                standard::IterateResult::Quit(_) => Err(PAErr::from(error::Code::Killed)),
+                standard::IterateResult::Err(e) => Err(e),
+            })
+    }
+
+    /// Writes the given number of frames of silence to the server (for playback streams).
+    ///
+    /// This is useful to keep the stream fed during gaps in the audio, avoiding underruns. The
+    /// silence is in the stream’s actual (negotiated) sample format, per
+    /// [`sample::Spec::silence_value`], and the data is written (copied) at the current write
+    /// index.
+    ///
+    /// Returns a [`BadState`] error if the sample spec is not yet known, or an [`Invalid`] error if
+    /// the amount of data is too large. Writing zero frames does nothing.
+    ///
+    /// [`sample::Spec::silence_value`]: ../sample/struct.Spec.html#method.silence_value
+    /// [`BadState`]: ../error/enum.Code.html#variant.BadState
+    /// [`Invalid`]: ../error/enum.Code.html#Invalid.v
+    pub fn write_silence(&mut self, frames: usize) -> Result<(), PAErr> {
+        let spec = match self.get_sample_spec() {
+            Some(spec) if spec.is_valid() => *spec,
+            _ => return Err(PAErr::from(error::Code::BadState)),
+        };
+        if frames == 0 {
+            return Ok(());
+        }
+        let len = frames.checked_mul(spec.frame_size())
+            .ok_or(PAErr::from(error::Code::Invalid))?;
+        let silence = vec![spec.silence_value(); len];
+        self.write_copy(&silence, 0, SeekMode::Relative).map_err(PAErr::from)
+    }
+
     /// Writes some data to the server (for playback streams).
     ///
     /// This function does exactly the same as [`write`] with the only difference being that a void
@@ -1015,38 +1698,100 @@ impl Stream {
     /// [`write`]: #method.write
     #[cfg(any(feature = "pa_v6", feature = "dox"))]
     pub fn write_ext_free(&mut self, data: &[u8], free_cb: Option<(def::FreeCb, *mut c_void)>,
-        offset: i64, seek: SeekMode) -> Result<(), PAErr>
+        offset: i64, seek: SeekMode) -> Result<(), WriteError>
     {
         let (cb_f, cb_d) = match free_cb {
             Some((f, d)) => (Some(f), d),
             None => (None, null_mut::<c_void>()),
         };
-        debug_assert_eq!(0, data.len().checked_rem(self.get_sample_spec().unwrap().frame_size())
-            .unwrap());
+        self.check_frame_aligned(data.len())?;
         let r = unsafe {
             capi::pa_stream_write_ext_free(self.ptr, data.as_ptr() as *const c_void, data.len(),
                 cb_f, cb_d, offset, seek.into())
         };
         match r {
             0 => Ok(()),
-            e => Err(PAErr(e)),
+            e => Err(WriteError::Failed(PAErr(e))),
         }
     }
 
-    /// Reads the next fragment from the buffer (for recording streams).
+    /// Writes part of an owned buffer to the server (for playback streams), without copying.
     ///
-    /// This function returns one of the [`PeekResult`] variants - either [`Empty`], [`Hole`] or
-    /// [`Data`]:
+    /// Ownership of the whole of `data` is passed to the C library, which keeps it for as long as
+    /// it needs it, but only the bytes within `range` are written. The buffer (with its original
+    /// capacity) is dropped once the C library is done with it, which may happen within this call.
+    /// This avoids copying in cases like that of a decoder producing a large allocation of which
+    /// only part is audio to play. Should an error be returned, the buffer has been dropped.
     ///
-    ///  * If there is data at the current read index, the [`Data`] variant will be returned, which
-    ///    contains a slice giving a view of the data. (The length of this slice can be less or more
-    ///    than a complete fragment). This is pointing into an internal buffer, so obviously you
-    ///    must make a copy of it if you want to keep it.
-    ///  * If there is no data at the current read index, it means that either the buffer is empty
-    ///    or it contains a hole (that is, the write index is ahead of the read index but there’s no
-    ///    data where the read index points at). If the buffer is empty, the [`Empty`] result
-    ///    variant will be returned. If there is a hole, the [`Hole`] variant will be returned,
-    ///    containing the length of the hole in bytes.
+    /// # Params
+    ///
+    /// * `data`: The buffer holding the data to write.
+    /// * `range`: The range of bytes within `data` to write. [`WriteError::InvalidRange`] is
+    ///   returned if this is empty or extends beyond the end of `data`, and
+    ///   [`WriteError::Misaligned`] if its length is not a multiple of the stream’s sample spec
+    ///   frame size.
+    /// * `offset`: Offset for seeking. Must be `0` for upload streams.
+    /// * `seek`: Seek mode, must be [`SeekMode::Relative`] for upload streams.
+    ///
+    /// Available since PA version 6.
+    ///
+    /// [`SeekMode::Relative`]: enum.SeekMode.html#Relative.v
+    /// [`WriteError::InvalidRange`]: enum.WriteError.html#variant.InvalidRange
+    /// [`WriteError::Misaligned`]: enum.WriteError.html#variant.Misaligned
+    #[cfg(any(feature = "pa_v6", feature = "dox"))]
+    pub fn write_owned(&mut self, data: Vec<u8>, range: Range<usize>, offset: i64,
+        seek: SeekMode) -> Result<(), WriteError>
+    {
+        if range.start >= range.end || range.end > data.len() {
+            return Err(WriteError::InvalidRange);
+        }
+        self.check_frame_aligned(range.len())?;
+        let buffer = Box::into_raw(Box::new(OwnedBuffer::new(data)));
+        let r = unsafe {
+            let start = (*buffer).ptr.add(range.start);
+            capi::pa_stream_write_ext_free(self.ptr, start as *const c_void, range.len(),
+                Some(owned_buffer_free_cb), buffer as *mut c_void, offset, seek.into())
+        };
+        match r {
+            0 => Ok(()),
+            e => {
+                // The C library only takes ownership upon success
+                owned_buffer_free_cb(buffer as *mut c_void);
+                Err(WriteError::Failed(PAErr(e)))
+            },
+        }
+    }
+
+    /// Checks that a length of data to be written is a multiple of the stream’s frame size.
+    ///
+    /// Returns [`WriteError::Misaligned`](enum.WriteError.html#variant.Misaligned) if not. If the
+    /// frame size is not yet known, checking is left to the C library.
+    fn check_frame_aligned(&mut self, len: usize) -> Result<(), WriteError> {
+        let frame_size = match self.get_sample_spec() {
+            Some(ss) if ss.is_valid() => ss.frame_size(),
+            _ => 0,
+        };
+        match frame_size {
+            0 => Ok(()),
+            fs if len % fs == 0 => Ok(()),
+            fs => Err(WriteError::Misaligned { len: len, frame_size: fs }),
+        }
+    }
+
+    /// Reads the next fragment from the buffer (for recording streams).
+    ///
+    /// This function returns one of the [`PeekResult`] variants - either [`Empty`], [`Hole`] or
+    /// [`Data`]:
+    ///
+    ///  * If there is data at the current read index, the [`Data`] variant will be returned, which
+    ///    contains a slice giving a view of the data. (The length of this slice can be less or more
+    ///    than a complete fragment). This is pointing into an internal buffer, so obviously you
+    ///    must make a copy of it if you want to keep it.
+    ///  * If there is no data at the current read index, it means that either the buffer is empty
+    ///    or it contains a hole (that is, the write index is ahead of the read index but there’s no
+    ///    data where the read index points at). If the buffer is empty, the [`Empty`] result
+    ///    variant will be returned. If there is a hole, the [`Hole`] variant will be returned,
+    ///    containing the length of the hole in bytes.
     ///
     /// Use [`discard`] to actually remove the data from the buffer and move the read index
     /// forward. [`discard`] should not be called if the buffer is empty, but it should be called if
@@ -1076,6 +1821,30 @@ impl Stream {
         }
     }
 
+    /// Reads data from the server (for record streams), blocking until `max` bytes are collected.
+    ///
+    /// This is a convenience for simple “record this much” use, the counterpart to [`write_all`].
+    /// It repeatedly peeks and discards whatever fragments are available, appending them to `out`,
+    /// iterating the (standard) mainloop, blocking, whenever no data is available. Note, this thus
+    /// dispatches any other events of the mainloop in the meantime, including running callbacks.
+    ///
+    /// Holes in the data are handled according to `holes`. Since fragments can only be discarded
+    /// whole, any part of the final fragment beyond `max` bytes is lost.
+    ///
+    /// Returns early, successfully, should the stream be terminated. Returns an error if reading
+    /// fails, if the stream is not connected (or fails), or if iterating the mainloop fails. Should
+    /// the mainloop be quit while waiting, [`error::Code::Killed`] is returned. In all cases, the
+    /// data collected so far remains in `out`.
+    ///
+    /// [`write_all`]: #method.write_all
+    /// [`error::Code::Killed`]: ../error/enum.Code.html#variant.Killed
+    pub fn read_all(&mut self, mainloop: &mut standard::Mainloop, out: &mut Vec<u8>, max: usize,
+        holes: HolePolicy) -> Result<(), PAErr>
+    {
+        let silence = self.get_sample_spec().map_or(0, |spec| spec.silence_value());
+        read_all_chunked(&mut RecordSource { stream: self, mainloop }, out, max, holes, silence)
+    }
+
     /// Removes the current fragment on record streams.
     ///
     /// It is invalid to do this without first calling [`peek`](#method.peek).
@@ -1117,17 +1886,31 @@ impl Stream {
     /// Use this for notification when the playback buffer is empty after playing all the audio in
     /// the buffer. Please note that only one drain operation per stream may be issued at a time.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     ///
-    /// Panics if the underlying C function returns a null pointer.
-    pub fn drain(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
-        -> Operation<dyn FnMut(bool)>
+    /// Panics if the underlying C function returns a null pointer. See
+    /// [`try_drain`](#method.try_drain) for a non-panicking equivalent.
+    pub fn drain(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+    {
+        self.try_drain(callback).unwrap()
+    }
+
+    /// Drains a playback stream.
+    ///
+    /// This is the same as [`drain`](#method.drain), except that errors are returned rather than
+    /// causing a panic: the context’s error should the request fail to be issued (e.g. `BadState`
+    /// if the stream is not connected). The callback is not called upon error.
+    ///
+    /// An operation is returned whether or not a callback is given, allowing completion to be
+    /// tracked by polling its state.
+    pub fn try_drain(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, success_cb_proxy);
         let ptr = unsafe { capi::pa_stream_drain(self.ptr, cb_fn, cb_data) };
-        assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        self.issued_success_op(ptr, cb_data)
     }
 
     /// Requests a timing info structure update for a stream.
@@ -1135,21 +1918,22 @@ impl Stream {
     /// Use [`get_timing_info`] to get access to the raw timing data, or [`get_time`] or
     /// [`get_latency`] to get cleaned up values.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     ///
     /// [`get_timing_info`]: #method.get_timing_info
     /// [`get_time`]: #method.get_time
     /// [`get_latency`]: #method.get_latency
-    pub fn update_timing_info(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
-        -> Operation<dyn FnMut(bool)>
+    pub fn update_timing_info(&mut self,
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, success_cb_proxy);
         let ptr = unsafe { capi::pa_stream_update_timing_info(self.ptr, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Sets the callback function that is called whenever the state of the stream changes.
@@ -1182,9 +1966,13 @@ impl Stream {
 
     /// Sets the callback function that is called when a buffer overflow happens. (Only for playback
     /// streams).
+    ///
+    /// Overflows are counted regardless of whether or not a callback is set, see
+    /// [`get_overflow_count`](#method.get_overflow_count).
     pub fn set_overflow_callback(&mut self, callback: Option<Box<dyn FnMut() + 'static>>) {
         let saved = &mut self.cb_ptrs.overflow;
-        *saved = NotifyCb::new(callback);
+        *saved = NotifyCb::new(Some(counting_notify_cb(Arc::clone(&self.xrun_counts),
+            |c| &c.overflow, callback)));
         let (cb_fn, cb_data) = saved.get_capi_params(notify_cb_proxy);
         unsafe { capi::pa_stream_set_overflow_callback(self.ptr, cb_fn, cb_data); }
     }
@@ -1204,14 +1992,36 @@ impl Stream {
 
     /// Sets the callback function that is called when a buffer underflow happens.
     ///
+    /// Underflows are counted regardless of whether or not a callback is set, see
+    /// [`get_underflow_count`](#method.get_underflow_count).
+    ///
     /// (Only for playback streams).
     pub fn set_underflow_callback(&mut self, callback: Option<Box<dyn FnMut() + 'static>>) {
         let saved = &mut self.cb_ptrs.underflow;
-        *saved = NotifyCb::new(callback);
+        *saved = NotifyCb::new(Some(counting_notify_cb(Arc::clone(&self.xrun_counts),
+            |c| &c.underflow, callback)));
         let (cb_fn, cb_data) = saved.get_capi_params(notify_cb_proxy);
         unsafe { capi::pa_stream_set_underflow_callback(self.ptr, cb_fn, cb_data); }
     }
 
+    /// Gets the number of buffer overflows that have occurred since the stream was created.
+    ///
+    /// This is a polling alternative to the overflow callback, useful for metrics. (Only for
+    /// playback streams).
+    #[inline]
+    pub fn get_overflow_count(&self) -> u64 {
+        self.xrun_counts.overflow.load(Ordering::Relaxed)
+    }
+
+    /// Gets the number of buffer underflows that have occurred since the stream was created.
+    ///
+    /// This is a polling alternative to the underflow callback, useful for metrics. (Only for
+    /// playback streams).
+    #[inline]
+    pub fn get_underflow_count(&self) -> u64 {
+        self.xrun_counts.underflow.load(Ordering::Relaxed)
+    }
+
     /// Sets the callback function that is called when the server starts playback after an underrun
     /// or on initial startup.
     ///
@@ -1226,9 +2036,9 @@ impl Stream {
 
     /// Sets the callback function that is called whenever a latency information update happens.
     ///
-    /// Useful on [`flags::AUTO_TIMING_UPDATE`] streams only.
+    /// Useful on [`Flags::AUTO_TIMING_UPDATE`] streams only.
     ///
-    /// [`flags::AUTO_TIMING_UPDATE`]: flags/constant.AUTO_TIMING_UPDATE.html
+    /// [`Flags::AUTO_TIMING_UPDATE`]: struct.Flags.html#associatedconstant.AUTO_TIMING_UPDATE
     pub fn set_latency_update_callback(&mut self, callback: Option<Box<dyn FnMut() + 'static>>) {
         let saved = &mut self.cb_ptrs.latency_update;
         *saved = NotifyCb::new(callback);
@@ -1272,10 +2082,64 @@ impl Stream {
     /// can be extended at any time. Also, server modules may introduce additional message types so
     /// make sure that your callback function ignores messages it doesn’t know. Some well known
     /// event names can be found in the [`event_names`](event_names/index.html) submodule. It is
-    /// also given an (owned) property list.
+    /// also given a view of a property list, which is only valid for the duration of the callback
+    /// (use [`ProplistRef::to_owned`] to keep a copy).
+    ///
+    /// Cork and uncork requests are not given to this callback while a callback set with
+    /// [`set_cork_request_callback`] is in place.
+    ///
+    /// [`set_cork_request_callback`]: #method.set_cork_request_callback
+    /// [`ProplistRef::to_owned`]: ../proplist/struct.ProplistRef.html#method.to_owned
     pub fn set_event_callback(&mut self,
-        callback: Option<Box<dyn FnMut(String, Proplist) + 'static>>)
+        callback: Option<Box<dyn FnMut(String, ProplistRef) + 'static>>)
     {
+        self.event_handlers.borrow_mut().generic = callback.map(|callback| {
+            Rc::new(RefCell::new(callback)) as Rc<RefCell<dyn FnMut(String, ProplistRef)>>
+        });
+        self.install_event_dispatcher();
+    }
+
+    /// Sets the callback function that is called whenever the server requests that the stream be
+    /// corked or uncorked.
+    ///
+    /// This is a convenience layered upon the generic event callback (see
+    /// [`set_event_callback`]): the [`EVENT_REQUEST_CORK`] and [`EVENT_REQUEST_UNCORK`] events are
+    /// decoded and given to this callback, while all other events continue to be given to the
+    /// generic event callback, untouched. The two callbacks can be set independently.
+    ///
+    /// [`set_event_callback`]: #method.set_event_callback
+    /// [`EVENT_REQUEST_CORK`]: event_names/constant.EVENT_REQUEST_CORK.html
+    /// [`EVENT_REQUEST_UNCORK`]: event_names/constant.EVENT_REQUEST_UNCORK.html
+    pub fn set_cork_request_callback(&mut self,
+        callback: Option<Box<dyn FnMut(CorkRequest) + 'static>>)
+    {
+        self.event_handlers.borrow_mut().cork_request = callback.map(|callback| {
+            Rc::new(RefCell::new(callback)) as Rc<RefCell<dyn FnMut(CorkRequest)>>
+        });
+        self.install_event_dispatcher();
+    }
+
+    /// Installs (or removes, if no user callbacks are set) the event callback to dispatch events
+    /// between the user callbacks.
+    ///
+    /// When called from within a user event callback, the dispatcher is left in place, since
+    /// replacing it would free it while it is running. Should the callbacks have been unset, it
+    /// then simply finds no callbacks to dispatch to.
+    fn install_event_dispatcher(&mut self) {
+        let have_any = {
+            let handlers = self.event_handlers.borrow();
+            if handlers.dispatching {
+                return;
+            }
+            handlers.generic.is_some() || handlers.cork_request.is_some()
+        };
+        let callback: Option<Box<dyn FnMut(String, ProplistRef)>> = match have_any {
+            true => {
+                let handlers = Rc::clone(&self.event_handlers);
+                Some(Box::new(move |name, proplist| dispatch_event(&handlers, name, proplist)))
+            },
+            false => None,
+        };
         let saved = &mut self.cb_ptrs.event;
         *saved = EventCb::new(callback);
         let (cb_fn, cb_data) = saved.get_capi_params(event_cb_proxy);
@@ -1304,23 +2168,37 @@ impl Stream {
     /// The pause operation is executed as quickly as possible. If a cork is very quickly followed
     /// by an uncork, this might not actually have any effect on the stream that is output. You can
     /// use [`is_corked`] to find out whether the stream is currently paused or not. Normally a
-    /// stream will be created in uncorked state. If you pass [`flags::START_CORKED`] as a flag when
+    /// stream will be created in uncorked state. If you pass [`Flags::START_CORKED`] as a flag when
     /// connecting the stream, it will be created in corked state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     ///
-    /// Panics if the underlying C function returns a null pointer.
+    /// Panics if the underlying C function returns a null pointer. See
+    /// [`try_cork`](#method.try_cork) for a non-panicking equivalent.
     ///
     /// [`is_corked`]: #method.is_corked
-    /// [`flags::START_CORKED`]: flags/constant.START_CORKED.html
-    pub fn cork(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
-        -> Operation<dyn FnMut(bool)>
+    /// [`Flags::START_CORKED`]: struct.Flags.html#associatedconstant.START_CORKED
+    pub fn cork(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+    {
+        self.try_cork(callback).unwrap()
+    }
+
+    /// Pauses playback of this stream.
+    ///
+    /// This is the same as [`cork`](#method.cork), except that errors are returned rather than
+    /// causing a panic: the context’s error should the request fail to be issued (e.g. `BadState`
+    /// if the stream is not connected). The callback is not called upon error.
+    ///
+    /// An operation is returned whether or not a callback is given, allowing completion to be
+    /// tracked by polling its state.
+    pub fn try_cork(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, success_cb_proxy);
         let ptr = unsafe { capi::pa_stream_cork(self.ptr, true as i32, cb_fn, cb_data) };
-        assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        self.issued_success_op(ptr, cb_data)
     }
 
     /// Resumes playback of this stream.
@@ -1330,23 +2208,61 @@ impl Stream {
     /// The un-pause operation is executed as quickly as possible. If an uncork is very quickly
     /// followed by a cork, this might not actually have any effect on the stream that is output.
     /// You can use [`is_corked`] to find out whether the stream is currently paused or not.
-    /// Normally a stream will be created in uncorked state. If you pass [`flags::START_CORKED`] as
+    /// Normally a stream will be created in uncorked state. If you pass [`Flags::START_CORKED`] as
     /// a flag when connecting the stream, it will be created in corked state.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     ///
-    /// Panics if the underlying C function returns a null pointer.
+    /// Panics if the underlying C function returns a null pointer. See
+    /// [`try_uncork`](#method.try_uncork) for a non-panicking equivalent.
     ///
     /// [`is_corked`]: #method.is_corked
-    /// [`flags::START_CORKED`]: flags/constant.START_CORKED.html
-    pub fn uncork(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
-        -> Operation<dyn FnMut(bool)>
+    /// [`Flags::START_CORKED`]: struct.Flags.html#associatedconstant.START_CORKED
+    pub fn uncork(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+    {
+        self.try_uncork(callback).unwrap()
+    }
+
+    /// Resumes playback of this stream.
+    ///
+    /// This is the same as [`uncork`](#method.uncork), except that errors are returned rather than
+    /// causing a panic: the context’s error should the request fail to be issued (e.g. `BadState`
+    /// if the stream is not connected). The callback is not called upon error.
+    ///
+    /// An operation is returned whether or not a callback is given, allowing completion to be
+    /// tracked by polling its state.
+    pub fn try_uncork(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, success_cb_proxy);
         let ptr = unsafe { capi::pa_stream_cork(self.ptr, false as i32, cb_fn, cb_data) };
-        assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        self.issued_success_op(ptr, cb_data)
+    }
+
+    /// Pauses the stream if currently playing, or resumes it if currently paused.
+    ///
+    /// This is a convenience for a simple play/pause control, checking [`is_corked`] and then
+    /// calling [`cork`] or [`uncork`] as appropriate. Note that the corked state reported by
+    /// [`is_corked`] is updated as soon as the request is issued, thus toggling repeatedly works as
+    /// expected, even before the server has acknowledged the previous request.
+    ///
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
+    ///
+    /// Returns an error if the corked state cannot be determined (e.g. if the stream is not
+    /// connected), or if the request fails to be issued.
+    ///
+    /// [`is_corked`]: #method.is_corked
+    /// [`cork`]: #method.cork
+    /// [`uncork`]: #method.uncork
+    pub fn toggle_cork(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
+    {
+        match self.is_corked()? {
+            true => self.try_uncork(callback),
+            false => self.try_cork(callback),
+        }
     }
 
     /// Flushes the playback or record buffer of this stream.
@@ -1354,36 +2270,50 @@ impl Stream {
     /// This discards any audio data in the buffer. Most of the time you’re better off using the
     /// parameter `seek` of [`write`](#method.write) instead of this function.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     ///
-    /// Panics if the underlying C function returns a null pointer.
-    pub fn flush(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
-        -> Operation<dyn FnMut(bool)>
+    /// Panics if the underlying C function returns a null pointer. See
+    /// [`try_flush`](#method.try_flush) for a non-panicking equivalent.
+    pub fn flush(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+    {
+        self.try_flush(callback).unwrap()
+    }
+
+    /// Flushes the playback or record buffer of this stream.
+    ///
+    /// This is the same as [`flush`](#method.flush), except that errors are returned rather than
+    /// causing a panic: the context’s error should the request fail to be issued (e.g. `BadState`
+    /// if the stream is not connected). The callback is not called upon error.
+    ///
+    /// An operation is returned whether or not a callback is given, allowing completion to be
+    /// tracked by polling its state.
+    pub fn try_flush(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, success_cb_proxy);
         let ptr = unsafe { capi::pa_stream_flush(self.ptr, cb_fn, cb_data) };
-        assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        self.issued_success_op(ptr, cb_data)
     }
 
     /// Re-enables prebuffering if specified in the [`def::BufferAttr`] structure.
     ///
     /// Available for playback streams only.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     ///
     /// [`def::BufferAttr`]: ../def/struct.BufferAttr.html
-    pub fn prebuf(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
-        -> Operation<dyn FnMut(bool)>
+    pub fn prebuf(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, success_cb_proxy);
         let ptr = unsafe { capi::pa_stream_prebuf(self.ptr, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Requests immediate start of playback on this stream.
@@ -1391,40 +2321,125 @@ impl Stream {
     /// This disables prebuffering temporarily if specified in the [`def::BufferAttr`] structure.
     /// Available for playback streams only.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     ///
     /// [`def::BufferAttr`]: ../def/struct.BufferAttr.html
-    pub fn trigger(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
-        -> Operation<dyn FnMut(bool)>
+    pub fn trigger(&mut self, callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, success_cb_proxy);
         let ptr = unsafe { capi::pa_stream_trigger(self.ptr, cb_fn, cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Renames the stream.
     ///
-    /// The optional callback must accept a `bool`, which indicates success.
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
     ///
-    /// Panics if the underlying C function returns a null pointer.
-    pub fn set_name(&mut self, name: &str, callback: Option<Box<dyn FnMut(bool) + 'static>>)
-        -> Operation<dyn FnMut(bool)>
+    /// Panics if the name contains a nul byte, or if the underlying C function returns a null
+    /// pointer. See [`try_set_name`](#method.try_set_name) for a non-panicking equivalent.
+    pub fn set_name(&mut self, name: &str,
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
     {
-        // Warning: New CStrings will be immediately freed if not bound to a
-        // variable, leading to as_ptr() giving dangling pointers!
-        let c_name = CString::new(name.clone()).unwrap();
+        self.try_set_name(name, callback).unwrap()
+    }
+
+    /// Renames the stream, e.g. to reflect the track currently being played.
+    ///
+    /// The optional callback must accept a `Result`, which gives the error upon failure.
+    ///
+    /// This is the same as [`set_name`](#method.set_name), except that errors are returned rather
+    /// than causing a panic: an `Invalid` error if the name contains a nul byte, otherwise the
+    /// context’s error should the request fail to be issued (e.g. `BadState` if the stream is not
+    /// connected). The callback is not called upon error.
+    pub fn try_set_name(&mut self, name: &str,
+        callback: Option<Box<dyn FnOnce(Result<(), PAErr>) + 'static>>)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
+    {
+        // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
+        // as_ptr() giving dangling pointers!
+        let c_name = CString::new(name).or(Err(PAErr::from(error::Code::Invalid)))?;
 
         let (cb_fn, cb_data): (Option<extern "C" fn(_, _, _)>, _) =
             get_su_capi_params::<_, _>(callback, success_cb_proxy);
         let ptr = unsafe {
             capi::pa_stream_set_name(self.ptr, c_name.as_ptr(), cb_fn, cb_data)
         };
-        assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        self.issued_success_op(ptr, cb_data)
+    }
+
+    /// Wraps the operation pointer returned for a request taking a success callback, or, should it
+    /// be null (the request having failed to be issued), releases the callback, which will never
+    /// be called, and returns the context’s error.
+    fn issued_success_op(&self, ptr: *mut capi::pa_operation, cb_data: *mut c_void)
+        -> Result<Operation<dyn FnOnce(Result<(), PAErr>)>, PAErr>
+    {
+        match ptr.is_null() {
+            false => Ok(Operation::from_raw(ptr,
+                cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)),
+            true => {
+                if !cb_data.is_null() {
+                    drop(get_su_callback::<dyn FnOnce(Result<(), PAErr>)>(cb_data));
+                }
+                Err(PAErr::from_context(unsafe { capi::pa_stream_get_context(self.ptr) }))
+            },
+        }
+    }
+
+    /// Applies a timing configuration, returning the flags to include when connecting the stream.
+    ///
+    /// The configuration’s latency update callback, if any, is installed (replacing any set with
+    /// [`set_latency_update_callback`]). The flags only take effect upon connecting, thus this is
+    /// to be used before then, for instance:
+    ///
+    /// ```rust,ignore
+    /// let flags = stream.apply_timing_config(TimingConfig::new());
+    /// stream.connect_playback(None, None, flags | stream::Flags::START_CORKED, None, None)?;
+    /// ```
+    ///
+    /// [`set_latency_update_callback`]: #method.set_latency_update_callback
+    pub fn apply_timing_config(&mut self, config: TimingConfig) -> Flags {
+        if config.latency_update.is_some() {
+            self.set_latency_update_callback(config.latency_update);
+        }
+        config.flags
+    }
+
+    /// Gets the current playback/recording position, as a time.
+    ///
+    /// This is [`get_time`], but giving an [`error::Code::NoData`] error should no timing
+    /// information have been received yet. How accurate and up to date the position is depends on
+    /// the timing configuration used upon connecting (see [`TimingConfig`]):
+    ///
+    ///  * With both [`Flags::AUTO_TIMING_UPDATE`] and [`Flags::INTERPOLATE_TIMING`] (the standard
+    ///    configuration), the position progresses smoothly, being estimated from the local time
+    ///    passed since the latest update. The estimate is corrected upon each update, gradually
+    ///    (favouring smoothness over accuracy), and never goes backwards unless
+    ///    [`Flags::NOT_MONOTONIC`] is used. This suits tying UI operations to the audio clock.
+    ///  * With only [`Flags::AUTO_TIMING_UPDATE`], the position is that of the latest update, only
+    ///    advancing with each update, the interval between which grows over time, to over a second.
+    ///  * Without [`Flags::AUTO_TIMING_UPDATE`], the position only changes upon updates requested
+    ///    with [`update_timing_info`] (interpolated in between, if [`Flags::INTERPOLATE_TIMING`] is
+    ///    used). This is the most accurate upon each update, at the cost of a round trip.
+    ///
+    /// In all cases, the position is in the sound card clock domain, which usually runs at a
+    /// slightly different rate than the system clock.
+    ///
+    /// [`get_time`]: #method.get_time
+    /// [`update_timing_info`]: #method.update_timing_info
+    /// [`TimingConfig`]: struct.TimingConfig.html
+    /// [`error::Code::NoData`]: ../error/enum.Code.html#variant.NoData
+    /// [`Flags::AUTO_TIMING_UPDATE`]: struct.Flags.html#associatedconstant.AUTO_TIMING_UPDATE
+    /// [`Flags::INTERPOLATE_TIMING`]: struct.Flags.html#associatedconstant.INTERPOLATE_TIMING
+    /// [`Flags::NOT_MONOTONIC`]: struct.Flags.html#associatedconstant.NOT_MONOTONIC
+    #[inline]
+    pub fn playback_position(&self) -> Result<MicroSeconds, PAErr> {
+        self.get_time()?.ok_or(PAErr::from(error::Code::NoData))
     }
 
     /// Gets the current playback/recording time.
@@ -1434,37 +2449,36 @@ impl Stream {
     /// rate than the system clock.
     ///
     /// This function will usually only return new data if a timing info update has been received.
-    /// Only if timing interpolation has been requested ([`flags::INTERPOLATE_TIMING`]) the data
+    /// Only if timing interpolation has been requested ([`Flags::INTERPOLATE_TIMING`]) the data
     /// from the last timing update is used for an estimation of the current playback/recording time
     /// based on the local time that passed since the timing info structure has been acquired.
     ///
     /// The time value returned by this function is guaranteed to increase monotonically (the
     /// returned value is always greater or equal to the value returned by the last call). This
-    /// behaviour can be disabled by using [`flags::NOT_MONOTONIC`]. This may be desirable to better
+    /// behaviour can be disabled by using [`Flags::NOT_MONOTONIC`]. This may be desirable to better
     /// deal with bad estimations of transport latencies, but may have strange effects if the
     /// application is not able to deal with time going ‘backwards’.
     ///
-    /// The time interpolator activated by [`flags::INTERPOLATE_TIMING`] favours ‘smooth’ time
+    /// The time interpolator activated by [`Flags::INTERPOLATE_TIMING`] favours ‘smooth’ time
     /// graphs over accurate ones to improve the smoothness of UI operations that are tied to the
     /// audio clock. If accuracy is more important to you, you might need to estimate your timing
     /// based on the data from [`get_timing_info`] yourself or not work with interpolated timing at
     /// all and instead always query the server side for the most up to date timing with
     /// [`update_timing_info`].
     ///
-    /// If no timing information has been received yet this call will return `Ok(None)`. For more
-    /// details see [`get_timing_info`].
+    /// If no timing information has been received yet, or the time is otherwise unknown (the
+    /// [`MicroSeconds::INVALID`] sentinel), this call will return `Ok(None)`. For more details see
+    /// [`get_timing_info`].
     ///
+    /// [`MicroSeconds::INVALID`]: ../time/struct.MicroSeconds.html#associatedconstant.INVALID
     /// [`get_timing_info`]: #method.get_timing_info
     /// [`update_timing_info`]: #method.update_timing_info
-    /// [`flags::INTERPOLATE_TIMING`]: flags/constant.INTERPOLATE_TIMING.html
-    /// [`flags::NOT_MONOTONIC`]: flags/constant.NOT_MONOTONIC.html
+    /// [`Flags::INTERPOLATE_TIMING`]: struct.Flags.html#associatedconstant.INTERPOLATE_TIMING
+    /// [`Flags::NOT_MONOTONIC`]: struct.Flags.html#associatedconstant.NOT_MONOTONIC
     pub fn get_time(&self) -> Result<Option<MicroSeconds>, PAErr> {
         let mut r_usecs = MicroSeconds(0);
-        match unsafe { capi::pa_stream_get_time(self.ptr, &mut r_usecs.0) } {
-            0 => Ok(Some(r_usecs)),
-            e if e == PAErr::from(error::Code::NoData).0 => Ok(None),
-            e => Err(PAErr(e)),
-        }
+        let ret = unsafe { capi::pa_stream_get_time(self.ptr, &mut r_usecs.0) };
+        time_result(ret, r_usecs)
     }
 
     /// Determines the total stream latency.
@@ -1476,23 +2490,32 @@ impl Stream {
     /// samples are not yet played, in which case `Ok(Latency::Negative(usecs))` will be returned
     /// instead of `Ok(Latency::Positive(usecs))`
     ///
-    /// If no timing information has been received yet, this call will return `Ok(Latency::None)`.
+    /// If no timing information has been received yet, or the latency is otherwise unknown (the
+    /// [`MicroSeconds::INVALID`] sentinel), this call will return `Ok(Latency::None)`.
     ///
     /// For more details see [`get_timing_info`] and [`get_time`].
     ///
+    /// [`MicroSeconds::INVALID`]: ../time/struct.MicroSeconds.html#associatedconstant.INVALID
     /// [`get_time`]: #method.get_time
     /// [`get_timing_info`]: #method.get_timing_info
     pub fn get_latency(&self) -> Result<Latency, PAErr> {
         let mut r_usecs = MicroSeconds(0);
         let mut negative: i32 = 0;
-        match unsafe { capi::pa_stream_get_latency(self.ptr, &mut r_usecs.0, &mut negative) } {
-            0 => match negative {
-                1 => Ok(Latency::Negative(r_usecs)),
-                _ => Ok(Latency::Positive(r_usecs)),
-            },
-            e if e == PAErr::from(error::Code::NoData).0 => Ok(Latency::None),
-            e => Err(PAErr(e)),
-        }
+        let ret = unsafe { capi::pa_stream_get_latency(self.ptr, &mut r_usecs.0, &mut negative) };
+        latency_result(ret, r_usecs, negative)
+    }
+
+    /// Gets the current playback time and latency together, for A/V synchronisation.
+    ///
+    /// This combines [`get_time`] and [`get_latency`], both based upon the same latest timing
+    /// information. Returns [`error::Code::NoData`] should no timing information have been
+    /// received yet, rather than a partial result.
+    ///
+    /// [`get_time`]: #method.get_time
+    /// [`get_latency`]: #method.get_latency
+    /// [`error::Code::NoData`]: ../error/enum.Code.html#variant.NoData
+    pub fn get_av_sync_info(&self) -> Result<AvSync, PAErr> {
+        av_sync(self.get_time(), self.get_latency())
     }
 
     /// Gets the latest raw timing data structure.
@@ -1502,7 +2525,7 @@ impl Stream {
     /// this data structure may be requested using [`update_timing_info`].
     ///
     /// If no timing information has been received before (i.e. by requesting [`update_timing_info`]
-    /// or by using [`flags::AUTO_TIMING_UPDATE`]), this function will return `None` (as it will
+    /// or by using [`Flags::AUTO_TIMING_UPDATE`]), this function will return `None` (as it will
     /// also if an error occurs).
     ///
     /// Please note that the `write_index` member field (and only this field) is updated on each
@@ -1510,7 +2533,7 @@ impl Stream {
     ///
     /// [`update_timing_info`]: #method.update_timing_info
     /// [`write`]: #method.write
-    /// [`flags::AUTO_TIMING_UPDATE`]: flags/constant.AUTO_TIMING_UPDATE.html
+    /// [`Flags::AUTO_TIMING_UPDATE`]: struct.Flags.html#associatedconstant.AUTO_TIMING_UPDATE
     pub fn get_timing_info<'a>(&mut self) -> Option<&'a def::TimingInfo> {
         unsafe {
             let ptr = capi::pa_stream_get_timing_info(self.ptr);
@@ -1534,11 +2557,16 @@ impl Stream {
         }
     }
 
-    /// Gets a pointer to the stream’s format.
-    pub fn get_format_info(&self) -> Option<format::Info> {
+    /// Gets a view of the stream’s format.
+    ///
+    /// The underlying object is owned by the stream, thus the view borrows it. Use
+    /// [`InfoRef::to_owned`] to get a copy that can be kept.
+    ///
+    /// [`InfoRef::to_owned`]: ../format/struct.InfoRef.html#method.to_owned
+    pub fn get_format_info(&self) -> Option<format::InfoRef<'_>> {
         let ptr = unsafe { capi::pa_stream_get_format_info(self.ptr) };
         match ptr.is_null() {
-            false => Some(format::Info::from_raw_weak(ptr as *mut InfoInternal)),
+            false => Some(format::InfoRef::from_raw(ptr as *mut InfoInternal)),
             true => None,
         }
     }
@@ -1548,12 +2576,12 @@ impl Stream {
     /// Only valid after the stream has been connected successfully. This will return the actual
     /// configured buffering metrics, which may differ from what was requested during
     /// [`connect_record`] or [`connect_playback`]. This call will always return the actual
-    /// per-stream server-side buffer metrics, regardless whether [`flags::ADJUST_LATENCY`] is set
+    /// per-stream server-side buffer metrics, regardless whether [`Flags::ADJUST_LATENCY`] is set
     /// or not.
     ///
     /// [`connect_record`]: #method.connect_record
     /// [`connect_playback`]: #method.connect_playback
-    /// [`flags::ADJUST_LATENCY`]: flags/constant.ADJUST_LATENCY.html
+    /// [`Flags::ADJUST_LATENCY`]: struct.Flags.html#associatedconstant.ADJUST_LATENCY
     pub fn get_buffer_attr<'a>(&mut self) -> Option<&'a def::BufferAttr> {
         unsafe {
             let ptr = capi::pa_stream_get_buffer_attr(self.ptr);
@@ -1566,44 +2594,45 @@ impl Stream {
     /// The server might have chosen different buffer metrics then requested. The selected metrics
     /// may be queried with [`get_buffer_attr`] as soon as the callback is called. Only valid after
     /// the stream has been connected successfully. Please be aware of the slightly different
-    /// semantics of the call depending whether [`flags::ADJUST_LATENCY`] is set or not.
+    /// semantics of the call depending whether [`Flags::ADJUST_LATENCY`] is set or not.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     ///
     /// [`get_buffer_attr`]: #method.get_buffer_attr
-    /// [`flags::ADJUST_LATENCY`]: flags/constant.ADJUST_LATENCY.html
+    /// [`Flags::ADJUST_LATENCY`]: struct.Flags.html#associatedconstant.ADJUST_LATENCY
     pub fn set_buffer_attr<F>(&mut self, attr: &def::BufferAttr, callback: F)
-        -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_stream_set_buffer_attr(self.ptr, attr.as_ref(),
             Some(success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Changes the stream sampling rate during playback.
     ///
-    /// You need to pass [`flags::VARIABLE_RATE`] in the flags parameter of [`connect_playback`] if
+    /// You need to pass [`Flags::VARIABLE_RATE`] in the flags parameter of [`connect_playback`] if
     /// you plan to use this function. Only valid after the stream has been connected successfully.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     ///
     /// [`connect_playback`]: #method.connect_playback
-    /// [`flags::VARIABLE_RATE`]: flags/constant.VARIABLE_RATE.html
-    pub fn update_sample_rate<F>(&mut self, rate: u32, callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    /// [`Flags::VARIABLE_RATE`]: struct.Flags.html#associatedconstant.VARIABLE_RATE
+    pub fn update_sample_rate<F>(&mut self, rate: u32, callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_stream_update_sample_rate(self.ptr, rate,
             Some(success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Updates the property list of the sink input/source output of this stream, adding new entries.
@@ -1612,29 +2641,30 @@ impl Stream {
     /// [`new_with_proplist`] as possible instead a posteriori with this function, since that
     /// information may be used to route this stream to the right device.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
     ///
     /// [`new_with_proplist`]: #method.new_with_proplist
     pub fn update_proplist<F>(&mut self, mode: proplist::UpdateMode, proplist: &mut Proplist,
-        callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+        callback: F) -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe { capi::pa_stream_proplist_update(self.ptr, mode, proplist.0.ptr,
             Some(success_cb_proxy), cb_data) };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// Updates the property list of the sink input/source output of this stream, removing entries.
     ///
-    /// The callback must accept a `bool`, which indicates success.
+    /// The callback must accept a `Result`, which gives the error upon failure.
     ///
     /// Panics if the underlying C function returns a null pointer.
-    pub fn remove_proplist<F>(&mut self, keys: &[&str], callback: F) -> Operation<dyn FnMut(bool)>
-        where F: FnMut(bool) + 'static
+    pub fn remove_proplist<F>(&mut self, keys: &[&str], callback: F)
+        -> Operation<dyn FnOnce(Result<(), PAErr>)>
+        where F: FnOnce(Result<(), PAErr>) + 'static
     {
         // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
         // as_ptr() giving dangling pointers!
@@ -1651,13 +2681,13 @@ impl Stream {
         }
         c_key_ptrs.push(null());
 
-        let cb_data = box_closure_get_capi_ptr::<dyn FnMut(bool)>(Box::new(callback));
+        let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(Box::new(callback));
         let ptr = unsafe {
             capi::pa_stream_proplist_remove(self.ptr, c_key_ptrs.as_ptr(),
                 Some(success_cb_proxy), cb_data)
         };
         assert!(!ptr.is_null());
-        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(bool)>)
+        Operation::from_raw(ptr, cb_data as *mut Box<dyn FnOnce(Result<(), PAErr>)>)
     }
 
     /// For record streams connected to a monitor source: monitors only a very specific sink input
@@ -1681,26 +2711,312 @@ impl Stream {
     }
 }
 
+/// Compares identity, i.e. whether two objects refer to the same underlying stream.
+impl PartialEq for Stream {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl Eq for Stream {}
+
 impl Drop for Stream {
     fn drop(&mut self) {
-        // Throw away the `Result` from disconnecting, it may legitimately be bad if stream failed.
+        // Disconnect only if the user has not already done so, and the stream has not failed, thus
+        // the server side stream is not leaked. Throw away the `Result`, it may legitimately be
+        // bad should the context have failed.
         // See https://github.com/jnqnfe/pulse-binding-rust/issues/11
-        let _ = self.disconnect();
+        let ptr = self.ptr;
+        disconnect_on_drop(self.get_state(), self.disconnected, || {
+            let _ = unsafe { capi::pa_stream_disconnect(ptr) };
+        });
         unsafe { capi::pa_stream_unref(self.ptr) };
         self.ptr = null_mut::<StreamInternal>();
     }
 }
 
+/// Calls `disconnect` if a stream being dropped in the given state is still connected (or
+/// connecting), and has not already been disconnected by the user.
+fn disconnect_on_drop<F: FnOnce()>(state: State, disconnected: bool, disconnect: F) {
+    if state.is_good() && !disconnected {
+        disconnect();
+    }
+}
+
+/// Gets the name of the monitor source of the named sink.
+#[inline]
+fn monitor_source_name(sink_name: &str) -> String {
+    format!("{}.monitor", sink_name)
+}
+
+/// Wraps an optional overflow/underflow callback, such that each fire also increments the counter
+/// selected by `counter`.
+fn counting_notify_cb(counts: Arc<XrunCounts>, counter: fn(&XrunCounts) -> &AtomicU64,
+    mut callback: Option<Box<dyn FnMut() + 'static>>) -> Box<dyn FnMut() + 'static>
+{
+    Box::new(move || {
+        counter(&counts).fetch_add(1, Ordering::Relaxed);
+        if let Some(ref mut callback) = callback {
+            (callback)();
+        }
+    })
+}
+
+/// Dispatches an event to the appropriate user callback, if any.
+///
+/// No borrow of the handlers is held while the callback runs, thus it may (re)set them.
+fn dispatch_event(handlers: &RefCell<EventHandlers>, name: String, proplist: ProplistRef) {
+    let (cork_request, generic) = {
+        let mut handlers = handlers.borrow_mut();
+        handlers.dispatching = true;
+        (handlers.cork_request.clone(), handlers.generic.clone())
+    };
+    match (CorkRequest::from_event_name(&name), cork_request) {
+        (Some(request), Some(callback)) => (&mut *callback.borrow_mut())(request),
+        _ => {
+            if let Some(callback) = generic {
+                (&mut *callback.borrow_mut())(name, proplist);
+            }
+        },
+    }
+    handlers.borrow_mut().dispatching = false;
+}
+
+/// Implements [`Stream::write_all`], with the stream and mainloop interaction abstracted.
+///
+/// [`Stream::write_all`]: struct.Stream.html#method.write_all
+fn write_all_chunked<W, F, I>(mut data: &[u8], mut seek: SeekMode, mut writable_size: W,
+    mut write: F, mut wait: I) -> Result<(), PAErr>
+    where W: FnMut() -> Option<usize>,
+          F: FnMut(&[u8], SeekMode) -> Result<(), PAErr>,
+          I: FnMut() -> Result<(), PAErr>
+{
+    while !data.is_empty() {
+        match writable_size() {
+            None => return Err(PAErr::from(error::Code::BadState)),
+            Some(0) => wait()?,
+            Some(size) => {
+                let (chunk, rest) = data.split_at(std::cmp::min(size, data.len()));
+                write(chunk, seek)?;
+                data = rest;
+                seek = SeekMode::Relative;
+            },
+        }
+    }
+    Ok(())
+}
+
+/// The stream and mainloop interaction needed by [`read_all_chunked`], abstracted for testing.
+///
+/// [`read_all_chunked`]: fn.read_all_chunked.html
+trait ReadSource {
+    /// As with `Stream::readable_size`, but giving `Ok(None)` once the stream has been terminated,
+    /// and an error should the stream otherwise not be readable.
+    fn readable_size(&mut self) -> Result<Option<usize>, PAErr>;
+    fn peek<'a>(&mut self) -> Result<PeekResult<'a>, PAErr>;
+    fn discard(&mut self) -> Result<(), PAErr>;
+    /// Waits for more data, iterating the mainloop.
+    fn wait(&mut self) -> Result<(), PAErr>;
+}
+
+/// The stream and mainloop used by [`Stream::read_all`].
+///
+/// [`Stream::read_all`]: struct.Stream.html#method.read_all
+struct RecordSource<'a> {
+    stream: &'a mut Stream,
+    mainloop: &'a mut standard::Mainloop,
+}
+
+impl<'a> ReadSource for RecordSource<'a> {
+    fn readable_size(&mut self) -> Result<Option<usize>, PAErr> {
+        match self.stream.readable_size() {
+            Some(size) => Ok(Some(size)),
+            None => match self.stream.get_state() {
+                State::Terminated => Ok(None),
+                _ => Err(PAErr::from(error::Code::BadState)),
+            },
+        }
+    }
+
+    fn peek<'b>(&mut self) -> Result<PeekResult<'b>, PAErr> {
+        self.stream.peek()
+    }
+
+    fn discard(&mut self) -> Result<(), PAErr> {
+        self.stream.discard()
+    }
+
+    fn wait(&mut self) -> Result<(), PAErr> {
+        match self.mainloop.iterate(true) {
+            standard::IterateResult::Success(_) => Ok(()),
+            standard::IterateResult::Quit(_) => Err(PAErr::from(error::Code::Killed)),
+            standard::IterateResult::Err(e) => Err(e),
+        }
+    }
+}
+
+/// Implements [`Stream::read_all`], with the stream and mainloop interaction abstracted.
+///
+/// [`Stream::read_all`]: struct.Stream.html#method.read_all
+fn read_all_chunked<S: ReadSource>(source: &mut S, out: &mut Vec<u8>, max: usize,
+    holes: HolePolicy, silence: u8) -> Result<(), PAErr>
+{
+    let mut remaining = max;
+    while remaining > 0 {
+        match source.readable_size()? {
+            None => break,
+            Some(0) => source.wait()?,
+            Some(_) => {
+                match source.peek()? {
+                    PeekResult::Empty => {
+                        source.wait()?;
+                        continue;
+                    },
+                    PeekResult::Hole(size) => match holes {
+                        HolePolicy::Silence => {
+                            let len = std::cmp::min(size, remaining);
+                            out.resize(out.len() + len, silence);
+                            remaining -= len;
+                        },
+                        HolePolicy::Skip => {},
+                        HolePolicy::Fail => return Err(PAErr::from(error::Code::NoData)),
+                    },
+                    PeekResult::Data(data) => {
+                        let len = std::cmp::min(data.len(), remaining);
+                        out.extend_from_slice(&data[..len]);
+                        remaining -= len;
+                    },
+                }
+                source.discard()?;
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the channel counts of a sample spec and channel map (if any) agree.
+fn check_channels(ss: &sample::Spec, map: Option<&channelmap::Map>) -> Result<(), ConnectError> {
+    match map {
+        Some(map) if map.len() != ss.channels => {
+            Err(ConnectError::ChannelMismatch { spec: ss.channels, map: map.len() })
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Builds the buffer attributes requesting the given latency, for
+/// [`Stream::connect_playback_with_latency`] and [`Stream::connect_record_with_latency`].
+///
+/// The latency-determining attribute (`tlength` for playback, `fragsize` for record) is set to the
+/// equivalent number of bytes, at least one frame, with all others left to the server.
+///
+/// [`Stream::connect_playback_with_latency`]: struct.Stream.html#method.connect_playback_with_latency
+/// [`Stream::connect_record_with_latency`]: struct.Stream.html#method.connect_record_with_latency
+fn latency_buffer_attr(spec: &sample::Spec, latency: MicroSeconds, direction: Direction)
+    -> def::BufferAttr
+{
+    let bytes = std::cmp::max(spec.usec_to_bytes(latency), spec.frame_size());
+    let bytes = std::cmp::min(bytes, (std::u32::MAX - 1) as usize) as u32;
+    let mut attr = def::BufferAttr {
+        maxlength: std::u32::MAX,
+        tlength: std::u32::MAX,
+        prebuf: std::u32::MAX,
+        minreq: std::u32::MAX,
+        fragsize: std::u32::MAX,
+    };
+    match direction {
+        Direction::Record => attr.fragsize = bytes,
+        _ => attr.tlength = bytes,
+    }
+    attr
+}
+
+/// Implements [`Stream::get_av_sync_info`], combining the results of the time and latency queries.
+///
+/// [`Stream::get_av_sync_info`]: struct.Stream.html#method.get_av_sync_info
+fn av_sync(time: Result<Option<MicroSeconds>, PAErr>, latency: Result<Latency, PAErr>)
+    -> Result<AvSync, PAErr>
+{
+    let no_data = PAErr::from(error::Code::NoData);
+    let play_time = time?.ok_or(no_data)?;
+    let sink_latency = match latency? {
+        Latency::None => return Err(no_data),
+        Latency::Positive(usecs) => usecs,
+        Latency::Negative(_) => MicroSeconds(0),
+    };
+    Ok(AvSync { play_time: play_time, sink_latency: sink_latency })
+}
+
+/// Converts the result of `pa_stream_get_time`, mapping the ‘no data’ error (no timing info
+/// received yet) and the ‘invalid’ time sentinel to `None`.
+fn time_result(ret: i32, usecs: MicroSeconds) -> Result<Option<MicroSeconds>, PAErr> {
+    match ret {
+        0 => Ok(usecs.valid()),
+        e if e == PAErr::from(error::Code::NoData).0 => Ok(None),
+        e => Err(PAErr(e)),
+    }
+}
+
+/// Converts the result of `pa_stream_get_latency`, mapping the ‘no data’ error (no timing info
+/// received yet) and the ‘invalid’ time sentinel to `Latency::None`.
+fn latency_result(ret: i32, usecs: MicroSeconds, negative: i32) -> Result<Latency, PAErr> {
+    match ret {
+        0 => match (usecs.valid(), negative) {
+            (None, _) => Ok(Latency::None),
+            (Some(usecs), 1) => Ok(Latency::Negative(usecs)),
+            (Some(usecs), _) => Ok(Latency::Positive(usecs)),
+        },
+        e if e == PAErr::from(error::Code::NoData).0 => Ok(Latency::None),
+        e => Err(PAErr(e)),
+    }
+}
+
+/// The raw parts of a `Vec<u8>` whose ownership has been passed to the C library.
+#[cfg(any(feature = "pa_v6", feature = "dox"))]
+struct OwnedBuffer {
+    ptr: *mut u8,
+    len: usize,
+    capacity: usize,
+}
+
+#[cfg(any(feature = "pa_v6", feature = "dox"))]
+impl OwnedBuffer {
+    fn new(data: Vec<u8>) -> Self {
+        let mut data = std::mem::ManuallyDrop::new(data);
+        Self { ptr: data.as_mut_ptr(), len: data.len(), capacity: data.capacity() }
+    }
+
+    /// Reconstructs the original vector.
+    ///
+    /// Safety: Must only be done once, with no references to the data remaining.
+    unsafe fn into_vec(self) -> Vec<u8> {
+        Vec::from_raw_parts(self.ptr, self.len, self.capacity)
+    }
+}
+
+/// Free callback for buffers given to the C library with [`Stream::write_owned`], dropping the
+/// `OwnedBuffer` box given as the data pointer along with the vector it describes.
+///
+/// [`Stream::write_owned`]: struct.Stream.html#method.write_owned
+#[cfg(any(feature = "pa_v6", feature = "dox"))]
+extern "C"
+fn owned_buffer_free_cb(userdata: *mut c_void) {
+    let buffer = unsafe { Box::from_raw(userdata as *mut OwnedBuffer) };
+    drop(unsafe { buffer.into_vec() });
+}
+
 /// Proxy for completion success callbacks.
 ///
 /// Warning: This is for single-use cases only! It destroys the actual closure callback.
 extern "C"
-fn success_cb_proxy(_: *mut StreamInternal, success: i32, userdata: *mut c_void) {
-    let success_actual = match success { 0 => false, _ => true };
+fn success_cb_proxy(s: *mut StreamInternal, success: i32, userdata: *mut c_void) {
     let _ = std::panic::catch_unwind(|| {
+        let context = unsafe { capi::pa_stream_get_context(s) };
+        let result = crate::context::success_result(context, success);
         // Note, destroys closure callback after use - restoring outer box means it gets dropped
-        let mut callback = get_su_callback::<dyn FnMut(bool)>(userdata);
-        (callback)(success_actual);
+        let callback = get_su_callback::<dyn FnOnce(Result<(), PAErr>)>(userdata);
+        (callback)(result);
     });
 }
 
@@ -1742,9 +3058,759 @@ fn event_cb_proxy(_: *mut StreamInternal, name: *const c_char, proplist: *mut Pr
             let tmp = unsafe { CStr::from_ptr(name) };
             tmp.to_string_lossy().into_owned()
         };
-        let pl = Proplist::from_raw_weak(proplist);
+        // Note, the property list is owned by PA and only valid for the duration of the callback,
+        // hence only a view is given.
+        let pl = ProplistRef::from_raw(proplist);
 
         let callback = EventCb::get_callback(userdata);
         (callback)(n, pl);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use crate::mainloop::standard::Mainloop;
+
+    fn new_test_stream() -> (Mainloop, Context, Stream) {
+        let mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "test").unwrap();
+        let spec = sample::Spec { format: sample::SAMPLE_S16NE, channels: 2, rate: 44100 };
+        let stream = Stream::new(&mut context, "test", &spec, None).unwrap();
+        (mainloop, context, stream)
+    }
+
+    #[test]
+    fn write_frame_alignment() {
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        // Frame size is four bytes (two channels of 16-bit samples)
+        let misaligned = [0u8; 6];
+        assert_eq!(stream.write_copy(&misaligned, 0, SeekMode::Relative),
+            Err(WriteError::Misaligned { len: 6, frame_size: 4 }));
+        // Aligned data gets past the alignment check (failing only because not connected)
+        let aligned = [0u8; 8];
+        assert_eq!(stream.write_copy(&aligned, 0, SeekMode::Relative),
+            Err(WriteError::Failed(PAErr::from(error::Code::BadState))));
+
+        // Converted for use as a general error
+        assert_eq!(PAErr::from(WriteError::Misaligned { len: 6, frame_size: 4 }),
+            PAErr::from(error::Code::Invalid));
+        assert_eq!(PAErr::from(WriteError::Failed(PAErr::from(error::Code::BadState))),
+            PAErr::from(error::Code::BadState));
+    }
+
+    #[test]
+    #[cfg(any(feature = "pa_v6", feature = "dox"))]
+    fn write_owned_ranges() {
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        let invalid = Err(WriteError::InvalidRange);
+        let data = || vec![0u8; 16];
+        assert_eq!(stream.write_owned(data(), 4..4, 0, SeekMode::Relative), invalid);
+        assert_eq!(stream.write_owned(data(), 8..20, 0, SeekMode::Relative), invalid);
+        assert_eq!(stream.write_owned(data(), 2..8, 0, SeekMode::Relative),
+            Err(WriteError::Misaligned { len: 6, frame_size: 4 }));
+        // A valid range gets as far as the C library, which reports not being connected, with the
+        // buffer reclaimed and dropped
+        assert_eq!(stream.write_owned(data(), 4..12, 0, SeekMode::Relative),
+            Err(WriteError::Failed(PAErr::from(error::Code::BadState))));
+    }
+
+    /// Ownership round trips through the raw parts given to the C library, with odd capacities,
+    /// and through the free callback (useful under a leak or address sanitizer).
+    #[test]
+    #[cfg(any(feature = "pa_v6", feature = "dox"))]
+    fn owned_buffer_round_trip() {
+        for &(len, capacity) in &[(1, 1), (3, 7), (999, 1001), (0, 13), (4096, 4099)] {
+            let mut data = Vec::with_capacity(capacity);
+            data.extend((0..len).map(|i| i as u8));
+            let (ptr, capacity) = (data.as_ptr(), data.capacity());
+
+            let buffer = OwnedBuffer::new(data);
+            assert_eq!((buffer.ptr as *const u8, buffer.len, buffer.capacity),
+                (ptr, len, capacity));
+            let data = unsafe { buffer.into_vec() };
+            assert_eq!((data.as_ptr(), data.len(), data.capacity()), (ptr, len, capacity));
+            assert!(data.iter().enumerate().all(|(i, &b)| b == i as u8));
+
+            let buffer = Box::into_raw(Box::new(OwnedBuffer::new(data)));
+            owned_buffer_free_cb(buffer as *mut c_void);
+        }
+    }
+
+    #[test]
+    fn try_cork_flush_drain_unconnected() {
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        let bad_state = Some(PAErr::from(error::Code::BadState));
+        assert_eq!(stream.try_cork(None).err(), bad_state);
+        assert_eq!(stream.try_uncork(None).err(), bad_state);
+        assert_eq!(stream.try_flush(None).err(), bad_state);
+        assert_eq!(stream.try_drain(None).err(), bad_state);
+
+        // The callback is released, uncalled, upon error
+        let called = Rc::new(RefCell::new(false));
+        let called_ref = Rc::clone(&called);
+        let result = stream.try_cork(Some(Box::new(move |_| *called_ref.borrow_mut() = true)));
+        assert_eq!(result.err(), bad_state);
+        assert_eq!(*called.borrow(), false);
+        assert_eq!(Rc::strong_count(&called), 1);
+    }
+
+    #[test]
+    fn write_silence_unconnected() {
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        assert_eq!(stream.write_silence(0), Ok(()));
+        // Gets as far as the C library, which reports not being connected
+        assert_eq!(stream.write_silence(3), Err(PAErr::from(error::Code::BadState)));
+        assert_eq!(stream.write_silence(std::usize::MAX),
+            Err(PAErr::from(error::Code::Invalid)));
+    }
+
+    #[test]
+    fn get_context() {
+        let (_mainloop, context, stream) = new_test_stream();
+        let (mainloop2, mut context2, stream2) = new_test_stream();
+        assert!(context != context2);
+        assert!(stream != stream2);
+
+        // Distinct objects, each holding a reference, for the same context
+        let shared = stream.get_context();
+        assert!(shared == context);
+        assert!(stream2.get_context() == context2);
+        drop(shared);
+        assert_eq!(context.get_state(), crate::context::State::Unconnected);
+        drop(context);
+        let shared = stream.get_context();
+        assert_eq!(shared.get_state(), crate::context::State::Unconnected);
+        assert!(stream.get_context() == shared);
+        drop(shared);
+        assert_eq!(stream.get_state(), State::Unconnected);
+
+        // Callbacks set through an obtained object are cleared when it is dropped, while those set
+        // on the original remain
+        let calls = Rc::new(RefCell::new((0, 0)));
+        let mut shared = stream2.get_context();
+        let calls_ref = Rc::clone(&calls);
+        shared.set_state_callback(Some(Box::new(move || calls_ref.borrow_mut().0 += 1)));
+        drop(shared);
+        let _ = context2.connect(Some("unix:/nonexistent/native"),
+            crate::context::Flags::NOAUTOSPAWN, None);
+        assert_eq!(calls.borrow().0, 0);
+
+        let mut context3 = Context::new(&mainloop2, "test").unwrap();
+        let calls_ref = Rc::clone(&calls);
+        context3.set_state_callback(Some(Box::new(move || calls_ref.borrow_mut().1 += 1)));
+        let _ = context3.connect(Some("unix:/nonexistent/native"),
+            crate::context::Flags::NOAUTOSPAWN, None);
+        assert!(calls.borrow().1 > 0);
+        context3.set_state_callback(None);
+    }
+
+    #[test]
+    fn connect_record_unconnected() {
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        // The context is not connected, so both paths should fail cleanly with a bad state error
+        assert_eq!(stream.connect_record(None, None, Flags::NOFLAGS),
+            Err(PAErr::from(error::Code::BadState)));
+        assert_eq!(stream.connect_record_monitor("foo", None, Flags::NOFLAGS),
+            Err(PAErr::from(error::Code::BadState)));
+        assert_eq!(stream.get_state(), State::Unconnected);
+    }
+
+    #[test]
+    fn try_new_channel_check() {
+        let mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "test").unwrap();
+        let spec = sample::Spec { format: sample::SAMPLE_S16NE, channels: 2, rate: 44100 };
+
+        let mut stereo = channelmap::Map::default();
+        stereo.init_stereo();
+        assert!(Stream::try_new(&mut context, "test", &spec, Some(&stereo)).is_ok());
+        assert!(Stream::try_new(&mut context, "test", &spec, None).is_ok());
+
+        let mut mono = channelmap::Map::default();
+        mono.init_mono();
+        assert_eq!(Stream::try_new(&mut context, "test", &spec, Some(&mono)).err(),
+            Some(ConnectError::ChannelMismatch { spec: 2, map: 1 }));
+        assert_eq!(Stream::try_new(&mut context, "te\0st", &spec, None).err(),
+            Some(ConnectError::InvalidName));
+    }
+
+    #[test]
+    fn latency_buffer_attrs() {
+        // 20 ms at 48 kHz is 960 frames of four bytes
+        let spec = sample::Spec { format: sample::SAMPLE_S16NE, channels: 2, rate: 48000 };
+        let attr = latency_buffer_attr(&spec, MicroSeconds(20_000), Direction::Playback);
+        assert_eq!(attr.tlength, 3840);
+        assert_eq!((attr.maxlength, attr.prebuf, attr.minreq, attr.fragsize),
+            (std::u32::MAX, std::u32::MAX, std::u32::MAX, std::u32::MAX));
+
+        let attr = latency_buffer_attr(&spec, MicroSeconds(20_000), Direction::Record);
+        assert_eq!(attr.fragsize, 3840);
+        assert_eq!(attr.tlength, std::u32::MAX);
+
+        // Never less than a frame
+        let attr = latency_buffer_attr(&spec, MicroSeconds(0), Direction::Playback);
+        assert_eq!(attr.tlength, 4);
+
+        // The context is not connected, so connecting fails cleanly with a bad state error
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        assert_eq!(stream.connect_playback_with_latency(None, MicroSeconds(20_000), Flags::NOFLAGS,
+            None), Err(PAErr::from(error::Code::BadState)));
+    }
+
+    #[test]
+    fn av_sync_info() {
+        let time = MicroSeconds(2_000_000);
+        assert_eq!(av_sync(Ok(Some(time)), Ok(Latency::Positive(MicroSeconds(40_000)))),
+            Ok(AvSync { play_time: time, sink_latency: MicroSeconds(40_000) }));
+        assert_eq!(av_sync(Ok(Some(time)), Ok(Latency::Negative(MicroSeconds(5_000)))),
+            Ok(AvSync { play_time: time, sink_latency: MicroSeconds(0) }));
+
+        // No timing info yet
+        let no_data = Err(PAErr::from(error::Code::NoData));
+        assert_eq!(av_sync(Ok(None), Ok(Latency::None)), no_data);
+        assert_eq!(av_sync(Ok(Some(time)), Ok(Latency::None)), no_data);
+
+        let bad_state = PAErr::from(error::Code::BadState);
+        assert_eq!(av_sync(Err(bad_state), Ok(Latency::None)), Err(bad_state));
+        let (_mainloop, _context, stream) = new_test_stream();
+        assert_eq!(stream.get_av_sync_info(), Err(bad_state));
+    }
+
+    #[test]
+    fn get_time() {
+        let usecs = MicroSeconds(1_500_000);
+        assert_eq!(time_result(0, usecs), Ok(Some(usecs)));
+        assert_eq!(time_result(0, MicroSeconds::INVALID), Ok(None));
+        assert_eq!(time_result(PAErr::from(error::Code::NoData).0, MicroSeconds(0)), Ok(None));
+        assert_eq!(time_result(PAErr::from(error::Code::BadState).0, MicroSeconds(0)),
+            Err(PAErr::from(error::Code::BadState)));
+
+        // Not connected, so there is no time to be had
+        let (_mainloop, _context, stream) = new_test_stream();
+        assert_eq!(stream.get_time(), Err(PAErr::from(error::Code::BadState)));
+    }
+
+    #[test]
+    fn get_latency() {
+        let usecs = MicroSeconds(30_000);
+        assert_eq!(latency_result(0, usecs, 0), Ok(Latency::Positive(usecs)));
+        assert_eq!(latency_result(0, usecs, 1), Ok(Latency::Negative(usecs)));
+        assert_eq!(latency_result(0, MicroSeconds::INVALID, 0), Ok(Latency::None));
+        assert_eq!(latency_result(0, MicroSeconds::INVALID, 1), Ok(Latency::None));
+        assert_eq!(latency_result(PAErr::from(error::Code::NoData).0, MicroSeconds(0), 0),
+            Ok(Latency::None));
+        assert_eq!(latency_result(PAErr::from(error::Code::BadState).0, MicroSeconds(0), 0),
+            Err(PAErr::from(error::Code::BadState)));
+
+        let (_mainloop, _context, stream) = new_test_stream();
+        assert_eq!(stream.get_latency(), Err(PAErr::from(error::Code::BadState)));
+    }
+
+    #[test]
+    fn timing_config() {
+        let standard = Flags::AUTO_TIMING_UPDATE | Flags::INTERPOLATE_TIMING;
+        assert_eq!(TimingConfig::new().flags(), standard);
+        assert_eq!(TimingConfig::default().flags(), standard);
+        assert_eq!(TimingConfig::new().interpolate(false).flags(), Flags::AUTO_TIMING_UPDATE);
+        assert_eq!(TimingConfig::new().auto_update(false).flags(), Flags::INTERPOLATE_TIMING);
+        assert_eq!(TimingConfig::new().monotonic(false).flags(), standard | Flags::NOT_MONOTONIC);
+        assert_eq!(TimingConfig::new().monotonic(false).monotonic(true).flags(), standard);
+        assert_eq!(TimingConfig::new().auto_update(false).interpolate(false).flags(),
+            Flags::NOFLAGS);
+
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        let installed = |stream: &Stream| {
+            !stream.cb_ptrs.latency_update.get_capi_params(notify_cb_proxy).1.is_null()
+        };
+        assert_eq!(stream.apply_timing_config(TimingConfig::new()), standard);
+        assert!(!installed(&stream));
+        let config = TimingConfig::new().interpolate(false).on_latency_update(|| {});
+        assert_eq!(stream.apply_timing_config(config), Flags::AUTO_TIMING_UPDATE);
+        assert!(installed(&stream));
+
+        // Not connected, so there is no position to be had
+        assert_eq!(stream.playback_position(), Err(PAErr::from(error::Code::BadState)));
+    }
+
+    #[test]
+    fn drop_disconnects() {
+        let disconnects = |state| {
+            let mut count = 0;
+            disconnect_on_drop(state, false, || count += 1);
+            count
+        };
+        assert_eq!(disconnects(State::Creating), 1);
+        assert_eq!(disconnects(State::Ready), 1);
+        // Never connected, already disconnected by the user, or failed
+        assert_eq!(disconnects(State::Unconnected), 0);
+        assert_eq!(disconnects(State::Terminated), 0);
+        assert_eq!(disconnects(State::Failed), 0);
+
+        // Disconnected by the user, but the server has yet to respond
+        let mut count = 0;
+        disconnect_on_drop(State::Ready, true, || count += 1);
+        assert_eq!(count, 0);
+
+        let (_mainloop, _context, stream) = new_test_stream();
+        assert_eq!(stream.get_state(), State::Unconnected);
+        drop(stream);
+    }
+
+    #[test]
+    fn monitor_name() {
+        assert_eq!(monitor_source_name("alsa_output.pci-0000_00_1b.0.analog-stereo"),
+            "alsa_output.pci-0000_00_1b.0.analog-stereo.monitor");
+    }
+
+    #[test]
+    fn flags() {
+        assert_eq!(std::mem::size_of::<Flags>(), std::mem::size_of::<capi::pa_stream_flags_t>());
+
+        let mut f = Flags::START_CORKED | Flags::ADJUST_LATENCY;
+        assert!(f.contains(Flags::START_CORKED));
+        assert!(!f.contains(Flags::START_CORKED | Flags::PEAK_DETECT));
+        assert!(f.intersects(Flags::START_CORKED | Flags::PEAK_DETECT));
+        f.insert(Flags::PEAK_DETECT);
+        f.remove(Flags::START_CORKED);
+        assert_eq!(f, Flags::ADJUST_LATENCY | Flags::PEAK_DETECT);
+        assert_eq!(f.bits(), capi::PA_STREAM_ADJUST_LATENCY | capi::PA_STREAM_PEAK_DETECT);
+        assert_eq!(Flags::from_bits_truncate(!0), Flags::all());
+        assert!((!Flags::all()).is_empty());
+    }
+
+    #[test]
+    fn new_with_proplist() {
+        let mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "test").unwrap();
+        let spec = sample::Spec { format: sample::SAMPLE_S16NE, channels: 2, rate: 44100 };
+
+        let mut proplist = Proplist::new().unwrap();
+        proplist.set_str(crate::proplist::properties::MEDIA_ROLE, "music").unwrap();
+
+        let stream = Stream::new_with_proplist(&mut context, "test", &spec, None, &proplist);
+        assert!(stream.is_some());
+        assert_eq!(stream.unwrap().get_state(), State::Unconnected);
+
+        // The name cannot contain a nul byte
+        assert!(Stream::new_with_proplist(&mut context, "te\0st", &spec, None, &proplist)
+            .is_none());
+
+        // Invalid sample spec
+        let bad_spec = sample::Spec { format: sample::Format::Invalid, channels: 2, rate: 44100 };
+        assert!(Stream::new_with_proplist(&mut context, "test", &bad_spec, None, &proplist)
+            .is_none());
+    }
+
+    #[test]
+    fn mainloop_dropped_first() {
+        let (mainloop, context, stream) = new_test_stream();
+        let inner = std::rc::Rc::downgrade(&mainloop._inner);
+        drop(mainloop);
+        drop(context);
+        // The stream keeps the mainloop internals alive, so is still usable
+        assert!(inner.upgrade().is_some());
+        assert_eq!(stream.get_state(), State::Unconnected);
+        drop(stream);
+        assert!(inner.upgrade().is_none());
+    }
+
+    #[test]
+    fn write_all_chunking() {
+        use std::cell::{Cell, RefCell};
+
+        // The stub server accepts up to 4 bytes whenever the mainloop is iterated
+        let data: Vec<u8> = (0..10).collect();
+        let writable = Cell::new(0usize);
+        let iterations = Cell::new(0);
+        let written = RefCell::new(Vec::new());
+        let result = write_all_chunked(&data, SeekMode::Absolute,
+            || Some(writable.get()),
+            |chunk, seek| {
+                writable.set(writable.get() - chunk.len());
+                written.borrow_mut().push((chunk.to_vec(), seek));
+                Ok(())
+            },
+            || {
+                iterations.set(iterations.get() + 1);
+                writable.set(4);
+                Ok(())
+            });
+        assert_eq!(result, Ok(()));
+        assert_eq!(iterations.get(), 3);
+        assert_eq!(*written.borrow(), vec![
+            (vec![0, 1, 2, 3], SeekMode::Absolute),
+            (vec![4, 5, 6, 7], SeekMode::Relative),
+            (vec![8, 9], SeekMode::Relative),
+        ]);
+
+        // Stops on mainloop quit or error, without further writes
+        let result = write_all_chunked(&data, SeekMode::Relative, || Some(0),
+            |_, _| panic!("nothing is writable"),
+            || Err(PAErr::from(error::Code::Killed)));
+        assert_eq!(result, Err(PAErr::from(error::Code::Killed)));
+
+        // Nothing to write
+        assert_eq!(write_all_chunked(&[], SeekMode::Relative, || None,
+            |_, _| panic!("nothing to write"), || panic!("nothing to wait for")), Ok(()));
+    }
+
+    /// A stub record source, serving a fixed sequence of peek results.
+    struct StubSource {
+        fragments: Vec<PeekResult<'static>>,
+        waits: usize,
+        discards: usize,
+    }
+
+    impl ReadSource for StubSource {
+        fn readable_size(&mut self) -> Result<Option<usize>, PAErr> {
+            match self.fragments.first() {
+                None => Ok(None),
+                Some(PeekResult::Empty) => Ok(Some(0)),
+                Some(PeekResult::Hole(size)) => Ok(Some(*size)),
+                Some(PeekResult::Data(data)) => Ok(Some(data.len())),
+            }
+        }
+
+        fn peek<'a>(&mut self) -> Result<PeekResult<'a>, PAErr> {
+            match self.fragments.first() {
+                Some(PeekResult::Hole(size)) => Ok(PeekResult::Hole(*size)),
+                Some(PeekResult::Data(data)) => Ok(PeekResult::Data(data)),
+                _ => Ok(PeekResult::Empty),
+            }
+        }
+
+        fn discard(&mut self) -> Result<(), PAErr> {
+            self.fragments.remove(0);
+            self.discards += 1;
+            Ok(())
+        }
+
+        fn wait(&mut self) -> Result<(), PAErr> {
+            // Data only becomes available after waiting
+            if let Some(PeekResult::Empty) = self.fragments.first() {
+                self.fragments.remove(0);
+            }
+            self.waits += 1;
+            Ok(())
+        }
+    }
+
+    fn stub_source() -> StubSource {
+        StubSource {
+            fragments: vec![
+                PeekResult::Data(&[1, 2]),
+                PeekResult::Empty,
+                PeekResult::Hole(2),
+                PeekResult::Data(&[3, 4, 5, 6]),
+            ],
+            waits: 0,
+            discards: 0,
+        }
+    }
+
+    #[test]
+    fn read_all_chunking() {
+        let mut source = stub_source();
+        let mut out = Vec::new();
+        assert_eq!(read_all_chunked(&mut source, &mut out, 5, HolePolicy::Silence, 0x80), Ok(()));
+        assert_eq!(out, vec![1, 2, 0x80, 0x80, 3]);
+        assert_eq!((source.waits, source.discards), (1, 3));
+
+        let mut source = stub_source();
+        let mut out = vec![9];
+        assert_eq!(read_all_chunked(&mut source, &mut out, 16, HolePolicy::Skip, 0), Ok(()));
+        assert_eq!(out, vec![9, 1, 2, 3, 4, 5, 6]);
+
+        let mut source = stub_source();
+        let mut out = Vec::new();
+        assert_eq!(read_all_chunked(&mut source, &mut out, 16, HolePolicy::Fail, 0),
+            Err(PAErr::from(error::Code::NoData)));
+        assert_eq!(out, vec![1, 2]);
+    }
+
+    #[test]
+    fn read_all_unconnected() {
+        let (mut mainloop, _context, mut stream) = new_test_stream();
+        let mut out = Vec::new();
+        assert_eq!(stream.read_all(&mut mainloop, &mut out, 8, HolePolicy::Skip),
+            Err(PAErr::from(error::Code::BadState)));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn success_results() {
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        // Force a failure, which the stream’s context records
+        assert!(stream.try_cork(None).is_err());
+
+        let result = Rc::new(Cell::new(None));
+        let deliver = |success| {
+            let result_ref = Rc::clone(&result);
+            let callback = Box::new(move |r| result_ref.set(Some(r)));
+            let cb_data = box_closure_get_capi_ptr::<dyn FnOnce(Result<(), PAErr>)>(callback);
+            success_cb_proxy(stream.ptr, success, cb_data);
+        };
+        deliver(0);
+        assert_eq!(result.get(), Some(Err(PAErr::from(error::Code::BadState))));
+        deliver(1);
+        assert_eq!(result.get(), Some(Ok(())));
+        assert_eq!(Rc::strong_count(&result), 1);
+    }
+
+    #[test]
+    fn write_all_unconnected() {
+        let (mut mainloop, _context, mut stream) = new_test_stream();
+        assert_eq!(stream.write_all(&[0u8; 8], &mut mainloop, SeekMode::Relative),
+            Err(PAErr::from(error::Code::BadState)));
+    }
+
+    #[test]
+    fn toggle_cork_unconnected() {
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        // The corked state cannot be determined, so no request is issued (which would panic)
+        assert_eq!(stream.toggle_cork(None).err(), Some(PAErr::from(error::Code::BadState)));
+    }
+
+    #[test]
+    fn connect_playback_synced_to_checks() {
+        let (mainloop, mut context, mut stream) = new_test_stream();
+        let spec = sample::Spec { format: sample::SAMPLE_S16NE, channels: 2, rate: 44100 };
+
+        // Master not connected
+        let master = Stream::new(&mut context, "master", &spec, None).unwrap();
+        assert_eq!(stream.connect_playback_synced_to(&master, None, None, Flags::START_CORKED,
+            None), Err(PAErr::from(error::Code::BadState)));
+
+        // Master of another context
+        let mut other_context = Context::new(&mainloop, "other").unwrap();
+        let other = Stream::new(&mut other_context, "master", &spec, None).unwrap();
+        assert_eq!(stream.connect_playback_synced_to(&other, None, None, Flags::START_CORKED,
+            None), Err(PAErr::from(error::Code::Invalid)));
+        assert_eq!(stream.get_state(), State::Unconnected);
+    }
+
+    #[test]
+    fn event_dispatch() {
+        let handlers = RefCell::new(EventHandlers::default());
+        let generic = Rc::new(RefCell::new(Vec::new()));
+        let requests = Rc::new(RefCell::new(Vec::new()));
+        let event = |name: &str| (name.to_string(), ProplistRef::from(Proplist::new().unwrap()));
+
+        // No callbacks
+        let (name, pl) = event(event_names::EVENT_REQUEST_CORK);
+        dispatch_event(&handlers, name, pl);
+
+        // Generic only, receives everything
+        let generic_ref = Rc::clone(&generic);
+        handlers.borrow_mut().generic = Some(Rc::new(RefCell::new(
+            move |name: String, _: ProplistRef| generic_ref.borrow_mut().push(name))));
+        let (name, pl) = event(event_names::EVENT_REQUEST_CORK);
+        dispatch_event(&handlers, name, pl);
+        assert_eq!(*generic.borrow(), vec![event_names::EVENT_REQUEST_CORK.to_string()]);
+
+        // With a cork request callback, which takes just the cork and uncork requests
+        let requests_ref = Rc::clone(&requests);
+        handlers.borrow_mut().cork_request = Some(Rc::new(RefCell::new(move |request| {
+            requests_ref.borrow_mut().push(request)
+        })));
+        generic.borrow_mut().clear();
+        for name in &[event_names::EVENT_REQUEST_UNCORK, event_names::EVENT_FORMAT_LOST,
+            event_names::EVENT_REQUEST_CORK, "some-module-event"]
+        {
+            let (name, pl) = event(name);
+            dispatch_event(&handlers, name, pl);
+        }
+        assert_eq!(*requests.borrow(), vec![CorkRequest::Uncork, CorkRequest::Cork]);
+        assert_eq!(*generic.borrow(), vec![event_names::EVENT_FORMAT_LOST.to_string(),
+            "some-module-event".to_string()]);
+
+        // Cork request callback only, other events are dropped
+        handlers.borrow_mut().generic = None;
+        let (name, pl) = event(event_names::EVENT_FORMAT_LOST);
+        dispatch_event(&handlers, name, pl);
+        let (name, pl) = event(event_names::EVENT_REQUEST_CORK);
+        dispatch_event(&handlers, name, pl);
+        assert_eq!(requests.borrow().len(), 3);
+        assert!(!handlers.borrow().dispatching);
+    }
+
+    #[test]
+    fn event_dispatch_reentrant() {
+        let handlers = Rc::new(RefCell::new(EventHandlers::default()));
+        let calls = Rc::new(Cell::new(0));
+
+        // A callback unsetting itself, as with `set_event_callback(None)` from within it
+        let handlers_ref = Rc::clone(&handlers);
+        let calls_ref = Rc::clone(&calls);
+        handlers.borrow_mut().generic = Some(Rc::new(RefCell::new(move |_: String, _: ProplistRef| {
+            calls_ref.set(calls_ref.get() + 1);
+            let mut handlers = handlers_ref.borrow_mut();
+            assert!(handlers.dispatching);
+            handlers.generic = None;
+        })));
+        for _ in 0..2 {
+            let pl = ProplistRef::from(Proplist::new().unwrap());
+            dispatch_event(&handlers, "some-module-event".to_string(), pl);
+        }
+        assert_eq!(calls.get(), 1);
+        assert!(handlers.borrow().generic.is_none());
+        assert!(!handlers.borrow().dispatching);
+    }
+
+    #[test]
+    fn try_set_name_errors() {
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        let flag = Rc::new(());
+        let flag_ref = Rc::clone(&flag);
+        let callback: Box<dyn FnOnce(Result<(), PAErr>)> = Box::new(move |_| {
+            let _ = &flag_ref;
+        });
+
+        assert_eq!(stream.try_set_name("Foo\0Bar", None).err(),
+            Some(PAErr::from(error::Code::Invalid)));
+        // Unconnected, the request cannot be issued, and the callback is released
+        assert_eq!(stream.try_set_name("Foo", Some(callback)).err(),
+            Some(PAErr::from(error::Code::BadState)));
+        assert_eq!(Rc::strong_count(&flag), 1);
+    }
+
+    #[test]
+    fn state_helpers() {
+        let states = [
+            (State::Unconnected, "unconnected", false, false, false),
+            (State::Creating, "creating", true, false, false),
+            (State::Ready, "ready", true, true, false),
+            (State::Failed, "failed", false, false, true),
+            (State::Terminated, "terminated", false, false, true),
+        ];
+        for &(state, name, good, ready, terminal) in states.iter() {
+            assert_eq!(state.to_string(), name);
+            assert_eq!(state.is_good(), good);
+            assert_eq!(state.is_ready(), ready);
+            assert_eq!(state.is_terminal(), terminal);
+            assert_eq!(State::from(state as i32), state);
+        }
+        // Unknown raw values are conservatively treated as failure
+        assert_eq!(State::from(State::Terminated as i32 + 1), State::Failed);
+        assert_eq!(State::from(-1), State::Failed);
+    }
+
+    #[test]
+    fn status_unconnected() {
+        let (_mainloop, _context, stream) = new_test_stream();
+        assert_eq!(stream.status(), Err(PAErr::from(error::Code::BadState)));
+    }
+
+    #[test]
+    fn xrun_counts() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        assert_eq!(stream.get_underflow_count(), 0);
+        assert_eq!(stream.get_overflow_count(), 0);
+
+        // Counted even with no user callback set
+        let (_, data) = stream.cb_ptrs.overflow.get_capi_params(notify_cb_proxy);
+        notify_cb_proxy(stream.ptr, data);
+        assert_eq!(stream.get_overflow_count(), 1);
+
+        let fired = Rc::new(Cell::new(0));
+        let fired_ref = Rc::clone(&fired);
+        stream.set_underflow_callback(Some(Box::new(move || fired_ref.set(fired_ref.get() + 1))));
+
+        // Simulate two underflows
+        let (_, data) = stream.cb_ptrs.underflow.get_capi_params(notify_cb_proxy);
+        notify_cb_proxy(stream.ptr, data);
+        notify_cb_proxy(stream.ptr, data);
+        assert_eq!(stream.get_underflow_count(), 2);
+        assert_eq!(fired.get(), 2);
+        assert_eq!(stream.get_overflow_count(), 1);
+
+        // Unsetting the user callback does not reset the count
+        stream.set_underflow_callback(None);
+        assert_eq!(stream.get_underflow_count(), 2);
+    }
+
+    #[test]
+    fn new_extended() {
+        let mainloop = Mainloop::new().unwrap();
+        let mut context = Context::new(&mainloop, "test").unwrap();
+        let spec = sample::Spec { format: sample::SAMPLE_S16NE, channels: 2, rate: 44100 };
+        let proplist = Proplist::new().unwrap();
+
+        // Without a server to negotiate with, a lone PCM format may fall back to being used as a
+        // plain sample spec, otherwise it must be refused as unsupported, and not otherwise fail.
+        let pcm = format::Info::new_from_sample_spec(&spec, None).unwrap();
+        match Stream::new_extended(&mut context, "test", &[&pcm], &proplist) {
+            Some(stream) => assert_eq!(stream.get_state(), State::Unconnected),
+            None => assert_eq!(context.errno(), PAErr::from(error::Code::NotSupported)),
+        }
+        // The caller's format info is untouched either way
+        assert!(pcm.is_valid());
+        assert!(pcm.is_pcm());
+        assert_eq!(pcm.get_encoding(), format::Encoding::PCM);
+
+        // Passthrough formats require negotiation
+        let mut ac3 = format::Info::new().unwrap();
+        ac3.set_encoding(format::Encoding::AC3_IEC61937);
+        assert!(Stream::new_extended(&mut context, "test", &[&ac3, &pcm], &proplist).is_none());
+        assert!(pcm.is_valid());
+
+        // The name cannot contain a nul byte
+        assert!(Stream::new_extended(&mut context, "te\0st", &[&pcm], &proplist).is_none());
+        // At least one format is required
+        assert!(Stream::new_extended(&mut context, "test", &[], &proplist).is_none());
+    }
+
+    #[test]
+    fn monitor_stream_unset() {
+        let (_mainloop, _context, stream) = new_test_stream();
+        assert_eq!(stream.get_monitor_stream(), None);
+    }
+
+    /// Connects a context to the default server, or returns `None` if no server is available.
+    fn connect(mainloop: &mut Mainloop) -> Option<Context> {
+        let mut context = Context::new(mainloop, "test").unwrap();
+        context.connect(None, crate::context::Flags::NOAUTOSPAWN, None).ok()?;
+        loop {
+            if !mainloop.iterate(true).is_success() {
+                return None;
+            }
+            match context.get_state() {
+                crate::context::State::Ready => return Some(context),
+                crate::context::State::Failed | crate::context::State::Terminated => return None,
+                _ => {},
+            }
+        }
+    }
+
+    #[test]
+    fn monitor_stream_set() {
+        let (_mainloop, _context, mut stream) = new_test_stream();
+        // The invalid index is refused, leaving nothing set
+        assert_eq!(stream.set_monitor_stream(def::INVALID_INDEX),
+            Err(PAErr::from(error::Code::Invalid)));
+        assert_eq!(stream.get_monitor_stream(), None);
+
+        // Otherwise the server's protocol version is checked, which needs a connected context, and
+        // thus a server (skipped if none is available)
+        let mut mainloop = Mainloop::new().unwrap();
+        let mut context = match connect(&mut mainloop) {
+            Some(context) => context,
+            None => return,
+        };
+        let spec = sample::Spec { format: sample::SAMPLE_S16NE, channels: 2, rate: 44100 };
+        let mut stream = Stream::new(&mut context, "test", &spec, None).unwrap();
+        assert_eq!(stream.set_monitor_stream(5), Ok(()));
+        assert_eq!(stream.get_monitor_stream(), Some(5));
+    }
+
+    #[test]
+    fn not_send_sync() {
+        assert_not_impl!(Stream: Send);
+        assert_not_impl!(Stream: Sync);
+    }
+}
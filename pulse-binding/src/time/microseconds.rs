@@ -21,9 +21,29 @@ use std::time::Duration;
 pub struct MicroSeconds(pub u64);
 
 impl MicroSeconds {
+    /// Invalid time, used by PulseAudio as a sentinel for ‘unknown’ (same as [`USEC_INVALID`]).
+    ///
+    /// [`USEC_INVALID`]: constant.USEC_INVALID.html
+    pub const INVALID: Self = super::USEC_INVALID;
+
+    /// Largest valid time value (same as [`USEC_MAX`]).
+    ///
+    /// [`USEC_MAX`]: constant.USEC_MAX.html
+    pub const MAX: Self = super::USEC_MAX;
+
+    /// Checks if the value is valid, i.e. not [`INVALID`](#associatedconstant.INVALID).
     #[inline]
     pub fn is_valid(&self) -> bool {
-        *self != super::USEC_INVALID
+        *self != Self::INVALID
+    }
+
+    /// Converts to an `Option`, mapping [`INVALID`](#associatedconstant.INVALID) to `None`.
+    #[inline]
+    pub fn valid(self) -> Option<Self> {
+        match self.is_valid() {
+            true => Some(self),
+            false => None,
+        }
     }
 
     #[inline]
@@ -62,6 +82,31 @@ impl MicroSeconds {
     pub fn checked_rem(self, rhs: u32) -> Option<Self> {
         self.0.checked_rem(rhs as u64).and_then(|i| Some(MicroSeconds(i)))
     }
+
+    /// Saturating addition. Saturates at [`USEC_MAX`], i.e. never produces [`USEC_INVALID`].
+    ///
+    /// [`USEC_MAX`]: constant.USEC_MAX.html
+    /// [`USEC_INVALID`]: constant.USEC_INVALID.html
+    #[inline]
+    pub fn saturating_add(self, other: Self) -> Self {
+        std::cmp::min(MicroSeconds(self.0.saturating_add(other.0)), super::USEC_MAX)
+    }
+
+    /// Saturating subtraction. Saturates at zero.
+    #[inline]
+    pub fn saturating_sub(self, other: Self) -> Self {
+        MicroSeconds(self.0.saturating_sub(other.0))
+    }
+
+    /// Saturating multiplication. Saturates at [`USEC_MAX`], i.e. never produces
+    /// [`USEC_INVALID`].
+    ///
+    /// [`USEC_MAX`]: constant.USEC_MAX.html
+    /// [`USEC_INVALID`]: constant.USEC_INVALID.html
+    #[inline]
+    pub fn saturating_mul(self, rhs: u32) -> Self {
+        std::cmp::min(MicroSeconds(self.0.saturating_mul(rhs as u64)), super::USEC_MAX)
+    }
 }
 
 impl Add for MicroSeconds {
@@ -174,3 +219,23 @@ impl std::fmt::Display for MicroSeconds {
         write!(f, "{} µs", self.0)
     }
 }
+
+#[test]
+fn saturating_ops() {
+    use super::{USEC_MAX, USEC_INVALID};
+    assert_eq!(USEC_MAX.saturating_add(MicroSeconds(1)), USEC_MAX);
+    assert_ne!(USEC_MAX.saturating_mul(2), USEC_INVALID);
+    assert_eq!(MicroSeconds(1).saturating_sub(MicroSeconds(2)), MicroSeconds(0));
+    assert_eq!(MicroSeconds(1).saturating_add(MicroSeconds(2)), MicroSeconds(3));
+}
+
+#[test]
+fn invalid_sentinel() {
+    assert_eq!(MicroSeconds::INVALID, MicroSeconds(std::u64::MAX));
+    assert_eq!(MicroSeconds::MAX, MicroSeconds(std::u64::MAX - 1));
+    assert!(!MicroSeconds::INVALID.is_valid());
+    assert!(MicroSeconds::MAX.is_valid());
+    assert!(MicroSeconds(0).is_valid());
+    assert_eq!(MicroSeconds::INVALID.valid(), None);
+    assert_eq!(MicroSeconds(5).valid(), Some(MicroSeconds(5)));
+}
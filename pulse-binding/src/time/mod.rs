@@ -58,25 +58,37 @@ impl From<MicroSeconds> for Timeval {
 impl From<Duration> for MicroSeconds {
     #[inline]
     fn from(t: Duration) -> Self {
-        MicroSeconds((t.as_secs() * MILLIS_PER_SEC) + t.subsec_millis() as u64)
+        MicroSeconds((t.as_secs() * MICROS_PER_SEC) + t.subsec_micros() as u64)
     }
 }
 impl From<MicroSeconds> for Duration {
     #[inline]
     fn from(t: MicroSeconds) -> Self {
-        Duration::from_millis(t.0)
+        Duration::from_micros(t.0)
     }
 }
 
 impl From<Duration> for Timeval {
     #[inline]
     fn from(t: Duration) -> Self {
-        Timeval::new(t.as_secs() as self::timeval::TvSecs, t.subsec_millis() as self::timeval::TvUsecs)
+        Timeval::new(t.as_secs() as self::timeval::TvSecs,
+            t.subsec_micros() as self::timeval::TvUsecs)
     }
 }
 impl From<Timeval> for Duration {
     #[inline]
     fn from(t: Timeval) -> Self {
-        Duration::from_millis((MicroSeconds::from(t)).0)
+        Duration::from_micros((MicroSeconds::from(t)).0)
     }
 }
+
+#[test]
+fn duration_conversions() {
+    let d = Duration::new(2, 345_678_000);
+    assert_eq!(MicroSeconds::from(d), MicroSeconds(2_345_678));
+    assert_eq!(Duration::from(MicroSeconds(2_345_678)), d);
+
+    let tv = Timeval::from(d);
+    assert_eq!((tv.0.tv_sec, tv.0.tv_usec), (2, 345_678));
+    assert_eq!(Duration::from(tv), d);
+}
@@ -114,12 +114,10 @@ impl Timeval {
         let wc_now = (UnixTs::now()).0;
         let rt_now = Timeval::from((MonotonicTs::now()).0);
 
-        let _ = match rt_now.cmp(self) {
+        *self = match rt_now.cmp(self) {
             Ordering::Less => { wc_now.add(Timeval::diff(self, &rt_now)) },
             _              => { wc_now.sub(Timeval::diff(&rt_now, self)) },
         };
-
-        *self = wc_now;
         self
     }
 
@@ -16,21 +16,14 @@
 //! Bindings are not provided here for most of the PulseAudio UTF-8 functions since Rust has built
 //! in UTF-8 handling and thus they should be entirely unnecessary.
 
-use std::os::raw::{c_char, c_void};
-use std::ffi::{CStr, CString};
+use std::ffi::CString;
+use crate::xmalloc::PaAllocated;
 
 /// Converts a UTF-8 string to the current locale.
 pub fn utf8_to_locale(s: &str) -> Option<String> {
     // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
     // as_ptr() giving dangling pointers!
     let c_str = CString::new(s.clone()).unwrap();
-    let tmp_ptr: *const c_char = unsafe { capi::pa_utf8_to_locale(c_str.as_ptr()) };
-    if tmp_ptr.is_null() {
-        return None;
-    }
-    unsafe {
-        let ret = Some(CStr::from_ptr(tmp_ptr).to_string_lossy().into_owned());
-        capi::pa_xfree(tmp_ptr as *mut c_void);
-        ret
-    }
+    let tmp_ptr = unsafe { capi::pa_utf8_to_locale(c_str.as_ptr()) };
+    unsafe { PaAllocated::from_raw(tmp_ptr) }.map(|s| s.to_string_lossy())
 }
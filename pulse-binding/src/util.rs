@@ -13,11 +13,18 @@
 
 //! Assorted utility functions.
 
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_ulong};
 
 macro_rules! fn_string_with_buffer {
     ( $fn_call:ident, $l:ident ) => {{
-        let mut tmp = Vec::with_capacity($l);
+        // The C functions require a buffer of at least one byte, for the terminating NUL.
+        if $l == 0 {
+            return None;
+        }
+        // Note, the buffer must actually be allocated (initialised) to the length we tell the C
+        // function it has available.
+        let mut tmp: Vec<c_char> = vec![0; $l];
         unsafe {
             // Need to check NULL return here because `get_binary_name` function is not
             // supported on all architectures and so may return NULL, and might as well check
@@ -33,6 +40,10 @@ macro_rules! fn_string_with_buffer {
 
 /// Gets the current username.
 ///
+/// The `l` param specifies the size of the buffer to allocate for obtaining the value, including
+/// a terminating NUL; the result will be truncated if longer. This applies to all similar
+/// functions in this module.
+///
 /// Returns `None` on failure.
 pub fn get_user_name(l: usize) -> Option<String> {
     fn_string_with_buffer!(pa_get_user_name, l)
@@ -47,6 +58,10 @@ pub fn get_host_name(l: usize) -> Option<String> {
 
 /// Gets the fully qualified domain name.
 ///
+/// **Warning**: This may perform a DNS lookup, which can block the calling thread for a long
+/// time (potentially many seconds where the network is unavailable or misconfigured). Do **not**
+/// call this from a mainloop callback or any other time-sensitive context, such as an audio thread.
+///
 /// Returns `None` on failure.
 pub fn get_fqdn(l: usize) -> Option<String> {
     fn_string_with_buffer!(pa_get_fqdn, l)
@@ -67,6 +82,51 @@ pub fn get_binary_name(l: usize) -> Option<String> {
     fn_string_with_buffer!(pa_get_binary_name, l)
 }
 
+/// Gets the filename portion of a path.
+///
+/// This returns everything after the last `/` of the given path (or the entire path if it does not
+/// contain one).
+pub fn path_get_filename(path: &str) -> Option<String> {
+    // Warning: New CStrings will be immediately freed if not bound to a variable, leading to
+    // as_ptr() giving dangling pointers!
+    let c_path = CString::new(path).ok()?;
+    unsafe {
+        // Note, the returned pointer points into our own string
+        let ptr = capi::pa_path_get_filename(c_path.as_ptr());
+        match ptr.is_null() {
+            true => None,
+            false => Some(CStr::from_ptr(ptr).to_string_lossy().into_owned()),
+        }
+    }
+}
+
+/// Waits for the specified amount of time, in milliseconds.
+///
+/// Returns `Err` if the sleep was interrupted.
+pub fn msleep(millis: u32) -> Result<(), ()> {
+    match unsafe { capi::pa_msleep(millis as c_ulong) } {
+        0 => Ok(()),
+        _ => Err(()),
+    }
+}
+
+/// Error returned when [`make_thread_realtime`] fails.
+///
+/// [`make_thread_realtime`]: fn.make_thread_realtime.html
+#[cfg(any(feature = "pa_v13", feature = "dox"))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MakeRealtimeError;
+
+#[cfg(any(feature = "pa_v13", feature = "dox"))]
+impl std::fmt::Display for MakeRealtimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "failed to acquire realtime scheduling")
+    }
+}
+
+#[cfg(any(feature = "pa_v13", feature = "dox"))]
+impl std::error::Error for MakeRealtimeError {}
+
 /// Makes the calling thread realtime if we can.
 ///
 /// On Linux, this uses RealTimeKit if available and POSIX APIs otherwise (the latter applies to
@@ -74,9 +134,23 @@ pub fn get_binary_name(l: usize) -> Option<String> {
 ///
 /// Available since PA version 13.
 #[cfg(any(feature = "pa_v13", feature = "dox"))]
-pub fn make_thread_realtime(rtprio: i32) -> Result<(), ()> {
+pub fn make_thread_realtime(rtprio: i32) -> Result<(), MakeRealtimeError> {
     match unsafe { capi::pa_thread_make_realtime(rtprio) } {
         0 => Ok(()),
-        _ => Err(()),
+        _ => Err(MakeRealtimeError),
     }
 }
+
+#[test]
+fn path_filename() {
+    assert_eq!(path_get_filename("/usr/bin/foo"), Some(String::from("foo")));
+    assert_eq!(path_get_filename("foo"), Some(String::from("foo")));
+    assert_eq!(path_get_filename("/usr/bin/"), Some(String::new()));
+    assert_eq!(path_get_filename("a\0b"), None);
+}
+
+#[test]
+fn zero_length_buffer() {
+    assert_eq!(get_user_name(0), None);
+    assert_eq!(get_host_name(0), None);
+}
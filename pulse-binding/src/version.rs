@@ -27,7 +27,9 @@
 //! # Runtime check
 //!
 //! The [`get_library_version`] function obtains at runtime the version of the actual PA client
-//! library in use.
+//! library in use. A parsed form of the version number can be obtained with
+//! [`get_library_version_numbers`], and [`library_version_is_at_least`] offers a simple way of
+//! checking whether the library in use is new enough to safely use some particular function.
 //!
 //! # Dynamic constants
 //!
@@ -52,6 +54,8 @@
 //! indicated will be v11.
 //!
 //! [`get_library_version`]: fn.get_library_version.html
+//! [`get_library_version_numbers`]: fn.get_library_version_numbers.html
+//! [`library_version_is_at_least`]: fn.library_version_is_at_least.html
 //! [`get_compatibility`]: fn.get_compatibility.html
 
 use capi;
@@ -68,3 +72,56 @@ pub use capi::version::pa_check_version as check_version;
 pub fn get_library_version() -> &'static CStr {
     unsafe { CStr::from_ptr(capi::pa_get_library_version()) }
 }
+
+/// Gets the version of the (PulseAudio client system) library actually in use at runtime, parsed
+/// into a (major, minor, micro) tuple.
+///
+/// Missing minor or micro components are given as zero. Any trailing suffix, such as those added
+/// by distributions or development builds (e.g. `13.99.1-rebootstrapped`), is ignored.
+///
+/// Returns `None` if the version string could not be parsed.
+pub fn get_library_version_numbers() -> Option<(u8, u8, u8)> {
+    pa_version_str_to_num(&get_library_version().to_string_lossy())
+}
+
+/// Checks whether the version of the (PulseAudio client system) library actually in use at runtime
+/// is equal to or newer than the one specified.
+///
+/// Returns `None` if the runtime version could not be determined.
+pub fn library_version_is_at_least(major: u8, minor: u8) -> Option<bool> {
+    get_library_version_numbers().map(|(ma, mi, _)| (ma, mi) >= (major, minor))
+}
+
+/// Parses a version string into a (major, minor, micro) tuple.
+///
+/// Parsing stops at the first character that is neither a digit nor a `.`, thus ignoring any
+/// suffix.
+fn pa_version_str_to_num(ver: &str) -> Option<(u8, u8, u8)> {
+    let end = ver.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(ver.len());
+    let mut parts = ver[..end].split('.');
+    let mut next = || -> Option<Option<u8>> {
+        match parts.next() {
+            None => Some(None),
+            Some(p) => p.parse::<u8>().ok().map(Some),
+        }
+    };
+    let major = next()??;
+    let minor = next()?.unwrap_or(0);
+    let micro = next()?.unwrap_or(0);
+    Some((major, minor, micro))
+}
+
+#[test]
+fn version_parsing() {
+    assert_eq!(pa_version_str_to_num("13.0"), Some((13, 0, 0)));
+    assert_eq!(pa_version_str_to_num("13.99.1"), Some((13, 99, 1)));
+    assert_eq!(pa_version_str_to_num("13.99.1-rebootstrapped"), Some((13, 99, 1)));
+    assert_eq!(pa_version_str_to_num("12.2-78-g5ac1e"), Some((12, 2, 0)));
+    assert_eq!(pa_version_str_to_num("11.1-1ubuntu7.5"), Some((11, 1, 0)));
+    assert_eq!(pa_version_str_to_num("4.0.0"), Some((4, 0, 0)));
+    assert_eq!(pa_version_str_to_num("14"), Some((14, 0, 0)));
+    assert_eq!(pa_version_str_to_num("8.0."), None);
+    assert_eq!(pa_version_str_to_num("1000.0"), None);
+    assert_eq!(pa_version_str_to_num("v13.0"), None);
+    assert_eq!(pa_version_str_to_num(""), None);
+}
@@ -50,9 +50,10 @@
 //!
 //! It’s often unknown what scale hardware volumes relate to. Don’t use the above functions on sink
 //! and source volumes, unless the sink or source in question has the
-//! [`def::sink_flags::DECIBEL_VOLUME`] or [`def::source_flags::DECIBEL_VOLUME`] flag set. The
-//! conversion functions are rarely needed anyway, most of the time it’s sufficient to treat all
-//! volumes as opaque with a range from [`VOLUME_MUTED`] \(0%) to [`VOLUME_NORM`] \(100%).
+//! [`def::SinkFlagSet::DECIBEL_VOLUME`][sink-db] or
+//! [`def::SourceFlagSet::DECIBEL_VOLUME`][source-db] flag set. The conversion functions are rarely
+//! needed anyway, most of the time it’s sufficient to treat all volumes as opaque with a range from
+//! [`VOLUME_MUTED`] \(0%) to [`VOLUME_NORM`] \(100%).
 //!
 //! [`Volume`]: struct.Volume.html
 //! [`VolumeDB`]: struct.VolumeDB.html
@@ -64,8 +65,8 @@
 //! [`ChannelVolumes::sw_multiply`]: struct.ChannelVolumes.html#method.sw_multiply
 //! [`VOLUME_MUTED`]: constant.VOLUME_MUTED.html
 //! [`VOLUME_NORM`]: constant.VOLUME_NORM.html
-//! [`def::sink_flags::DECIBEL_VOLUME`]: ../def/sink_flags/constant.DECIBEL_VOLUME.html
-//! [`def::source_flags::DECIBEL_VOLUME`]: ../def/source_flags/constant.DECIBEL_VOLUME.html
+//! [sink-db]: ../def/struct.SinkFlagSet.html#associatedconstant.DECIBEL_VOLUME
+//! [source-db]: ../def/struct.SourceFlagSet.html#associatedconstant.DECIBEL_VOLUME
 
 use std::borrow::{Borrow, BorrowMut};
 use std::ffi::CStr;
@@ -110,7 +111,7 @@ impl Default for VolumeLinear {
 
 /// A structure encapsulating a per-channel volume
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Copy, Clone)]
 pub struct ChannelVolumes {
     /* NOTE: This struct must be directly usable by the C API, thus same attributes/layout/etc */
     /// Number of channels.
@@ -158,6 +159,27 @@ impl From<capi::pa_cvolume> for ChannelVolumes {
     }
 }
 
+impl Default for ChannelVolumes {
+    /// Creates an initialised, but invalid, set, as per [`init`](#method.init).
+    fn default() -> Self {
+        Self {
+            channels: 0,
+            values: [VOLUME_INVALID; sample::CHANNELS_MAX],
+        }
+    }
+}
+
+impl std::fmt::Debug for ChannelVolumes {
+    /// Only the volumes of the used channels are shown.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let values: &[Volume] = self.borrow();
+        f.debug_struct("ChannelVolumes")
+            .field("channels", &self.channels)
+            .field("values", &values)
+            .finish()
+    }
+}
+
 impl PartialEq for ChannelVolumes {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
@@ -261,6 +283,39 @@ impl Volume {
         *self == VOLUME_MAX
     }
 
+    /// Converts a linear factor to a volume.
+    ///
+    /// This is a shortcut for converting via [`VolumeLinear`]. Note that `Volume` values are on a
+    /// cubic scale, as appropriate for mapping directly to UI sliders, thus the linear factor is
+    /// the cube of the fractional volume (e.g. `0.125` gives 50% of [`VOLUME_NORM`]). The decibel
+    /// conversions ([`VolumeDB`]) are related to the linear factor, being `20 * log10(factor)`.
+    ///
+    /// `0.0` and less is muted while `1.0` is [`VOLUME_NORM`].
+    ///
+    /// This is only valid for software volumes!
+    ///
+    /// [`VolumeLinear`]: struct.VolumeLinear.html
+    /// [`VolumeDB`]: struct.VolumeDB.html
+    /// [`VOLUME_NORM`]: constant.VOLUME_NORM.html
+    #[inline]
+    pub fn from_linear(v: f64) -> Self {
+        Volume::from(VolumeLinear(v))
+    }
+
+    /// Converts a volume to a linear factor.
+    ///
+    /// This is a shortcut for converting via [`VolumeLinear`]. See [`from_linear`] for an
+    /// explanation of the scales involved.
+    ///
+    /// This is only valid for software volumes!
+    ///
+    /// [`VolumeLinear`]: struct.VolumeLinear.html
+    /// [`from_linear`]: #method.from_linear
+    #[inline]
+    pub fn to_linear(&self) -> f64 {
+        VolumeLinear::from(*self).0
+    }
+
     /// Get the recommended maximum volume to show in user facing UIs.
     ///
     /// Note: UIs should deal gracefully with volumes greater than this value and not cause feedback
@@ -283,6 +338,57 @@ impl Volume {
         self.0 = capi::pa_clamp_volume(self.0)
     }
 
+    /// Returns the volume limited to at most [`VOLUME_NORM`], i.e. with any amplification removed.
+    ///
+    /// [`VOLUME_NORM`]: constant.VOLUME_NORM.html
+    #[inline]
+    pub fn clamp_to_norm(self) -> Self {
+        match self > VOLUME_NORM {
+            true => VOLUME_NORM,
+            false => self,
+        }
+    }
+
+    /// Returns the volume limited to at most [`VOLUME_MAX`], the largest valid volume.
+    ///
+    /// This is the by-value equivalent of [`clamp`](#method.clamp).
+    ///
+    /// [`VOLUME_MAX`]: constant.VOLUME_MAX.html
+    #[inline]
+    pub fn clamp_to_max(self) -> Self {
+        Volume(capi::pa_clamp_volume(self.0))
+    }
+
+    /// Adds two volumes, returning `None` if either is invalid or the result would exceed
+    /// [`VOLUME_MAX`].
+    ///
+    /// Adding the values directly (`a.0 + b.0`) can overflow or produce an invalid volume, and
+    /// clamping (e.g. with [`clamp_to_max`]) silently limits the result; this allows the caller to
+    /// choose how to handle the excess.
+    ///
+    /// [`VOLUME_MAX`]: constant.VOLUME_MAX.html
+    /// [`clamp_to_max`]: #method.clamp_to_max
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        if !self.is_valid() || !other.is_valid() {
+            return None;
+        }
+        match self.0.checked_add(other.0) {
+            Some(v) if v <= VOLUME_MAX.0 => Some(Volume(v)),
+            _ => None,
+        }
+    }
+
+    /// Subtracts `other` from this volume, returning `None` if either is invalid or the result
+    /// would be below [`VOLUME_MUTED`].
+    ///
+    /// [`VOLUME_MUTED`]: constant.VOLUME_MUTED.html
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        if !self.is_valid() || !other.is_valid() {
+            return None;
+        }
+        self.0.checked_sub(other.0).map(Volume)
+    }
+
     /// Multiplies two software volumes, returning the result.
     ///
     /// This uses [`VOLUME_NORM`](constant.VOLUME_NORM.html) as neutral element of multiplication.
@@ -304,6 +410,64 @@ impl Volume {
         Volume(unsafe { capi::pa_sw_volume_divide(a.0, b.0) })
     }
 
+    /// Converts the volume to a percentage of [`VOLUME_NORM`].
+    ///
+    /// The result is not rounded. Rounding it to the nearest integer (i.e. with [`f64::round`])
+    /// gives the same figure as displayed by [`print`] (and thus `pactl` and friends).
+    ///
+    /// [`VOLUME_NORM`]: constant.VOLUME_NORM.html
+    /// [`print`]: #method.print
+    /// [`f64::round`]: https://doc.rust-lang.org/std/primitive.f64.html#method.round
+    #[inline]
+    pub fn to_percent(&self) -> f64 {
+        self.0 as f64 * 100.0 / VOLUME_NORM.0 as f64
+    }
+
+    /// Converts a percentage of [`VOLUME_NORM`] to a volume, rounding to the nearest volume step.
+    ///
+    /// Negative percentages give [`VOLUME_MUTED`]. Returns `None` if the percentage is above that
+    /// of [`ui_max`] (or is NaN); use [`from_percent_clamped`] to clamp such values instead.
+    ///
+    /// [`VOLUME_NORM`]: constant.VOLUME_NORM.html
+    /// [`VOLUME_MUTED`]: constant.VOLUME_MUTED.html
+    /// [`ui_max`]: #method.ui_max
+    /// [`from_percent_clamped`]: #method.from_percent_clamped
+    pub fn from_percent(percent: f64) -> Option<Self> {
+        if percent.is_nan() {
+            return None;
+        }
+        let ui_max = Self::ui_max();
+        match Self::from_percent_unchecked(percent) {
+            v if v > ui_max => None,
+            v => Some(v),
+        }
+    }
+
+    /// Converts a percentage of [`VOLUME_NORM`] to a volume, rounding to the nearest volume step.
+    ///
+    /// As with [`from_percent`], except that percentages above that of [`ui_max`] (and NaN) give
+    /// `ui_max()` rather than failing.
+    ///
+    /// [`VOLUME_NORM`]: constant.VOLUME_NORM.html
+    /// [`from_percent`]: #method.from_percent
+    /// [`ui_max`]: #method.ui_max
+    pub fn from_percent_clamped(percent: f64) -> Self {
+        Self::from_percent(percent).unwrap_or_else(Self::ui_max)
+    }
+
+    /// Converts without an upper limit check (beyond that of saturating at `u32::MAX`).
+    pub(crate) fn from_percent_unchecked(percent: f64) -> Self {
+        match percent > 0.0 {
+            // Note, float to int `as` casts only saturate from Rust 1.45 (being undefined behaviour
+            // for out of range values before then), hence the explicit clamping.
+            true => {
+                let raw = (percent * VOLUME_NORM.0 as f64 / 100.0).round();
+                Volume(raw.min(std::u32::MAX as f64) as u32)
+            },
+            false => VOLUME_MUTED,
+        }
+    }
+
     /// Pretty prints a volume.
     pub fn print(&self) -> String {
         const PRINT_MAX: usize = capi::PA_VOLUME_SNPRINT_MAX;
@@ -342,9 +506,14 @@ impl Volume {
     }
 }
 
+/// Formats as per [`print`](#method.print), or with the alternate flag (`{:#}`), as per both
+/// [`print`](#method.print) and [`print_db`](#method.print_db), e.g. `100% (0.00 dB)`.
 impl std::fmt::Display for Volume {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", &self.print())
+        match f.alternate() && self.is_valid() {
+            true => write!(f, "{} ({})", &self.print(), &self.print_db()),
+            false => write!(f, "{}", &self.print()),
+        }
     }
 }
 
@@ -377,12 +546,21 @@ impl ChannelVolumes {
     /// the internal array considered “active” and thus available for reading/writing (i.e. when
     /// borrowing `self` as a slice).
     ///
+    /// When growing, the newly active channels are set to [`VOLUME_MUTED`], rather than exposing
+    /// whatever stale values they previously held.
+    ///
     /// **Panics** if the number of channels specified is greater than [`sample::CHANNELS_MAX`].
     ///
     /// [`sample::CHANNELS_MAX`]: ../sample/constant.CHANNELS_MAX.html
+    /// [`VOLUME_MUTED`]: constant.VOLUME_MUTED.html
     #[inline]
     pub fn set_len(&mut self, channels: u8) {
         assert!(channels as usize <= sample::CHANNELS_MAX);
+        if channels > self.channels {
+            for v in &mut self.values[self.channels as usize..channels as usize] {
+                *v = VOLUME_MUTED;
+            }
+        }
         self.channels = channels;
     }
 
@@ -399,19 +577,69 @@ impl ChannelVolumes {
     }
 
     /// Sets the volume of the specified number of channels to the supplied volume.
+    ///
+    /// **Panics** if the number of channels specified is zero or greater than
+    /// [`sample::CHANNELS_MAX`] (the C API would otherwise abort).
+    ///
+    /// [`sample::CHANNELS_MAX`]: ../sample/constant.CHANNELS_MAX.html
     #[inline]
     pub fn set(&mut self, channels: u32, v: Volume) -> &Self {
+        assert!(channels > 0 && channels as usize <= sample::CHANNELS_MAX);
         unsafe { capi::pa_cvolume_set(self.as_mut(), channels, v.0) };
         self
     }
 
+    /// Sets the volume of the specified number of channels to the supplied percentage of
+    /// [`VOLUME_NORM`].
+    ///
+    /// Returns `None`, leaving the volumes unchanged, if the percentage is out of range, as per
+    /// [`Volume::from_percent`].
+    ///
+    /// [`VOLUME_NORM`]: constant.VOLUME_NORM.html
+    /// [`Volume::from_percent`]: struct.Volume.html#method.from_percent
+    pub fn set_percent(&mut self, channels: u32, percent: f64) -> Option<&Self> {
+        let v = Volume::from_percent(percent)?;
+        Some(self.set(channels, v))
+    }
+
+    /// Sets the volume of the specified number of channels to the supplied percentage of
+    /// [`VOLUME_NORM`], clamping as per [`Volume::from_percent_clamped`].
+    ///
+    /// [`VOLUME_NORM`]: constant.VOLUME_NORM.html
+    /// [`Volume::from_percent_clamped`]: struct.Volume.html#method.from_percent_clamped
+    #[inline]
+    pub fn set_percent_clamped(&mut self, channels: u32, percent: f64) -> &Self {
+        self.set(channels, Volume::from_percent_clamped(percent))
+    }
+
+    /// Gets the volume of each channel as a percentage of [`VOLUME_NORM`], as per
+    /// [`Volume::to_percent`].
+    ///
+    /// [`VOLUME_NORM`]: constant.VOLUME_NORM.html
+    /// [`Volume::to_percent`]: struct.Volume.html#method.to_percent
+    pub fn to_percent(&self) -> Vec<f64> {
+        self.get().iter().map(Volume::to_percent).collect()
+    }
+
+    /// Gets the average volume of all channels as a percentage of [`VOLUME_NORM`].
+    ///
+    /// [`VOLUME_NORM`]: constant.VOLUME_NORM.html
+    #[inline]
+    pub fn avg_percent(&self) -> f64 {
+        self.avg().to_percent()
+    }
+
     /// Sets the volume of the first n channels to [`VOLUME_NORM`](constant.VOLUME_NORM.html).
+    ///
+    /// **Panics** under the same conditions as [`set`](#method.set).
     #[inline]
     pub fn reset(&mut self, channels: u32) -> &Self {
         self.set(channels, VOLUME_NORM)
     }
 
     /// Sets the volume of the first n channels to [`VOLUME_MUTED`](constant.VOLUME_MUTED.html).
+    ///
+    /// **Panics** under the same conditions as [`set`](#method.set).
     #[inline]
     pub fn mute(&mut self, channels: u32) -> &Self {
         self.set(channels, VOLUME_MUTED)
@@ -495,6 +723,7 @@ impl ChannelVolumes {
     /// Returns pointer to self.
     #[inline]
     pub fn sw_multiply(&mut self, with: Option<&Self>) -> &mut Self {
+        debug_assert!(self.is_valid() && with.map_or(true, |w| w.is_valid()));
         unsafe { capi::pa_sw_cvolume_multiply(self.as_mut(), self.as_mut(),
             with.unwrap_or(self).as_ref()) };
         self
@@ -505,6 +734,7 @@ impl ChannelVolumes {
     /// This is only valid for software volumes! Returns pointer to self.
     #[inline]
     pub fn sw_multiply_scalar(&mut self, with: Volume) -> &mut Self {
+        debug_assert!(self.is_valid());
         unsafe { capi::pa_sw_cvolume_multiply_scalar(self.as_mut(), self.as_ref(), with.0) };
         self
     }
@@ -515,6 +745,7 @@ impl ChannelVolumes {
     /// pointer to self.
     #[inline]
     pub fn sw_divide(&mut self, with: Option<&Self>) -> &mut Self {
+        debug_assert!(self.is_valid() && with.map_or(true, |w| w.is_valid()));
         unsafe { capi::pa_sw_cvolume_divide(self.as_mut(), self.as_mut(),
             with.unwrap_or(self).as_ref()) };
         self
@@ -525,17 +756,22 @@ impl ChannelVolumes {
     /// This is only valid for software volumes! Returns pointer to self.
     #[inline]
     pub fn sw_divide_scalar(&mut self, with: Volume) -> &mut Self {
+        debug_assert!(self.is_valid());
         unsafe { capi::pa_sw_cvolume_divide_scalar(self.as_mut(), self.as_ref(), with.0) };
         self
     }
 
     /// Remaps a volume from one channel mapping to a different channel mapping.
     ///
-    /// Returns pointer to self.
+    /// Both maps must be valid, and `from` must be compatible with this volume (i.e. have the same
+    /// number of channels), otherwise `None` is returned and the volume is left unmodified.
+    ///
+    /// Returns pointer to self, or `None` on error.
     #[inline]
-    pub fn remap(&mut self, from: &channelmap::Map, to: &channelmap::Map) -> &mut Self {
-        unsafe { capi::pa_cvolume_remap(self.as_mut(), from.as_ref(), to.as_ref()) };
-        self
+    pub fn remap(&mut self, from: &channelmap::Map, to: &channelmap::Map) -> Option<&mut Self> {
+        let ptr =
+            unsafe { capi::pa_cvolume_remap(self.as_mut(), from.as_ref(), to.as_ref()) };
+        match ptr.is_null() { false => Some(self), true => None }
     }
 
     /// Checks if the specified volume is compatible with the specified sample spec.
@@ -802,8 +1038,266 @@ impl ChannelVolumes {
     }
 }
 
+/// Formats as per [`print`](#method.print), or with the alternate flag (`{:#}`), giving each
+/// channel's volume both as a percentage and in dB, e.g. `0: 100% (0.00 dB) 1:  50% (-18.06 dB)`.
 impl std::fmt::Display for ChannelVolumes {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", &self.print())
+        if !f.alternate() || !self.is_valid() {
+            return write!(f, "{}", &self.print());
+        }
+        for (i, v) in self.get().iter().enumerate() {
+            match i {
+                0 => write!(f, "{}: {:#}", i, v)?,
+                _ => write!(f, " {}: {:#}", i, v)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plain value types can be freely shared between threads.
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ChannelVolumes>();
+        assert_send_sync::<Volume>();
+    }
+
+    #[test]
+    fn default_and_debug() {
+        let mut cv = ChannelVolumes::default();
+        assert_eq!(cv.len(), 0);
+        assert!(!cv.is_valid());
+        assert_eq!(format!("{:?}", cv), "ChannelVolumes { channels: 0, values: [] }");
+
+        cv.set(2, Volume(0x100));
+        assert_eq!(format!("{:?}", cv),
+            "ChannelVolumes { channels: 2, values: [Volume(256), Volume(256)] }");
+    }
+
+    #[test]
+    fn display() {
+        let half = Volume(VOLUME_NORM.0 / 2);
+        assert_eq!(format!("{}", VOLUME_NORM), VOLUME_NORM.print());
+        assert_eq!(format!("{:#}", VOLUME_NORM), "100% (0.00 dB)");
+        assert_eq!(format!("{:#}", VOLUME_MUTED), "  0% (-inf dB)");
+        assert_eq!(format!("{:#}", half), " 50% (-18.06 dB)");
+        assert_eq!(format!("{:#}", half), format!("{} ({})", half.print(), half.print_db()));
+        assert_eq!(format!("{:#}", Volume(VOLUME_MAX.0 + 1)), "(invalid)");
+
+        let mut cv = ChannelVolumes::default();
+        cv.set(2, VOLUME_NORM);
+        cv.get_mut()[1] = half;
+        assert_eq!(format!("{}", cv), cv.print());
+        assert_eq!(format!("{}", cv), "0: 100% 1:  50%");
+        assert_eq!(format!("{:#}", cv), "0: 100% (0.00 dB) 1:  50% (-18.06 dB)");
+        assert_eq!(format!("{:#}", ChannelVolumes::default()), "(invalid)");
+    }
+
+    #[test]
+    fn lifecycle() {
+        let mut cv = ChannelVolumes::default();
+        cv.init();
+        assert_eq!(cv.len(), 0);
+        assert!(!cv.is_valid());
+
+        cv.set(2, VOLUME_NORM);
+        assert!(cv.is_valid());
+        assert_eq!(cv.get(), &[VOLUME_NORM, VOLUME_NORM]);
+
+        // Growing exposes muted channels, not stale or invalid values
+        cv.set_len(4);
+        assert!(cv.is_valid());
+        assert_eq!(cv.get(), &[VOLUME_NORM, VOLUME_NORM, VOLUME_MUTED, VOLUME_MUTED]);
+
+        cv.set_len(1);
+        assert_eq!(cv.get(), &[VOLUME_NORM]);
+        cv.set_len(2);
+        assert_eq!(cv.get(), &[VOLUME_NORM, VOLUME_MUTED]);
+
+        cv.set_len(sample::CHANNELS_MAX as u8);
+        assert!(cv.is_valid());
+
+        // Values from the C API (e.g. introspection results) are taken as is
+        let raw: capi::pa_cvolume = *cv.as_ref();
+        let from_raw = ChannelVolumes::from(raw);
+        assert!(from_raw.is_valid());
+        assert_eq!(from_raw.get(), cv.get());
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_len_too_many() {
+        ChannelVolumes::default().set_len(sample::CHANNELS_MAX as u8 + 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_too_many() {
+        ChannelVolumes::default().set(sample::CHANNELS_MAX as u32 + 1, VOLUME_NORM);
+    }
+
+    #[test]
+    fn clamping_and_checked_arithmetic() {
+        let high = Volume(VOLUME_MAX.0 - 0x100);
+
+        // Sum of two high volumes exceeds the valid range
+        assert_eq!(high.checked_add(high), None);
+        assert_eq!(Volume(high.0 + high.0).clamp_to_max(), VOLUME_MAX);
+        assert_eq!(Volume(high.0 + high.0).clamp_to_norm(), VOLUME_NORM);
+
+        // Within range, checked and clamped agree
+        let sum = VOLUME_NORM.checked_add(Volume(0x100));
+        assert_eq!(sum, Some(Volume(VOLUME_NORM.0 + 0x100)));
+        assert_eq!(sum.unwrap().clamp_to_max(), sum.unwrap());
+        assert_eq!(sum.unwrap().clamp_to_norm(), VOLUME_NORM);
+        assert_eq!(high.checked_add(Volume(0x100)), Some(VOLUME_MAX));
+
+        assert_eq!(VOLUME_NORM.checked_sub(VOLUME_NORM), Some(VOLUME_MUTED));
+        assert_eq!(VOLUME_MUTED.checked_sub(Volume(1)), None);
+        assert_eq!(VOLUME_INVALID.checked_add(VOLUME_MUTED), None);
+        assert_eq!(VOLUME_NORM.checked_sub(VOLUME_INVALID), None);
+        assert_eq!(VOLUME_MUTED.clamp_to_norm(), VOLUME_MUTED);
+    }
+
+    #[test]
+    fn percent_conversions() {
+        assert_eq!(VOLUME_NORM.to_percent(), 100.0);
+        assert_eq!(VOLUME_MUTED.to_percent(), 0.0);
+        assert_eq!(Volume::from_percent(100.0), Some(VOLUME_NORM));
+        assert_eq!(Volume::from_percent(50.0), Some(Volume(VOLUME_NORM.0 / 2)));
+        assert_eq!(Volume::from_percent(-5.0), Some(VOLUME_MUTED));
+        assert_eq!(Volume::from_percent(std::f64::NAN), None);
+
+        // Above the UI maximum
+        let ui_max = Volume::ui_max();
+        let above = ui_max.to_percent() + 1.0;
+        assert_eq!(Volume::from_percent(above), None);
+        assert_eq!(Volume::from_percent_clamped(above), ui_max);
+        assert_eq!(Volume::from_percent_clamped(1e12), ui_max);
+        assert_eq!(Volume::from_percent(ui_max.to_percent()), Some(ui_max));
+
+        // Round trip, and agreement with the rounding of `print`, across the whole range
+        let mut v = 0u32;
+        loop {
+            let vol = Volume(v);
+            let back = Volume::from_percent(vol.to_percent()).unwrap();
+            assert!((back.0 as i64 - v as i64).abs() <= 1, "{} vs {}", back.0, v);
+            assert_eq!(format!("{}%", vol.to_percent().round()), vol.print().trim());
+            if v == ui_max.0 {
+                break;
+            }
+            v = std::cmp::min(v + 97, ui_max.0);
+        }
+
+        let mut cv = ChannelVolumes::default();
+        assert!(cv.set_percent(2, 80.0).is_some());
+        assert_eq!(cv.to_percent().len(), 2);
+        assert!((cv.avg_percent() - 80.0).abs() < 0.01);
+        assert!(cv.set_percent(2, above).is_none());
+        assert!((cv.avg_percent() - 80.0).abs() < 0.01);
+        cv.set_percent_clamped(2, above);
+        assert_eq!(cv.avg(), ui_max);
+    }
+
+    #[test]
+    fn linear_conversions() {
+        assert_eq!(Volume::from_linear(1.0), VOLUME_NORM);
+        assert_eq!(Volume::from_linear(0.0), VOLUME_MUTED);
+        assert_eq!(Volume::from_linear(-1.0), VOLUME_MUTED);
+        assert_eq!(VOLUME_NORM.to_linear(), 1.0);
+
+        let factors = [0.001, 0.01, 0.125, 0.5, 1.0, 2.0];
+        for pair in factors.windows(2) {
+            assert!(Volume::from_linear(pair[0]) < Volume::from_linear(pair[1]));
+        }
+
+        // Cubic scale
+        let half = Volume::from_linear(0.125);
+        assert!((half.0 as i64 - (VOLUME_NORM.0 / 2) as i64).abs() <= 1);
+    }
+
+    #[test]
+    fn balance() {
+        let mut stereo = channelmap::Map::default();
+        stereo.init_stereo();
+        let mut mono = channelmap::Map::default();
+        mono.init_mono();
+
+        let mut cv = ChannelVolumes::default();
+        cv.set(2, VOLUME_NORM);
+        assert_eq!(cv.get_balance(&stereo), 0.0);
+
+        assert!(cv.set_balance(&stereo, -1.0).is_some());
+        assert_eq!(cv.get_balance(&stereo), -1.0);
+        assert_eq!(cv.get()[0], VOLUME_NORM);
+        assert_eq!(cv.get()[1], VOLUME_MUTED);
+
+        // Balance not applicable
+        let mut cv = ChannelVolumes::default();
+        cv.set(1, VOLUME_NORM);
+        assert!(!mono.can_balance());
+        assert!(cv.set_balance(&mono, -1.0).is_none());
+        assert_eq!(cv.get_balance(&mono), 0.0);
+    }
+
+    #[test]
+    fn fade() {
+        let mut stereo = channelmap::Map::default();
+        stereo.init_stereo();
+        let mut quad = channelmap::Map::default();
+        quad.init_auto(4, channelmap::MapDef::ALSA).unwrap();
+
+        let mut cv = ChannelVolumes::default();
+        cv.set(4, VOLUME_NORM);
+        assert!(quad.can_fade());
+        assert!(cv.set_fade(&quad, 1.0).is_some());
+        assert_eq!(cv.get_fade(&quad), 1.0);
+
+        // Fade not applicable
+        let mut cv = ChannelVolumes::default();
+        cv.set(2, VOLUME_NORM);
+        assert!(!stereo.can_fade());
+        assert!(cv.set_fade(&stereo, 1.0).is_none());
+        assert_eq!(cv.get_fade(&stereo), 0.0);
+    }
+
+    #[test]
+    fn remap_stereo_to_mono() {
+        let mut stereo = channelmap::Map::default();
+        stereo.init_stereo();
+        let mut mono = channelmap::Map::default();
+        mono.init_mono();
+
+        let mut cv = ChannelVolumes::default();
+        cv.set(2, Volume(0x8000));
+        cv.get_mut()[1] = Volume(0x4000);
+
+        assert!(cv.remap(&stereo, &mono).is_some());
+        assert_eq!(cv.len(), 1);
+        assert_eq!(cv.get()[0], Volume(0x6000));
+    }
+
+    #[test]
+    fn remap_mismatch() {
+        let mut stereo = channelmap::Map::default();
+        stereo.init_stereo();
+        let mut mono = channelmap::Map::default();
+        mono.init_mono();
+
+        // Volume length does not match the `from` map
+        let mut cv = ChannelVolumes::default();
+        cv.set(1, VOLUME_NORM);
+        assert!(cv.remap(&stereo, &mono).is_none());
+        assert_eq!(cv.len(), 1);
+
+        // Invalid `to` map
+        let mut cv = ChannelVolumes::default();
+        cv.set(2, VOLUME_NORM);
+        assert!(cv.remap(&stereo, &channelmap::Map::default()).is_none());
     }
 }
@@ -0,0 +1,155 @@
+// Copyright 2020 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Ownership of memory allocated by the C library for the caller.
+//!
+//! Various C functions return strings or arrays allocated with `pa_xmalloc` (and friends), which
+//! the caller must free with `pa_xfree`. The wrappers here free such memory upon being dropped,
+//! thus the binding functions returning owned copies of it cannot leak it, whether by an early
+//! return or a panic part way through copying.
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+
+/// A value allocated by the C library, freed with `pa_xfree` upon drop.
+pub(crate) struct PaAllocated<T> {
+    ptr: *mut T,
+}
+
+impl<T> PaAllocated<T> {
+    /// Takes ownership of memory allocated by the C library, giving `None` for a null pointer.
+    ///
+    /// Safety: `ptr` must be null or have been allocated with `pa_xmalloc` (or friends), and not
+    /// be freed elsewhere.
+    #[inline]
+    pub unsafe fn from_raw(ptr: *mut T) -> Option<Self> {
+        match ptr.is_null() {
+            false => Some(Self { ptr: ptr }),
+            true => None,
+        }
+    }
+}
+
+impl<T: Copy> PaAllocated<T> {
+    /// Copies out an array of `len` elements.
+    ///
+    /// Safety: The allocation must hold at least `len` elements.
+    pub unsafe fn to_vec(&self, len: usize) -> Vec<T> {
+        std::slice::from_raw_parts(self.ptr, len).to_vec()
+    }
+}
+
+impl PaAllocated<c_char> {
+    /// Copies out the (nul terminated) string, replacing any invalid UTF-8 sequences.
+    pub fn to_string_lossy(&self) -> String {
+        unsafe { CStr::from_ptr(self.ptr).to_string_lossy().into_owned() }
+    }
+}
+
+impl<T> Drop for PaAllocated<T> {
+    fn drop(&mut self) {
+        unsafe { capi::pa_xfree(self.ptr as *mut c_void) };
+    }
+}
+
+/// An array of strings allocated by the C library, with each string, and the array itself, freed
+/// with `pa_xfree` upon drop (as `pa_format_info_free_string_array` does).
+pub(crate) struct PaStringArray {
+    ptr: *mut *mut c_char,
+    len: usize,
+}
+
+impl PaStringArray {
+    /// Takes ownership of an array of `len` strings allocated by the C library, giving `None` for a
+    /// null pointer. Individual strings may be null.
+    ///
+    /// Safety: As for [`PaAllocated::from_raw`], for both the array and each string, and the
+    /// array must hold at least `len` elements.
+    ///
+    /// [`PaAllocated::from_raw`]: struct.PaAllocated.html#method.from_raw
+    #[inline]
+    pub unsafe fn from_raw(ptr: *mut *mut c_char, len: usize) -> Option<Self> {
+        match ptr.is_null() {
+            false => Some(Self { ptr: ptr, len: len }),
+            true => None,
+        }
+    }
+
+    /// Copies out the strings, skipping any null ones, and replacing any invalid UTF-8 sequences.
+    pub fn to_vec_lossy(&self) -> Vec<String> {
+        let strings = unsafe { std::slice::from_raw_parts(self.ptr, self.len) };
+        strings.iter()
+            .filter(|p| !p.is_null())
+            .map(|&p| unsafe { CStr::from_ptr(p).to_string_lossy().into_owned() })
+            .collect()
+    }
+}
+
+impl Drop for PaStringArray {
+    fn drop(&mut self) {
+        unsafe {
+            for &p in std::slice::from_raw_parts(self.ptr, self.len) {
+                capi::pa_xfree(p as *mut c_void);
+            }
+            capi::pa_xfree(self.ptr as *mut c_void);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use std::mem;
+    use std::ptr::null_mut;
+
+    fn xstrdup(s: &[u8]) -> *mut c_char {
+        let c_str = CString::new(s).unwrap();
+        unsafe { capi::pa_xstrdup(c_str.as_ptr()) }
+    }
+
+    #[test]
+    fn strings() {
+        let owned = unsafe { PaAllocated::from_raw(xstrdup(b"foo")) }.unwrap();
+        assert_eq!(owned.to_string_lossy(), "foo");
+        let owned = unsafe { PaAllocated::from_raw(xstrdup(b"a\xffb")) }.unwrap();
+        assert_eq!(owned.to_string_lossy(), "a\u{fffd}b");
+        let owned = unsafe { PaAllocated::from_raw(xstrdup(b"")) }.unwrap();
+        assert_eq!(owned.to_string_lossy(), "");
+        assert!(unsafe { PaAllocated::from_raw(null_mut::<c_char>()) }.is_none());
+    }
+
+    #[test]
+    fn arrays() {
+        let ptr = unsafe { capi::pa_xmalloc(3 * mem::size_of::<i32>()) } as *mut i32;
+        unsafe {
+            for (i, v) in [1, -2, 3].iter().enumerate() {
+                *ptr.add(i) = *v;
+            }
+        }
+        let owned = unsafe { PaAllocated::from_raw(ptr) }.unwrap();
+        assert_eq!(unsafe { owned.to_vec(3) }, [1, -2, 3]);
+        assert_eq!(unsafe { owned.to_vec(0) }, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn string_arrays() {
+        let ptr = unsafe { capi::pa_xmalloc(3 * mem::size_of::<*mut c_char>()) }
+            as *mut *mut c_char;
+        unsafe {
+            *ptr = xstrdup(b"foo");
+            *ptr.add(1) = null_mut();
+            *ptr.add(2) = xstrdup(b"bar");
+        }
+        let owned = unsafe { PaStringArray::from_raw(ptr, 3) }.unwrap();
+        assert_eq!(owned.to_vec_lossy(), ["foo", "bar"]);
+        assert!(unsafe { PaStringArray::from_raw(null_mut(), 0) }.is_none());
+    }
+}